@@ -0,0 +1,70 @@
+use ark_ff::{Field, UniformRand};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use poseidon_rust::{
+    bn254::{circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS},
+    parameters::{matrix::Matrix, PoseidonParams},
+    poseidon::Poseidon,
+};
+use rand::thread_rng;
+use std::sync::Arc;
+
+type Scalar = ark_bn254::Fr;
+
+// Synthetic parameters for t = 12 with a Cauchy MDS matrix and random round constants
+fn synthetic_params(t: usize) -> Arc<PoseidonParams<Scalar>> {
+    let mut rng = thread_rng();
+    let mds = (0..t)
+        .map(|i| {
+            (0..t)
+                .map(|j| Scalar::from((i + t + j) as u64).inverse().unwrap())
+                .collect()
+        })
+        .collect();
+    let rc = (0..68)
+        .map(|_| (0..t).map(|_| Scalar::rand(&mut rng)).collect())
+        .collect();
+    Arc::new(PoseidonParams::new(t, 5, 8, 60, mds, rc).unwrap())
+}
+
+fn matrix_storage(c: &mut Criterion) {
+    let mut rng = thread_rng();
+    let mut group = c.benchmark_group("mat_vec_mul");
+    for t in [3, 4, 5, 6, 8, 9, 12, 16] {
+        let rows: Vec<Vec<Scalar>> = (0..t)
+            .map(|_| (0..t).map(|_| Scalar::rand(&mut rng)).collect())
+            .collect();
+        let input: Vec<Scalar> = (0..t).map(|_| Scalar::rand(&mut rng)).collect();
+        let nested = Matrix::new_nested(rows.to_owned()).unwrap();
+        let flat = Matrix::new_flat(rows).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("nested", t), &input, |b, i| {
+            b.iter(|| nested.mat_vec_mul(black_box(i)))
+        });
+        group.bench_with_input(BenchmarkId::new("flat", t), &input, |b, i| {
+            b.iter(|| flat.mat_vec_mul(black_box(i)))
+        });
+    }
+    group.finish();
+}
+
+fn permutation(c: &mut Criterion) {
+    let mut rng = thread_rng();
+    let mut group = c.benchmark_group("permutation");
+    let params = [
+        POSEIDON_CIRCOM_BN_3_PARAMS.to_owned(),
+        POSEIDON_CIRCOM_BN_4_PARAMS.to_owned(),
+        synthetic_params(12),
+    ];
+    for params in params.iter() {
+        let poseidon = Poseidon::new(params);
+        let t = poseidon.get_t();
+        let input: Vec<Scalar> = (0..t).map(|_| Scalar::rand(&mut rng)).collect();
+        group.bench_with_input(BenchmarkId::from_parameter(t), &input, |b, i| {
+            b.iter(|| poseidon.permutation(black_box(i.to_owned())).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, matrix_storage, permutation);
+criterion_main!(benches);
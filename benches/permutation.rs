@@ -0,0 +1,146 @@
+use ark_ff::{Field, UniformRand};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use poseidon_rust::{
+    goldilocks::{
+        field::Goldilocks, t12::POSEIDON_GOLDILOCKS_12_PARAMS, t8::POSEIDON_GOLDILOCKS_8_PARAMS,
+    },
+    parameters::PoseidonParams,
+    poseidon::Poseidon,
+};
+use rand::thread_rng;
+use std::sync::Arc;
+
+type Scalar = ark_bn254::Fr;
+
+fn full_rounds_only(c: &mut Criterion) {
+    let mut rng = thread_rng();
+    let t = 3;
+    // 16 full rounds and no partial rounds, using a Cauchy MDS matrix and random round constants
+    let mds = (0..t)
+        .map(|i| {
+            (0..t)
+                .map(|j| Scalar::from((i + t + j) as u64).inverse().unwrap())
+                .collect()
+        })
+        .collect();
+    let rc = (0..16)
+        .map(|_| (0..t).map(|_| Scalar::rand(&mut rng)).collect())
+        .collect();
+    let params = Arc::new(PoseidonParams::new(t, 5, 16, 0, mds, rc).unwrap());
+    let poseidon = Poseidon::new(&params);
+
+    let input: Vec<Scalar> = (0..t).map(|_| Scalar::rand(&mut rng)).collect();
+    let mut group = c.benchmark_group("full_rounds_only");
+    group.bench_function("permutation", |b| {
+        b.iter(|| poseidon.permutation(black_box(input.to_owned())).unwrap())
+    });
+    group.bench_function("permutation_not_opt", |b| {
+        b.iter(|| {
+            poseidon
+                .permutation_not_opt(black_box(input.to_owned()))
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+fn goldilocks(c: &mut Criterion) {
+    let mut rng = thread_rng();
+    let mut group = c.benchmark_group("goldilocks");
+    for (t, params) in [
+        (8, &*POSEIDON_GOLDILOCKS_8_PARAMS),
+        (12, &*POSEIDON_GOLDILOCKS_12_PARAMS),
+    ] {
+        let poseidon = Poseidon::new(params);
+        let input: Vec<Goldilocks> = (0..t).map(|_| Goldilocks::rand(&mut rng)).collect();
+        group.bench_function(format!("permutation_t{t}"), |b| {
+            b.iter(|| poseidon.permutation(black_box(input.to_owned())).unwrap())
+        });
+    }
+    group.finish();
+}
+
+// Compares the driver loop of `permutation` with `permutation_in_place`, which calls the round
+// core directly
+fn driver(c: &mut Criterion) {
+    use poseidon_rust::bn254::{
+        circom_t17::POSEIDON_CIRCOM_BN_17_PARAMS, circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS,
+    };
+
+    let mut rng = thread_rng();
+    let mut group = c.benchmark_group("driver");
+    for (t, params) in [
+        (5, &*POSEIDON_CIRCOM_BN_5_PARAMS),
+        (17, &*POSEIDON_CIRCOM_BN_17_PARAMS),
+    ] {
+        let poseidon = Poseidon::new(params);
+        let input: Vec<Scalar> = (0..t).map(|_| Scalar::rand(&mut rng)).collect();
+        group.bench_function(format!("driver_t{t}"), |b| {
+            b.iter(|| poseidon.permutation(black_box(input.to_owned())).unwrap())
+        });
+        group.bench_function(format!("in_place_t{t}"), |b| {
+            b.iter(|| {
+                let mut state = black_box(input.to_owned());
+                poseidon.permutation_in_place(&mut state).unwrap();
+                state
+            })
+        });
+    }
+    group.finish();
+}
+
+// Compares the scalar path with the lanes of the `lanes` feature for a layer of 64 states
+#[cfg(feature = "lanes")]
+fn lanes(c: &mut Criterion) {
+    use poseidon_rust::bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS;
+
+    fn bench<F: ark_ff::PrimeField>(c: &mut Criterion, name: &str, poseidon: &Poseidon<F>) {
+        let mut rng = thread_rng();
+        let t = poseidon.get_t();
+        let states: Vec<Vec<F>> = (0..64)
+            .map(|_| (0..t).map(|_| F::rand(&mut rng)).collect())
+            .collect();
+        let mut group = c.benchmark_group(format!("lanes_{name}"));
+        group.bench_function("scalar", |b| {
+            b.iter(|| {
+                let mut states = black_box(states.to_owned());
+                for state in states.iter_mut() {
+                    poseidon.permutation_in_place(state).unwrap();
+                }
+                states
+            })
+        });
+        group.bench_function("lanes_4", |b| {
+            b.iter(|| {
+                let mut states = black_box(states.to_owned());
+                poseidon.permutation_many(&mut states).unwrap();
+                states
+            })
+        });
+        group.bench_function("lanes_8", |b| {
+            b.iter(|| {
+                let mut states = black_box(states.to_owned());
+                for chunk in states.chunks_exact_mut(8) {
+                    poseidon
+                        .permutation_lanes::<8>(chunk.try_into().unwrap())
+                        .unwrap();
+                }
+                states
+            })
+        });
+        group.finish();
+    }
+
+    bench(c, "bn254_t3", &Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS));
+    bench(
+        c,
+        "goldilocks_t12",
+        &Poseidon::new(&POSEIDON_GOLDILOCKS_12_PARAMS),
+    );
+}
+
+#[cfg(feature = "lanes")]
+criterion_group!(benches, full_rounds_only, goldilocks, driver, lanes);
+#[cfg(not(feature = "lanes"))]
+criterion_group!(benches, full_rounds_only, goldilocks, driver);
+criterion_main!(benches);
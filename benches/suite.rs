@@ -0,0 +1,150 @@
+// The benchmark suite covering all supported fields, run with
+// `cargo bench --features all-fields --bench suite`
+use ark_ff::{PrimeField, UniformRand};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use poseidon_rust::{
+    bn254::{
+        circom_t17::POSEIDON_CIRCOM_BN_17_PARAMS, circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+        circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS, circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS,
+        circom_t9::POSEIDON_CIRCOM_BN_9_PARAMS, poseidon2_t3::POSEIDON2_BN_3_PARAMS,
+    },
+    goldilocks::{t12::POSEIDON_GOLDILOCKS_12_PARAMS, t8::POSEIDON_GOLDILOCKS_8_PARAMS},
+    grumpkin::{t3::POSEIDON_GRUMPKIN_3_PARAMS, t4::POSEIDON_GRUMPKIN_4_PARAMS},
+    merkle::tree::MerkleTree,
+    parameters::PoseidonParams,
+    pasta::{fp_t3::POSEIDON_PASTA_FP_3_PARAMS, fq_t3::POSEIDON_PASTA_FQ_3_PARAMS},
+    poseidon::Poseidon,
+    poseidon2::Poseidon2,
+    small_fields::{
+        babybear_t16::POSEIDON_BABYBEAR_16_PARAMS, babybear_t24::POSEIDON_BABYBEAR_24_PARAMS,
+        mersenne31_t16::POSEIDON_MERSENNE31_16_PARAMS,
+        mersenne31_t24::POSEIDON_MERSENNE31_24_PARAMS,
+    },
+    sponge::PoseidonSponge,
+};
+use rand::thread_rng;
+use std::sync::Arc;
+
+type Scalar = ark_bn254::Fr;
+
+fn bench_permutation<F: PrimeField>(
+    c: &mut Criterion,
+    field: &str,
+    params: &[&Arc<PoseidonParams<F>>],
+) {
+    let mut rng = thread_rng();
+    let mut group = c.benchmark_group(format!("permutation_{field}"));
+    for params in params {
+        let poseidon = Poseidon::new(params);
+        let t = poseidon.get_t();
+        let input: Vec<F> = (0..t).map(|_| F::rand(&mut rng)).collect();
+        group.bench_with_input(BenchmarkId::new("opt", t), &input, |b, i| {
+            b.iter(|| poseidon.permutation(black_box(i.to_owned())).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("not_opt", t), &input, |b, i| {
+            b.iter(|| {
+                poseidon
+                    .permutation_not_opt(black_box(i.to_owned()))
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn permutation(c: &mut Criterion) {
+    bench_permutation(
+        c,
+        "bn254",
+        &[
+            &POSEIDON_CIRCOM_BN_3_PARAMS,
+            &POSEIDON_CIRCOM_BN_5_PARAMS,
+            &POSEIDON_CIRCOM_BN_9_PARAMS,
+            &POSEIDON_CIRCOM_BN_17_PARAMS,
+        ],
+    );
+    bench_permutation(
+        c,
+        "grumpkin",
+        &[&POSEIDON_GRUMPKIN_3_PARAMS, &POSEIDON_GRUMPKIN_4_PARAMS],
+    );
+    bench_permutation(c, "pallas", &[&POSEIDON_PASTA_FP_3_PARAMS]);
+    bench_permutation(c, "vesta", &[&POSEIDON_PASTA_FQ_3_PARAMS]);
+    bench_permutation(
+        c,
+        "goldilocks",
+        &[
+            &POSEIDON_GOLDILOCKS_8_PARAMS,
+            &POSEIDON_GOLDILOCKS_12_PARAMS,
+        ],
+    );
+    bench_permutation(
+        c,
+        "babybear",
+        &[&POSEIDON_BABYBEAR_16_PARAMS, &POSEIDON_BABYBEAR_24_PARAMS],
+    );
+    bench_permutation(
+        c,
+        "mersenne31",
+        &[
+            &POSEIDON_MERSENNE31_16_PARAMS,
+            &POSEIDON_MERSENNE31_24_PARAMS,
+        ],
+    );
+}
+
+fn poseidon2(c: &mut Criterion) {
+    let mut rng = thread_rng();
+    let poseidon2 = Poseidon2::new(&POSEIDON2_BN_3_PARAMS);
+    let input: Vec<Scalar> = (0..3).map(|_| Scalar::rand(&mut rng)).collect();
+    c.bench_function("poseidon2_bn254_t3", |b| {
+        b.iter(|| poseidon2.permutation(black_box(input.to_owned())).unwrap())
+    });
+}
+
+fn sponge(c: &mut Criterion) {
+    let mut rng = thread_rng();
+    let mut group = c.benchmark_group("sponge_bn254_t5");
+    for len in [64, 1024] {
+        let input: Vec<Scalar> = (0..len).map(|_| Scalar::rand(&mut rng)).collect();
+        group.bench_with_input(BenchmarkId::from_parameter(len), &input, |b, i| {
+            b.iter(|| {
+                let mut sponge = PoseidonSponge::new(&POSEIDON_CIRCOM_BN_5_PARAMS).unwrap();
+                sponge.absorb(black_box(i)).unwrap();
+                sponge.finalize().unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn merkle_tree(c: &mut Criterion) {
+    let mut rng = thread_rng();
+    let mut group = c.benchmark_group("merkle_tree_bn254");
+    group.sample_size(10);
+    for log_leaves in [4, 8, 12] {
+        let leaves: Vec<Scalar> = (0..1 << log_leaves)
+            .map(|_| Scalar::rand(&mut rng))
+            .collect();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(1 << log_leaves),
+            &leaves,
+            |b, l| {
+                b.iter(|| MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, black_box(l.to_owned())))
+            },
+        );
+    }
+    group.finish();
+
+    // hashing the leaves with the circom t = 4 instance, as commitments are computed
+    let inputs: Vec<[Scalar; 3]> = (0..256)
+        .map(|_| [(); 3].map(|_| Scalar::rand(&mut rng)))
+        .collect();
+    let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS);
+    c.bench_function("hash_many_bn254_t4_256", |b| {
+        b.iter(|| poseidon.hash_many(black_box(&inputs)).unwrap())
+    });
+}
+
+criterion_group!(benches, permutation, poseidon2, sponge, merkle_tree);
+criterion_main!(benches);
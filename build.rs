@@ -0,0 +1,92 @@
+use std::{fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    generate_parameter_blobs();
+    #[cfg(feature = "ffi")]
+    generate_header();
+}
+
+// Translates every parameter set in `params/<module>/<name>.json` into the binary encoding
+// loaded by `PoseidonParams::from_bytes` and written to `$OUT_DIR/params/<module>/<name>.bin`
+fn generate_parameter_blobs() {
+    println!("cargo:rerun-if-changed=params");
+    let out_dir = std::env::var("OUT_DIR").expect("set by cargo");
+    let modules = fs::read_dir("params").expect("the params directory exists");
+    for module in modules {
+        let module = module.expect("params directory is readable").path();
+        let target = Path::new(&out_dir).join(&module);
+        fs::create_dir_all(&target).expect("OUT_DIR is writable");
+        for file in fs::read_dir(&module).expect("module directory is readable") {
+            let file = file.expect("module directory is readable").path();
+            if file.extension().is_some_and(|ext| ext == "json") {
+                println!("cargo:rerun-if-changed={}", file.display());
+                let blob = parameter_blob(&file);
+                let name = file.with_extension("bin");
+                fs::write(target.join(name.file_name().expect("is a file")), blob)
+                    .expect("OUT_DIR is writable");
+            }
+        }
+    }
+}
+
+// Concatenates the MDS matrix (or the Poseidon2 diagonal, or the first row of a circulant
+// matrix) and the round constants as little-endian integers, each with the byte size given by
+// its zero-padded hex string
+fn parameter_blob(file: &Path) -> Vec<u8> {
+    let json = fs::read_to_string(file).expect("parameter file is readable");
+    let params: serde_json::Value = serde_json::from_str(&json)
+        .unwrap_or_else(|e| panic!("{} is not valid JSON: {e}", file.display()));
+    let linear = params
+        .get("M")
+        .or(params.get("diag"))
+        .or(params.get("circulant"));
+    let linear =
+        linear.unwrap_or_else(|| panic!("{} has no M, diag, or circulant", file.display()));
+    let constants = params
+        .get("C")
+        .unwrap_or_else(|| panic!("{} has no C", file.display()));
+    let mut elements = Vec::new();
+    flatten(linear, &mut elements);
+    flatten(constants, &mut elements);
+
+    let mut blob = Vec::new();
+    for element in elements {
+        let hex = element.trim_start_matches("0x");
+        assert!(hex.len() % 2 == 0, "{element} is not padded to full bytes");
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .rev()
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_else(|_| panic!("{element} is not a hex string"));
+        blob.extend(bytes);
+    }
+    blob
+}
+
+fn flatten<'a>(value: &'a serde_json::Value, res: &mut Vec<&'a str>) {
+    match value {
+        serde_json::Value::Array(values) => values.iter().for_each(|v| flatten(v, res)),
+        serde_json::Value::String(s) => res.push(s),
+        _ => panic!("unexpected value {value} in parameter file"),
+    }
+}
+
+// Generates the C header for the `ffi` module into `$OUT_DIR/poseidon_rust.h`, the ffi tests
+// check that the copy in `include/` matches it
+#[cfg(feature = "ffi")]
+fn generate_header() {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("set by cargo");
+    let out_dir = std::env::var("OUT_DIR").expect("set by cargo");
+    let config = cbindgen::Config::from_file(format!("{crate_dir}/cbindgen.toml"))
+        .expect("cbindgen.toml is valid");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{crate_dir}/src/ffi.rs"))
+        .generate()
+        .expect("the ffi module can be translated to C")
+        .write_to_file(format!("{out_dir}/poseidon_rust.h"));
+}
@@ -0,0 +1,27 @@
+// Parses arbitrary strings as field elements, which must never panic, and checks that the hex
+// string of every parsed element parses to the same element.
+#![no_main]
+
+use ark_bn254::Fr;
+use libfuzzer_sys::fuzz_target;
+use poseidon_rust::{field_from_hex_string, field_from_str_radix, field_to_hex_string};
+
+fuzz_target!(|data: &[u8]| {
+    let Some((radix, str)) = data.split_first() else {
+        return;
+    };
+    let Ok(str) = core::str::from_utf8(str) else {
+        return;
+    };
+
+    if let Ok(el) = field_from_hex_string::<Fr>(str) {
+        assert_eq!(
+            field_from_hex_string::<Fr>(&field_to_hex_string(&el)).unwrap(),
+            el
+        );
+    }
+    if let Ok(el) = field_from_str_radix::<Fr>(str, u32::from(*radix)) {
+        let hex = field_to_hex_string(&el);
+        assert_eq!(field_from_str_radix::<Fr>(&hex[2..], 16).unwrap(), el);
+    }
+});
@@ -0,0 +1,27 @@
+// Loads parameters from the binary encoding with arbitrary sizes, which must never panic, and
+// checks that loaded parameters round-trip and agree with the unoptimized permutation.
+#![no_main]
+
+use ark_bn254::Fr;
+use libfuzzer_sys::fuzz_target;
+use poseidon_rust::{parameters::PoseidonParams, poseidon::Poseidon};
+use std::sync::Arc;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((sizes, bytes)) = data.split_first_chunk::<4>() else {
+        return;
+    };
+    let [t, d, rounds_f, rounds_p] = sizes.map(usize::from);
+    let Ok(params) = PoseidonParams::<Fr>::from_bytes(t, d, rounds_f, rounds_p, bytes) else {
+        return;
+    };
+    assert_eq!(params.to_bytes(), bytes);
+
+    let params = Arc::new(params);
+    let poseidon = Poseidon::new(&params);
+    let input: Vec<Fr> = (0..t as u64).map(Fr::from).collect();
+    assert_eq!(
+        poseidon.permutation(input.clone()).unwrap(),
+        poseidon.permutation_not_opt(input).unwrap()
+    );
+});
@@ -0,0 +1,26 @@
+// Loads parameters from arbitrary JSON in both supported formats, which must never panic, and
+// checks that loaded parameters can be used for hashing.
+#![no_main]
+
+use ark_bn254::Fr;
+use libfuzzer_sys::fuzz_target;
+use poseidon_rust::{poseidon::Poseidon, try_params_from_json};
+use std::sync::Arc;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(json) = core::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(params) = try_params_from_json::<Fr>(json) else {
+        return;
+    };
+
+    let params = Arc::new(params);
+    let poseidon = Poseidon::new(&params);
+    let input = vec![Fr::from(1u64); params.t()];
+    assert_eq!(
+        poseidon.permutation(input.clone()).unwrap(),
+        poseidon.permutation_not_opt(input).unwrap()
+    );
+    let _ = poseidon.hash_bytes(data);
+});
@@ -0,0 +1,37 @@
+// Drives the sponge with an arbitrary sequence of operations, which must never panic, and checks
+// that absorbing the elements in chunks is equivalent to absorbing them at once.
+#![no_main]
+
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use libfuzzer_sys::fuzz_target;
+use poseidon_rust::{bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, sponge::PoseidonSponge};
+
+fuzz_target!(|data: &[u8]| {
+    let mut chunked = PoseidonSponge::<Fr>::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+    let mut elements = Vec::new();
+    for op in data.chunks(8) {
+        let el = Fr::from_le_bytes_mod_order(&op[1..]);
+        match op[0] % 3 {
+            0 => {
+                chunked.absorb(&[el]).unwrap();
+                elements.push(el);
+            }
+            1 => {
+                chunked.absorb(&[]).unwrap();
+            }
+            _ => {
+                let mut other = chunked.clone();
+                let _ = other.absorb_bytes(op);
+                let _ = other.squeeze(usize::from(op[0] % 8));
+                let _ = other.absorb(&[el]);
+            }
+        }
+    }
+
+    let mut at_once = PoseidonSponge::<Fr>::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+    at_once.absorb(&elements).unwrap();
+    assert_eq!(chunked.finalize().unwrap(), at_once.finalize().unwrap());
+
+    let _ = poseidon_rust::try_hash_bytes(data);
+});
@@ -0,0 +1,94 @@
+#ifndef POSEIDON_RUST_H
+#define POSEIDON_RUST_H
+
+/* Autogenerated by cbindgen from src/ffi.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The size of an encoded field element in bytes.
+ */
+#define POSEIDON_FIELD_SIZE 32
+
+/**
+ * The status codes returned by the FFI functions.
+ */
+typedef enum PoseidonStatus {
+  POSEIDON_STATUS_OK = 0,
+  /**
+   * A required pointer was null
+   */
+  POSEIDON_STATUS_NULL_POINTER = 1,
+  /**
+   * An encoded field element is not smaller than the modulus
+   */
+  POSEIDON_STATUS_NON_CANONICAL = 2,
+  /**
+   * The input does not have the expected length
+   */
+  POSEIDON_STATUS_INVALID_LENGTH = 3,
+  /**
+   * No instance is available for the number of inputs or the statesize
+   */
+  POSEIDON_STATUS_UNSUPPORTED_ARITY = 4,
+  /**
+   * The input has more elements than fit into the state
+   */
+  POSEIDON_STATUS_TOO_MANY_INPUTS = 5,
+  /**
+   * The parameters have a statesize which is not supported by the called function
+   */
+  POSEIDON_STATUS_WRONG_STATESIZE = 6,
+  /**
+   * The parameters of the instance are invalid
+   */
+  POSEIDON_STATUS_INVALID_PARAMETERS = 7,
+  /**
+   * Any other error
+   */
+  POSEIDON_STATUS_OTHER = 255,
+} PoseidonStatus;
+
+/**
+ * Hashes `n` (1 to 16) field elements like circomlibjs' `poseidon(inputs)`.
+ *
+ * # Safety
+ * `inputs` must point to `n * 32` readable bytes and `out` to 32 writable bytes.
+ */
+enum PoseidonStatus poseidon_hash_bn254(const uint8_t *inputs, size_t n, uint8_t *out);
+
+/**
+ * Applies the circom permutation with statesize `t` (2 to 17) to the state in place.
+ *
+ * # Safety
+ * `state` must point to `t * 32` readable and writable bytes.
+ */
+enum PoseidonStatus poseidon_permutation_bn254(uint8_t *state, size_t t);
+
+/**
+ * Hashes an arbitrary byte string with the circom t=3 instance, see
+ * [`Poseidon::hash_bytes`].
+ *
+ * # Safety
+ * `data` must point to `len` readable bytes (or may be null if `len` is zero) and `out` to 32
+ * writable bytes.
+ */
+enum PoseidonStatus poseidon_hash_bytes_bn254(const uint8_t *data, size_t len, uint8_t *out);
+
+/**
+ * Computes the guessing game commitment, see [`crate::guessing_game_commit_bytes`].
+ *
+ * # Safety
+ * `address` must point to 20 readable bytes, `r` to 32 readable bytes, and `out` to 32
+ * writable bytes.
+ */
+enum PoseidonStatus guessing_game_commit_bn254(uint16_t guess,
+                                               const uint8_t *address,
+                                               const uint8_t *r,
+                                               uint8_t *out);
+
+#endif  /* POSEIDON_RUST_H */
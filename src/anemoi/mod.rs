@@ -0,0 +1,202 @@
+//! # Anemoi
+//! Contains the Anemoi permutation. The state of 2 * l elements is split into the halves x and
+//! y, and every round
+//!
+//! 1. adds the round constants c to x and d to y,
+//! 2. applies the linear layer x = M * x, y = M * (y <<< 1), followed by the pseudo-Hadamard
+//!    transform y = y + x, x = x + y,
+//! 3. applies the open Flystel to every pair (x_i, y_i):
+//!    x = x - Q_gamma(y), y = y - x^(1/alpha), x = x + Q_delta(y).
+//!
+//! The linear layer is applied once more after the last round. The parameters are given
+//! explicitly. No instances are shipped: the constants of the published instances would have
+//! to be checked against known answers of the reference implementation, which is not
+//! available as a Rust crate, so the permutation is only tested against the specification.
+
+pub mod parameters;
+
+use crate::error::Error;
+use alloc::{sync::Arc, vec, vec::Vec};
+use ark_ff::PrimeField;
+use parameters::AnemoiParams;
+
+#[derive(Clone, Debug)]
+pub struct Anemoi<F: PrimeField> {
+    pub(crate) params: Arc<AnemoiParams<F>>,
+}
+
+impl<F: PrimeField> Anemoi<F> {
+    pub fn new(params: &Arc<AnemoiParams<F>>) -> Self {
+        Anemoi {
+            params: params.clone(),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.params.t()
+    }
+
+    pub fn permutation(&self, input: Vec<F>) -> Result<Vec<F>, Error> {
+        let mut state = input;
+        self.permutation_in_place(&mut state)?;
+        Ok(state)
+    }
+
+    /// Applies the permutation to the given state [x_0, .., x_(l-1), y_0, .., y_(l-1)] in place.
+    pub fn permutation_in_place(&self, state: &mut [F]) -> Result<(), Error> {
+        let params = &self.params;
+        let l = params.l;
+        if state.len() != 2 * l {
+            return Err(Error::WrongInputLength {
+                expected: 2 * l,
+                got: state.len(),
+            });
+        }
+        let mut scratch = vec![F::zero(); l];
+        for (c, d) in params.c.iter().zip(params.d.iter()) {
+            let (x, y) = state.split_at_mut(l);
+            x.iter_mut().zip(c.iter()).for_each(|(x, c)| *x += c);
+            y.iter_mut().zip(d.iter()).for_each(|(y, d)| *y += d);
+            self.linear(x, y, &mut scratch);
+            x.iter_mut()
+                .zip(y.iter_mut())
+                .for_each(|(x, y)| self.flystel(x, y));
+        }
+        let (x, y) = state.split_at_mut(l);
+        self.linear(x, y, &mut scratch);
+        Ok(())
+    }
+
+    fn linear(&self, x: &mut [F], y: &mut [F], scratch: &mut [F]) {
+        let mds = &self.params.mds;
+        mds.mat_vec_mul_into(x, scratch);
+        x.copy_from_slice(scratch);
+        y.rotate_left(1);
+        mds.mat_vec_mul_into(y, scratch);
+        y.copy_from_slice(scratch);
+        for (x, y) in x.iter_mut().zip(y.iter_mut()) {
+            *y += *x;
+            *x += *y;
+        }
+    }
+
+    fn flystel(&self, x: &mut F, y: &mut F) {
+        let params = &self.params;
+        *x -= params.beta * y.square();
+        *y -= x.pow(&params.alpha_inv);
+        *x += params.beta * y.square() + params.delta;
+    }
+}
+
+#[cfg(test)]
+mod anemoi_tests {
+    use super::*;
+    use crate::{field_hasher::FieldHasher, poseidon::pow_sbox};
+    use ark_ff::{Field, UniformRand};
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+
+    fn params(l: usize, rounds: usize) -> Arc<AnemoiParams<Scalar>> {
+        let mut rng = thread_rng();
+        // circ(1, 2) for l = 2, as in the Anemoi paper, and the identity for l = 1
+        let mds = (0..l)
+            .map(|i| {
+                (0..l)
+                    .map(|j| Scalar::from(if i == j { 1 } else { 2 }))
+                    .collect()
+            })
+            .collect();
+        let mut rc = || {
+            (0..rounds)
+                .map(|_| (0..l).map(|_| Scalar::rand(&mut rng)).collect())
+                .collect()
+        };
+        let (c, d) = (rc(), rc());
+        Arc::new(AnemoiParams::new(l, 5, rounds, Scalar::from(5), mds, c, d).unwrap())
+    }
+
+    #[test]
+    fn flystel() {
+        let mut rng = thread_rng();
+        let anemoi = Anemoi::new(&params(1, 4));
+        let p = &anemoi.params;
+        for _ in 0..5 {
+            let (x0, y0) = (Scalar::rand(&mut rng), Scalar::rand(&mut rng));
+            let (mut x, mut y) = (x0, y0);
+            anemoi.flystel(&mut x, &mut y);
+            // closed Flystel: undo the last step, then invert the middle one with x^alpha
+            let u = x - p.beta * y.square() - p.delta;
+            assert_eq!(u + p.beta * (y + u.pow(&p.alpha_inv)).square(), x0);
+            assert_eq!(pow_sbox(u.pow(&p.alpha_inv), 5), u);
+            assert_eq!(y0 - u.pow(&p.alpha_inv), y);
+        }
+    }
+
+    #[test]
+    fn permutation() {
+        let mut rng = thread_rng();
+        let anemoi = Anemoi::new(&params(2, 14));
+        let input: Vec<Scalar> = (0..4).map(|_| Scalar::rand(&mut rng)).collect();
+        let output = anemoi.permutation(input.to_owned()).unwrap();
+        assert_ne!(output, input);
+        assert!(anemoi.permutation(vec![Scalar::from(0); 3]).is_err());
+
+        // the linear layer of l = 2
+        let (mut x, mut y) = ([input[0], input[1]], [input[2], input[3]]);
+        anemoi.linear(&mut x, &mut y, &mut [Scalar::from(0); 2]);
+        let y_expected = [
+            input[3] + input[2].double() + input[0] + input[1].double(),
+            input[3].double() + input[2] + input[0].double() + input[1],
+        ];
+        assert_eq!(y, y_expected);
+        assert_eq!(
+            x,
+            [
+                input[0] + input[1].double() + y_expected[0],
+                input[0].double() + input[1] + y_expected[1]
+            ]
+        );
+
+        let mut state = vec![Scalar::from(0); 4];
+        state[1..].copy_from_slice(&input[..3]);
+        anemoi.permutation_in_place(&mut state).unwrap();
+        assert_eq!(anemoi.hash(&input[..3]).unwrap(), state[0]);
+        assert!(anemoi.compress(input[0], input[1]).is_err());
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let new = |l, g, c: Vec<Vec<Scalar>>| {
+            AnemoiParams::new(
+                l,
+                5,
+                2,
+                g,
+                vec![vec![Scalar::from(1); l]; l],
+                c.to_owned(),
+                c,
+            )
+        };
+        assert!(new(2, Scalar::from(5), vec![vec![Scalar::from(1); 2]; 2]).is_ok());
+        assert!(new(2, Scalar::from(0), vec![vec![Scalar::from(1); 2]; 2]).is_err());
+        assert!(matches!(
+            new(2, Scalar::from(5), vec![vec![Scalar::from(1); 2]; 3]),
+            Err(Error::RoundConstantShape {
+                expected: (2, 2),
+                got: (3, 2)
+            })
+        ));
+        // 3 divides p - 1
+        assert!(AnemoiParams::new(
+            1,
+            3,
+            1,
+            Scalar::from(5),
+            vec![vec![Scalar::from(1)]],
+            vec![vec![Scalar::from(1)]],
+            vec![vec![Scalar::from(1)]]
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    error::Error,
+    parameters::{check_mds, check_round_constants, inverse_exponent, matrix::Matrix},
+};
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+
+#[derive(Clone, Debug)]
+pub struct AnemoiParams<F: PrimeField> {
+    pub(crate) l: usize,            // number of columns, the statesize is 2 * l
+    pub(crate) alpha: usize,        // degree of the inverse of the open Flystel sbox
+    pub(crate) alpha_inv: Vec<u64>, // 1 / alpha mod p - 1
+    pub(crate) rounds: usize,
+    pub(crate) beta: F,        // Q_gamma(y) = beta * y^2
+    pub(crate) delta: F,       // Q_delta(y) = beta * y^2 + delta
+    pub(crate) mds: Matrix<F>, // l x l matrix applied to both halves
+    pub(crate) c: Vec<Vec<F>>, // round constants of the x half, l per round
+    pub(crate) d: Vec<Vec<F>>, // round constants of the y half, l per round
+}
+
+impl<F: PrimeField> AnemoiParams<F> {
+    /// Creates a new parameter set for a state of 2 * l elements. The Flystel uses the
+    /// quadratic functions Q_gamma(y) = g * y^2 and Q_delta(y) = g * y^2 + g^-1 with the
+    /// generator g of the field, and x^alpha has to be a permutation. The matrix has size l x l
+    /// and the round constants c and d contain one row of length l per round.
+    pub fn new(
+        l: usize,
+        alpha: usize,
+        rounds: usize,
+        g: F,
+        mds: Vec<Vec<F>>,
+        c: Vec<Vec<F>>,
+        d: Vec<Vec<F>>,
+    ) -> Result<Self, Error> {
+        if l == 0 || alpha < 3 || rounds == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        check_mds(l, &mds)?;
+        check_round_constants(rounds, l, &c)?;
+        check_round_constants(rounds, l, &d)?;
+        let alpha_inv = inverse_exponent::<F>(alpha)?;
+        let delta = g.inverse().ok_or(Error::InvalidParameters)?;
+
+        Ok(AnemoiParams {
+            l,
+            alpha,
+            alpha_inv,
+            rounds,
+            beta: g,
+            delta,
+            mds: Matrix::new(mds)?,
+            c,
+            d,
+        })
+    }
+
+    /// The statesize 2 * l.
+    pub fn t(&self) -> usize {
+        2 * self.l
+    }
+
+    /// The number of columns l.
+    pub fn l(&self) -> usize {
+        self.l
+    }
+
+    /// The degree of the inverse of the Flystel, the Flystel itself uses x^(1/alpha).
+    pub fn alpha(&self) -> usize {
+        self.alpha
+    }
+
+    /// The number of rounds, the permutation applies the linear layer once more after them.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    pub fn mds(&self) -> &Matrix<F> {
+        &self.mds
+    }
+}
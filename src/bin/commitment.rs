@@ -1,9 +1,14 @@
-// cargo run --release --bin commitment -- --guess <GUESS> --rand <RAND> --address <ADDRESS>
+// cargo run --release --bin commitment -- --guess <GUESS> --rand <RAND> --address <ADDRESS> [--require-checksum] [--json]
 // e.g., cargo run --release --bin commitment -- --guess 5 --rand 0xa --address 0x70997970c51812dc3a010c7d01b50e0d17dc79c8
 
 use clap::Parser;
-use num_bigint::BigUint;
-use poseidon_rust::guessing_game_commit;
+use poseidon_rust::{
+    cli::{parse_address, print_json},
+    guessing_game::parse_hex,
+    guessing_game_commit_bytes,
+    output::Digest,
+};
+use serde_json::json;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -12,30 +17,52 @@ struct Args {
     #[arg(short, long)]
     guess: u16,
 
-    /// randomness as hexstring
+    /// randomness as hexstring (with or without 0x prefix)
     #[arg(short, long)]
     rand: String,
 
-    /// randomness as hexstring
+    /// Ethereum address (40 hex digits), mixed-case addresses have to match their EIP-55 checksum
     #[arg(short, long)]
     address: String,
+
+    /// Rejects addresses without EIP-55 checksum
+    #[arg(long)]
+    require_checksum: bool,
+
+    /// Prints the inputs and the commitment together with the parameter set as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+fn fail(msg: &str, e: impl std::fmt::Display) -> ! {
+    eprintln!("{msg}: {e}");
+    std::process::exit(1);
 }
 
 fn main() {
     let args = Args::parse();
 
-    let commitment = guessing_game_commit(args.guess, &args.address, &args.rand);
-    let commitment = match commitment {
-        Ok(c) => c,
-        Err(e) => {
-            println!("Failed to parse the inputs: {:?}", e);
-            std::process::exit(1);
-        }
-    };
-    let biguint: BigUint = commitment.into(); // For output in hex
+    let address = parse_address(&args.address, args.require_checksum)
+        .unwrap_or_else(|e| fail("Failed to parse the address", e));
+    let rand = parse_hex(&args.rand).unwrap_or_else(|e| fail("Failed to parse the rand", e));
+    let commitment = guessing_game_commit_bytes(args.guess, address.as_bytes(), rand)
+        .unwrap_or_else(|e| fail("Failed to compute the commitment", e));
+    let commitment = Digest(commitment).to_string();
+    let address = address.to_checksum_string();
 
+    if args.json {
+        print_json(&json!({
+            "parameters": "bn254/circom_t4",
+            "input_encoding": { "guess": "decimal", "address": "hex", "rand": "hex" },
+            "guess": args.guess,
+            "address": address,
+            "rand": args.rand,
+            "commitment": commitment,
+        }));
+        return;
+    }
     println!("guess: {}", args.guess);
-    println!("address: {}", args.address);
+    println!("address: {address}");
     println!("rand: {}", args.rand);
-    println!("commitment: 0x{}", biguint.to_str_radix(16));
+    println!("commitment: {commitment}");
 }
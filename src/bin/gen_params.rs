@@ -0,0 +1,216 @@
+// cargo run --release --bin gen_params -- --prime <PRIME> -t <statesize> [-d <degree>] [--security <bits>] [--format <FORMAT>] [-n <name>] [--scalar <path>] [-o <output file>]
+// e.g., cargo run --release --bin gen_params -- --prime grumpkin -t 5 -o grumpkin_t5.rs
+//       cargo run --release --bin gen_params -- --prime 0x7fffffff -t 16 --format json
+//       cargo run --release --bin gen_params -- --prime pallas -t 3 --format halo2
+
+use ark_ff::PrimeField;
+use clap::{Parser, ValueEnum};
+use num_bigint::BigUint;
+use poseidon_rust::{
+    codegen::{halo2::halo2_spec, rust::rust_module},
+    goldilocks::field::Goldilocks,
+    grumpkin,
+    parameters::{
+        generate::{generate, generate_with_rounds},
+        PoseidonParams,
+    },
+    pasta::fields::{Fp, Fq},
+    small_fields::fields::{BabyBear, Mersenne31},
+};
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum OutputFormat {
+    /// A Rust module defining the parameters as lazy_static
+    #[default]
+    Rust,
+    /// The JSON format of the parameter files in params/
+    Json,
+    /// The binary encoding of PoseidonParams::to_bytes
+    Bin,
+    /// A Rust module with the constants and a Spec implementation for halo2_gadgets::poseidon
+    Halo2,
+}
+
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// The prime field, either by name (bn254, grumpkin, pallas, vesta, goldilocks, babybear,
+    /// mersenne31) or by its modulus (in decimal, or in hex with 0x prefix)
+    #[arg(short, long)]
+    prime: String,
+
+    /// Statesize
+    #[arg(short, long)]
+    t: usize,
+
+    /// Degree of the sbox x^d
+    #[arg(short, long, default_value_t = 5)]
+    d: usize,
+
+    /// Security level in bits, which determines the round numbers
+    #[arg(short, long, default_value_t = 128)]
+    security: usize,
+
+    /// Overrides the number of full rounds (requires --rounds-p)
+    #[arg(long, requires = "rounds_p")]
+    rounds_f: Option<usize>,
+
+    /// Overrides the number of partial rounds (requires --rounds-f)
+    #[arg(long, requires = "rounds_f")]
+    rounds_p: Option<usize>,
+
+    /// The output format
+    #[arg(short, long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// Name of the static in the Rust module (default: POSEIDON_<PRIME>_<t>_PARAMS), or of the
+    /// Spec struct for halo2 (default: Poseidon<Prime>T<t>)
+    #[arg(short, long)]
+    name: Option<String>,
+
+    /// Path of the field type in the generated module (default: the type of this crate, or of
+    /// pasta_curves and halo2curves for halo2, where the small fields have no default)
+    #[arg(long)]
+    scalar: Option<String>,
+
+    /// Output file (default: stdout)
+    #[arg(short, long)]
+    output: Option<String>,
+}
+
+fn modulus<F: PrimeField>() -> BigUint {
+    F::MODULUS.into()
+}
+
+fn parse_modulus(prime: &str) -> Option<BigUint> {
+    match prime.strip_prefix("0x") {
+        Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16),
+        None => BigUint::parse_bytes(prime.as_bytes(), 10),
+    }
+}
+
+fn run<F: PrimeField>(
+    args: &Args,
+    field: &str,
+    scalar: &str,
+    halo2_scalar: Option<&str>,
+) -> Vec<u8> {
+    let params: PoseidonParams<F> = match (args.rounds_f, args.rounds_p) {
+        (Some(rounds_f), Some(rounds_p)) => {
+            generate_with_rounds(args.t, args.d, rounds_f, rounds_p)
+        }
+        _ => generate(args.t, args.d, args.security),
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("Failed to generate the parameters: {e}");
+        std::process::exit(1);
+    });
+
+    match args.format {
+        OutputFormat::Rust => {
+            let name = args
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("POSEIDON_{}_{}_PARAMS", field.to_uppercase(), args.t));
+            rust_module(&params, &name, args.scalar.as_deref().unwrap_or(scalar))
+                .expect("Failed to generate the module")
+                .into_bytes()
+        }
+        OutputFormat::Json => (params.to_params_json() + "\n").into_bytes(),
+        OutputFormat::Bin => params.to_bytes(),
+        OutputFormat::Halo2 => {
+            let Some(scalar) = args.scalar.as_deref().or(halo2_scalar) else {
+                eprintln!("No halo2 field type is known for {field}, pass it with --scalar");
+                std::process::exit(1);
+            };
+            let name = args.name.clone().unwrap_or_else(|| {
+                let mut chars = field.chars();
+                let first = chars.next().map(|c| c.to_ascii_uppercase());
+                format!(
+                    "Poseidon{}{}T{}",
+                    first.unwrap_or_default(),
+                    chars.as_str(),
+                    args.t
+                )
+            });
+            halo2_spec(&params, &name, scalar)
+                .expect("Failed to generate the module")
+                .into_bytes()
+        }
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let prime = args.prime.to_lowercase();
+    let by_modulus = parse_modulus(&prime);
+    let is = |name: &str, modulus: BigUint| prime == name || by_modulus.as_ref() == Some(&modulus);
+
+    let out = if is("bn254", modulus::<ark_bn254::Fr>()) {
+        run::<ark_bn254::Fr>(
+            &args,
+            "bn254",
+            "ark_bn254::Fr",
+            Some("halo2curves::bn256::Fr"),
+        )
+    } else if is("grumpkin", modulus::<grumpkin::Fr>()) {
+        run::<grumpkin::Fr>(
+            &args,
+            "grumpkin",
+            "poseidon_rust::grumpkin::Fr",
+            Some("halo2curves::grumpkin::Fr"),
+        )
+    } else if is("pallas", modulus::<Fp>()) {
+        run::<Fp>(
+            &args,
+            "pallas",
+            "poseidon_rust::pasta::fields::Fp",
+            Some("pasta_curves::Fp"),
+        )
+    } else if is("vesta", modulus::<Fq>()) {
+        run::<Fq>(
+            &args,
+            "vesta",
+            "poseidon_rust::pasta::fields::Fq",
+            Some("pasta_curves::Fq"),
+        )
+    } else if is("goldilocks", modulus::<Goldilocks>()) {
+        run::<Goldilocks>(
+            &args,
+            "goldilocks",
+            "poseidon_rust::goldilocks::field::Goldilocks",
+            None,
+        )
+    } else if is("babybear", modulus::<BabyBear>()) {
+        run::<BabyBear>(
+            &args,
+            "babybear",
+            "poseidon_rust::small_fields::fields::BabyBear",
+            None,
+        )
+    } else if is("mersenne31", modulus::<Mersenne31>()) {
+        run::<Mersenne31>(
+            &args,
+            "mersenne31",
+            "poseidon_rust::small_fields::fields::Mersenne31",
+            None,
+        )
+    } else {
+        eprintln!(
+            "Unsupported prime {}: only the fields implemented in this crate are supported",
+            args.prime
+        );
+        std::process::exit(1);
+    };
+
+    match args.output {
+        Some(path) => std::fs::write(path, out).expect("Failed to write the output file"),
+        None => {
+            use std::io::Write;
+            std::io::stdout()
+                .write_all(&out)
+                .expect("Failed to write to stdout");
+        }
+    }
+}
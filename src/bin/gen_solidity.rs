@@ -0,0 +1,47 @@
+// cargo run --release --bin gen_solidity -- -t <statesize> [-n <library name>] [-o <output file>] [--json]
+// e.g., cargo run --release --bin gen_solidity -- -t 3 -o PoseidonT3.sol
+
+use clap::Parser;
+use poseidon_rust::{circom::params_for_arity, codegen::solidity::solidity_library};
+use serde_json::json;
+
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// Statesize of the circom parameters for BN254 (2..=17)
+    #[arg(short, long)]
+    t: usize,
+
+    /// Name of the Solidity library (default: PoseidonT<t>)
+    #[arg(short, long)]
+    name: Option<String>,
+
+    /// Output file (default: stdout)
+    #[arg(short, long)]
+    output: Option<String>,
+
+    /// Emits the library name, the parameter set, and the code as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let params = params_for_arity(args.t.saturating_sub(1)).expect("Unsupported statesize");
+    let name = args.name.unwrap_or_else(|| format!("PoseidonT{}", args.t));
+    let mut code = solidity_library(params, &name).expect("Failed to generate the library");
+    if args.json {
+        let value = json!({
+            "parameters": format!("bn254/circom_t{}", args.t),
+            "name": name,
+            "code": code,
+        });
+        code = serde_json::to_string_pretty(&value).expect("serializing cannot fail") + "\n";
+    }
+
+    match args.output {
+        Some(path) => std::fs::write(path, code).expect("Failed to write the output file"),
+        None => print!("{code}"),
+    }
+}
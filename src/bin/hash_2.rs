@@ -1,37 +1,47 @@
-// cargo run --release --bin hash_2 -- -a <input_a> -b <input_b>
+// cargo run --release --bin hash_2 -- -a <input_a> -b <input_b> [--json]
 // e.g., cargo run --release --bin hash_2 -- -a 54939530 -b 190384929
+//
+// Alias of `poseidon -- --format decimal <input_a> <input_b>`, which is kept for existing scripts.
 
-use ark_bn254::Fr;
 use clap::Parser;
-use num_traits::identities::Zero;
-use poseidon_rust::{bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, poseidon::Poseidon};
-use std::str::FromStr;
+use poseidon_rust::cli::{print_json, Field, Format, InputEncoding};
+use serde_json::json;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 struct Args {
-    /// First input (in decimal)
+    /// First input (in decimal, or in hex with 0x prefix)
     #[arg(short, long)]
     a: String,
 
-    /// Second input (in decimal)
+    /// Second input (in decimal, or in hex with 0x prefix)
     #[arg(short, long)]
     b: String,
+
+    /// Prints the inputs and the hash together with the parameter set as JSON
+    #[arg(long)]
+    json: bool,
 }
 
 fn main() {
     let args = Args::parse();
 
-    let input_a = Fr::from_str(&args.a).expect("Failed to parse the first input");
-    let input_b = Fr::from_str(&args.b).expect("Failed to parse the second input");
-
-    let input = vec![Fr::zero(), input_a, input_b];
-    let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
-    let hash = poseidon
-        .permutation(input)
-        .expect("Failed to hash the inputs")[0];
+    let output = Field::Bn254
+        .hash(&[&args.a, &args.b], Format::Decimal)
+        .expect("Failed to hash the inputs");
 
-    println!("input_a: {}", input_a);
-    println!("input_b: {}", input_b);
-    println!("hash: {}", hash);
+    if args.json {
+        print_json(&json!({
+            "parameters": output.parameters,
+            "input_encoding": InputEncoding::Arguments.name(),
+            "input_a": args.a,
+            "input_b": args.b,
+            "format": Format::Decimal.name(),
+            "hash": output.hash,
+        }));
+    } else {
+        println!("input_a: {}", args.a);
+        println!("input_b: {}", args.b);
+        println!("hash: {}", output.hash);
+    }
 }
@@ -0,0 +1,166 @@
+// cargo run --release --bin merkle -- root --input <LEAVES>
+// cargo run --release --bin merkle -- prove --input <LEAVES> --index <N> [--output <PROOF>]
+// cargo run --release --bin merkle -- verify --proof <PROOF>
+// With --json, the root and the verification result are printed as JSON, and the proof
+// additionally contains the parameter set and the input encoding.
+// e.g., cargo run --release --bin merkle -- prove --input leaves.json --index 2 -o proof.json
+//
+// The leaves are read from a file (- for stdin) with one leaf per line or a JSON array of field
+// elements (numbers, or strings in decimal or in hex with 0x prefix), and the tree is the binary Merkle tree with the circom t=3 compression over BN254,
+// where the leaves are padded with zeros to the next power of two.
+
+use ark_bn254::Fr;
+use clap::{Parser, Subcommand};
+use poseidon_rust::{
+    bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+    cli::{open_input, print_json, read_elements, InputEncoding, ProofFile},
+    field_to_hex_string,
+    merkle::tree::MerkleTree,
+    poseidon::Poseidon,
+};
+use serde_json::json;
+use std::io::Read;
+
+// the identifier of the parameter set of the tree
+const PARAMETERS: &str = "bn254/circom_t3";
+
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Prints the results as JSON, including the parameter set and the input encoding
+    #[arg(long, global = true)]
+    json: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Prints the root of the tree over the leaves
+    Root {
+        /// File with the leaves (- for stdin)
+        #[arg(short, long)]
+        input: String,
+    },
+    /// Prints the inclusion proof of a leaf as JSON
+    Prove {
+        /// File with the leaves (- for stdin)
+        #[arg(short, long)]
+        input: String,
+
+        /// Index of the leaf
+        #[arg(long)]
+        index: usize,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Verifies a proof written by `prove` against its root
+    Verify {
+        /// JSON file with the proof (- for stdin)
+        #[arg(short, long)]
+        proof: String,
+
+        /// Depth of the tree, which the number of siblings has to match
+        #[arg(long)]
+        depth: usize,
+    },
+}
+
+fn read(path: &str) -> String {
+    let mut str = String::new();
+    open_input(path)
+        .and_then(|mut reader| {
+            reader
+                .read_to_string(&mut str)
+                .map_err(|e| e.to_string().into())
+        })
+        .unwrap_or_else(|e| fail("Failed to read the input file", e));
+    str
+}
+
+fn fail(msg: &str, e: impl std::fmt::Display) -> ! {
+    eprintln!("{msg}: {e}");
+    std::process::exit(1);
+}
+
+fn build_tree(path: &str) -> (MerkleTree<Fr>, InputEncoding) {
+    let (leaves, encoding) = open_input(path)
+        .and_then(read_elements)
+        .unwrap_or_else(|e| fail("Failed to read the leaves", e));
+    let tree = MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, leaves)
+        .unwrap_or_else(|e| fail("Failed to build the tree", e));
+    (tree, encoding)
+}
+
+fn main() {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Root { input } => {
+            let (tree, encoding) = build_tree(&input);
+            let root = field_to_hex_string(&tree.root());
+            if args.json {
+                print_json(&json!({
+                    "parameters": PARAMETERS,
+                    "input_encoding": encoding.name(),
+                    "num_leaves": tree.num_leaves(),
+                    "depth": tree.depth(),
+                    "root": root,
+                }));
+            } else {
+                println!("{root}");
+            }
+        }
+        Command::Prove {
+            input,
+            index,
+            output,
+        } => {
+            let (tree, encoding) = build_tree(&input);
+            let proof = tree
+                .proof(index)
+                .unwrap_or_else(|e| fail("Failed to compute the proof", e));
+            let file = ProofFile {
+                root: tree.root(),
+                leaf: tree.leaves()[index],
+                proof,
+            };
+            let mut value = file.to_value();
+            if args.json {
+                value["parameters"] = PARAMETERS.into();
+                value["input_encoding"] = encoding.name().into();
+            }
+            let json = serde_json::to_string_pretty(&value).expect("serializing cannot fail");
+            match output {
+                Some(path) => std::fs::write(path, json + "\n")
+                    .unwrap_or_else(|e| fail("Failed to write the output file", e)),
+                None => println!("{json}"),
+            }
+        }
+        Command::Verify { proof, depth } => {
+            let file = ProofFile::<Fr>::from_json(&read(&proof))
+                .unwrap_or_else(|e| fail("Failed to parse the proof", e));
+            let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+            let valid =
+                MerkleTree::verify_proof(&poseidon, &file.root, depth, file.leaf, &file.proof)
+                    .unwrap_or_else(|e| fail("Failed to verify the proof", e));
+            if args.json {
+                print_json(&json!({
+                    "parameters": PARAMETERS,
+                    "root": field_to_hex_string(&file.root),
+                    "leaf": field_to_hex_string(&file.leaf),
+                    "index": file.proof.index,
+                    "valid": valid,
+                }));
+            } else {
+                println!("{}", if valid { "valid" } else { "invalid" });
+            }
+            if !valid {
+                std::process::exit(1);
+            }
+        }
+    }
+}
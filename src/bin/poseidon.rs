@@ -0,0 +1,88 @@
+// cargo run --release --bin poseidon -- [--field <FIELD>] [--format <FORMAT>] [--json] <INPUTS>...
+// cargo run --release --bin poseidon -- [--chain] --input-file <FILE>
+// e.g., cargo run --release --bin poseidon -- 1 0x2 3
+//       cargo run --release --bin poseidon -- --field goldilocks --format decimal 1 2 3
+//       seq 1 100000 | cargo run --release --bin poseidon -- --chain --input-file -
+
+use clap::{ArgGroup, Parser};
+use poseidon_rust::cli::{open_input, print_json, read_inputs, Field, Format, InputEncoding};
+use serde_json::json;
+
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+#[command(group(ArgGroup::new("input").required(true).args(["inputs", "input_file"])))]
+struct Args {
+    /// The inputs (in decimal, or in hex with 0x prefix). The parameter set is selected by the
+    /// number of inputs
+    inputs: Vec<String>,
+
+    /// Reads the inputs from a file (- for stdin), either one per line or as JSON array
+    #[arg(short, long)]
+    input_file: Option<String>,
+
+    /// Folds the inputs with the t=3 hash chain instead of hashing them at once, inputs given one
+    /// per line are streamed
+    #[arg(long)]
+    chain: bool,
+
+    /// The field of the inputs and the hash
+    #[arg(short, long, value_enum, default_value_t)]
+    field: Field,
+
+    /// The output format of the hash
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+
+    /// Prints the hash together with the parameter set and the input encoding as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+fn fail(msg: &str, e: impl std::fmt::Display) -> ! {
+    eprintln!("{msg}: {e}");
+    std::process::exit(1);
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let result = match (&args.input_file, args.chain) {
+        (Some(path), true) => {
+            open_input(path).and_then(|reader| args.field.hash_chain(reader, args.format))
+        }
+        (None, true) => args
+            .field
+            .hash_chain(args.inputs.join("\n").as_bytes(), args.format)
+            .map(|(output, _)| (output, InputEncoding::Arguments)),
+        (Some(path), false) => {
+            let (inputs, encoding) = open_input(path)
+                .and_then(read_inputs)
+                .unwrap_or_else(|e| fail("Failed to read the inputs", e));
+            let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+            args.field
+                .hash(&inputs, args.format)
+                .map(|output| (output, encoding))
+        }
+        (None, false) => {
+            let inputs: Vec<&str> = args.inputs.iter().map(String::as_str).collect();
+            args.field
+                .hash(&inputs, args.format)
+                .map(|output| (output, InputEncoding::Arguments))
+        }
+    };
+    let (output, encoding) = result.unwrap_or_else(|e| fail("Failed to hash the inputs", e));
+
+    if args.json {
+        print_json(&json!({
+            "parameters": output.parameters,
+            "field": args.field.name(),
+            "mode": if args.chain { "chain" } else { "hash" },
+            "input_encoding": encoding.name(),
+            "num_inputs": output.num_inputs,
+            "format": args.format.name(),
+            "hash": output.hash,
+        }));
+    } else {
+        println!("{}", output.hash);
+    }
+}
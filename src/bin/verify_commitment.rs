@@ -0,0 +1,75 @@
+// cargo run --release --bin verify_commitment -- --guess <GUESS> --rand <RAND> --address <ADDRESS> --commitment <COMMITMENT> [--require-checksum] [--json]
+// e.g., cargo run --release --bin verify_commitment -- --guess 5 --rand 0xa --address 0x70997970c51812dc3a010c7d01b50e0d17dc79c8 --commitment 0x2346b3b208c9e65959af9824ccab4da69ae27d222204fcf0ace7f725e02e512d
+// Exits with status 0 if the commitment opens correctly and with status 1 otherwise.
+
+use ark_bn254::Fr;
+use clap::Parser;
+use poseidon_rust::{
+    cli::{parse_address, print_json},
+    guessing_game::{parse_hex, verify_commitment},
+};
+use serde_json::json;
+
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// The guess
+    #[arg(short, long)]
+    guess: u16,
+
+    /// randomness as hexstring (with or without 0x prefix)
+    #[arg(short, long)]
+    rand: String,
+
+    /// Ethereum address (40 hex digits), mixed-case addresses have to match their EIP-55 checksum
+    #[arg(short, long)]
+    address: String,
+
+    /// Rejects addresses without EIP-55 checksum
+    #[arg(long)]
+    require_checksum: bool,
+
+    /// The claimed commitment as hexstring (with or without 0x prefix)
+    #[arg(short, long)]
+    commitment: String,
+
+    /// Prints the inputs and the result together with the parameter set as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+fn fail(msg: &str, e: impl std::fmt::Display) -> ! {
+    eprintln!("{msg}: {e}");
+    std::process::exit(1);
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let address = parse_address(&args.address, args.require_checksum)
+        .unwrap_or_else(|e| fail("Failed to parse the address", e));
+    let rand = parse_hex(&args.rand).unwrap_or_else(|e| fail("Failed to parse the rand", e));
+    let commitment =
+        parse_hex(&args.commitment).unwrap_or_else(|e| fail("Failed to parse the commitment", e));
+    let valid = verify_commitment(&commitment, Fr::from(args.guess), address.as_bytes(), rand)
+        .unwrap_or_else(|e| fail("Failed to compute the commitment", e));
+
+    if args.json {
+        print_json(&json!({
+            "parameters": "bn254/circom_t4",
+            "input_encoding": { "guess": "decimal", "address": "hex", "rand": "hex", "commitment": "hex" },
+            "guess": args.guess,
+            "address": address.to_checksum_string(),
+            "rand": args.rand,
+            "commitment": args.commitment,
+            "valid": valid,
+        }));
+    } else if valid {
+        println!("The commitment opens correctly");
+    } else {
+        println!("The commitment does NOT open to the given guess, address, and randomness");
+    }
+    if !valid {
+        std::process::exit(1);
+    }
+}
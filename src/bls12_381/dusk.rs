@@ -0,0 +1,285 @@
+//! # dusk-poseidon
+//! The hash of dusk-network's [`dusk-poseidon`](https://github.com/dusk-network/poseidon252)
+//! (Hades-252) over the BLS12-381 scalar field, such that users migrating from that crate get
+//! identical digests.
+//!
+//! dusk's `Hash` is a SAFE sponge with rate 4 and capacity 1 over the Hades-252 permutation
+//! (t = 5, x^5, R_F = 8, R_P = 60). The capacity element is the tag of the IO pattern, i.e., the
+//! BLAKE2b-512 hash of the encoded absorb and squeeze calls and the [`Domain`], reduced modulo p.
+//! The inputs are absorbed by addition into the rate without padding.
+//!
+//! Hades-252 applies the S-box of the partial rounds to the last element of the state, while
+//! [`Poseidon`] applies it to the first one. The parameters in
+//! [`hades_t5`](super::hades_t5) are therefore stored in reversed state order, and
+//! [`Hades::permutation`] reverses the state before and after the permutation.
+
+use super::{hades_t5::POSEIDON_HADES_5_PARAMS, Fr};
+use crate::{error::Error, poseidon::Poseidon};
+use alloc::vec::Vec;
+use ark_ff::{PrimeField, Zero};
+use blake2::{Blake2b512, Digest};
+
+const WIDTH: usize = 5;
+const RATE: usize = WIDTH - 1;
+const ABSORB_FLAG: u32 = 1 << 31;
+
+/// The domain separator of dusk's `Hash`, which is encoded into the tag of the sponge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Domain {
+    /// The nodes of a Merkle tree with arity 4, the input has to be exactly 4 elements.
+    Merkle4,
+    /// The nodes of a Merkle tree with arity 2, the input has to be exactly 2 elements.
+    Merkle2,
+    /// The hash used by dusk's encryption.
+    Encryption,
+    /// Any other input.
+    Other,
+}
+
+impl Domain {
+    fn value(self) -> u64 {
+        match self {
+            Domain::Merkle4 => (1 << 4) - 1,
+            Domain::Merkle2 => (1 << 2) - 1,
+            Domain::Encryption => 1 << 32,
+            Domain::Other => 0,
+        }
+    }
+}
+
+/// The Hades-252 permutation and the sponge of `dusk-poseidon`.
+#[derive(Clone, Debug)]
+pub struct Hades {
+    poseidon: Poseidon<Fr>,
+}
+
+impl Default for Hades {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hades {
+    pub fn new() -> Self {
+        Hades {
+            poseidon: Poseidon::new(&POSEIDON_HADES_5_PARAMS),
+        }
+    }
+
+    /// Applies the Hades-252 permutation in the state order of dusk-poseidon.
+    pub fn permutation(&self, state: &mut [Fr; WIDTH]) -> Result<(), Error> {
+        state.reverse();
+        self.poseidon.permutation_in_place(state)?;
+        state.reverse();
+        Ok(())
+    }
+
+    /// Hashes the input to a single element, as dusk's `Hash::digest(domain, input)[0]`.
+    pub fn digest(&self, domain: Domain, input: &[Fr]) -> Result<Fr, Error> {
+        Ok(self.hash(domain, input, 1)?[0])
+    }
+
+    /// Hashes the input to `output_len` elements, as dusk's `Hash` with the given output length.
+    /// Splitting the input over several calls to `Hash::update` does not change the digest, since
+    /// consecutive absorb calls are aggregated in the tag.
+    pub fn hash(&self, domain: Domain, input: &[Fr], output_len: usize) -> Result<Vec<Fr>, Error> {
+        let merkle_len = match domain {
+            Domain::Merkle2 => Some(2),
+            Domain::Merkle4 => Some(4),
+            Domain::Encryption | Domain::Other => None,
+        };
+        if merkle_len.is_some_and(|len| input.len() != len || output_len != 1) {
+            return Err(Error::IOPatternViolation);
+        }
+        let in_range = |len: usize| len > 0 && len < ABSORB_FLAG as usize;
+        if !in_range(input.len()) || !in_range(output_len) {
+            return Err(Error::IOPatternViolation);
+        }
+
+        let mut hasher = Blake2b512::new();
+        hasher.update((ABSORB_FLAG | input.len() as u32).to_be_bytes());
+        hasher.update((output_len as u32).to_be_bytes());
+        hasher.update(domain.value().to_be_bytes());
+        let tag = Fr::from_le_bytes_mod_order(&hasher.finalize());
+        self.sponge(tag, input, output_len)
+    }
+
+    fn sponge(&self, tag: Fr, input: &[Fr], output_len: usize) -> Result<Vec<Fr>, Error> {
+        let mut state = [Fr::zero(); WIDTH];
+        state[0] = tag;
+        let mut pos = 0;
+        for el in input {
+            if pos == RATE {
+                self.permutation(&mut state)?;
+                pos = 0;
+            }
+            state[pos + 1] += el;
+            pos += 1;
+        }
+
+        // squeezing always starts with a permutation
+        let mut output = Vec::with_capacity(output_len);
+        let mut pos = RATE;
+        for _ in 0..output_len {
+            if pos == RATE {
+                self.permutation(&mut state)?;
+                pos = 0;
+            }
+            output.push(state[pos + 1]);
+            pos += 1;
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod dusk_tests {
+    use super::{Domain, Fr, Hades};
+    use crate::{error::Error, field_from_hex_string};
+    use ark_ff::{One, PrimeField, Zero};
+    use std::vec::Vec;
+
+    fn from_hex(hex: &str) -> Fr {
+        field_from_hex_string(hex).unwrap()
+    }
+
+    // dusk-bytes encodes the scalars as little-endian hex
+    fn from_le_hex(hex: &str) -> Fr {
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+        Fr::from_le_bytes_mod_order(&bytes)
+    }
+
+    // The `poseidon_hash` test of dusk-poseidon's `src/hades.rs`, which uses the sponge with a zero
+    // tag and absorbs a padding of one after the input.
+    #[test]
+    fn dusk_test_suite() {
+        let inputs: Vec<Fr> = [
+            "bb67ed265bf1db490ded2e1ede55c0d14c55521509dc73f9c354e98ab76c9625",
+            "7e74220084d75e10c89e9435d47bb5b8075991b2e29be3b84421dac3b1ee6007",
+            "5ce5481a4d78cca03498f72761da1b9f1d2aa8fb300be39f0e4fe2534f9d4308",
+            "b1e710e3c4a8c35154b0ce4e4f4af6f498ebd79f8e7cdf3150372c7501be250b",
+            "33c9e2025f86b5d82149f1ab8e20a168fc3d99d09b48cbce0286db8752cc3306",
+            "e98206bfdce791e4e5144079b997d4fc25006194b35655f0e48490b26e24ea35",
+            "86d2a95cc552de8d5bb20bd4a407fee5ffdc314e93dfe6b2dc792bc71fd8cc2d",
+            "4edd8307ce28a8c70963d20a7bc28df1e1720bbbc93878a18bd07fad7d51fa15",
+            "eabc7a296704a68aa01f95adc85f6dd758b175745336d8fc795a17984024b21e",
+            "cfc108673c93df305e31c283b9c767b7097ae4e174a223e0c24b15a67b701a3a",
+        ]
+        .iter()
+        .map(|hex| from_le_hex(hex))
+        .collect();
+        let expected = [
+            (
+                3,
+                "0x26abf2d0476f154e69bf19740092fe36265680c294462b8e759ad73a99567dd5",
+            ),
+            (
+                4,
+                "0x1cc40219c7ec92919d6db7a41cd41953333a2ed544606daca182e4eaa6c7db2d",
+            ),
+            (
+                5,
+                "0x707c98a0e9a6e4832ac33ee08811bce122017a58dbbbf66a2f6fcdc69d45462d",
+            ),
+            (
+                6,
+                "0x26905a794d3d2fb0c3ed2276abc696c27a5bfdea7f106e596cbeedd86891c461",
+            ),
+            (
+                8,
+                "0x1b98a2c5f1fe54d21b5ce9bf0dcc99ea8784a64f3c544fa06d3f73569741006e",
+            ),
+            (
+                10,
+                "0x211b7ea21c9afca93dabdfbda8b2d5275b2dd802fed87bb431e98557c61667d2",
+            ),
+        ];
+        let hades = Hades::new();
+        for (len, expected) in expected {
+            let mut input = inputs[..len].to_vec();
+            input.push(Fr::one());
+            let hash = hades.sponge(Fr::zero(), &input, 1).unwrap();
+            assert_eq!(hash, [from_hex(expected)]);
+        }
+    }
+
+    // The outputs of dusk-poseidon 0.40.0's `Hash` for the inputs [1, 2, .., len].
+    #[test]
+    fn hash() {
+        let inputs: Vec<Fr> = (1..=10).map(Fr::from).collect();
+        let hades = Hades::new();
+        let other = [
+            (
+                1,
+                "0x32862a6ada85b7757e44fc4eae2fdf4e96ccd63a6431e807f2a4a3817513bb3a",
+            ),
+            (
+                3,
+                "0x6b977799aca478656a619e33d07ac2e7e975cbc809c759d7beaa44aa588755f5",
+            ),
+            (
+                4,
+                "0x58ece8f1b769dba187317c5b335e2e969012881cfe988d7b5e275a736c24763a",
+            ),
+            (
+                5,
+                "0x301d08fa186dd1b0071c141efaa53f49ec4bab6a1f6d189d5963282cc69fbb5a",
+            ),
+            (
+                10,
+                "0x359038c691a2d97086eb27f2c523ba7f706d1828227506a83a03053e8c078983",
+            ),
+        ];
+        for (len, expected) in other {
+            let hash = hades.digest(Domain::Other, &inputs[..len]).unwrap();
+            assert_eq!(hash, from_hex(expected));
+        }
+
+        let merkle2 = hades.digest(Domain::Merkle2, &inputs[..2]).unwrap();
+        assert_eq!(
+            merkle2,
+            from_hex("0x1e9c24ac667e5a6093d8704ba658cc520b61ef2631745f80b60276422715b767")
+        );
+        let merkle4 = hades.digest(Domain::Merkle4, &inputs[..4]).unwrap();
+        assert_eq!(
+            merkle4,
+            from_hex("0x6c8d39beb2d4f4c85bd6e06291cb41e08966b578f3242c7e536e2eb3a03dba07")
+        );
+
+        // updated with [1, 2, 3] and [4, 5], squeezing 6 elements
+        let hash = hades.hash(Domain::Other, &inputs[..5], 6).unwrap();
+        let expected: Vec<Fr> = [
+            "0x218784f151209ea1e95f6a1de85e4c7612b62b0ca3ffbc5f033eabcd144b12d9",
+            "0x6a03fe4dd1658b67f75801ac6ae66f231a52437f20a23918e49fc1a667b4886f",
+            "0x005c3a995c32fc206d69211580e34acd49356a5b50fdf0ba931434902284f823",
+            "0x147977af2f3efd1473693513ba223c7d9a9dde5f43c554b0a6b2825db27a15a9",
+            "0x4adce0ea78c15d3401cfa96f4211f662355bd368c036a41155496f0ebc815c71",
+            "0x06e73a30c77312199858c14a2ba2fe83b2ab800a6c5ddf48d2007d7131effb47",
+        ]
+        .iter()
+        .map(|hex| from_hex(hex))
+        .collect();
+        assert_eq!(hash, expected);
+    }
+
+    #[test]
+    fn io_pattern() {
+        let hades = Hades::new();
+        let inputs = [Fr::one(); 4];
+        for (domain, input, output_len) in [
+            (Domain::Merkle2, &inputs[..3], 1),
+            (Domain::Merkle4, &inputs[..2], 1),
+            (Domain::Merkle4, &inputs[..], 2),
+            (Domain::Other, &inputs[..0], 1),
+            (Domain::Other, &inputs[..], 0),
+        ] {
+            assert!(matches!(
+                hades.hash(domain, input, output_len),
+                Err(Error::IOPatternViolation)
+            ));
+        }
+    }
+}
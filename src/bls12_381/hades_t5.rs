@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4]) = [0x2f2095e1cce1d99de452b2ed1c9b5b841bc16530170d64e5df3d3a7c7fddb1ac, 0x710f0fd0e2cc52ad3e0dbb00fdb98435976a8d6f99d0429b40ec762ab40f574c, 0x235b6f2bb20db22b620eea122e60fb27708de917fa3be5251de52b85ee99ed6d, 0x22f5fff4224872ff4716a013b71891810b1fd115ab380bcadba6e5ef73ae7b01, 0x67fcd2e4d33a44a1cd810e3dfeb8084b198d84468f11c144c788f6c8068a8457];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::bls12_381::Fr;
+
+lazy_static! {
+    pub static ref POSEIDON_HADES_5_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            5,
+            5,
+            8,
+            60,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/bls12_381/hades_t5.bin")),
+        )
+        .unwrap()
+    );
+}
@@ -0,0 +1,221 @@
+//! # BLS12-381
+//! This module contains the Poseidon parameters of
+//! [neptune](https://github.com/argumentcomputer/neptune) (and thus Filecoin) over the scalar field
+//! of BLS12-381 for the arities 2, 4 and 8 (t = 3, 5, 9, x^5, R_F = 8, R_P = 55, 56, 57 at
+//! neptune's `Strength::Standard`). The round constants are neptune's output of the Grain LFSR and the
+//! MDS matrix is neptune's Cauchy matrix `1 / (i + t + j)`.
+//!
+//! neptune places the domain tag in the first state element and reads the digest from the
+//! second one, so [`Poseidon::hash_neptune`](crate::poseidon::Poseidon::hash_neptune) has to be
+//! used to reproduce its digests. The tests check the permutations, the Merkle tree and
+//! constant-length hashes against the output of neptune 13.0.0, and against the vectors of
+//! neptune's own test suite.
+//!
+//! The [`dusk`] module contains the Hades-252 permutation (t = 5, R_F = 8, R_P = 60) and the hash of
+//! dusk-network's `dusk-poseidon`, with the constants of dusk's `assets` in
+//! [`hades_t5`](hades_t5::POSEIDON_HADES_5_PARAMS).
+
+pub mod dusk;
+pub mod hades_t5;
+pub mod neptune_t3;
+pub mod neptune_t5;
+pub mod neptune_t9;
+
+/// The scalar field of BLS12-381.
+pub type Fr = ark_bls12_381::Fr;
+
+#[cfg(test)]
+mod bls12_381_tests {
+    use super::{
+        hades_t5::POSEIDON_HADES_5_PARAMS, neptune_t3::POSEIDON_NEPTUNE_3_PARAMS,
+        neptune_t5::POSEIDON_NEPTUNE_5_PARAMS, neptune_t9::POSEIDON_NEPTUNE_9_PARAMS, Fr,
+    };
+    use crate::{
+        field_from_hex_string,
+        poseidon::{DomainTag, Poseidon},
+    };
+
+    fn from_hex(hex: &str) -> Fr {
+        field_from_hex_string(hex).unwrap()
+    }
+
+    fn range(n: u64) -> Vec<Fr> {
+        (0..n).map(Fr::from).collect()
+    }
+
+    fn check_kat(poseidon: &Poseidon<Fr>, expected: &[&str]) {
+        let input = range(expected.len() as u64);
+        let expected: Vec<Fr> = expected.iter().map(|e| from_hex(e)).collect();
+        assert_eq!(poseidon.permutation(input.to_owned()).unwrap(), expected);
+        assert_eq!(poseidon.permutation_not_opt(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn kats() {
+        check_kat(
+            &Poseidon::new(&POSEIDON_NEPTUNE_3_PARAMS),
+            &[
+                "0x2436d8dceb6b34e9d7f0b8099264c423d932a576cd0ee7fbea6afa2dbb82c193",
+                "0x0106f5f58a687d47c68235834c3a46f843d253af0207f417409ad8e7db8d8e9b",
+                "0x12593a925abf3a28ac025ebda67ea46c5579806a7ea061fa06a8a09925c9042a",
+            ],
+        );
+        check_kat(
+            &Poseidon::new(&POSEIDON_NEPTUNE_5_PARAMS),
+            &[
+                "0x2458e92b41aaa43a2619f26b22bd483e5f8d34bbb0b657c75d4ff7aaeb329baa",
+                "0x6f5f297b0ab0d1e7400501b9bdd4c3be2fe676b6a05deb845143b87355167a8d",
+                "0x6120b5d443ea8ba7148e54fcb7de8ac54c29ed57be9088eb117d2b5bc4f6e654",
+                "0x53b540c674e11dbdd92a105f451948d888e4d4b5c72cd42bfa110178b8f5e661",
+                "0x1b134b276d81845c729ec2f0523952ee748c02c86933a0ae62be7f99ecb64fbe",
+            ],
+        );
+        check_kat(
+            &Poseidon::new(&POSEIDON_NEPTUNE_9_PARAMS),
+            &[
+                "0x1b23274b6ef00f59971062d45a83cd6bf570c5c043dda1b3d0f08d115895f06f",
+                "0x6abcc4115be38a79ca3974ed4eceeeaf39f50e7daa844752cbdc1298425803a8",
+                "0x6235e18caea4b0900343b6d410078acaeb5f1280fe0188cb1b7f23739b744754",
+                "0x2c816b122a04cc18fd18bef13769ee61a15aead776389fd28bb0e89496210626",
+                "0x1e1fe5480ef69e4433d09bea92f87859ba9ae4b8d817bdbe0188c7ebbd26982d",
+                "0x06810dc22d8bb8a74686775bc13d631480a4495a42541036204606a6a1833951",
+                "0x425cd5dfb3a45eaba5a6fc1afc01a80e8079e194bae4b9490da0f4b16fff18d9",
+                "0x71e7366583328ba37e6900b925e2a86c21f9c4c9cb28b50df7b5ff2ba23868b4",
+                "0x455b33bc8c0dc9ea65c9c695730f514aa439385a8b24608d4b5ce19d9b8dfa00",
+            ],
+        );
+        check_kat(
+            &Poseidon::new(&POSEIDON_HADES_5_PARAMS),
+            &[
+                "0x2f2095e1cce1d99de452b2ed1c9b5b841bc16530170d64e5df3d3a7c7fddb1ac",
+                "0x710f0fd0e2cc52ad3e0dbb00fdb98435976a8d6f99d0429b40ec762ab40f574c",
+                "0x235b6f2bb20db22b620eea122e60fb27708de917fa3be5251de52b85ee99ed6d",
+                "0x22f5fff4224872ff4716a013b71891810b1fd115ab380bcadba6e5ef73ae7b01",
+                "0x67fcd2e4d33a44a1cd810e3dfeb8084b198d84468f11c144c788f6c8068a8457",
+            ],
+        );
+    }
+
+    // The vectors of `hash_values` in neptune's `src/poseidon.rs` (`Strength::Standard`).
+    #[test]
+    fn neptune_test_suite() {
+        let merkle = [
+            (
+                &*POSEIDON_NEPTUNE_3_PARAMS,
+                "0x396508d75e76a56b739e0fd902efe161a6fba9339d05a69d2e203c369a02e7ff",
+            ),
+            (
+                &*POSEIDON_NEPTUNE_5_PARAMS,
+                "0x58a54b10a9e5848a00db3c6579229399fb6b4605bf1327ec019814ff6662075d",
+            ),
+            (
+                &*POSEIDON_NEPTUNE_9_PARAMS,
+                "0x2394611da3a5de5512010042116770774b682e9d9cc4aed92a9934f56d38a5e6",
+            ),
+        ];
+        for (params, expected) in merkle {
+            let poseidon = Poseidon::new(params);
+            let inputs = range(params.t as u64 - 1);
+            let hash = poseidon.hash_neptune(DomainTag::MerkleTree, &inputs);
+            assert_eq!(hash.unwrap(), from_hex(expected));
+        }
+
+        let constant_length = [
+            (
+                &*POSEIDON_NEPTUNE_5_PARAMS,
+                "0x3a21a6ae86754a2939443980077d75934984de08542c99778935b00a07909d45",
+            ),
+            (
+                &*POSEIDON_NEPTUNE_9_PARAMS,
+                "0x5c6de4b69de9d792cad8b9f8aeb1578f203e3c7c4a85c1f9370a94532f818897",
+            ),
+        ];
+        for (params, expected) in constant_length {
+            let poseidon = Poseidon::new(params);
+            let hash = poseidon.hash_neptune(DomainTag::ConstantLength, &range(4));
+            assert_eq!(hash.unwrap(), from_hex(expected));
+        }
+    }
+
+    #[test]
+    fn hash_neptune() {
+        let merkle = [
+            (
+                &*POSEIDON_NEPTUNE_3_PARAMS,
+                "0x6d6f8106657f1f4d7babcbaf436a9d7669c04e726e5896d89317d9833e5fa9be",
+            ),
+            (
+                &*POSEIDON_NEPTUNE_5_PARAMS,
+                "0x3d181224e2607dea961f35d9f769acb7cdefca33095ca2f3146437bcf428d9c5",
+            ),
+            (
+                &*POSEIDON_NEPTUNE_9_PARAMS,
+                "0x04edd42e8fc4e07643d1f36a1129c4e83ecaefec78e2ee10b834a106c1e1c07e",
+            ),
+        ];
+        for (params, expected) in merkle {
+            let inputs: Vec<Fr> = (1..params.t as u64).map(Fr::from).collect();
+            let hash = Poseidon::new(params).hash_neptune(DomainTag::MerkleTree, &inputs);
+            assert_eq!(hash.unwrap(), from_hex(expected));
+        }
+
+        let constant_length = [
+            (
+                &*POSEIDON_NEPTUNE_3_PARAMS,
+                1,
+                "0x421ead840f0f9e1b3dd0b92d2dce93493884bcca1cd0edc630a76e61e2c1a51c",
+            ),
+            (
+                &*POSEIDON_NEPTUNE_3_PARAMS,
+                2,
+                "0x2607b4c1a7375d47575d1387c9446f649dd2bb1364624ab7b0481c6f79695fa9",
+            ),
+            (
+                &*POSEIDON_NEPTUNE_5_PARAMS,
+                3,
+                "0x2c598683f162cef57721d8562dc0f662e6d89ae0d696aa111872c3926adb03f7",
+            ),
+            (
+                &*POSEIDON_NEPTUNE_9_PARAMS,
+                5,
+                "0x22ed57aba200e4ca4d11c354f14ca0dd2a0bd3e56fd37d6e4c43bfe6ed18ea18",
+            ),
+        ];
+        for (params, len, expected) in constant_length {
+            let inputs: Vec<Fr> = (1..=len).map(Fr::from).collect();
+            let hash = Poseidon::new(params).hash_neptune(DomainTag::ConstantLength, &inputs);
+            assert_eq!(hash.unwrap(), from_hex(expected));
+        }
+
+        // The root of an arity-8 tree over the leaves 0..64, hashing each level with neptune's
+        // `Poseidon::new_with_preimage(..).hash()`.
+        let poseidon = Poseidon::new(&POSEIDON_NEPTUNE_9_PARAMS);
+        let nodes: Vec<Fr> = range(64)
+            .chunks(8)
+            .map(|leaves| {
+                poseidon
+                    .hash_neptune(DomainTag::MerkleTree, leaves)
+                    .unwrap()
+            })
+            .collect();
+        let root = poseidon
+            .hash_neptune(DomainTag::MerkleTree, &nodes)
+            .unwrap();
+        assert_eq!(
+            root,
+            from_hex("0x2883f95f43a1073ad3f0789bc8f6366bb711a906e9ce1ea330658074169e0989")
+        );
+    }
+
+    #[test]
+    fn security() {
+        for params in [
+            &*POSEIDON_NEPTUNE_3_PARAMS,
+            &*POSEIDON_NEPTUNE_5_PARAMS,
+            &*POSEIDON_NEPTUNE_9_PARAMS,
+            &*POSEIDON_HADES_5_PARAMS,
+        ] {
+            params.validate_security(128).unwrap();
+        }
+    }
+}
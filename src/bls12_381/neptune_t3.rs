@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2]) = [0x2436d8dceb6b34e9d7f0b8099264c423d932a576cd0ee7fbea6afa2dbb82c193, 0x0106f5f58a687d47c68235834c3a46f843d253af0207f417409ad8e7db8d8e9b, 0x12593a925abf3a28ac025ebda67ea46c5579806a7ea061fa06a8a09925c9042a];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::bls12_381::Fr;
+
+lazy_static! {
+    pub static ref POSEIDON_NEPTUNE_3_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            3,
+            5,
+            8,
+            55,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/bls12_381/neptune_t3.bin")),
+        )
+        .unwrap()
+    );
+}
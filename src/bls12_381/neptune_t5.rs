@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4]) = [0x2458e92b41aaa43a2619f26b22bd483e5f8d34bbb0b657c75d4ff7aaeb329baa, 0x6f5f297b0ab0d1e7400501b9bdd4c3be2fe676b6a05deb845143b87355167a8d, 0x6120b5d443ea8ba7148e54fcb7de8ac54c29ed57be9088eb117d2b5bc4f6e654, 0x53b540c674e11dbdd92a105f451948d888e4d4b5c72cd42bfa110178b8f5e661, 0x1b134b276d81845c729ec2f0523952ee748c02c86933a0ae62be7f99ecb64fbe];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::bls12_381::Fr;
+
+lazy_static! {
+    pub static ref POSEIDON_NEPTUNE_5_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            5,
+            5,
+            8,
+            56,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/bls12_381/neptune_t5.bin")),
+        )
+        .unwrap()
+    );
+}
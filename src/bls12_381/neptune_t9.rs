@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4, 5, 6, 7, 8]) = [0x1b23274b6ef00f59971062d45a83cd6bf570c5c043dda1b3d0f08d115895f06f, 0x6abcc4115be38a79ca3974ed4eceeeaf39f50e7daa844752cbdc1298425803a8, 0x6235e18caea4b0900343b6d410078acaeb5f1280fe0188cb1b7f23739b744754, 0x2c816b122a04cc18fd18bef13769ee61a15aead776389fd28bb0e89496210626, 0x1e1fe5480ef69e4433d09bea92f87859ba9ae4b8d817bdbe0188c7ebbd26982d, 0x06810dc22d8bb8a74686775bc13d631480a4495a42541036204606a6a1833951, 0x425cd5dfb3a45eaba5a6fc1afc01a80e8079e194bae4b9490da0f4b16fff18d9, 0x71e7366583328ba37e6900b925e2a86c21f9c4c9cb28b50df7b5ff2ba23868b4, 0x455b33bc8c0dc9ea65c9c695730f514aa439385a8b24608d4b5ce19d9b8dfa00];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::bls12_381::Fr;
+
+lazy_static! {
+    pub static ref POSEIDON_NEPTUNE_9_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            9,
+            5,
+            8,
+            57,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/bls12_381/neptune_t9.bin")),
+        )
+        .unwrap()
+    );
+}
@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]) = [0x1278779aaafc5ca58bf573151005830cdb4683fb26591c85a7464d4f0e527776, 0x2d64704eca5d49e32e9197ac1a5a3711a63f656f6613702b9923342b7c0de564, 0x1680dc88faa68f8feb931ff6a354841982709982cad49cbc69fa6da19da156ec, 0x1af8b609eddf056b7948c627be62a34d9c8a76ec4e1971e5ddec6a08764af426, 0x2d5638f7bda0c876dd02c9bad4cc6549bd22dcb84931b0397b4fff5f5d394202, 0x1fd1f2ea09dcc50977684aa52246bfd81890ef9727df517c081e2a8c52c05d38, 0x29625342193f43f713db200fea91809e6d2c08c0299908ee3b8dca7f4f793cd5, 0x25e22cb8ab14acc4ffa7256115c6765ec55fc1595490ee877beb81d55da22d1d, 0x153d1502b32742ba0a5de44dadc5d8ffacb650aa8cb7317f0f899ef3a6bbd07f, 0x2e959f3d844d8f3b8109ec8f9c8ba42ed58228da8f25595e0bc9d862ba537c54, 0x168c71ee340f95b0cda19b0d0f5d065c930d5b51871446b7f558b6e6576b3b53, 0x19efe5cbb29438c23ecd685ef65b6a8cf4171b5f8517280e0e179751460d5f22, 0x09ab2614d353e152d5090a8faed57b92f585f410bb5ee5136cf5fbf834674549, 0x2eaf12da8de0c3cf18e0a33536f68758137a3f3266c4514bd6121b99940954e3, 0x2c24786e78a255df1c1f11c09c5bea75c4ac1f96ad7978e6867f033363ed6bda];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = ark_bn254::Fr;
+
+lazy_static! {
+    pub static ref POSEIDON_CIRCOM_BN_15_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            15,
+            5,
+            8,
+            60,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/bn254/circom_t15.bin")),
+        )
+        .unwrap()
+    );
+}
@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]) = [0x094ae33b67a845998abb55e917642d4022d078d96f7c36ea11da4273ecf20f50, 0x1da0f3444a1c18bda64e45d401a153bfb2da562279138b085012fcf2543619c4, 0x065cf37275c3a390bccc8c34fff7eb0ff410a741a7b2d633db1b682729b2c7cd, 0x0fb98d28c9f637561c6f41bb61a249ff2116ac499f0d887b655ab9e3a72a01a6, 0x157ddf2e63212066c4c4828a7f5e54cc3043d74dfeed206ff5e0e02929ade571, 0x2b920ba8f84f6e624b96040c7480e4fa97bc088064dd6a203c5a1d0a438bb3f2, 0x016cb3137b1c0b15cf22828f7c1d35f29c47cc13c92e00c7e3a1b66fee708d09, 0x28b3bc3ca5071a8a3f81f949aad1cd14d05c28c9447f8230f14e39199cd5af26, 0x2ed36b82e14759e48a405633b82c70e2700404d821fad4dbb619038040230462, 0x0151a90891b1d73ce466cf48019b7ed5128f0bcb64b5897c970391cf2e586543, 0x11a714b365c260930e70bfce45333b7692d49690fd6671e4b7885595ecd11b3a, 0x21b74abfdc4c05fa91b948d8c54804e0c528f588c38bb52ea2adf9fea50fb3ae, 0x25704eddf4b86bad8e17158ea71fbc088a69f6a2a0db8f8959b35f0fa4697d78, 0x1f89d2093542d5b2abeabe937a66c355e9cc7f0219504b75a56f3850c1d57ea5, 0x060e63ba04b412c5d1a2a6f48bb76f7bd35cd9a3ef9025f1696fcc3722319f05, 0x254e179b1f643318769c2480e0bdbc9f8e0aaeda3bb50be1284c184c0ce9d2a4];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = ark_bn254::Fr;
+
+lazy_static! {
+    pub static ref POSEIDON_CIRCOM_BN_16_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            16,
+            5,
+            8,
+            64,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/bn254/circom_t16.bin")),
+        )
+        .unwrap()
+    );
+}
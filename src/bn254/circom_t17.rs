@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]) = [0x16159a551cbb66108281a48099fff949ae08afd7f1f2ec06de2ffb96b919b765, 0x1264d5c8601e941fea856bfc791659f69d3bf4be2290220c1d94b93375706e96, 0x2fd5c59e96f5228df98caf93b6aed5c5632ab87ce7ef9abba90489fc7410c644, 0x0cf3a887a79c3a0aecd791d105ca313fe682e1b5bb432b0eebe2c6af59eea88c, 0x14bc9522f69d5be57330506903d25597c7489a7283c863fec8bcf747ff1a41f5, 0x010c0810e15f7aa26276f22d32ea447ae217870605ec6a83aa33c73c51a462b6, 0x0e8c097c15b9f29e4f2caf8e5c600a0c87795a574a56e7a197fb7a64e4d0cc9c, 0x2bbc6e6eaf511b517ed021f3896a0cfdc22c518e5f9d0fc7ddb776d57bf53fe4, 0x23805d61e919fc55785bcf0e574c38834ad23bf534ba364cd316ff9c5e40aa44, 0x29f8c3fee33964e0afc33e42544bc00ab95807396c4868b13afd41fa8731cc2d, 0x0f79873cf4e71a442f868e5d5a12fb489a64eb88abaeae9c4e34efde77f98f02, 0x2b2c47729f01d6b7b67fd9a107e767e2a02917f993c80606b9c80510c7709a54, 0x2b446917eb82d83dda0506fe6d478b03a2b35f451b1f998883b728dccff52821, 0x10bc47f38996264d82b39a87b6f668118bceba60af0212b43279cf238fa10ff3, 0x035cf82860cbc78419697a1bbc2d71863ea6c5ac62a13274ed6ffc0e64344843, 0x221d5ce9715487d6c57d479ca4a00e4927112039d0363ee9a5a425d1730bcc8c, 0x0ffa1bd9b53dbedee9ab5742283c8968d0435c3b3a566fcb66ca61ce04a5b5bf];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = ark_bn254::Fr;
+
+lazy_static! {
+    pub static ref POSEIDON_CIRCOM_BN_17_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            17,
+            5,
+            8,
+            68,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/bn254/circom_t17.bin")),
+        )
+        .unwrap()
+    );
+}
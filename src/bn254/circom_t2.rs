@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1]) = [0x29176100eaa962bdc1fe6c654d6a3c130e96a4d1168b33848b897dc502820133, 0x112a4f9241e384b0ede4655e6d2bbf7ebd9595775de9e7536df87cd487852fc4];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = ark_bn254::Fr;
+
+lazy_static! {
+    pub static ref POSEIDON_CIRCOM_BN_2_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            2,
+            5,
+            8,
+            56,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/bn254/circom_t2.bin")),
+        )
+        .unwrap()
+    );
+}
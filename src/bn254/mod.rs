@@ -1,2 +1,24 @@
+//! # BN254
+//! Contains the Poseidon parameters of circom for the scalar field of BN254 with t = 2 to t = 17,
+//! and the Poseidon2 parameters for t = 3. The scalar field of BN254 is also the base field of
+//! the Baby Jubjub and Grumpkin curves, hence these parameters are the ones used by protocols
+//! hashing Baby Jubjub points or keys, e.g., circomlib's EdDSA, and by circuits over BN254 in a
+//! BN254/Grumpkin cycle. See [`grumpkin`](crate::grumpkin) for the other side of the cycle.
+
+pub mod circom_t10;
+pub mod circom_t11;
+pub mod circom_t12;
+pub mod circom_t13;
+pub mod circom_t14;
+pub mod circom_t15;
+pub mod circom_t16;
+pub mod circom_t17;
+pub mod circom_t2;
 pub mod circom_t3;
 pub mod circom_t4;
+pub mod circom_t5;
+pub mod circom_t6;
+pub mod circom_t7;
+pub mod circom_t8;
+pub mod circom_t9;
+pub mod poseidon2_t3;
@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon2([0, 1, 2]) = [0x0bb61d24daca55eebcb1929a82650f328134334da98ea4f847f760054f4a3033, 0x303b6f7c86d043bfcbcc80214f26a30277a15d3f74ca654992defe7ff8d03570, 0x1ed25194542b12eef8617361c3ba7c52e660b145994427cc86296242cf766ec8];
+use crate::poseidon2::parameters::Poseidon2Params;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = ark_bn254::Fr;
+
+lazy_static! {
+    pub static ref POSEIDON2_BN_3_PARAMS: Arc<Poseidon2Params<Scalar>> = Arc::new(
+        Poseidon2Params::from_bytes(
+            3,
+            5,
+            8,
+            56,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/bn254/poseidon2_t3.bin")),
+        )
+        .unwrap()
+    );
+}
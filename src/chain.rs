@@ -0,0 +1,158 @@
+//! # Hash Chain
+//! Contains a streaming hash chain, which folds a sequence of field elements with the circom t=3
+//! compression.
+
+use crate::{error::Error, parameters::PoseidonParams, poseidon::Poseidon};
+use alloc::{sync::Arc, vec::Vec};
+use ark_ff::PrimeField;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A hash chain starting at zero, where each input `x` updates the current value `h` to
+/// `compress(h, x)` (see [`Poseidon::compress`]). The current value is the whole state of the
+/// chain, so a chain can be checkpointed with [`Self::current`] and continued with
+/// [`Self::resume`].
+#[derive(Clone, Debug)]
+pub struct HashChain<F: PrimeField> {
+    poseidon: Poseidon<F>,
+    current: F,
+}
+
+impl<F: PrimeField> HashChain<F> {
+    /// Creates an empty chain. The parameters must have statesize t = 3.
+    pub fn new(params: &Arc<PoseidonParams<F>>) -> Result<Self, Error> {
+        Self::resume(params, F::zero())
+    }
+
+    /// Continues a chain from a value previously returned by [`Self::current`].
+    pub fn resume(params: &Arc<PoseidonParams<F>>, current: F) -> Result<Self, Error> {
+        if params.t != 3 {
+            return Err(Error::WrongStatesize {
+                expected: 3,
+                got: params.t,
+            });
+        }
+        Ok(HashChain {
+            poseidon: Poseidon::new(params),
+            current,
+        })
+    }
+
+    pub fn update(&mut self, input: F) -> Result<(), Error> {
+        self.current = self.poseidon.compress(self.current, input)?;
+        Ok(())
+    }
+
+    pub fn update_many(&mut self, inputs: &[F]) -> Result<(), Error> {
+        inputs.iter().try_for_each(|input| self.update(*input))
+    }
+
+    /// Applies all inputs and returns the value of the chain after each of them, i.e., the last
+    /// element of the trace is the new current value.
+    pub fn update_many_with_trace(&mut self, inputs: &[F]) -> Result<Vec<F>, Error> {
+        inputs
+            .iter()
+            .map(|input| {
+                self.update(*input)?;
+                Ok(self.current)
+            })
+            .collect()
+    }
+
+    pub fn current(&self) -> F {
+        self.current
+    }
+
+    pub fn finalize(self) -> F {
+        self.current
+    }
+}
+
+/// Resets the chain to the empty chain.
+#[cfg(feature = "zeroize")]
+impl<F: PrimeField> Zeroize for HashChain<F> {
+    fn zeroize(&mut self) {
+        self.current.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<F: PrimeField> Drop for HashChain<F> {
+    fn drop(&mut self) {
+        self.current.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<F: PrimeField> ZeroizeOnDrop for HashChain<F> {}
+
+#[cfg(test)]
+mod hash_chain_tests {
+    use super::*;
+    use crate::{
+        bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, poseidon_hash_chain,
+        poseidon_hash_chain_with_trace, verify_chain,
+    };
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize() {
+        let mut chain = HashChain::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+        chain.update(Scalar::from(42)).unwrap();
+        chain.zeroize();
+        assert_eq!(chain.current(), Scalar::from(0));
+    }
+
+    #[test]
+    fn checkpoint_and_resume() {
+        let mut rng = thread_rng();
+        let inputs: Vec<Scalar> = (0..10).map(|_| Scalar::rand(&mut rng)).collect();
+
+        let mut chain = HashChain::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+        chain.update_many(&inputs[..4]).unwrap();
+        let checkpoint = chain.current();
+        let mut resumed = HashChain::resume(&POSEIDON_CIRCOM_BN_3_PARAMS, checkpoint).unwrap();
+        for input in &inputs[4..] {
+            resumed.update(*input).unwrap();
+        }
+        let result = resumed.finalize();
+        assert_eq!(result, poseidon_hash_chain(inputs.to_owned()).unwrap());
+
+        // the layout of the original one-shot implementation
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let mut state = vec![Scalar::from(0); 3];
+        for input in inputs {
+            state = poseidon
+                .permutation(vec![Scalar::from(0), state[0], input])
+                .unwrap();
+        }
+        assert_eq!(result, state[0]);
+
+        let empty = HashChain::<Scalar>::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+        assert_eq!(empty.finalize(), poseidon_hash_chain(vec![]).unwrap());
+    }
+
+    #[test]
+    fn trace_and_verify() {
+        let mut rng = thread_rng();
+        let inputs: Vec<Scalar> = (0..10).map(|_| Scalar::rand(&mut rng)).collect();
+
+        let trace = poseidon_hash_chain_with_trace(inputs.to_owned()).unwrap();
+        assert_eq!(trace.len(), inputs.len());
+        for (i, state) in trace.iter().enumerate() {
+            assert_eq!(*state, poseidon_hash_chain(inputs[..=i].to_vec()).unwrap());
+        }
+
+        // every segment between two intermediate states verifies
+        assert!(verify_chain(Scalar::from(0), &inputs[..3], trace[2]).unwrap());
+        assert!(verify_chain(trace[2], &inputs[3..7], trace[6]).unwrap());
+        assert!(verify_chain(trace[6], &[], trace[6]).unwrap());
+        assert!(!verify_chain(trace[2], &inputs[3..7], trace[7]).unwrap());
+        assert!(!verify_chain(trace[3], &inputs[3..7], trace[6]).unwrap());
+        assert!(poseidon_hash_chain_with_trace(vec![]).unwrap().is_empty());
+    }
+}
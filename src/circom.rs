@@ -0,0 +1,130 @@
+//! # Circom
+//! Contains a hash helper reproducing the semantics of circomlib's `Poseidon(n)` template and
+//! circomlibjs' `poseidon([..])` over the BN254 scalar field.
+
+use crate::{
+    bn254::{
+        circom_t10::POSEIDON_CIRCOM_BN_10_PARAMS, circom_t11::POSEIDON_CIRCOM_BN_11_PARAMS,
+        circom_t12::POSEIDON_CIRCOM_BN_12_PARAMS, circom_t13::POSEIDON_CIRCOM_BN_13_PARAMS,
+        circom_t14::POSEIDON_CIRCOM_BN_14_PARAMS, circom_t15::POSEIDON_CIRCOM_BN_15_PARAMS,
+        circom_t16::POSEIDON_CIRCOM_BN_16_PARAMS, circom_t17::POSEIDON_CIRCOM_BN_17_PARAMS,
+        circom_t2::POSEIDON_CIRCOM_BN_2_PARAMS, circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+        circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS, circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS,
+        circom_t6::POSEIDON_CIRCOM_BN_6_PARAMS, circom_t7::POSEIDON_CIRCOM_BN_7_PARAMS,
+        circom_t8::POSEIDON_CIRCOM_BN_8_PARAMS, circom_t9::POSEIDON_CIRCOM_BN_9_PARAMS,
+    },
+    error::Error,
+    parameters::PoseidonParams,
+    poseidon::Poseidon,
+};
+use alloc::sync::Arc;
+use ark_bn254::Fr;
+
+/// Returns the circom parameter set for hashing the given number of inputs, i.e., the one with
+/// statesize `arity + 1`.
+pub fn params_for_arity(arity: usize) -> Result<&'static Arc<PoseidonParams<Fr>>, Error> {
+    match arity {
+        1 => Ok(&POSEIDON_CIRCOM_BN_2_PARAMS),
+        2 => Ok(&POSEIDON_CIRCOM_BN_3_PARAMS),
+        3 => Ok(&POSEIDON_CIRCOM_BN_4_PARAMS),
+        4 => Ok(&POSEIDON_CIRCOM_BN_5_PARAMS),
+        5 => Ok(&POSEIDON_CIRCOM_BN_6_PARAMS),
+        6 => Ok(&POSEIDON_CIRCOM_BN_7_PARAMS),
+        7 => Ok(&POSEIDON_CIRCOM_BN_8_PARAMS),
+        8 => Ok(&POSEIDON_CIRCOM_BN_9_PARAMS),
+        9 => Ok(&POSEIDON_CIRCOM_BN_10_PARAMS),
+        10 => Ok(&POSEIDON_CIRCOM_BN_11_PARAMS),
+        11 => Ok(&POSEIDON_CIRCOM_BN_12_PARAMS),
+        12 => Ok(&POSEIDON_CIRCOM_BN_13_PARAMS),
+        13 => Ok(&POSEIDON_CIRCOM_BN_14_PARAMS),
+        14 => Ok(&POSEIDON_CIRCOM_BN_15_PARAMS),
+        15 => Ok(&POSEIDON_CIRCOM_BN_16_PARAMS),
+        16 => Ok(&POSEIDON_CIRCOM_BN_17_PARAMS),
+        _ => Err(Error::UnsupportedArity(arity)),
+    }
+}
+
+/// Hashes the inputs like circomlibjs' `poseidon(inputs)`: the state is the capacity element
+/// zero followed by the inputs, and the output is the first element of the permuted state.
+pub fn poseidon_hash(inputs: &[Fr]) -> Result<Fr, Error> {
+    let params = params_for_arity(inputs.len())?;
+    Poseidon::new(params).hash(inputs)
+}
+
+#[cfg(test)]
+mod circom_tests {
+    use super::*;
+    use crate::field_from_hex_string;
+    use alloc::{vec, vec::Vec};
+    use ark_ff::Zero;
+
+    #[test]
+    fn circomlibjs_kats() {
+        let hash = poseidon_hash(&[Fr::from(1), Fr::from(2)]).unwrap();
+        assert_eq!(
+            hash,
+            field_from_hex_string(
+                "0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"
+            )
+            .unwrap()
+        );
+        let hash = poseidon_hash(&[Fr::from(1), Fr::from(2), Fr::from(3)]).unwrap();
+        assert_eq!(
+            hash,
+            field_from_hex_string(
+                "0x0e7732d89e6939c0ff03d5e58dab6302f3230e269dc5b968f725df34ab36d732"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn circomlibjs_kats_more_inputs() {
+        let hash = poseidon_hash(&[Fr::from(1)]).unwrap();
+        assert_eq!(
+            hash,
+            field_from_hex_string(
+                "0x29176100eaa962bdc1fe6c654d6a3c130e96a4d1168b33848b897dc502820133"
+            )
+            .unwrap()
+        );
+        let inputs: Vec<Fr> = (1..=4).map(Fr::from).collect();
+        let hash = poseidon_hash(&inputs).unwrap();
+        assert_eq!(
+            hash,
+            field_from_hex_string(
+                "0x299c867db6c1fdd79dcefa40e4510b9837e60ebb1ce0663dbaa525df65250465"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn all_arities() {
+        for arity in 1..=16 {
+            let params = params_for_arity(arity).unwrap();
+            assert_eq!(params.t, arity + 1);
+            let inputs: Vec<Fr> = (0..arity as u64).map(Fr::from).collect();
+            let poseidon = Poseidon::new(params);
+            let mut state = vec![Fr::zero()];
+            state.extend_from_slice(&inputs);
+            assert_eq!(
+                poseidon.permutation(state.to_owned()).unwrap(),
+                poseidon.permutation_not_opt(state).unwrap()
+            );
+            poseidon_hash(&inputs).unwrap();
+        }
+    }
+
+    #[test]
+    fn unsupported_arity() {
+        assert!(matches!(
+            poseidon_hash(&[]),
+            Err(Error::UnsupportedArity(0))
+        ));
+        assert!(matches!(
+            poseidon_hash(&[Fr::zero(); 17]),
+            Err(Error::UnsupportedArity(17))
+        ));
+    }
+}
@@ -0,0 +1,566 @@
+//! # CLI
+//! Helpers shared by the binaries: parsing field elements given in decimal or hex, selecting the
+//! field and its parameter sets, reading inputs from files or stdin, formatting the results, and
+//! the JSON files of the `merkle` binary.
+//!
+//! With `--json`, the binaries print a JSON object instead of plain lines, which contains the
+//! identifier of the parameter set (as in [`test_vectors`](crate::test_vectors), e.g.,
+//! `bn254/circom_t3`), the encoding of the inputs, and the result.
+
+use crate::{
+    bls12_381::{
+        neptune_t3::POSEIDON_NEPTUNE_3_PARAMS, neptune_t5::POSEIDON_NEPTUNE_5_PARAMS,
+        neptune_t9::POSEIDON_NEPTUNE_9_PARAMS,
+    },
+    bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+    chain::HashChain,
+    circom::{params_for_arity, poseidon_hash},
+    error::Error,
+    field_from_str, field_to_hex_string,
+    goldilocks::{t12::POSEIDON_GOLDILOCKS_12_PARAMS, t8::POSEIDON_GOLDILOCKS_8_PARAMS},
+    grumpkin::{t3::POSEIDON_GRUMPKIN_3_PARAMS, t4::POSEIDON_GRUMPKIN_4_PARAMS},
+    guessing_game::EthAddress,
+    merkle::tree::MerkleProof,
+    parameters::{bytes::encode_elements, PoseidonParams},
+    sponge::{Padding, PoseidonSponge},
+};
+use alloc::sync::Arc;
+use ark_ff::PrimeField;
+use clap::ValueEnum;
+use num_bigint::BigUint;
+use serde_json::{json, Value};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+};
+
+/// The fields supported by the binaries.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Field {
+    /// The BN254 scalar field with the circom parameters for 1..=16 inputs
+    #[default]
+    Bn254,
+    /// The BLS12-381 scalar field with neptune's parameters for t = 3, t = 5, and t = 9
+    Bls381,
+    /// The Goldilocks field with the parameters for t = 8 and t = 12
+    Goldilocks,
+    /// The BN254 base field (Grumpkin scalar field) with the parameters for t = 3 and t = 4
+    Grumpkin,
+}
+
+/// The output formats of field elements.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Hex with `0x` prefix, zero-padded to the byte size of the modulus
+    #[default]
+    Hex,
+    /// Decimal
+    Decimal,
+    /// The canonical little-endian bytes, as used by the byte encodings of this crate
+    Bytes,
+}
+
+impl Field {
+    /// The name of the field, as accepted by `--field`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::Bn254 => "bn254",
+            Field::Bls381 => "bls381",
+            Field::Goldilocks => "goldilocks",
+            Field::Grumpkin => "grumpkin",
+        }
+    }
+}
+
+impl Format {
+    /// The name of the format, as accepted by `--format`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Hex => "hex",
+            Format::Decimal => "decimal",
+            Format::Bytes => "bytes",
+        }
+    }
+}
+
+/// How the inputs were given to a binary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputEncoding {
+    /// As command line arguments
+    Arguments,
+    /// One per line in a file or stdin
+    Lines,
+    /// As JSON array in a file or stdin
+    Json,
+}
+
+impl InputEncoding {
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputEncoding::Arguments => "arguments",
+            InputEncoding::Lines => "lines",
+            InputEncoding::Json => "json",
+        }
+    }
+}
+
+/// The result of [`Field::hash`] and [`Field::hash_chain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashOutput {
+    /// The identifier of the parameter set, e.g., `bn254/circom_t3`
+    pub parameters: String,
+    pub num_inputs: usize,
+    /// The formatted hash
+    pub hash: String,
+}
+
+/// Parses the Ethereum address of the guessing game binaries. Mixed-case addresses have to match
+/// their EIP-55 checksum, and with `require_checksum` the checksum must be present.
+pub fn parse_address(address: &str, require_checksum: bool) -> Result<EthAddress, Error> {
+    let address = address.trim();
+    if require_checksum {
+        EthAddress::parse_checksummed(address)
+    } else {
+        address.parse()
+    }
+}
+
+/// Formats a field element in the given format.
+pub fn format_element<F: PrimeField>(el: &F, format: Format) -> String {
+    match format {
+        Format::Hex => field_to_hex_string(el),
+        Format::Decimal => {
+            let biguint: BigUint = el.into_bigint().into();
+            biguint.to_string()
+        }
+        Format::Bytes => format!("{:?}", encode_elements(core::iter::once(el))),
+    }
+}
+
+fn parse_inputs<F: PrimeField>(inputs: &[&str]) -> Result<Vec<F>, Error> {
+    if inputs.is_empty() {
+        return Err(Error::UnsupportedArity(0));
+    }
+    inputs.iter().map(|input| field_from_str(input)).collect()
+}
+
+/// Hashes the inputs with the named parameter set with the smallest statesize t > inputs. The
+/// sponge with [`Padding::FixedLength`] places the number of inputs in the capacity element, such
+/// that inputs which only differ in trailing zeros do not collide.
+fn hash_with<F: PrimeField>(
+    sets: &[(&str, &Arc<PoseidonParams<F>>)],
+    inputs: &[&str],
+    format: Format,
+) -> Result<HashOutput, Error> {
+    let inputs = parse_inputs::<F>(inputs)?;
+    let (name, params) = sets
+        .iter()
+        .find(|(_, params)| params.t > inputs.len())
+        .ok_or(Error::UnsupportedArity(inputs.len()))?;
+    let mut sponge = PoseidonSponge::with_padding(params, Padding::FixedLength(inputs.len()))?;
+    sponge.absorb(&inputs)?;
+    let hash = sponge.finalize()?;
+    Ok(HashOutput {
+        parameters: name.to_string(),
+        num_inputs: inputs.len(),
+        hash: format_element(&hash, format),
+    })
+}
+
+fn hash_chain_with<F: PrimeField>(
+    (name, params): (&str, &Arc<PoseidonParams<F>>),
+    reader: impl BufRead,
+    format: Format,
+) -> Result<(HashOutput, InputEncoding), Error> {
+    let mut chain = HashChain::new(params)?;
+    let mut num_inputs = 0;
+    let encoding = for_each_input(reader, |input| {
+        num_inputs += 1;
+        chain.update(field_from_str(input)?)
+    })?;
+    let output = HashOutput {
+        parameters: name.to_string(),
+        num_inputs,
+        hash: format_element(&chain.finalize(), format),
+    };
+    Ok((output, encoding))
+}
+
+impl Field {
+    /// Hashes the inputs with the parameter set with the smallest statesize t > n for n inputs.
+    /// For BN254, this is circomlib's `Poseidon(n)`, whose parameter set has t = n + 1. The other
+    /// fields use the sponge with the length in the capacity element, which is neptune's
+    /// constant-length hash for BLS12-381.
+    pub fn hash(&self, inputs: &[&str], format: Format) -> Result<HashOutput, Error> {
+        match self {
+            Field::Bn254 => {
+                let inputs = parse_inputs(inputs)?;
+                let params = params_for_arity(inputs.len())?;
+                Ok(HashOutput {
+                    parameters: format!("bn254/circom_t{}", params.t),
+                    num_inputs: inputs.len(),
+                    hash: format_element(&poseidon_hash(&inputs)?, format),
+                })
+            }
+            Field::Bls381 => hash_with(
+                &[
+                    ("bls12_381/neptune_t3", &POSEIDON_NEPTUNE_3_PARAMS),
+                    ("bls12_381/neptune_t5", &POSEIDON_NEPTUNE_5_PARAMS),
+                    ("bls12_381/neptune_t9", &POSEIDON_NEPTUNE_9_PARAMS),
+                ],
+                inputs,
+                format,
+            ),
+            Field::Goldilocks => hash_with(
+                &[
+                    ("goldilocks/t8", &POSEIDON_GOLDILOCKS_8_PARAMS),
+                    ("goldilocks/t12", &POSEIDON_GOLDILOCKS_12_PARAMS),
+                ],
+                inputs,
+                format,
+            ),
+            Field::Grumpkin => hash_with(
+                &[
+                    ("grumpkin/t3", &POSEIDON_GRUMPKIN_3_PARAMS),
+                    ("grumpkin/t4", &POSEIDON_GRUMPKIN_4_PARAMS),
+                ],
+                inputs,
+                format,
+            ),
+        }
+    }
+
+    /// Folds the elements of the input with the t=3 compression, see [`HashChain`]. Inputs
+    /// with one element per line are streamed, such that large files are not kept in memory.
+    /// Fails with [`Error::UnsupportedField`] for Goldilocks, which has no parameters for t = 3.
+    pub fn hash_chain(
+        &self,
+        reader: impl BufRead,
+        format: Format,
+    ) -> Result<(HashOutput, InputEncoding), Error> {
+        match self {
+            Field::Bn254 => hash_chain_with(
+                ("bn254/circom_t3", &POSEIDON_CIRCOM_BN_3_PARAMS),
+                reader,
+                format,
+            ),
+            Field::Bls381 => hash_chain_with(
+                ("bls12_381/neptune_t3", &POSEIDON_NEPTUNE_3_PARAMS),
+                reader,
+                format,
+            ),
+            Field::Goldilocks => Err(Error::UnsupportedField {
+                field: self.name(),
+                operation: "the t = 3 hash chain",
+            }),
+            Field::Grumpkin => {
+                hash_chain_with(("grumpkin/t3", &POSEIDON_GRUMPKIN_3_PARAMS), reader, format)
+            }
+        }
+    }
+}
+
+/// Opens the file at the given path for reading, or stdin for `-`.
+pub fn open_input(path: &str) -> Result<Box<dyn BufRead>, Error> {
+    if path == "-" {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = File::open(path).map_err(|e| Error::Other(format!("{path}: {e}")))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+/// Calls `f` for every input, i.e., every element of a JSON array of numbers and strings, or
+/// every non-empty line (trimmed) otherwise, and returns which of the two was read. Lines are
+/// read one at a time, whereas a JSON array is parsed as a whole.
+pub fn for_each_input(
+    mut reader: impl BufRead,
+    mut f: impl FnMut(&str) -> Result<(), Error>,
+) -> Result<InputEncoding, Error> {
+    let io_error = |e: io::Error| Error::Other(e.to_string());
+    let start = loop {
+        let buf = reader.fill_buf().map_err(io_error)?;
+        match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(pos) => break Some(buf[pos]),
+            None if buf.is_empty() => break None,
+            None => {
+                let len = buf.len();
+                reader.consume(len);
+            }
+        }
+    };
+    if start == Some(b'[') {
+        let mut json = String::new();
+        reader.read_to_string(&mut json).map_err(io_error)?;
+        let value: Value =
+            serde_json::from_str(&json).map_err(|e| Error::InvalidJson(e.to_string()))?;
+        value
+            .as_array()
+            .ok_or(Error::InvalidJson("expected an array".to_owned()))?
+            .iter()
+            .try_for_each(|value| f(&input_from_json(value)?))?;
+        return Ok(InputEncoding::Json);
+    }
+    for line in reader.lines() {
+        let line = line.map_err(io_error)?;
+        let line = line.trim();
+        if !line.is_empty() {
+            f(line)?;
+        }
+    }
+    Ok(InputEncoding::Lines)
+}
+
+/// Reads all inputs, see [`for_each_input`].
+pub fn read_inputs(reader: impl BufRead) -> Result<(Vec<String>, InputEncoding), Error> {
+    let mut inputs = Vec::new();
+    let encoding = for_each_input(reader, |input| {
+        inputs.push(input.to_owned());
+        Ok(())
+    })?;
+    Ok((inputs, encoding))
+}
+
+/// Reads all inputs as field elements, see [`for_each_input`] and [`field_from_str`].
+pub fn read_elements<F: PrimeField>(
+    reader: impl BufRead,
+) -> Result<(Vec<F>, InputEncoding), Error> {
+    let mut elements = Vec::new();
+    let encoding = for_each_input(reader, |input| {
+        elements.push(field_from_str(input)?);
+        Ok(())
+    })?;
+    Ok((elements, encoding))
+}
+
+/// Prints the JSON value in a pretty layout to stdout.
+pub fn print_json(value: &Value) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).expect("serializing a JSON value cannot fail")
+    );
+}
+
+/// Parses a JSON array of field elements, each given as number or as string in decimal or in hex
+/// with `0x` prefix.
+pub fn parse_elements_json<F: PrimeField>(json: &str) -> Result<Vec<F>, Error> {
+    let value: Value = serde_json::from_str(json).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    value
+        .as_array()
+        .ok_or(Error::InvalidJson("expected an array".to_owned()))?
+        .iter()
+        .map(element_from_json)
+        .collect()
+}
+
+fn input_from_json(value: &Value) -> Result<String, Error> {
+    match value {
+        Value::String(str) => Ok(str.to_owned()),
+        Value::Number(num) if num.is_u64() => Ok(num.to_string()),
+        _ => Err(Error::InvalidJson(format!(
+            "expected a field element, got {value}"
+        ))),
+    }
+}
+
+fn element_from_json<F: PrimeField>(value: &Value) -> Result<F, Error> {
+    field_from_str(&input_from_json(value)?)
+}
+
+/// A [`MerkleProof`] together with the proven leaf and the root, as written by `merkle prove`
+/// and read by `merkle verify`:
+///
+/// `{"root": "0x..", "leaf": "0x..", "index": 5, "siblings": ["0x..", ..]}`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofFile<F: PrimeField> {
+    pub root: F,
+    pub leaf: F,
+    pub proof: MerkleProof<F>,
+}
+
+impl<F: PrimeField> ProofFile<F> {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_value())
+            .expect("serializing a JSON value cannot fail")
+    }
+
+    pub fn to_value(&self) -> Value {
+        let siblings: Vec<String> = self
+            .proof
+            .siblings
+            .iter()
+            .map(field_to_hex_string)
+            .collect();
+        json!({
+            "root": field_to_hex_string(&self.root),
+            "leaf": field_to_hex_string(&self.leaf),
+            "index": self.proof.index,
+            "siblings": siblings,
+        })
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let value: Value =
+            serde_json::from_str(json).map_err(|e| Error::InvalidJson(e.to_string()))?;
+        let index = value["index"]
+            .as_u64()
+            .ok_or(Error::InvalidJson("missing index".to_owned()))?;
+        let siblings = value["siblings"]
+            .as_array()
+            .ok_or(Error::InvalidJson("missing siblings".to_owned()))?
+            .iter()
+            .map(element_from_json)
+            .collect::<Result<Vec<F>, _>>()?;
+        Ok(ProofFile {
+            root: element_from_json(&value["root"])?,
+            leaf: element_from_json(&value["leaf"])?,
+            proof: MerkleProof {
+                index: usize::try_from(index)
+                    .map_err(|_| Error::InvalidJson("index out of range".to_owned()))?,
+                siblings,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod cli_tests {
+    use super::*;
+    use crate::circom::poseidon_hash;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn parse_and_format() {
+        let el: Fr = field_from_str("0x2a").unwrap();
+        assert_eq!(format_element(&el, Format::Decimal), "42");
+        assert_eq!(format_element(&el, Format::Hex), format!("0x{:0>64}", "2a"));
+        assert!(format_element(&el, Format::Bytes).starts_with("[42, 0, "));
+
+        let address = " 0x70997970C51812dc3A010C7d01b50e0d17dc79C8\n";
+        assert_eq!(
+            parse_address(address, true).unwrap(),
+            parse_address("0x70997970c51812dc3a010c7d01b50e0d17dc79c8", false).unwrap()
+        );
+        assert!(parse_address("0x70997970c51812dc3a010c7d01b50e0d17dc79c8", true).is_err());
+    }
+
+    #[test]
+    fn hash_by_arity() {
+        let expected = poseidon_hash(&[Fr::from(1), Fr::from(2), Fr::from(3)]).unwrap();
+        assert_eq!(
+            Field::Bn254.hash(&["1", "0x2", "3"], Format::Hex).unwrap(),
+            HashOutput {
+                parameters: "bn254/circom_t4".to_owned(),
+                num_inputs: 3,
+                hash: field_to_hex_string(&expected),
+            }
+        );
+        assert!(Field::Bn254.hash(&[], Format::Hex).is_err());
+        assert!(Field::Bn254.hash(&["1"; 17], Format::Hex).is_err());
+
+        // the length is encoded, so trailing zeros change the hash
+        let inputs = ["1", "2", "3"];
+        let mut padded = inputs.to_vec();
+        padded.extend(["0"; 4]);
+        let output = Field::Goldilocks.hash(&inputs, Format::Decimal).unwrap();
+        assert_eq!(output.parameters, "goldilocks/t8");
+        assert_ne!(
+            output.hash,
+            Field::Goldilocks
+                .hash(&padded, Format::Decimal)
+                .unwrap()
+                .hash
+        );
+        assert!(Field::Goldilocks.hash(&["1"; 12], Format::Hex).is_err());
+        assert!(Field::Grumpkin.hash(&["1", "2", "3"], Format::Hex).is_ok());
+    }
+
+    #[test]
+    fn neptune_constant_length() {
+        use crate::{
+            bls12_381::{neptune_t5::POSEIDON_NEPTUNE_5_PARAMS, Fr},
+            poseidon::{DomainTag, Poseidon},
+        };
+
+        for n in [3, 4] {
+            let inputs: Vec<String> = (1..=n).map(|i| i.to_string()).collect();
+            let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+            let output = Field::Bls381.hash(&inputs, Format::Hex).unwrap();
+            assert_eq!(output.parameters, "bls12_381/neptune_t5");
+            let elements: Vec<Fr> = (1..=n).map(Fr::from).collect();
+            let expected = Poseidon::new(&POSEIDON_NEPTUNE_5_PARAMS)
+                .hash_neptune(DomainTag::ConstantLength, &elements)
+                .unwrap();
+            assert_eq!(output.hash, field_to_hex_string(&expected));
+        }
+        assert!(Field::Bls381.hash(&["1"; 9], Format::Hex).is_err());
+        assert_eq!(
+            Field::Bls381
+                .hash_chain("1\n2\n".as_bytes(), Format::Hex)
+                .unwrap()
+                .0
+                .parameters,
+            "bls12_381/neptune_t3"
+        );
+    }
+
+    #[test]
+    fn input_files() {
+        let expected: Vec<Fr> = (1..=3).map(Fr::from).collect();
+        let lines = read_elements::<Fr>("1\n\n0x2\n  3  \n".as_bytes()).unwrap();
+        assert_eq!(lines, (expected.to_owned(), InputEncoding::Lines));
+        let json = read_elements::<Fr>(" \n [1, \"2\", \"0x3\"]\n".as_bytes()).unwrap();
+        assert_eq!(json, (expected.to_owned(), InputEncoding::Json));
+        assert!(read_elements::<Fr>("".as_bytes()).unwrap().0.is_empty());
+        assert!(read_elements::<Fr>("1\nx\n".as_bytes()).is_err());
+        assert_eq!(
+            read_inputs("[1, \"0x2\"]".as_bytes()).unwrap().0,
+            vec!["1".to_owned(), "0x2".to_owned()]
+        );
+
+        let (chain, encoding) = Field::Bn254
+            .hash_chain("1\n2\n3\n".as_bytes(), Format::Hex)
+            .unwrap();
+        assert_eq!(encoding, InputEncoding::Lines);
+        assert_eq!(chain.parameters, "bn254/circom_t3");
+        assert_eq!(chain.num_inputs, 3);
+        assert_eq!(
+            chain.hash,
+            field_to_hex_string(&crate::poseidon_hash_chain(expected).unwrap())
+        );
+        assert!(matches!(
+            Field::Goldilocks.hash_chain("1\n".as_bytes(), Format::Hex),
+            Err(Error::UnsupportedField {
+                field: "goldilocks",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn proof_json() {
+        use crate::{bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, merkle::tree::MerkleTree};
+
+        let leaves: Vec<Fr> = parse_elements_json(r#"[1, "2", "0x3", "4", "5"]"#).unwrap();
+        assert_eq!(leaves, (1..=5).map(Fr::from).collect::<Vec<_>>());
+        assert!(parse_elements_json::<Fr>(r#"{"leaves": []}"#).is_err());
+        assert!(parse_elements_json::<Fr>("[-1]").is_err());
+
+        let tree = MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, leaves.to_owned()).unwrap();
+        let file = ProofFile {
+            root: tree.root(),
+            leaf: leaves[4],
+            proof: tree.proof(4).unwrap(),
+        };
+        let parsed = ProofFile::from_json(&file.to_json()).unwrap();
+        assert_eq!(parsed, file);
+        assert!(MerkleTree::verify_proof(
+            tree.poseidon(),
+            &parsed.root,
+            tree.depth(),
+            parsed.leaf,
+            &parsed.proof
+        )
+        .unwrap());
+        assert!(ProofFile::<Fr>::from_json(r#"{"root": "0x1", "leaf": "0x1"}"#).is_err());
+    }
+}
@@ -0,0 +1,377 @@
+//! # Circom
+//! Generates a circom file implementing the Poseidon permutation for a parameter set, consisting
+//! of functions returning the constants and a `<name>Permutation()` template. The circuit has to
+//! be compiled for the prime of the field of the parameters (see the `-p` option of circom).
+
+use crate::{error::Error, field_to_hex_string, parameters::PoseidonParams};
+use alloc::{format, string::String, vec::Vec};
+use ark_ff::PrimeField;
+
+/// The representation of the round constants and matrices in the generated circuit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircomForm {
+    /// The round constants and the MDS matrix as given, i.e., t constants and a dense matrix
+    /// multiplication in every round.
+    Full,
+    /// The equivalent representation of the partial rounds with a single constant and a sparse
+    /// matrix per round, which is also used by [`Poseidon::permutation`]. This results in
+    /// significantly fewer linear operations for the same number of constraints.
+    ///
+    /// [`Poseidon::permutation`]: crate::poseidon::Poseidon::permutation
+    Optimized,
+}
+
+fn array<F: PrimeField>(values: &[F]) -> String {
+    let values = values.iter().map(field_to_hex_string).collect::<Vec<_>>();
+    format!("[{}]", values.join(", "))
+}
+
+fn matrix<'a, F: PrimeField>(rows: impl Iterator<Item = &'a [F]>) -> String {
+    let rows = rows.map(array).collect::<Vec<_>>();
+    format!("[\n        {}\n    ]", rows.join(",\n        "))
+}
+
+fn function(out: &mut String, name: &str, value: String) {
+    out.push_str(&format!(
+        "function {name}() {{\n    return {value};\n}}\n\n"
+    ));
+}
+
+// Emits the sbox x^d as square-and-multiply chain, one constraint per step
+fn sbox_template(d: usize, name: &str, out: &mut String) {
+    let bits = usize::BITS - d.leading_zeros();
+    // one squaring per bit after the leading one and one multiplication per further set bit
+    let steps = (bits - 1 + d.count_ones() - 1) as usize;
+    out.push_str(&format!("template {name}Sbox() {{\n"));
+    out.push_str("    signal input in;\n    signal output out;\n");
+    out.push_str(&format!("    signal x[{}];\n", steps + 1));
+    out.push_str("    x[0] <== in;\n");
+    let mut k = 0;
+    for i in (0..bits - 1).rev() {
+        out.push_str(&format!("    x[{}] <== x[{k}] * x[{k}];\n", k + 1));
+        k += 1;
+        if (d >> i) & 1 == 1 {
+            out.push_str(&format!("    x[{}] <== x[{k}] * in;\n", k + 1));
+            k += 1;
+        }
+    }
+    out.push_str(&format!("    out <== x[{k}];\n}}\n\n"));
+}
+
+const FULL_ROUND: &str = "
+        for (var i = 0; i < t; i++) {
+            sbox[k] = NAMESbox();
+            sbox[k].in <== state[i];
+            state[i] = sbox[k].out;
+            k++;
+        }";
+
+const MIX: &str = "
+        for (var i = 0; i < t; i++) {
+            mixed[i] = 0;
+            for (var j = 0; j < t; j++) {
+                mixed[i] += M[i][j] * state[j];
+            }
+        }
+        for (var i = 0; i < t; i++) {
+            state[i] = mixed[i];
+        }";
+
+/// Returns the source of a circom file implementing the permutation for the given parameters.
+/// The template `<name>Permutation()` has the signals `in[t]` and `out[t]`, and the constants are
+/// returned by the functions `<name>_C()`, `<name>_M()` and, for the optimized form,
+/// `<name>_CP()`, `<name>_MI()`, `<name>_W()`, and `<name>_V()`.
+pub fn circom_template<F: PrimeField>(
+    params: &PoseidonParams<F>,
+    name: &str,
+    form: CircomForm,
+) -> Result<String, Error> {
+    let t = params.t;
+    let rounds_f = params.rounds_f_beginning + params.rounds_f_end;
+    let rounds_p = params.rounds_p;
+    let form = if rounds_p == 0 {
+        CircomForm::Full
+    } else {
+        form
+    };
+
+    let mut out = String::new();
+    out.push_str("pragma circom 2.0.0;\n\n");
+    out.push_str("// Autogenerated by poseidon-rust, do not edit.\n");
+    out.push_str(&format!(
+        "// t = {t}, d = {}, R_F = {rounds_f}, R_P = {rounds_p}\n\n",
+        params.d
+    ));
+
+    let mds = matrix(params.mds.rows());
+    let p_end = params.rounds_f_beginning + rounds_p;
+    match form {
+        CircomForm::Full => {
+            let c = params.round_constants.as_flat();
+            function(&mut out, &format!("{name}_C"), array(c));
+            function(&mut out, &format!("{name}_M"), mds);
+        }
+        CircomForm::Optimized => {
+            // constants of the full rounds only
+            let c = (0..params.rounds_f_beginning)
+                .chain(p_end..params.rounds)
+                .flat_map(|r| params.round_constants.round(r))
+                .copied()
+                .collect::<Vec<_>>();
+            function(&mut out, &format!("{name}_C"), array(&c));
+            function(&mut out, &format!("{name}_M"), mds);
+            // t constants before the partial rounds, afterwards one per round but the last
+            let optimized = params.optimized()?;
+            let cp = optimized.round_constants.iter().flatten().copied();
+            function(
+                &mut out,
+                &format!("{name}_CP"),
+                array(&cp.collect::<Vec<_>>()),
+            );
+            function(
+                &mut out,
+                &format!("{name}_MI"),
+                matrix(optimized.m_i.rows()),
+            );
+            // the sparse matrices in the order of the rounds
+            let w = optimized.w_hat.iter().rev().map(Vec::as_slice);
+            let v = optimized.v.iter().rev().map(Vec::as_slice);
+            function(&mut out, &format!("{name}_W"), matrix(w));
+            function(&mut out, &format!("{name}_V"), matrix(v));
+        }
+    }
+
+    sbox_template(params.d, name, &mut out);
+
+    let num_sboxes = rounds_f * t + rounds_p;
+    out.push_str(&format!("template {name}Permutation() {{\n"));
+    out.push_str(&format!(
+        "    var t = {t};\n    var nRoundsF = {rounds_f};\n    var nRoundsP = {rounds_p};\n"
+    ));
+    out.push_str(&format!(
+        "    var C[{}] = {name}_C();\n",
+        c_len(params, form)
+    ));
+    out.push_str(&format!("    var M[{t}][{t}] = {name}_M();\n"));
+    if form == CircomForm::Optimized {
+        out.push_str(&format!(
+            "    var CP[{}] = {name}_CP();\n    var MI[{t}][{t}] = {name}_MI();\n",
+            t + rounds_p - 1
+        ));
+        out.push_str(&format!(
+            "    var W[{rounds_p}][{}] = {name}_W();\n    var V[{rounds_p}][{}] = {name}_V();\n",
+            t - 1,
+            t - 1
+        ));
+    }
+    out.push_str("\n    signal input in[t];\n    signal output out[t];\n");
+    out.push_str(&format!("    component sbox[{num_sboxes}];\n\n"));
+    out.push_str("    var state[t];\n    var mixed[t];\n    var k = 0;\n");
+    out.push_str("    for (var i = 0; i < t; i++) {\n        state[i] = in[i];\n    }\n\n");
+
+    let full_round = FULL_ROUND.replace("NAME", name);
+    let add_c = "
+        for (var i = 0; i < t; i++) {
+            state[i] += C[r * t + i];
+        }";
+    match form {
+        CircomForm::Full => {
+            out.push_str("    for (var r = 0; r < nRoundsF + nRoundsP; r++) {");
+            out.push_str(add_c);
+            out.push_str("\n        if (r < nRoundsF / 2 || r >= nRoundsF / 2 + nRoundsP) {");
+            out.push_str(&full_round.replace("\n", "\n    "));
+            out.push_str("\n        } else {\n");
+            out.push_str(&format!(
+                "            sbox[k] = {name}Sbox();\n            sbox[k].in <== state[0];\n            state[0] = sbox[k].out;\n            k++;\n        }}"
+            ));
+            out.push_str(MIX);
+            out.push_str("\n    }\n");
+        }
+        CircomForm::Optimized => {
+            out.push_str("    for (var r = 0; r < nRoundsF / 2; r++) {");
+            out.push_str(add_c);
+            out.push_str(&full_round);
+            out.push_str(MIX);
+            out.push_str("\n    }\n\n");
+
+            out.push_str("    for (var i = 0; i < t; i++) {\n        state[i] += CP[i];\n    }\n");
+            out.push_str(&MIX.replace("M[i][j]", "MI[i][j]").replace("\n    ", "\n"));
+            out.push_str("\n    for (var r = 0; r < nRoundsP; r++) {\n");
+            out.push_str(&format!(
+                "        sbox[k] = {name}Sbox();\n        sbox[k].in <== state[0];\n        state[0] = sbox[k].out;\n        k++;\n"
+            ));
+            out.push_str(
+                "        if (r < nRoundsP - 1) {\n            state[0] += CP[t + r];\n        }\n",
+            );
+            out.push_str("        var first = M[0][0] * state[0];\n");
+            out.push_str("        for (var i = 1; i < t; i++) {\n            first += W[r][i - 1] * state[i];\n            state[i] += V[r][i - 1] * state[0];\n        }\n");
+            out.push_str("        state[0] = first;\n    }\n\n");
+
+            out.push_str("    for (var r = nRoundsF / 2; r < nRoundsF; r++) {");
+            out.push_str(add_c);
+            out.push_str(&full_round);
+            out.push_str(MIX);
+            out.push_str("\n    }\n");
+        }
+    }
+    out.push_str("\n    for (var i = 0; i < t; i++) {\n        out[i] <== state[i];\n    }\n}\n");
+    Ok(out)
+}
+
+fn c_len<F: PrimeField>(params: &PoseidonParams<F>, form: CircomForm) -> usize {
+    let t = params.t;
+    match form {
+        CircomForm::Full => params.rounds * t,
+        CircomForm::Optimized => (params.rounds - params.rounds_p) * t,
+    }
+}
+#[cfg(test)]
+mod circom_tests {
+    use super::*;
+    use crate::{
+        bn254::circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS, field_from_hex_string,
+        goldilocks::t8::POSEIDON_GOLDILOCKS_8_PARAMS, poseidon::Poseidon,
+    };
+    use std::sync::Arc;
+
+    // Returns the flattened values returned by the constant function `name` of the circom file
+    fn constants<F: PrimeField>(code: &str, name: &str) -> Vec<F> {
+        let start = code
+            .find(&format!("function {name}() {{"))
+            .expect("function is emitted");
+        let body = &code[start..start + code[start..].find("\n}\n").unwrap()];
+        let body = &body[body.find("return").unwrap()..];
+        body.split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|token| token.starts_with("0x"))
+            .map(|token| field_from_hex_string(token).unwrap())
+            .collect()
+    }
+
+    fn mix<F: PrimeField>(m: &[F], state: &mut [F]) {
+        let t = state.len();
+        let mixed = m
+            .chunks_exact(t)
+            .map(|row| row.iter().zip(state.iter()).map(|(a, b)| *a * b).sum())
+            .collect::<Vec<F>>();
+        state.copy_from_slice(&mixed);
+    }
+
+    // Evaluates the template emitted by `circom_template` with the constants parsed from the
+    // emitted functions, following the loops of the template.
+    fn evaluate<F: PrimeField>(
+        params: &PoseidonParams<F>,
+        code: &str,
+        form: CircomForm,
+        input: &[F],
+    ) -> Vec<F> {
+        let (t, rounds_f, rounds_p) = (params.t, params.rounds_f_beginning * 2, params.rounds_p);
+        let sbox = |x: &mut F| *x = x.pow([params.d as u64]);
+        let c = constants::<F>(code, "P_C");
+        let m = constants::<F>(code, "P_M");
+        assert_eq!(c.len(), c_len(params, form));
+        let full_round = |state: &mut [F], r: usize| {
+            state.iter_mut().zip(&c[r * t..]).for_each(|(s, c)| *s += c);
+            state.iter_mut().for_each(sbox);
+            mix(&m, state);
+        };
+
+        let mut state = input.to_vec();
+        match form {
+            CircomForm::Full => {
+                for r in 0..rounds_f + rounds_p {
+                    if r < rounds_f / 2 || r >= rounds_f / 2 + rounds_p {
+                        full_round(&mut state, r);
+                    } else {
+                        state.iter_mut().zip(&c[r * t..]).for_each(|(s, c)| *s += c);
+                        sbox(&mut state[0]);
+                        mix(&m, &mut state);
+                    }
+                }
+            }
+            CircomForm::Optimized => {
+                let cp = constants::<F>(code, "P_CP");
+                let mi = constants::<F>(code, "P_MI");
+                let w = constants::<F>(code, "P_W");
+                let v = constants::<F>(code, "P_V");
+                assert_eq!(cp.len(), t + rounds_p - 1);
+                assert_eq!(w.len(), rounds_p * (t - 1));
+                assert_eq!(v.len(), rounds_p * (t - 1));
+                for r in 0..rounds_f / 2 {
+                    full_round(&mut state, r);
+                }
+                state.iter_mut().zip(&cp).for_each(|(s, c)| *s += c);
+                mix(&mi, &mut state);
+                for r in 0..rounds_p {
+                    sbox(&mut state[0]);
+                    if r < rounds_p - 1 {
+                        state[0] += cp[t + r];
+                    }
+                    let mut first = m[0] * state[0];
+                    for i in 1..t {
+                        first += w[r * (t - 1) + i - 1] * state[i];
+                        let s0 = state[0];
+                        state[i] += v[r * (t - 1) + i - 1] * s0;
+                    }
+                    state[0] = first;
+                }
+                for r in rounds_f / 2..rounds_f {
+                    full_round(&mut state, r);
+                }
+            }
+        }
+        state
+    }
+
+    fn check_evaluation<F: PrimeField>(params: &Arc<PoseidonParams<F>>) {
+        let poseidon = Poseidon::new(params);
+        let input = (0..params.t as u64).map(F::from).collect::<Vec<_>>();
+        let expected = poseidon.permutation(input.to_owned()).unwrap();
+        for form in [CircomForm::Full, CircomForm::Optimized] {
+            let code = circom_template(params, "P", form).unwrap();
+            assert_eq!(evaluate(params, &code, form, &input), expected);
+        }
+    }
+
+    #[test]
+    fn evaluation() {
+        // the BN254 instance is circomlib's, so the emitted constants reproduce its KAT
+        check_evaluation(&POSEIDON_CIRCOM_BN_4_PARAMS);
+        check_evaluation(&POSEIDON_GOLDILOCKS_8_PARAMS);
+    }
+
+    #[test]
+    fn forms() {
+        let params = &POSEIDON_CIRCOM_BN_4_PARAMS;
+        let full = circom_template(params, "PoseidonT4", CircomForm::Full).unwrap();
+        assert!(full.contains("template PoseidonT4Permutation() {"));
+        assert!(full.contains("var C[256] = PoseidonT4_C();"));
+        assert!(full.contains("component sbox[88];"));
+        assert!(!full.contains("PoseidonT4_CP"));
+        for c in params.round_constants.as_flat().iter() {
+            assert!(full.contains(&field_to_hex_string(c)));
+        }
+
+        let opt = circom_template(params, "PoseidonT4", CircomForm::Optimized).unwrap();
+        assert!(opt.contains("var C[32] = PoseidonT4_C();"));
+        assert!(opt.contains("var CP[59] = PoseidonT4_CP();"));
+        assert!(opt.contains("var W[56][3] = PoseidonT4_W();"));
+        for c in params.optimized().unwrap().round_constants.iter().flatten() {
+            assert!(opt.contains(&field_to_hex_string(c)));
+        }
+        assert!(!opt.contains(&field_to_hex_string(&params.round_constants.round(4)[1])));
+        for code in [full, opt] {
+            assert_eq!(code.matches('{').count(), code.matches('}').count());
+            assert_eq!(code.matches('[').count(), code.matches(']').count());
+        }
+    }
+
+    #[test]
+    fn sbox() {
+        let code = circom_template(&POSEIDON_GOLDILOCKS_8_PARAMS, "G", CircomForm::Full).unwrap();
+        // x^7 = ((x^2 * x)^2) * x
+        assert!(code.contains(
+            "    x[1] <== x[0] * x[0];\n    x[2] <== x[1] * in;\n    x[3] <== x[2] * x[2];\n    \
+             x[4] <== x[3] * in;\n    out <== x[4];"
+        ));
+    }
+}
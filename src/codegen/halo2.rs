@@ -0,0 +1,165 @@
+//! # halo2
+//! Exports a parameter set in the layout of `halo2_gadgets::poseidon`, whose `Spec` trait
+//! provides the round constants as `Vec<[F; T]>` and the MDS matrix and its inverse as
+//! `[[F; T]; T]`. The permutations agree, since halo2 uses the same round structure (e.g., the
+//! `pasta` instances of this crate are halo2's `P128Pow5T3`). Note that the sponge of halo2
+//! places the capacity element last, so its hashes differ from the circom-style hashes of this
+//! crate.
+
+use crate::{error::Error, field_to_hex_string, parameters::PoseidonParams, poseidon::Poseidon};
+use alloc::{format, string::String, sync::Arc, vec::Vec};
+use ark_ff::PrimeField;
+use itertools::Itertools;
+
+/// The constants of a parameter set as returned by `Spec::constants`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Halo2Constants<F: PrimeField> {
+    /// The round constants of all rounds, without the optimization of the partial rounds.
+    pub round_constants: Vec<Vec<F>>,
+    pub mds: Vec<Vec<F>>,
+    pub mds_inv: Vec<Vec<F>>,
+}
+
+/// Returns the constants of the parameter set in the layout of `Spec::constants`. Fails with
+/// [`Error::NonInvertibleSubmatrix`] if the MDS matrix is singular.
+pub fn halo2_constants<F: PrimeField>(
+    params: &PoseidonParams<F>,
+) -> Result<Halo2Constants<F>, Error> {
+    let mds = params.mds.to_vec();
+    Ok(Halo2Constants {
+        round_constants: params.round_constants.to_vec(),
+        mds_inv: PoseidonParams::mat_inverse(&mds)?,
+        mds,
+    })
+}
+
+// `Scalar::from_raw([..])` with the canonical little-endian u64 limbs, as used by the field
+// types of pasta_curves and halo2curves
+fn from_raw<F: PrimeField>(el: &F) -> String {
+    let limbs = el.into_bigint();
+    format!(
+        "Scalar::from_raw([{}])",
+        limbs
+            .as_ref()
+            .iter()
+            .map(|limb| format!("{limb:#018x}"))
+            .format(", ")
+    )
+}
+
+fn const_rows<F: PrimeField>(name: &str, rows: &[Vec<F>], out: &mut String) {
+    let t = rows.first().map_or(0, Vec::len);
+    out.push_str(&format!(
+        "pub const {name}: [[Scalar; {t}]; {}] = [\n",
+        rows.len()
+    ));
+    for row in rows {
+        out.push_str(&format!(
+            "    [{}],\n",
+            row.iter().map(from_raw).format(", ")
+        ));
+    }
+    out.push_str("];\n\n");
+}
+
+/// Returns the source code of a Rust module defining the constants as `ROUND_CONSTANTS`, `MDS`,
+/// and `MDS_INV`, and a unit struct with the given name implementing
+/// `halo2_gadgets::poseidon::primitives::Spec<Scalar, t, t - 1>`, where `Scalar` is an alias
+/// for the given path of the field type, e.g., `pasta_curves::Fp`. The field type has to
+/// provide the const constructor `from_raw` from little-endian u64 limbs. `Spec::constants`
+/// returns the exported constants, so `secure_mds`, which only selects the matrix of the default
+/// `constants`, returns 0. Like the other generators, the header contains the permutation of
+/// [0, 1, .., t - 1] as known-answer test.
+pub fn halo2_spec<F: PrimeField>(
+    params: &PoseidonParams<F>,
+    name: &str,
+    scalar: &str,
+) -> Result<String, Error> {
+    let t = params.t;
+    let constants = halo2_constants(params)?;
+    let input: Vec<F> = (0..t as u64).map(F::from).collect();
+    let kat = Poseidon::new(&Arc::new(params.clone())).permutation(input)?;
+
+    let mut out = String::new();
+    out.push_str("// Autogenerated by poseidon-rust, do not edit.\n// KAT:\n");
+    out.push_str(&format!(
+        "// Poseidon([{}]) = [{}];\n",
+        (0..t).format(", "),
+        kat.iter().map(field_to_hex_string).format(", ")
+    ));
+    out.push_str("use ff::Field;\n");
+    out.push_str("use halo2_gadgets::poseidon::primitives::{Mds, Spec};\n\n");
+    out.push_str(&format!("type Scalar = {scalar};\n\n"));
+
+    const_rows("ROUND_CONSTANTS", &constants.round_constants, &mut out);
+    const_rows("MDS", &constants.mds, &mut out);
+    const_rows("MDS_INV", &constants.mds_inv, &mut out);
+
+    out.push_str(&format!(
+        "#[derive(Clone, Copy, Debug)]\npub struct {name};\n\n\
+         impl Spec<Scalar, {t}, {rate}> for {name} {{\n    \
+         fn full_rounds() -> usize {{\n        {}\n    }}\n\n    \
+         fn partial_rounds() -> usize {{\n        {}\n    }}\n\n    \
+         fn sbox(val: Scalar) -> Scalar {{\n        val.pow_vartime([{}])\n    }}\n\n    \
+         // only selects the MDS matrix of the default `constants`, which is overridden below\n    \
+         fn secure_mds() -> usize {{\n        0\n    }}\n\n    \
+         fn constants() -> (Vec<[Scalar; {t}]>, Mds<Scalar, {t}>, Mds<Scalar, {t}>) {{\n        \
+         (ROUND_CONSTANTS[..].to_vec(), MDS, MDS_INV)\n    }}\n}}\n",
+        params.rounds_full(),
+        params.rounds_p,
+        params.d,
+        rate = t - 1,
+    ));
+    Ok(out)
+}
+
+#[cfg(test)]
+mod halo2_tests {
+    use super::*;
+    use crate::pasta::{fields::Fp, fp_t3::POSEIDON_PASTA_FP_3_PARAMS};
+    use ark_ff::{One, Zero};
+
+    #[test]
+    fn constants() {
+        let constants = halo2_constants(&POSEIDON_PASTA_FP_3_PARAMS).unwrap();
+        assert_eq!(constants.round_constants.len(), 64);
+        for i in 0..3 {
+            for j in 0..3 {
+                let el: Fp = (0..3)
+                    .map(|k| constants.mds[i][k] * constants.mds_inv[k][j])
+                    .sum();
+                assert_eq!(el, if i == j { Fp::one() } else { Fp::zero() });
+            }
+        }
+    }
+
+    #[test]
+    fn pasta_t3() {
+        let params = &POSEIDON_PASTA_FP_3_PARAMS;
+        let code = halo2_spec(params, "P128Pow5T3", "pasta_curves::Fp").unwrap();
+        assert!(code.contains("type Scalar = pasta_curves::Fp;"));
+        assert!(code.contains("pub const ROUND_CONSTANTS: [[Scalar; 3]; 64] = ["));
+        assert!(code.contains("pub const MDS_INV: [[Scalar; 3]; 3] = ["));
+        assert!(code.contains("impl Spec<Scalar, 3, 2> for P128Pow5T3 {"));
+        assert!(code.contains("val.pow_vartime([5])"));
+        assert!(code.contains("fn secure_mds() -> usize {\n        0\n    }"));
+        assert!(!code.contains("unimplemented!"));
+        assert!(code.contains("        8\n") && code.contains("        56\n"));
+        assert_eq!(code.matches("Scalar::from_raw([").count(), 3 * (64 + 3 + 3));
+        assert!(code.contains(&from_raw(params.mds.get(2, 2))));
+        assert_eq!(code.matches('{').count(), code.matches('}').count());
+
+        // MDS_INV[0][0] of P128Pow5T3
+        assert!(code.contains(
+            "[Scalar::from_raw([0xc6de463cd1404e6b, 0x4543705f35e98ab5, 0xcc59ffd00de86443, \
+             0x2cc057f3fa14687a]),"
+        ));
+
+        // the limbs are the canonical integer in little-endian order
+        assert_eq!(
+            from_raw(&Fp::from(0x0102030405060708u64)),
+            "Scalar::from_raw([0x0102030405060708, 0x0000000000000000, 0x0000000000000000, \
+             0x0000000000000000])"
+        );
+    }
+}
@@ -0,0 +1,9 @@
+//! # Code Generation
+//! Contains generators emitting the Poseidon permutation for a parameter set in other languages,
+//! such that on-chain and in-circuit implementations are guaranteed to use the same constants as
+//! the Rust side.
+
+pub mod circom;
+pub mod halo2;
+pub mod rust;
+pub mod solidity;
@@ -0,0 +1,102 @@
+//! # Rust
+//! Generates a standalone Rust module defining a parameter set as `lazy_static`, for crates which
+//! depend on this crate but use parameters that are not shipped with it. The MDS matrix and the
+//! round constants are inlined as hex strings and parsed when the parameters are first used.
+
+use crate::{error::Error, field_to_hex_string, parameters::PoseidonParams, poseidon::Poseidon};
+use alloc::{format, string::String, vec::Vec};
+use ark_ff::PrimeField;
+use itertools::Itertools;
+
+fn hex_rows<'a, F: PrimeField>(
+    rows: impl Iterator<Item = &'a [F]>,
+    out: &mut String,
+) -> Result<(), Error> {
+    for row in rows {
+        let row = row
+            .iter()
+            .map(|el| format!("\"{}\"", field_to_hex_string(el)));
+        out.push_str(&format!("    [{}],\n", row.format(", ")));
+    }
+    Ok(())
+}
+
+/// Returns the source code of a Rust module defining the parameters as
+/// `pub static ref <name>: Arc<PoseidonParams<Scalar>>`, where `Scalar` is an alias for the given
+/// path of the field type, e.g., `ark_bn254::Fr`. Like the shipped parameter modules, the header
+/// contains the permutation of [0, 1, .., t - 1] as known-answer test.
+pub fn rust_module<F: PrimeField>(
+    params: &PoseidonParams<F>,
+    name: &str,
+    scalar: &str,
+) -> Result<String, Error> {
+    let t = params.t;
+    let input: Vec<F> = (0..t as u64).map(F::from).collect();
+    let kat = Poseidon::new(&alloc::sync::Arc::new(params.clone())).permutation(input)?;
+
+    let mut out = String::new();
+    out.push_str("// Autogenerated by poseidon-rust, do not edit.\n// KAT:\n");
+    out.push_str(&format!(
+        "// Poseidon([{}]) = [{}];\n",
+        (0..t).format(", "),
+        kat.iter().map(field_to_hex_string).format(", ")
+    ));
+    out.push_str("use lazy_static::lazy_static;\n");
+    out.push_str("use poseidon_rust::{field_from_hex_string, parameters::PoseidonParams};\n");
+    out.push_str("use std::sync::Arc;\n\n");
+    out.push_str(&format!("type Scalar = {scalar};\n\n"));
+
+    out.push_str(&format!("const MDS: [[&str; {t}]; {t}] = [\n"));
+    hex_rows(params.mds.rows(), &mut out)?;
+    out.push_str("];\n\n");
+    out.push_str(&format!(
+        "const ROUND_CONSTANTS: [[&str; {t}]; {}] = [\n",
+        params.round_constants.len()
+    ));
+    hex_rows(params.round_constants.rounds(), &mut out)?;
+    out.push_str("];\n\n");
+
+    out.push_str(&format!(
+        "fn parse(rows: &[[&str; {t}]]) -> Vec<Vec<Scalar>> {{\n    rows.iter()\n        \
+         .map(|row| row.iter().map(|el| field_from_hex_string(el).unwrap()).collect())\n        \
+         .collect()\n}}\n\n"
+    ));
+    out.push_str("lazy_static! {\n");
+    out.push_str(&format!(
+        "    pub static ref {name}: Arc<PoseidonParams<Scalar>> = Arc::new(\n        \
+         PoseidonParams::new({t}, {}, {}, {}, parse(&MDS), parse(&ROUND_CONSTANTS)).unwrap()\n    \
+         );\n}}\n",
+        params.d,
+        params.rounds_full(),
+        params.rounds_p
+    ));
+    Ok(out)
+}
+
+#[cfg(test)]
+mod rust_tests {
+    use super::*;
+    use crate::goldilocks::t8::POSEIDON_GOLDILOCKS_8_PARAMS;
+
+    #[test]
+    fn goldilocks_t8() {
+        let params = &POSEIDON_GOLDILOCKS_8_PARAMS;
+        let code = rust_module(
+            params,
+            "POSEIDON_GOLDILOCKS_8_PARAMS",
+            "poseidon_rust::goldilocks::field::Goldilocks",
+        )
+        .unwrap();
+        // the same KAT as in the header of the shipped module
+        assert!(code.contains(
+            "// Poseidon([0, 1, 2, 3, 4, 5, 6, 7]) = [0xf533635a5ef2ccc1, 0x8e047e3bdb6e0749, \
+             0x34b5f58f92bab302, 0x2221ed296798fb59, 0x967aeb7b96faab9a, 0xdf94d95c2be1fbb1, \
+             0x918df8c207450abd, 0xe27ac1a8d768814b];"
+        ));
+        assert!(code.contains("const ROUND_CONSTANTS: [[&str; 8]; 30] = ["));
+        assert!(code.contains("PoseidonParams::new(8, 7, 8, 22, parse(&MDS)"));
+        assert_eq!(code.matches("    [\"0x").count(), 8 + 30);
+        assert!(code.contains(&field_to_hex_string(params.mds.get(7, 7))));
+        assert_eq!(code.matches('{').count(), code.matches('}').count());
+    }
+}
@@ -0,0 +1,161 @@
+//! # Solidity
+//! Generates a Solidity library implementing the Poseidon permutation for a parameter set. All
+//! rounds are unrolled and the round constants and MDS matrix are inlined as literals, so the
+//! library has no storage or constructor and can be deployed or linked as is.
+
+use crate::{error::Error, field_to_hex_string, parameters::PoseidonParams};
+use alloc::{format, string::String};
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+
+// Emits the body of `x^d mod F` as square-and-multiply chain on the variable `y`
+fn sbox_body(d: usize, out: &mut String) {
+    let bits = usize::BITS - d.leading_zeros();
+    out.push_str("        y = x;\n");
+    for i in (0..bits - 1).rev() {
+        out.push_str("        y = mulmod(y, y, F);\n");
+        if (d >> i) & 1 == 1 {
+            out.push_str("        y = mulmod(y, x, F);\n");
+        }
+    }
+}
+
+/// Returns the source code of a Solidity library with the given name, containing
+/// - `permutation(uint256[t])`, the Poseidon permutation for the given parameters,
+/// - `hash(uint256[t - 1])`, the hash in the circom layout, i.e., the permutation of the zero
+///   capacity element followed by the inputs, returning the first element.
+///
+/// Fails for fields whose elements do not fit into `uint256`.
+pub fn solidity_library<F: PrimeField>(
+    params: &PoseidonParams<F>,
+    name: &str,
+) -> Result<String, Error> {
+    if F::MODULUS_BIT_SIZE > 256 {
+        return Err(Error::InvalidParameters);
+    }
+    let t = params.t;
+    let modulus: BigUint = F::MODULUS.into();
+    let mut out = String::new();
+    let mut emit = |line: String| {
+        out.push_str(&line);
+        out.push('\n');
+    };
+
+    emit("// SPDX-License-Identifier: MIT".into());
+    emit("// Autogenerated by poseidon-rust, do not edit.".into());
+    emit("pragma solidity ^0.8.0;".into());
+    emit(String::new());
+    emit(format!("library {name} {{"));
+    emit(format!("    uint256 internal constant F = {modulus};"));
+    emit(String::new());
+
+    emit(format!(
+        "    function hash(uint256[{}] memory inputs) internal pure returns (uint256) {{",
+        t - 1
+    ));
+    emit(format!("        uint256[{t}] memory state;"));
+    for i in 1..t {
+        emit(format!("        state[{i}] = inputs[{}];", i - 1));
+    }
+    emit("        return permutation(state)[0];".into());
+    emit("    }".into());
+    emit(String::new());
+
+    emit(format!(
+        "    function permutation(uint256[{t}] memory s) internal pure returns (uint256[{t}] memory) {{"
+    ));
+    for i in 0..t {
+        emit(format!("        require(s[{i}] < F);"));
+    }
+    let p_end = params.rounds_f_beginning + params.rounds_p;
+    for (r, rc) in params.round_constants.rounds().enumerate() {
+        let full = r < params.rounds_f_beginning || r >= p_end;
+        emit(format!(
+            "        // round {r} ({})",
+            if full { "full" } else { "partial" }
+        ));
+        for (i, c) in rc.iter().enumerate() {
+            emit(format!(
+                "        s[{i}] = addmod(s[{i}], {}, F);",
+                field_to_hex_string(c)
+            ));
+        }
+        let sboxes = if full { t } else { 1 };
+        for i in 0..sboxes {
+            emit(format!("        s[{i}] = sbox(s[{i}]);"));
+        }
+        emit("        s = mix(s);".into());
+    }
+    emit("        return s;".into());
+    emit("    }".into());
+    emit(String::new());
+
+    emit(format!(
+        "    function mix(uint256[{t}] memory s) private pure returns (uint256[{t}] memory r) {{"
+    ));
+    for (i, row) in params.mds.rows().enumerate() {
+        let mut sum = format!("mulmod({}, s[0], F)", field_to_hex_string(&row[0]));
+        for (j, m) in row.iter().enumerate().skip(1) {
+            sum = format!(
+                "addmod({sum}, mulmod({}, s[{j}], F), F)",
+                field_to_hex_string(m)
+            );
+        }
+        emit(format!("        r[{i}] = {sum};"));
+    }
+    emit("    }".into());
+    emit(String::new());
+
+    emit("    function sbox(uint256 x) private pure returns (uint256 y) {".into());
+    let mut body = String::new();
+    sbox_body(params.d, &mut body);
+    out.push_str(&body);
+    out.push_str("    }\n}\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod solidity_tests {
+    use super::*;
+    use crate::bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS;
+
+    #[test]
+    fn circom_t3() {
+        let params = &POSEIDON_CIRCOM_BN_3_PARAMS;
+        let code = solidity_library(params, "PoseidonT3").unwrap();
+        assert!(code.contains("library PoseidonT3 {"));
+        assert!(code.contains(
+            "uint256 internal constant F = \
+             21888242871839275222246405745257275088548364400416034343698204186575808495617;"
+        ));
+        assert!(code.contains("function hash(uint256[2] memory inputs)"));
+        assert_eq!(code.matches("addmod(s[").count(), 3 * 65);
+        assert_eq!(code.matches("s = mix(s);").count(), 65);
+        assert_eq!(code.matches("= sbox(s[").count(), 3 * 8 + 57);
+        for c in params.round_constants.round(0).iter() {
+            assert!(code.contains(&field_to_hex_string(c)));
+        }
+        assert!(code.contains(&field_to_hex_string(params.mds.get(2, 2))));
+        assert_eq!(code.matches('{').count(), code.matches('}').count());
+    }
+
+    #[test]
+    fn sbox_chain() {
+        // evaluates the emitted chain on integers
+        for d in [3, 5, 7, 11] {
+            let mut body = String::new();
+            sbox_body(d, &mut body);
+            let x = 3u64;
+            let mut y = 0;
+            for line in body.lines().map(str::trim) {
+                y = match line {
+                    "y = x;" => x,
+                    "y = mulmod(y, y, F);" => y * y,
+                    "y = mulmod(y, x, F);" => y * x,
+                    _ => unreachable!(),
+                };
+            }
+            assert_eq!(y, x.pow(d as u32));
+        }
+    }
+}
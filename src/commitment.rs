@@ -0,0 +1,212 @@
+//! # Commitment
+//! Contains a hiding commitment scheme for a list of field elements. The commitment is the output
+//! of the Poseidon sponge, whose capacity element is set to a fixed domain tag, after absorbing the
+//! blinding factor followed by the values. The blinding factor has to be sampled uniformly at
+//! random, e.g., with [`sample_blinding`], for the commitment to be hiding.
+
+use crate::{
+    bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+    error::Error,
+    field_from_str_with,
+    field_hasher::FieldHasher,
+    parameters::PoseidonParams,
+    poseidon::{hash_bytes_with, Poseidon},
+    sponge::PoseidonSponge,
+    FieldDecode,
+};
+use alloc::{borrow::ToOwned, sync::Arc, vec::Vec};
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use ark_std::rand::{CryptoRng, RngCore};
+use lazy_static::lazy_static;
+
+/// The tag from which the domain separator of the commitments is derived.
+pub const DOMAIN_TAG: &str = "poseidon-rust commitment";
+
+/// The values and the blinding factor which open a commitment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Opening<F: PrimeField> {
+    pub values: Vec<F>,
+    pub blinding: F,
+}
+
+impl<F: PrimeField> Opening<F> {
+    /// Parses an opening given as decimal or `0x`-prefixed hex strings. Values which are not
+    /// smaller than the modulus are rejected ([`FieldDecode::Strict`]), such that an opening
+    /// has a unique encoding.
+    pub fn parse(values: &[&str], blinding: &str) -> Result<Self, Error> {
+        let parse = |str: &str| field_from_str_with(str, FieldDecode::Strict);
+        Ok(Opening {
+            values: values
+                .iter()
+                .map(|str| parse(str))
+                .collect::<Result<_, _>>()?,
+            blinding: parse(blinding)?,
+        })
+    }
+}
+
+/// The commitment scheme for a parameter set, or for the permutation of any other
+/// [`FieldHasher`].
+#[derive(Clone, Debug)]
+pub struct CommitmentScheme<F: PrimeField, H = Poseidon<F>> {
+    hasher: H,
+    domain: F,
+}
+
+impl Default for CommitmentScheme<Fr> {
+    /// Uses the circom parameters over BN254 with statesize t = 3.
+    fn default() -> Self {
+        Self::new(&POSEIDON_CIRCOM_BN_3_PARAMS).expect("the circom parameters are valid")
+    }
+}
+
+impl<F: PrimeField> CommitmentScheme<F> {
+    pub fn new(params: &Arc<PoseidonParams<F>>) -> Result<Self, Error> {
+        Self::with_hasher(Poseidon::new(params))
+    }
+}
+
+impl<F: PrimeField, H: FieldHasher<F> + Clone> CommitmentScheme<F, H> {
+    /// Creates the commitment scheme over the permutation of the given hasher. The domain
+    /// separator is derived from [`DOMAIN_TAG`] like [`Poseidon::domain_separator`].
+    pub fn with_hasher(hasher: H) -> Result<Self, Error> {
+        let domain = hash_bytes_with(&hasher, DOMAIN_TAG.as_bytes())?;
+        Ok(CommitmentScheme { hasher, domain })
+    }
+
+    /// Commits to the values with the given blinding factor.
+    pub fn commit(&self, values: &[F], blinding: F) -> Result<F, Error> {
+        let mut sponge =
+            PoseidonSponge::with_hasher_and_capacity(self.hasher.to_owned(), self.domain)?;
+        sponge.absorb(&[blinding])?;
+        sponge.absorb(values)?;
+        sponge.finalize()
+    }
+
+    /// Commits to the values with a fresh blinding factor and returns the commitment together
+    /// with its opening.
+    pub fn commit_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        values: Vec<F>,
+        rng: &mut R,
+    ) -> Result<(F, Opening<F>), Error> {
+        let blinding = sample_blinding(rng);
+        let commitment = self.commit(&values, blinding)?;
+        Ok((commitment, Opening { values, blinding }))
+    }
+
+    /// Checks whether the opening opens the commitment.
+    pub fn verify(&self, commitment: &F, opening: &Opening<F>) -> Result<bool, Error> {
+        Ok(self.commit(&opening.values, opening.blinding)? == *commitment)
+    }
+}
+
+lazy_static! {
+    // The scheme behind `commit` and `verify`, such that the domain separator is only derived once
+    static ref DEFAULT_SCHEME: CommitmentScheme<Fr> = CommitmentScheme::default();
+}
+
+/// Samples a blinding factor uniformly at random from a cryptographically secure RNG.
+pub fn sample_blinding<F: PrimeField, R: RngCore + CryptoRng>(rng: &mut R) -> F {
+    F::rand(rng)
+}
+
+/// Commits to the values over BN254, see [`CommitmentScheme::commit`].
+pub fn commit(values: &[Fr], blinding: Fr) -> Result<Fr, Error> {
+    DEFAULT_SCHEME.commit(values, blinding)
+}
+
+/// Returns the opening of a commitment created with [`commit`].
+pub fn open(values: &[Fr], blinding: Fr) -> Opening<Fr> {
+    Opening {
+        values: values.to_vec(),
+        blinding,
+    }
+}
+
+/// Checks whether the opening opens the commitment over BN254, see [`CommitmentScheme::verify`].
+pub fn verify(commitment: &Fr, opening: &Opening<Fr>) -> Result<bool, Error> {
+    DEFAULT_SCHEME.verify(commitment, opening)
+}
+
+#[cfg(test)]
+mod commitment_tests {
+    use super::*;
+    use crate::{
+        bn254::poseidon2_t3::POSEIDON2_BN_3_PARAMS,
+        pasta::{fields::Fp, fp_t3::POSEIDON_PASTA_FP_3_PARAMS},
+        poseidon2::Poseidon2,
+    };
+    use rand::thread_rng;
+
+    #[test]
+    fn commit_and_verify() {
+        let mut rng = thread_rng();
+        let values: Vec<Fr> = (0..5).map(Fr::from).collect();
+        let blinding = sample_blinding(&mut rng);
+        let commitment = commit(&values, blinding).unwrap();
+        assert!(verify(&commitment, &open(&values, blinding)).unwrap());
+
+        // wrong blinding, wrong values, and a prefix of the values do not open the commitment
+        assert!(!verify(&commitment, &open(&values, blinding + Fr::from(1))).unwrap());
+        assert!(!verify(&commitment, &open(&[Fr::from(1); 5], blinding)).unwrap());
+        assert!(!verify(&commitment, &open(&values[..4], blinding)).unwrap());
+
+        // the domain tag separates commitments from plain sponge hashes
+        let mut sponge = PoseidonSponge::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+        sponge.absorb(&[blinding]).unwrap();
+        sponge.absorb(&values).unwrap();
+        assert_ne!(sponge.finalize().unwrap(), commitment);
+    }
+
+    #[test]
+    fn parse_opening() {
+        let opening = Opening::<Fr>::parse(&["1", "0x2"], "0xa").unwrap();
+        assert_eq!(opening, open(&[Fr::from(1), Fr::from(2)], Fr::from(10)));
+
+        // p + 1 encodes the same element as 1, but is not accepted
+        let p_plus_1 =
+            "21888242871839275222246405745257275088548364400416034343698204186575808495618";
+        assert!(matches!(
+            Opening::<Fr>::parse(&[p_plus_1, "2"], "10"),
+            Err(Error::NonCanonical)
+        ));
+        assert!(matches!(
+            Opening::<Fr>::parse(&["1"], p_plus_1),
+            Err(Error::NonCanonical)
+        ));
+    }
+
+    #[test]
+    fn with_rng() {
+        let mut rng = thread_rng();
+        let scheme = CommitmentScheme::new(&POSEIDON_PASTA_FP_3_PARAMS).unwrap();
+        let values: Vec<Fp> = (0..3).map(Fp::from).collect();
+        let (commitment1, opening1) = scheme.commit_with_rng(values.to_owned(), &mut rng).unwrap();
+        let (commitment2, opening2) = scheme.commit_with_rng(values, &mut rng).unwrap();
+        // hiding: the same values result in different commitments
+        assert_ne!(commitment1, commitment2);
+        assert!(scheme.verify(&commitment1, &opening1).unwrap());
+        assert!(!scheme.verify(&commitment1, &opening2).unwrap());
+    }
+
+    #[test]
+    fn poseidon2() {
+        let values: Vec<Fr> = (0..3).map(Fr::from).collect();
+        let blinding = Fr::from(42);
+        let scheme = CommitmentScheme::with_hasher(Poseidon2::new(&POSEIDON2_BN_3_PARAMS)).unwrap();
+        let commitment = scheme.commit(&values, blinding).unwrap();
+        assert!(scheme
+            .verify(&commitment, &open(&values, blinding))
+            .unwrap());
+        assert_ne!(commitment, commit(&values, blinding).unwrap());
+
+        // with Poseidon as hasher, the scheme matches the one created from the parameters
+        let poseidon = CommitmentScheme::with_hasher(Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS));
+        assert_eq!(
+            poseidon.unwrap().commit(&values, blinding).unwrap(),
+            commit(&values, blinding).unwrap()
+        );
+    }
+}
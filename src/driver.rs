@@ -0,0 +1,216 @@
+//! # Permutation Driver
+//! Contains the Poseidon permutation as an explicit state machine over its rounds, such that the
+//! sbox layers can be evaluated outside of this crate, e.g., by an MPC protocol or a hardware
+//! backend, which may interleave communication between the rounds. A permutation is driven as
+//! follows:
+//!
+//! ```ignore
+//! let mut driver = poseidon.driver(input)?;
+//! while driver.next_linear_layer(&ctx)? {
+//!     let outputs = evaluate_sbox(driver.sbox_inputs(), driver.sbox_degree());
+//!     driver.feed_sbox_outputs(&outputs)?;
+//! }
+//! let output = driver.finish()?;
+//! ```
+//!
+//! The state elements are of any type implementing [`PoseidonField`], e.g., secret shares, and
+//! the linear layers only use its additions and public constants, where the round constants are
+//! added with [`PoseidonField::add_public`] and the given context. For plain field elements, the
+//! context is `()`. The sbox inputs are the whole state in full rounds and only the first element
+//! in partial rounds, since the linear layers use the optimized representation of the partial
+//! rounds.
+
+use crate::{error::Error, mpc::PoseidonField, poseidon::Poseidon};
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Stage {
+    Linear,
+    Sbox,
+    Done,
+}
+
+/// The state of a permutation between its linear layers and sbox layers, with state elements of
+/// type T.
+#[derive(Clone, Debug)]
+pub struct PermutationDriver<'a, F: PrimeField, T: PoseidonField<F> = F> {
+    poseidon: &'a Poseidon<F>,
+    state: Vec<T>,
+    scratch: Vec<T>,
+    round: usize,
+    stage: Stage,
+}
+
+impl<F: PrimeField> Poseidon<F> {
+    /// Starts driving the permutation of the input, which must have size t.
+    pub fn driver<T: PoseidonField<F>>(
+        &self,
+        input: Vec<T>,
+    ) -> Result<PermutationDriver<'_, F, T>, Error> {
+        let t = self.params.t;
+        if input.len() != t {
+            return Err(Error::WrongInputLength {
+                expected: t,
+                got: input.len(),
+            });
+        }
+        Ok(PermutationDriver {
+            poseidon: self,
+            scratch: input.clone(),
+            state: input,
+            round: 0,
+            stage: Stage::Linear,
+        })
+    }
+}
+
+impl<F: PrimeField, T: PoseidonField<F>> PermutationDriver<'_, F, T> {
+    /// Applies the linear operations up to the next sbox layer, where the round constants are
+    /// added with the given context. Returns false if the permutation is complete instead, i.e.,
+    /// the final linear layer was applied.
+    pub fn next_linear_layer(&mut self, ctx: &T::Context) -> Result<bool, Error> {
+        match self.stage {
+            Stage::Sbox => Err(Error::InvalidDriverState),
+            Stage::Done => Ok(false),
+            Stage::Linear => {
+                self.poseidon
+                    .linear_layer(&mut self.state, &mut self.scratch, self.round, ctx)?;
+                if self.round == self.poseidon.params.rounds {
+                    self.stage = Stage::Done;
+                    Ok(false)
+                } else {
+                    self.stage = Stage::Sbox;
+                    Ok(true)
+                }
+            }
+        }
+    }
+
+    /// Returns the inputs of the pending sbox layer, which is empty if no sbox layer is pending.
+    pub fn sbox_inputs(&self) -> &[T] {
+        match self.stage {
+            Stage::Sbox if self.poseidon.is_full_round(self.round) => &self.state,
+            Stage::Sbox => &self.state[..1],
+            _ => &[],
+        }
+    }
+
+    /// The degree d of the sbox x^d.
+    pub fn sbox_degree(&self) -> usize {
+        self.poseidon.params.d
+    }
+
+    /// Returns the index of the current round.
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    /// Completes the pending sbox layer with its outputs, which must have the length of
+    /// [`Self::sbox_inputs`].
+    pub fn feed_sbox_outputs(&mut self, outputs: &[T]) -> Result<(), Error> {
+        let len = self.sbox_inputs().len();
+        if self.stage != Stage::Sbox {
+            return Err(Error::InvalidDriverState);
+        }
+        if outputs.len() != len {
+            return Err(Error::InvalidInputLength(outputs.len()));
+        }
+        self.state[..len].clone_from_slice(outputs);
+        self.stage = Stage::Linear;
+        self.round += 1;
+        Ok(())
+    }
+
+    /// Evaluates the pending sbox layer with [`PoseidonField::pow_batch`].
+    pub fn apply_sbox(&mut self, ctx: &mut T::Context) -> Result<(), Error> {
+        if self.stage != Stage::Sbox {
+            return Err(Error::InvalidDriverState);
+        }
+        let len = self.sbox_inputs().len();
+        let d = self.sbox_degree();
+        T::pow_batch(&mut self.state[..len], d, ctx)?;
+        self.stage = Stage::Linear;
+        self.round += 1;
+        Ok(())
+    }
+
+    /// Returns the output of the permutation, fails if the permutation is not complete.
+    pub fn finish(self) -> Result<Vec<T>, Error> {
+        if self.stage != Stage::Done {
+            return Err(Error::InvalidDriverState);
+        }
+        Ok(self.state)
+    }
+}
+
+#[cfg(test)]
+mod driver_tests {
+    use super::*;
+    use crate::{
+        bn254::{circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS},
+        goldilocks::t12::POSEIDON_GOLDILOCKS_12_PARAMS,
+    };
+    use rand::thread_rng;
+
+    fn check<F: PrimeField>(poseidon: &Poseidon<F>) {
+        let mut rng = thread_rng();
+        let t = poseidon.get_t();
+        let input: Vec<F> = (0..t).map(|_| F::rand(&mut rng)).collect();
+
+        // evaluating the sbox outside of the driver
+        let mut driver = poseidon.driver(input.to_owned()).unwrap();
+        let mut sbox_layers = 0;
+        while driver.next_linear_layer(&()).unwrap() {
+            let d = driver.sbox_degree() as u64;
+            let outputs: Vec<F> = driver.sbox_inputs().iter().map(|el| el.pow([d])).collect();
+            driver.feed_sbox_outputs(&outputs).unwrap();
+            sbox_layers += 1;
+        }
+        assert!(!driver.next_linear_layer(&()).unwrap());
+        assert_eq!(sbox_layers, poseidon.params.rounds);
+        let output = driver.finish().unwrap();
+        assert_eq!(output, poseidon.permutation_not_opt(input).unwrap());
+    }
+
+    #[test]
+    fn matches_permutation() {
+        check(&Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS));
+        check(&Poseidon::new(&POSEIDON_CIRCOM_BN_5_PARAMS));
+        check(&Poseidon::new(&POSEIDON_GOLDILOCKS_12_PARAMS));
+    }
+
+    #[test]
+    fn invalid_calls() {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let zero = ark_bn254::Fr::from(0);
+        assert!(poseidon.driver(vec![zero; 2]).is_err());
+
+        let mut driver = poseidon.driver(vec![zero; 3]).unwrap();
+        assert!(driver.sbox_inputs().is_empty());
+        assert!(matches!(
+            driver.feed_sbox_outputs(&[zero; 3]),
+            Err(Error::InvalidDriverState)
+        ));
+        driver.next_linear_layer(&()).unwrap();
+        assert!(matches!(
+            driver.next_linear_layer(&()),
+            Err(Error::InvalidDriverState)
+        ));
+        assert!(matches!(
+            driver.feed_sbox_outputs(&[zero; 1]),
+            Err(Error::InvalidInputLength(1))
+        ));
+        assert!(matches!(
+            driver.clone().finish(),
+            Err(Error::InvalidDriverState)
+        ));
+        // the first partial round has a single sbox input
+        for _ in 0..4 {
+            driver.apply_sbox(&mut ()).unwrap();
+            driver.next_linear_layer(&()).unwrap();
+        }
+        assert_eq!(driver.round(), 4);
+        assert_eq!(driver.sbox_inputs().len(), 1);
+    }
+}
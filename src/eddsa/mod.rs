@@ -0,0 +1,411 @@
+//! # EdDSA-Poseidon
+//! Contains the EdDSA signature of circomlib over the Baby Jubjub curve, where the message is a
+//! BN254 scalar field element and the challenge is the Poseidon hash (circom layout, t = 6) of
+//! the nonce point, the public key, and the message. Keys, signatures, and verification match
+//! circomlibjs' `signPoseidon` and `verifyPoseidon` and the `EdDSAPoseidonVerifier` circuit:
+//!
+//! - the secret scalar s is the pruned first half of the BLAKE-512 hash of the private key, the
+//!   public key is A = (s >> 3) * B8, where B8 generates the prime-order subgroup,
+//! - the nonce r is the BLAKE-512 hash of the second half and the message (32 bytes,
+//!   little-endian) modulo the subgroup order l, and R8 = r * B8,
+//! - the signature is (R8, S) with S = r + Poseidon(R8, A, m) * s mod l,
+//! - verification checks S * B8 = R8 + 8 * Poseidon(R8, A, m) * A.
+//!
+//! The curve arithmetic is the one of `ark-ed-on-bn254`, whose curve is isomorphic with a = 1, and
+//! BLAKE-512 is the one of `blake-hash`. Multiplications with secret scalars use a Montgomery
+//! ladder with the same sequence of group operations for every scalar, and the expanded private
+//! key and the nonce are zeroized after use.
+
+use crate::{circom::poseidon_hash, error::Error};
+use ark_bn254::Fr;
+use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_ed_on_bn254::{EdwardsAffine, EdwardsProjective, Fr as Scalar};
+use ark_ff::{BigInteger, Field, MontFp, One, PrimeField, Zero};
+use ark_std::rand::{CryptoRng, RngCore};
+use blake_hash::{Blake512, Digest};
+use core::fmt;
+use num_bigint::BigUint;
+use zeroize::{Zeroize, Zeroizing};
+
+/// The coefficient a of the twisted Edwards curve a * x^2 + y^2 = 1 + d * x^2 * y^2.
+pub const A: Fr = MontFp!("168700");
+/// The coefficient d of the twisted Edwards curve a * x^2 + y^2 = 1 + d * x^2 * y^2.
+pub const D: Fr = MontFp!("168696");
+
+/// The generator of the prime-order subgroup.
+pub const BASE8: Point = Point {
+    x: MontFp!("5299619240641551281634865583518297030282874472190772894086521144482721001553"),
+    y: MontFp!("16950150798460657717958625567821834550301663161624707787222815936182638968203"),
+};
+
+/// The order l of the prime-order subgroup, in decimal.
+pub const SUBORDER: &str =
+    "2736030358979909402780800718157159386076813972158567259200215660948447373041";
+
+// A square root of A, such that (x, y) -> (sqrt(a) * x, y) maps the points to the curve of
+// ark-ed-on-bn254, x^2 + y^2 = 1 + (d / a) * x^2 * y^2
+const SQRT_A: Fr =
+    MontFp!("7214280148105020021932206872019688659210616427216992810330019057549499971851");
+const SQRT_A_INV: Fr =
+    MontFp!("2957874849018779266517920829765869116077630550401372566248359756137677864698");
+
+fn suborder() -> BigUint {
+    BigUint::parse_bytes(SUBORDER.as_bytes(), 10).expect("the suborder is a decimal number")
+}
+
+/// An affine point on Baby Jubjub.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Point {
+    x: Fr,
+    y: Fr,
+}
+
+impl Point {
+    /// The neutral element (0, 1).
+    pub const IDENTITY: Point = Point {
+        x: MontFp!("0"),
+        y: MontFp!("1"),
+    };
+
+    /// Creates a point from its coordinates, fails if it is not on the curve.
+    pub fn new(x: Fr, y: Fr) -> Result<Self, Error> {
+        let x2 = x.square();
+        let y2 = y.square();
+        if A * x2 + y2 != Fr::one() + D * x2 * y2 {
+            return Err(Error::InvalidParameters);
+        }
+        Ok(Point { x, y })
+    }
+
+    pub fn x(&self) -> Fr {
+        self.x
+    }
+
+    pub fn y(&self) -> Fr {
+        self.y
+    }
+
+    pub fn add(&self, other: &Point) -> Point {
+        Point::from_edwards(self.to_edwards() + other.to_edwards())
+    }
+
+    /// Multiplies the point with a non-negative integer. The running time depends on the scalar,
+    /// so it must not be secret.
+    pub fn mul(&self, scalar: &BigUint) -> Point {
+        Point::from_edwards(self.to_edwards().mul_bigint(scalar.to_u64_digits()))
+    }
+
+    // Multiplies the point with a secret little-endian integer using a Montgomery ladder over all
+    // 256 bits, which swaps the registers arithmetically instead of branching on the bits
+    fn mul_secret(&self, scalar: &[u8; 32]) -> Point {
+        let mut r0 = EdwardsProjective::zero();
+        let mut r1 = self.to_edwards().into_group();
+        for i in (0..256).rev() {
+            let bit = Fr::from((scalar[i / 8] >> (i % 8)) & 1);
+            conditional_swap(&mut r0, &mut r1, bit);
+            r1 += &r0;
+            r0.double_in_place();
+            conditional_swap(&mut r0, &mut r1, bit);
+        }
+        Point::from_edwards(r0)
+    }
+
+    fn to_edwards(self) -> EdwardsAffine {
+        EdwardsAffine::new_unchecked(self.x * SQRT_A, self.y)
+    }
+
+    fn from_edwards(point: EdwardsProjective) -> Point {
+        let point = point.into_affine();
+        Point {
+            x: point.x * SQRT_A_INV,
+            y: point.y,
+        }
+    }
+}
+
+// Swaps the points if bit is one and keeps them if it is zero
+fn conditional_swap(a: &mut EdwardsProjective, b: &mut EdwardsProjective, bit: Fr) {
+    for (x, y) in [
+        (&mut a.x, &mut b.x),
+        (&mut a.y, &mut b.y),
+        (&mut a.t, &mut b.t),
+        (&mut a.z, &mut b.z),
+    ] {
+        let diff = bit * (*y - *x);
+        *x += diff;
+        *y -= diff;
+    }
+}
+
+// Returns the little-endian bytes of the scalar, which are zeroized when dropped
+fn scalar_bytes(scalar: &Scalar) -> Zeroizing<[u8; 32]> {
+    let mut bytes = Zeroizing::new([0u8; 32]);
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(scalar.into_bigint().0) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    bytes
+}
+
+/// A private key, i.e., 32 random bytes. `Debug` does not print the key, and keys are compared
+/// with [`PrivateKey::ct_eq`] instead of `==`.
+#[derive(Clone)]
+pub struct PrivateKey([u8; 32]);
+
+/// A public key, i.e., a point in the prime-order subgroup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicKey(pub Point);
+
+/// A signature, S is smaller than the subgroup order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature {
+    pub r8: Point,
+    pub s: Fr,
+}
+
+impl PrivateKey {
+    pub fn new(bytes: [u8; 32]) -> Self {
+        PrivateKey(bytes)
+    }
+
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        PrivateKey(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Compares the keys without branching on their bytes.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        let diff = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        core::hint::black_box(diff) == 0
+    }
+
+    // Returns the pruned secret scalar s as little-endian bytes and the second half of the hash
+    // of the private key, both are zeroized when dropped
+    fn expand(&self) -> (Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>) {
+        let mut hash = Blake512::digest(&self.0);
+        let mut pruned = Zeroizing::new([0u8; 32]);
+        let mut prefix = Zeroizing::new([0u8; 32]);
+        pruned.copy_from_slice(&hash[..32]);
+        prefix.copy_from_slice(&hash[32..]);
+        hash.as_mut_slice().zeroize();
+        pruned[0] &= 0xf8;
+        pruned[31] &= 0x7f;
+        pruned[31] |= 0x40;
+        (pruned, prefix)
+    }
+
+    // Returns s >> 3, which is the discrete logarithm of the public key to the base B8
+    fn public_scalar(pruned: &[u8; 32]) -> Zeroizing<[u8; 32]> {
+        let mut scalar = Zeroizing::new([0u8; 32]);
+        for (i, byte) in scalar.iter_mut().enumerate() {
+            *byte = pruned[i] >> 3 | pruned.get(i + 1).map_or(0, |next| next << 5);
+        }
+        scalar
+    }
+
+    pub fn public(&self) -> PublicKey {
+        let (s, _) = self.expand();
+        PublicKey(BASE8.mul_secret(&Self::public_scalar(&s)))
+    }
+
+    /// Signs the message deterministically.
+    pub fn sign(&self, message: Fr) -> Result<Signature, Error> {
+        let (s, prefix) = self.expand();
+        let public = BASE8.mul_secret(&Self::public_scalar(&s));
+
+        let mut nonce_input = Zeroizing::new([0u8; 64]);
+        nonce_input[..32].copy_from_slice(&*prefix);
+        nonce_input[32..].copy_from_slice(&message.into_bigint().to_bytes_le());
+        let mut nonce_hash = Blake512::digest(&*nonce_input);
+        let mut r = Scalar::from_le_bytes_mod_order(&nonce_hash);
+        nonce_hash.as_mut_slice().zeroize();
+        let r8 = BASE8.mul_secret(&scalar_bytes(&r));
+
+        let hm = challenge(&r8, &public, message)?;
+        let hm = Scalar::from_le_bytes_mod_order(&hm.into_bigint().to_bytes_le());
+        let mut s = Scalar::from_le_bytes_mod_order(&*s);
+        let signature_s = r + hm * s;
+        r.zeroize();
+        s.zeroize();
+        Ok(Signature {
+            r8,
+            s: Fr::from(signature_s.into_bigint()),
+        })
+    }
+}
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PrivateKey(..)")
+    }
+}
+
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl PublicKey {
+    /// Verifies the signature of the message. Fails if the key or R8 is the identity or not in
+    /// the prime-order subgroup.
+    pub fn verify(&self, message: Fr, signature: &Signature) -> Result<bool, Error> {
+        let order = suborder();
+        for point in [&self.0, &signature.r8] {
+            if *point == Point::IDENTITY
+                || !point
+                    .to_edwards()
+                    .is_in_correct_subgroup_assuming_on_curve()
+            {
+                return Err(Error::InvalidParameters);
+            }
+        }
+        let s: BigUint = signature.s.into_bigint().into();
+        if s >= order {
+            return Ok(false);
+        }
+        let hm: BigUint = challenge(&signature.r8, &self.0, message)?
+            .into_bigint()
+            .into();
+        let left = BASE8.mul(&s);
+        let right = signature.r8.add(&self.0.mul(&(hm * 8u32)));
+        Ok(left == right)
+    }
+}
+
+fn challenge(r8: &Point, public: &Point, message: Fr) -> Result<Fr, Error> {
+    poseidon_hash(&[r8.x, r8.y, public.x, public.y, message])
+}
+
+#[cfg(test)]
+mod eddsa_tests {
+    use super::*;
+    use rand::thread_rng;
+
+    fn dec(str: &str) -> Fr {
+        crate::field_from_str_radix(str, 10).unwrap()
+    }
+
+    #[test]
+    fn blake512_kat() {
+        let expected = "a8cfbbd73726062df0c6864dda65defe58ef0cc52a5625090fa17601e1eecd1b\
+                        628e94f396ae402a00acc9eab77b4d4c2e852aaaa25a636d80af3fc7913ef5b8";
+        let hash: String = Blake512::digest(b"")
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        assert_eq!(hash, expected);
+    }
+
+    #[test]
+    fn curve() {
+        assert!(Point::new(BASE8.x, BASE8.y).is_ok());
+        assert!(Point::new(BASE8.x, BASE8.x).is_err());
+        assert_eq!(BASE8.mul(&suborder()), Point::IDENTITY);
+        assert_eq!(
+            BASE8.mul(&BigUint::from(3u32)),
+            BASE8.add(&BASE8).add(&BASE8)
+        );
+    }
+
+    #[test]
+    fn ark_ed_on_bn254() {
+        assert_eq!(SQRT_A.square(), A);
+        assert_eq!(SQRT_A * SQRT_A_INV, Fr::one());
+        let base8 = BASE8.to_edwards();
+        assert!(base8.is_on_curve());
+        assert!(base8.is_in_correct_subgroup_assuming_on_curve());
+        assert_eq!(Point::from_edwards(base8.into_group()), BASE8);
+    }
+
+    #[test]
+    fn ladder() {
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            let mut scalar = [0u8; 32];
+            rng.fill_bytes(&mut scalar);
+            assert_eq!(
+                BASE8.mul_secret(&scalar),
+                BASE8.mul(&BigUint::from_bytes_le(&scalar))
+            );
+        }
+        assert_eq!(BASE8.mul_secret(&[0u8; 32]), Point::IDENTITY);
+        let r = Scalar::from(5u64);
+        assert_eq!(
+            BASE8.mul_secret(&scalar_bytes(&r)),
+            BASE8.mul(&BigUint::from(5u32))
+        );
+    }
+
+    // The vector of circomlibjs' test "Sign (using Poseidon) a single number"
+    #[test]
+    fn circomlibjs_vector() {
+        let mut bytes = [0u8; 32];
+        bytes
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, b)| *b = (i % 10) as u8);
+        let key = PrivateKey::new(bytes);
+        let public = key.public();
+        assert_eq!(
+            public.0.x(),
+            dec("13277427435165878497778222415993513565335242147425444199013288855685581939618")
+        );
+        assert_eq!(
+            public.0.y(),
+            dec("13622229784656158136036771217484571176836296686641868549125388198837476602820")
+        );
+
+        // the message is the little-endian integer of the bytes 000102030405060708090000
+        let message = Fr::from_le_bytes_mod_order(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 0]);
+        let signature = key.sign(message).unwrap();
+        assert_eq!(
+            signature.r8.x(),
+            dec("11384336176656855268977457483345535180380036354188103142384839473266348197733")
+        );
+        assert_eq!(
+            signature.r8.y(),
+            dec("15383486972088797283337779941324724402501462225528836549661220478783371668959")
+        );
+        assert_eq!(
+            signature.s,
+            dec("1672775540645840396591609181675628451599263765380031905495115170613215233181")
+        );
+        assert!(public.verify(message, &signature).unwrap());
+    }
+
+    #[test]
+    fn sign_verify() {
+        let mut rng = thread_rng();
+        let key = PrivateKey::random(&mut rng);
+        let public = key.public();
+        let message = Fr::from(42);
+        let signature = key.sign(message).unwrap();
+        assert!(public.verify(message, &signature).unwrap());
+        assert!(!public.verify(Fr::from(43), &signature).unwrap());
+        let other = PrivateKey::random(&mut rng).public();
+        assert!(!other.verify(message, &signature).unwrap());
+
+        assert!(key.ct_eq(&key.clone()));
+        assert!(!key.ct_eq(&PrivateKey::new([0; 32])));
+        assert_eq!(format!("{key:?}"), "PrivateKey(..)");
+
+        let mut wrong = signature;
+        wrong.s += Fr::from(1);
+        assert!(!public.verify(message, &wrong).unwrap());
+        // S + l is rejected instead of being reduced
+        wrong.s = signature.s + Fr::from(suborder());
+        assert!(!public.verify(message, &wrong).unwrap());
+        assert!(PublicKey(Point::IDENTITY)
+            .verify(message, &signature)
+            .is_err());
+    }
+}
@@ -0,0 +1,197 @@
+//! # Encryption
+//! Contains the Poseidon based authenticated encryption scheme used by MACI and zk-kit, following
+//! the construction of their `poseidonEncrypt`/`poseidonDecrypt` functions and the
+//! corresponding circom templates: the state is initialized to `[0, key[0], key[1], nonce +
+//! length * 2^128]`, the message is absorbed in blocks of three elements, and the tag is the
+//! second element of the final state.
+//!
+//! The ciphertexts are NOT checked against test vectors of `@zk-kit/poseidon-cipher` or MACI,
+//! since no such vectors (or a Rust port of either implementation) were available. The tests
+//! only pin the layout described above and the output of this implementation, so applications
+//! which have to interoperate with MACI or zk-kit have to check a ciphertext of the other side
+//! before relying on this module.
+//!
+//! With the `zeroize` feature, the internal states (which contain the key) are cleared before
+//! returning. The key itself is owned by the caller, `[Fr; 2]` implements `Zeroize`.
+
+use crate::{bn254::circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS, error::Error, poseidon::Poseidon};
+use alloc::{vec, vec::Vec};
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+
+// The message length is encoded together with the nonce as nonce + length * 2^128
+const NONCE_BITS: u32 = 128;
+
+// Overwrites values derived from the key or the message with the `zeroize` feature
+fn clear(_values: &mut [Fr]) {
+    #[cfg(feature = "zeroize")]
+    _values.iter_mut().for_each(zeroize::Zeroize::zeroize);
+}
+
+fn initial_state(key: &[Fr; 2], nonce: Fr, length: usize) -> Result<Vec<Fr>, Error> {
+    if nonce.into_bigint().num_bits() > NONCE_BITS {
+        return Err(Error::InvalidParameters);
+    }
+    let domain = nonce + Fr::from(length as u64) * Fr::from(2u64).pow([NONCE_BITS as u64]);
+    Ok(vec![Fr::zero(), key[0], key[1], domain])
+}
+
+/// Encrypts the message with the shared key (usually the coordinates of an ECDH shared point)
+/// and a nonce smaller than 2^128. The message is zero-padded to a multiple of three elements,
+/// and the ciphertext contains one additional element authenticating the message.
+pub fn poseidon_encrypt(message: &[Fr], key: &[Fr; 2], nonce: Fr) -> Result<Vec<Fr>, Error> {
+    let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS);
+    let mut state = initial_state(key, nonce, message.len())?;
+
+    let mut padded = message.to_vec();
+    padded.resize(message.len().div_ceil(3) * 3, Fr::zero());
+
+    let mut ciphertext = Vec::with_capacity(padded.len() + 1);
+    for block in padded.chunks_exact(3) {
+        poseidon.permutation_in_place(&mut state)?;
+        for (s, m) in state[1..].iter_mut().zip(block) {
+            *s += m;
+        }
+        ciphertext.extend_from_slice(&state[1..]);
+    }
+    poseidon.permutation_in_place(&mut state)?;
+    ciphertext.push(state[1]);
+    clear(&mut state);
+    clear(&mut padded);
+    Ok(ciphertext)
+}
+
+/// Decrypts a ciphertext created by [`poseidon_encrypt`] for a message of the given length.
+/// Fails with [`Error::DecryptionFailed`] if the authentication fails.
+pub fn poseidon_decrypt(
+    ciphertext: &[Fr],
+    key: &[Fr; 2],
+    nonce: Fr,
+    length: usize,
+) -> Result<Vec<Fr>, Error> {
+    let num_blocks = length.div_ceil(3);
+    if ciphertext.len() != num_blocks * 3 + 1 {
+        return Err(Error::DecryptionFailed);
+    }
+    let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS);
+    let mut state = initial_state(key, nonce, length)?;
+
+    let mut message = Vec::with_capacity(num_blocks * 3);
+    for block in ciphertext[..num_blocks * 3].chunks_exact(3) {
+        poseidon.permutation_in_place(&mut state)?;
+        for (s, c) in state[1..].iter_mut().zip(block) {
+            message.push(*c - *s);
+            *s = *c;
+        }
+    }
+
+    // The padding has to be zero
+    let padding_valid = message[length..].iter().all(Zero::is_zero);
+    poseidon.permutation_in_place(&mut state)?;
+    let tag_valid = ciphertext[num_blocks * 3] == state[1];
+    clear(&mut state);
+    if !padding_valid || !tag_valid {
+        clear(&mut message);
+        return Err(Error::DecryptionFailed);
+    }
+    message.truncate(length);
+    Ok(message)
+}
+
+#[cfg(test)]
+mod encryption_tests {
+    use super::*;
+    use ark_ff::{MontFp, UniformRand};
+    use rand::thread_rng;
+
+    // Computed with this implementation to detect regressions, NOT taken from zk-kit or MACI
+    #[test]
+    fn regression() {
+        let key = [Fr::from(1), Fr::from(2)];
+        let message = [Fr::from(4), Fr::from(5), Fr::from(6), Fr::from(7)];
+        let expected: [Fr; 7] = [
+            MontFp!(
+                "11952518615044017953157140871899770913637355268798377668368071113911770270006"
+            ),
+            MontFp!(
+                "19334719298824714585318942243535096950962847758934086235896923523013857403665"
+            ),
+            MontFp!("7041741008934180616531113999708900846089584684216860163568545868682193887025"),
+            MontFp!("7183806874284754800107323957639016675807976571949479792527150376506095480888"),
+            MontFp!("3199343126735459035517285501419737344216471932135875726610359327727980620667"),
+            MontFp!("8443505035782759496251030217954715234768327772763078527962969665464337723782"),
+            MontFp!("7517704800374825390781728589607232126438529606520049947412810554170145284354"),
+        ];
+        let ciphertext = poseidon_encrypt(&message, &key, Fr::from(3)).unwrap();
+        assert_eq!(ciphertext, expected);
+        assert_eq!(
+            poseidon_decrypt(&ciphertext, &key, Fr::from(3), 4).unwrap(),
+            message
+        );
+    }
+
+    // the layout of zk-kit's `poseidonEncrypt`, spelled out with the circom t = 4 permutation
+    #[test]
+    fn layout() {
+        let key = [Fr::from(1), Fr::from(2)];
+        let message = [Fr::from(4), Fr::from(5), Fr::from(6), Fr::from(7)];
+        let nonce = Fr::from(3);
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS);
+        let length_domain = Fr::from(4) * Fr::from(2u64).pow([128]);
+        let mut state = vec![Fr::zero(), key[0], key[1], nonce + length_domain];
+        let mut expected = Vec::new();
+        for block in [
+            [message[0], message[1], message[2]],
+            [message[3], Fr::zero(), Fr::zero()],
+        ] {
+            state = poseidon.permutation(state).unwrap();
+            for i in 0..3 {
+                state[i + 1] += block[i];
+            }
+            expected.extend_from_slice(&state[1..]);
+        }
+        expected.push(poseidon.permutation(state).unwrap()[1]);
+        assert_eq!(poseidon_encrypt(&message, &key, nonce).unwrap(), expected);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut rng = thread_rng();
+        let key = [Fr::rand(&mut rng), Fr::rand(&mut rng)];
+        let nonce = Fr::from(u128::MAX);
+
+        for length in 0..8usize {
+            let message: Vec<Fr> = (0..length).map(|_| Fr::rand(&mut rng)).collect();
+            let ciphertext = poseidon_encrypt(&message, &key, nonce).unwrap();
+            assert_eq!(ciphertext.len(), length.div_ceil(3) * 3 + 1);
+            let decrypted = poseidon_decrypt(&ciphertext, &key, nonce, length).unwrap();
+            assert_eq!(decrypted, message);
+        }
+    }
+
+    #[test]
+    fn authentication() {
+        let mut rng = thread_rng();
+        let key = [Fr::rand(&mut rng), Fr::rand(&mut rng)];
+        let nonce = Fr::from(5);
+        let message: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+        let ciphertext = poseidon_encrypt(&message, &key, nonce).unwrap();
+
+        let wrong_key = [key[0], key[1] + Fr::from(1)];
+        assert!(matches!(
+            poseidon_decrypt(&ciphertext, &wrong_key, nonce, 4),
+            Err(Error::DecryptionFailed)
+        ));
+        assert!(poseidon_decrypt(&ciphertext, &key, Fr::from(6), 4).is_err());
+        assert!(poseidon_decrypt(&ciphertext, &key, nonce, 5).is_err());
+        assert!(poseidon_decrypt(&ciphertext, &key, nonce, 7).is_err());
+        for i in 0..ciphertext.len() {
+            let mut tampered = ciphertext.to_owned();
+            tampered[i] += Fr::from(1);
+            assert!(poseidon_decrypt(&tampered, &key, nonce, 4).is_err());
+        }
+
+        let large_nonce = Fr::from(u128::MAX) + Fr::from(1);
+        assert!(poseidon_encrypt(&message, &key, large_nonce).is_err());
+    }
+}
@@ -1,17 +1,140 @@
 //! # Error
 //! Contains the Error messages from this crate.
 
+use alloc::{borrow::ToOwned, string::String};
 use thiserror::Error;
 
 /// An Error enum capturing the errors produced by this crate.
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum Error {
     /// The provided parameters are invalid
     #[error("The provided parameters are invalid")]
     InvalidParameters,
+    /// The input does not have the expected number of elements
+    #[error("The input has {got} elements, expected {expected}")]
+    WrongInputLength { expected: usize, got: usize },
+    /// The inputs of a batch do not have the rate t - 1 of the parameters as length
+    #[error("The batch inputs have {got} elements, expected the rate {expected}")]
+    WrongBatchRate { expected: usize, got: usize },
+    /// The input has more elements than fit into the state
+    #[error("The input has {got} elements, expected at most {max}")]
+    TooManyInputs { max: usize, got: usize },
+    /// The parameters have a statesize which is not supported by the called function
+    #[error("The parameters have statesize {got}, expected {expected}")]
+    WrongStatesize { expected: usize, got: usize },
+    /// The MDS matrix is not a t x t matrix
+    #[error("The MDS matrix has {}x{} elements, expected {t}x{t}", .got.0, .got.1)]
+    NonSquareMds { t: usize, got: (usize, usize) },
+    /// The round constants do not consist of t elements for each round
+    #[error(
+        "The round constants have {}x{} elements, expected {}x{}",
+        .got.0, .got.1, .expected.0, .expected.1
+    )]
+    RoundConstantShape {
+        expected: (usize, usize),
+        got: (usize, usize),
+    },
+    /// The number of full rounds is not even
+    #[error("The number of full rounds {0} is not even")]
+    OddFullRounds(usize),
+    /// A matrix required for the optimized partial rounds is not invertible
+    #[error("A submatrix of the MDS matrix is not invertible")]
+    NonInvertibleSubmatrix,
+    /// An element of the provided parameters is malformed
+    #[error("The element in row {row}, column {column} of the {part} is invalid: {reason}")]
+    InvalidConstant {
+        part: &'static str,
+        row: usize,
+        column: usize,
+        reason: String,
+    },
+    /// A row of the provided parameters has the wrong number of elements
+    #[error("Row {row} of the {part} has {got} elements, expected {expected}")]
+    InvalidRowLength {
+        part: &'static str,
+        row: usize,
+        expected: usize,
+        got: usize,
+    },
+    /// A part of the provided parameters has the wrong number of rows
+    #[error("The {part} has {got} rows, expected {expected}")]
+    InvalidRowCount {
+        part: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    /// The provided parameters do not reach the requested security level
+    #[error("The provided parameters are insecure: {0}")]
+    InsecureParameters(String),
     /// The provided string is not a field element
     #[error("The provided string is not a field element")]
     ParseString,
+    /// The provided radix is not supported
+    #[error("The provided radix {0} is not supported, expected a radix in 2..=16")]
+    InvalidRadix(u32),
+    /// The provided value is not smaller than the field modulus
+    #[error("The provided value is not smaller than the field modulus")]
+    NonCanonical,
+    /// The provided JSON is malformed
+    #[error("The provided JSON is malformed: {0}")]
+    InvalidJson(String),
+    /// No parameters are available for the provided number of inputs
+    #[error("No parameters are available for {0} inputs")]
+    UnsupportedArity(usize),
+    /// The field has no parameter set for the requested operation
+    #[error("The field {field} has no parameters for {operation}")]
+    UnsupportedField {
+        field: &'static str,
+        operation: &'static str,
+    },
+    /// No parameter set with the provided name is known
+    #[error("No parameter set with the name {0} is known")]
+    UnknownParameters(String),
+    /// The provided Ethereum address is malformed
+    #[error("The provided address is invalid: {0}")]
+    InvalidAddress(String),
+    /// The provided Ethereum address does not match its EIP-55 checksum
+    #[error("The provided address does not match its EIP-55 checksum")]
+    AddressChecksum,
+    /// A parameter set with the provided name is already registered
+    #[error("A parameter set with the name {0} is already registered")]
+    DuplicateParameters(String),
+    /// The parameter set with the provided name is defined over a different field
+    #[error("The parameter set {0} is defined over a different field")]
+    ParametersFieldMismatch(String),
+    /// The provided byte input has an invalid length
+    #[error("The provided input of {0} bytes has an invalid length")]
+    InvalidInputLength(usize),
+    /// The provided index is out of bounds
+    #[error("The index {0} is out of bounds")]
+    IndexOutOfBounds(usize),
+    /// A required input of a builder is not set
+    #[error("The required input {0} is not set")]
+    MissingField(&'static str),
+    /// The storage of the Merkle tree nodes failed
+    #[error("The node storage failed: {0}")]
+    Storage(String),
+    /// The key does not fit into the depth of the sparse Merkle tree
+    #[error("The key does not fit into a tree of depth {0}")]
+    KeyOutOfRange(usize),
+    /// The Merkle proof does not have one sibling per level of the tree
+    #[error("The proof has {got} siblings, expected {expected}")]
+    WrongProofLength { expected: usize, got: usize },
+    /// The Merkle tree has no space left for further leaves
+    #[error("The Merkle tree is full")]
+    TreeFull,
+    /// The sponge was already squeezed
+    #[error("Cannot absorb into a sponge after squeezing")]
+    AbsorbAfterSqueeze,
+    /// The call to the sponge does not match the declared IO pattern
+    #[error("The call to the sponge does not match the declared IO pattern")]
+    IOPatternViolation,
+    /// The permutation driver does not expect this call in its current round
+    #[error("The permutation driver does not expect this call in its current round")]
+    InvalidDriverState,
+    /// The ciphertext could not be decrypted
+    #[error("Decryption failed, the ciphertext or the key is invalid")]
+    DecryptionFailed,
     #[error("Err: {0}")]
     Other(String),
 }
@@ -0,0 +1,313 @@
+//! # FFI
+//! Contains a C interface for the BN254 instances. Field elements are passed as 32 bytes in
+//! canonical little-endian encoding, i.e., values which are not smaller than the modulus are
+//! rejected. The header `include/poseidon_rust.h` is generated by cbindgen into `OUT_DIR` when
+//! building with the `ffi` feature, and the tests check that the checked-in copy is up to date.
+//! To build a shared library, run
+//! `cargo rustc --release --features ffi --crate-type cdylib`.
+
+use crate::{
+    bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+    circom::{self, params_for_arity},
+    error::Error,
+    poseidon::Poseidon,
+};
+use alloc::vec::Vec;
+use ark_bn254::Fr;
+use ark_ff::{BigInt, BigInteger, PrimeField};
+use core::slice;
+
+/// The size of an encoded field element in bytes.
+pub const POSEIDON_FIELD_SIZE: usize = 32;
+
+/// The status codes returned by the FFI functions.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoseidonStatus {
+    Ok = 0,
+    /// A required pointer was null
+    NullPointer = 1,
+    /// An encoded field element is not smaller than the modulus
+    NonCanonical = 2,
+    /// The input does not have the expected length
+    InvalidLength = 3,
+    /// No instance is available for the number of inputs or the statesize
+    UnsupportedArity = 4,
+    /// The input has more elements than fit into the state
+    TooManyInputs = 5,
+    /// The parameters have a statesize which is not supported by the called function
+    WrongStatesize = 6,
+    /// The parameters of the instance are invalid
+    InvalidParameters = 7,
+    /// Any other error
+    Other = 255,
+}
+
+impl From<Error> for PoseidonStatus {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::NonCanonical => PoseidonStatus::NonCanonical,
+            Error::WrongInputLength { .. }
+            | Error::WrongBatchRate { .. }
+            | Error::InvalidInputLength(_) => PoseidonStatus::InvalidLength,
+            Error::UnsupportedArity(_) => PoseidonStatus::UnsupportedArity,
+            Error::TooManyInputs { .. } => PoseidonStatus::TooManyInputs,
+            Error::WrongStatesize { .. } => PoseidonStatus::WrongStatesize,
+            Error::InvalidParameters | Error::NonInvertibleSubmatrix => {
+                PoseidonStatus::InvalidParameters
+            }
+            _ => PoseidonStatus::Other,
+        }
+    }
+}
+
+fn decode(bytes: &[u8]) -> Result<Fr, Error> {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().expect("chunks have 8 bytes"));
+    }
+    Fr::from_bigint(BigInt(limbs)).ok_or(Error::NonCanonical)
+}
+
+fn encode(el: &Fr, out: &mut [u8]) {
+    out.copy_from_slice(&el.into_bigint().to_bytes_le());
+}
+
+// Reads n field elements from the pointer, which must not be null. The caller has to validate n
+// before, such that the slice does not cover more memory than the caller promised.
+unsafe fn read_elements(ptr: *const u8, n: usize) -> Result<Vec<Fr>, Error> {
+    let len = n
+        .checked_mul(POSEIDON_FIELD_SIZE)
+        .filter(|len| *len <= isize::MAX as usize)
+        .ok_or(Error::InvalidInputLength(n))?;
+    slice::from_raw_parts(ptr, len)
+        .chunks_exact(POSEIDON_FIELD_SIZE)
+        .map(decode)
+        .collect()
+}
+
+fn status(result: Result<(), Error>) -> PoseidonStatus {
+    match result {
+        Ok(()) => PoseidonStatus::Ok,
+        Err(e) => e.into(),
+    }
+}
+
+/// Hashes `n` (1 to 16) field elements like circomlibjs' `poseidon(inputs)`.
+///
+/// # Safety
+/// `inputs` must point to `n * 32` readable bytes and `out` to 32 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn poseidon_hash_bn254(
+    inputs: *const u8,
+    n: usize,
+    out: *mut u8,
+) -> PoseidonStatus {
+    if inputs.is_null() || out.is_null() {
+        return PoseidonStatus::NullPointer;
+    }
+    if let Err(e) = params_for_arity(n) {
+        return e.into();
+    }
+    status(read_elements(inputs, n).and_then(|inputs| {
+        let hash = circom::poseidon_hash(&inputs)?;
+        encode(&hash, slice::from_raw_parts_mut(out, POSEIDON_FIELD_SIZE));
+        Ok(())
+    }))
+}
+
+/// Applies the circom permutation with statesize `t` (2 to 17) to the state in place.
+///
+/// # Safety
+/// `state` must point to `t * 32` readable and writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn poseidon_permutation_bn254(state: *mut u8, t: usize) -> PoseidonStatus {
+    if state.is_null() {
+        return PoseidonStatus::NullPointer;
+    }
+    let params = match t.checked_sub(1).map(params_for_arity) {
+        Some(Ok(params)) => params,
+        _ => return PoseidonStatus::UnsupportedArity,
+    };
+    status(read_elements(state, t).and_then(|mut elements| {
+        Poseidon::new(params).permutation_in_place(&mut elements)?;
+        let out = slice::from_raw_parts_mut(state, t * POSEIDON_FIELD_SIZE);
+        for (el, out) in elements
+            .iter()
+            .zip(out.chunks_exact_mut(POSEIDON_FIELD_SIZE))
+        {
+            encode(el, out);
+        }
+        Ok(())
+    }))
+}
+
+/// Hashes an arbitrary byte string with the circom t=3 instance, see
+/// [`Poseidon::hash_bytes`].
+///
+/// # Safety
+/// `data` must point to `len` readable bytes (or may be null if `len` is zero) and `out` to 32
+/// writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn poseidon_hash_bytes_bn254(
+    data: *const u8,
+    len: usize,
+    out: *mut u8,
+) -> PoseidonStatus {
+    if (data.is_null() && len != 0) || out.is_null() {
+        return PoseidonStatus::NullPointer;
+    }
+    let data = if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len)
+    };
+    status(
+        Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS)
+            .hash_bytes(data)
+            .map(|hash| encode(&hash, slice::from_raw_parts_mut(out, POSEIDON_FIELD_SIZE))),
+    )
+}
+
+/// Computes the guessing game commitment, see [`crate::guessing_game_commit_bytes`].
+///
+/// # Safety
+/// `address` must point to 20 readable bytes, `r` to 32 readable bytes, and `out` to 32
+/// writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn guessing_game_commit_bn254(
+    guess: u16,
+    address: *const u8,
+    r: *const u8,
+    out: *mut u8,
+) -> PoseidonStatus {
+    if address.is_null() || r.is_null() || out.is_null() {
+        return PoseidonStatus::NullPointer;
+    }
+    let address: &[u8; 20] = &*(address as *const [u8; 20]);
+    status(read_elements(r, 1).and_then(|r| {
+        let commitment = crate::guessing_game_commit_bytes(guess, address, r[0])?;
+        encode(
+            &commitment,
+            slice::from_raw_parts_mut(out, POSEIDON_FIELD_SIZE),
+        );
+        Ok(())
+    }))
+}
+
+#[cfg(test)]
+mod ffi_tests {
+    use super::*;
+    use crate::bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS;
+
+    fn encoded(els: &[Fr]) -> Vec<u8> {
+        els.iter()
+            .flat_map(|el| el.into_bigint().to_bytes_le())
+            .collect()
+    }
+
+    #[test]
+    fn hash_and_permutation() {
+        let inputs = encoded(&[Fr::from(1), Fr::from(2)]);
+        let mut out = [0u8; 32];
+        let status = unsafe { poseidon_hash_bn254(inputs.as_ptr(), 2, out.as_mut_ptr()) };
+        assert_eq!(status, PoseidonStatus::Ok);
+        assert_eq!(
+            decode(&out).unwrap(),
+            circom::poseidon_hash(&[1.into(), 2.into()]).unwrap()
+        );
+
+        let mut state = encoded(&[Fr::from(0), Fr::from(1), Fr::from(2)]);
+        let status = unsafe { poseidon_permutation_bn254(state.as_mut_ptr(), 3) };
+        assert_eq!(status, PoseidonStatus::Ok);
+        assert_eq!(state[..32], out);
+
+        let status = unsafe { poseidon_permutation_bn254(state.as_mut_ptr(), 1) };
+        assert_eq!(status, PoseidonStatus::UnsupportedArity);
+        let status = unsafe { poseidon_hash_bn254(inputs.as_ptr(), 0, out.as_mut_ptr()) };
+        assert_eq!(status, PoseidonStatus::UnsupportedArity);
+        // the arity is checked before the input is read, and the size is not overflowing
+        let status = unsafe { poseidon_hash_bn254(inputs.as_ptr(), 17, out.as_mut_ptr()) };
+        assert_eq!(status, PoseidonStatus::UnsupportedArity);
+        let status = unsafe { poseidon_hash_bn254(inputs.as_ptr(), usize::MAX, out.as_mut_ptr()) };
+        assert_eq!(status, PoseidonStatus::UnsupportedArity);
+        let status = unsafe { poseidon_permutation_bn254(state.as_mut_ptr(), usize::MAX) };
+        assert_eq!(status, PoseidonStatus::UnsupportedArity);
+        let status = unsafe { poseidon_hash_bn254(core::ptr::null(), 2, out.as_mut_ptr()) };
+        assert_eq!(status, PoseidonStatus::NullPointer);
+    }
+
+    #[test]
+    fn status_codes() {
+        assert_eq!(
+            PoseidonStatus::from(Error::WrongInputLength {
+                expected: 2,
+                got: 3
+            }),
+            PoseidonStatus::InvalidLength
+        );
+        assert_eq!(
+            PoseidonStatus::from(Error::TooManyInputs { max: 2, got: 3 }),
+            PoseidonStatus::TooManyInputs
+        );
+        assert_eq!(
+            PoseidonStatus::from(Error::WrongStatesize {
+                expected: 3,
+                got: 4
+            }),
+            PoseidonStatus::WrongStatesize
+        );
+        assert_eq!(
+            PoseidonStatus::from(Error::NonInvertibleSubmatrix),
+            PoseidonStatus::InvalidParameters
+        );
+        assert_eq!(
+            PoseidonStatus::from(Error::DecryptionFailed),
+            PoseidonStatus::Other
+        );
+        let overflow =
+            unsafe { read_elements(core::ptr::NonNull::dangling().as_ptr(), usize::MAX) };
+        assert!(matches!(
+            overflow,
+            Err(Error::InvalidInputLength(usize::MAX))
+        ));
+    }
+
+    #[test]
+    fn header_is_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/poseidon_rust.h"));
+        let checked_in = include_str!("../include/poseidon_rust.h");
+        assert_eq!(
+            generated, checked_in,
+            "include/poseidon_rust.h is outdated, copy it from OUT_DIR"
+        );
+    }
+
+    #[test]
+    fn canonical_encoding() {
+        let modulus = Fr::MODULUS.to_bytes_le();
+        let mut out = [0u8; 32];
+        let status = unsafe { poseidon_hash_bn254(modulus.as_ptr(), 1, out.as_mut_ptr()) };
+        assert_eq!(status, PoseidonStatus::NonCanonical);
+
+        let status = unsafe { poseidon_hash_bytes_bn254(b"abc".as_ptr(), 3, out.as_mut_ptr()) };
+        assert_eq!(status, PoseidonStatus::Ok);
+        let expected = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS)
+            .hash_bytes(b"abc")
+            .unwrap();
+        assert_eq!(decode(&out).unwrap(), expected);
+    }
+
+    #[test]
+    fn commitment() {
+        let address = [7u8; 20];
+        let r = encoded(&[Fr::from(10)]);
+        let mut out = [0u8; 32];
+        let status = unsafe {
+            guessing_game_commit_bn254(5, address.as_ptr(), r.as_ptr(), out.as_mut_ptr())
+        };
+        assert_eq!(status, PoseidonStatus::Ok);
+        let expected = crate::guessing_game_commit_bytes(5, &address, Fr::from(10)).unwrap();
+        assert_eq!(decode(&out).unwrap(), expected);
+    }
+}
@@ -0,0 +1,223 @@
+//! # Field Hasher
+//! Contains the [`FieldHasher`] trait, which abstracts over the permutation behind the Merkle
+//! trees, the sponge, and the commitments. It is implemented by [`Poseidon`], [`Poseidon2`],
+//! [`RescuePrime`], and, with the corresponding features, by `Griffin`, `Anemoi`, and (over
+//! Goldilocks) `Monolith`, so switching a construction to another permutation only requires passing a different hasher.
+//!
+//! The provided methods use the circom layout: the capacity element (state element 0) is zero,
+//! followed by the inputs, and the output is the first element of the permuted state.
+
+#[cfg(feature = "anemoi")]
+use crate::anemoi::Anemoi;
+#[cfg(feature = "griffin")]
+use crate::griffin::Griffin;
+use crate::{error::Error, poseidon::Poseidon, poseidon2::Poseidon2, rescue::RescuePrime};
+#[cfg(feature = "monolith")]
+use crate::{goldilocks::field::Goldilocks, monolith::Monolith};
+use alloc::{vec, vec::Vec};
+use ark_ff::PrimeField;
+
+/// A hash function over field elements built from a permutation with statesize t.
+pub trait FieldHasher<F: PrimeField> {
+    /// Returns the statesize t of the permutation.
+    fn state_size(&self) -> usize;
+
+    /// Applies the permutation to a state of t elements in place.
+    fn permute(&self, state: &mut [F]) -> Result<(), Error>;
+
+    /// Applies the permutation to every state of a buffer of consecutive states, see
+    /// [`Poseidon::permute_strided`].
+    fn permute_strided(&self, buf: &mut [F], stride: usize) -> Result<(), Error> {
+        let t = self.state_size();
+        if stride < t {
+            return Err(Error::WrongInputLength {
+                expected: t,
+                got: stride,
+            });
+        }
+        for chunk in buf.chunks_mut(stride) {
+            if chunk.len() < t {
+                return Err(Error::WrongInputLength {
+                    expected: t,
+                    got: chunk.len(),
+                });
+            }
+            self.permute(&mut chunk[..t])?;
+        }
+        Ok(())
+    }
+
+    /// Hashes exactly t - 1 inputs.
+    fn hash(&self, inputs: &[F]) -> Result<F, Error> {
+        let t = self.state_size();
+        if inputs.len() + 1 != t {
+            return Err(Error::WrongInputLength {
+                expected: t - 1,
+                got: inputs.len(),
+            });
+        }
+        let mut state = vec![F::zero(); t];
+        state[1..].copy_from_slice(inputs);
+        self.permute(&mut state)?;
+        Ok(state[0])
+    }
+
+    /// Compresses two field elements into one, requires statesize t = 3.
+    fn compress(&self, left: F, right: F) -> Result<F, Error> {
+        let t = self.state_size();
+        if t != 3 {
+            return Err(Error::WrongStatesize {
+                expected: 3,
+                got: t,
+            });
+        }
+        self.hash(&[left, right])
+    }
+
+    /// Returns the hashes of all-zero subtrees for the levels 0..=depth, where level 0 is the
+    /// zero leaf and level i is the compression of two level i-1 subtrees.
+    fn zero_hashes(&self, depth: usize) -> Result<Vec<F>, Error> {
+        let mut zeros = Vec::with_capacity(depth + 1);
+        zeros.push(F::zero());
+        for i in 0..depth {
+            zeros.push(self.compress(zeros[i], zeros[i])?);
+        }
+        Ok(zeros)
+    }
+}
+
+impl<F: PrimeField> FieldHasher<F> for Poseidon<F> {
+    fn state_size(&self) -> usize {
+        self.get_t()
+    }
+
+    fn permute(&self, state: &mut [F]) -> Result<(), Error> {
+        self.permutation_in_place(state)
+    }
+
+    fn permute_strided(&self, buf: &mut [F], stride: usize) -> Result<(), Error> {
+        Poseidon::permute_strided(self, buf, stride)
+    }
+
+    fn hash(&self, inputs: &[F]) -> Result<F, Error> {
+        Poseidon::hash(self, inputs)
+    }
+
+    fn compress(&self, left: F, right: F) -> Result<F, Error> {
+        Poseidon::compress(self, left, right)
+    }
+}
+
+impl<F: PrimeField> FieldHasher<F> for Poseidon2<F> {
+    fn state_size(&self) -> usize {
+        self.get_t()
+    }
+
+    fn permute(&self, state: &mut [F]) -> Result<(), Error> {
+        self.permutation_in_place(state)
+    }
+}
+
+impl<F: PrimeField> FieldHasher<F> for RescuePrime<F> {
+    fn state_size(&self) -> usize {
+        self.get_t()
+    }
+
+    fn permute(&self, state: &mut [F]) -> Result<(), Error> {
+        self.permutation_in_place(state)
+    }
+}
+
+#[cfg(feature = "griffin")]
+impl<F: PrimeField> FieldHasher<F> for Griffin<F> {
+    fn state_size(&self) -> usize {
+        self.get_t()
+    }
+
+    fn permute(&self, state: &mut [F]) -> Result<(), Error> {
+        self.permutation_in_place(state)
+    }
+}
+
+#[cfg(feature = "anemoi")]
+impl<F: PrimeField> FieldHasher<F> for Anemoi<F> {
+    fn state_size(&self) -> usize {
+        self.get_t()
+    }
+
+    fn permute(&self, state: &mut [F]) -> Result<(), Error> {
+        self.permutation_in_place(state)
+    }
+}
+
+#[cfg(feature = "monolith")]
+impl FieldHasher<Goldilocks> for Monolith {
+    fn state_size(&self) -> usize {
+        self.get_t()
+    }
+
+    fn permute(&self, state: &mut [Goldilocks]) -> Result<(), Error> {
+        self.permutation_in_place(state)
+    }
+}
+
+#[cfg(test)]
+mod field_hasher_tests {
+    use super::*;
+    use crate::bn254::{
+        circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS,
+        poseidon2_t3::POSEIDON2_BN_3_PARAMS,
+    };
+
+    type Scalar = ark_bn254::Fr;
+
+    // Uses only the provided methods, i.e., the permutation of the wrapped hasher
+    struct Provided<H>(H);
+
+    impl<F: PrimeField, H: FieldHasher<F>> FieldHasher<F> for Provided<H> {
+        fn state_size(&self) -> usize {
+            self.0.state_size()
+        }
+
+        fn permute(&self, state: &mut [F]) -> Result<(), Error> {
+            self.0.permute(state)
+        }
+    }
+
+    #[test]
+    fn provided_methods_match_poseidon() {
+        let (a, b) = (Scalar::from(1), Scalar::from(2));
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let provided = Provided(poseidon.to_owned());
+        assert_eq!(
+            FieldHasher::compress(&provided, a, b).unwrap(),
+            poseidon.compress(a, b).unwrap()
+        );
+        assert_eq!(
+            provided.zero_hashes(3).unwrap(),
+            poseidon.zero_hashes(3).unwrap()
+        );
+        assert!(FieldHasher::hash(&provided, &[a]).is_err());
+
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS);
+        let provided = Provided(poseidon.to_owned());
+        assert_eq!(
+            FieldHasher::hash(&provided, &[a, b, a]).unwrap(),
+            poseidon.hash(&[a, b, a]).unwrap()
+        );
+        assert!(FieldHasher::compress(&provided, a, b).is_err());
+    }
+
+    #[test]
+    fn poseidon2() {
+        let (a, b) = (Scalar::from(1), Scalar::from(2));
+        let poseidon2 = Poseidon2::new(&POSEIDON2_BN_3_PARAMS);
+        let state = poseidon2.permutation(vec![Scalar::from(0), a, b]).unwrap();
+        assert_eq!(poseidon2.compress(a, b).unwrap(), state[0]);
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        assert_ne!(
+            poseidon2.compress(a, b).unwrap(),
+            poseidon.compress(a, b).unwrap()
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! # Goldilocks Field
+//! Contains the 64-bit Goldilocks prime field with modulus p = 2^64 - 2^32 + 1. Instead of the
+//! Montgomery backend of ark-ff, elements are stored in canonical form and products are reduced
+//! using the special shape of the modulus, i.e., 2^64 = 2^32 - 1 mod p and 2^96 = -1 mod p.
+
+use ark_ff::{
+    fields::{Fp64, FpConfig},
+    BigInt, SqrtPrecomputation,
+};
+use core::marker::PhantomData;
+
+/// The Goldilocks modulus 2^64 - 2^32 + 1.
+pub const MODULUS: u64 = 0xffff_ffff_0000_0001;
+// 2^64 mod p
+const EPSILON: u64 = 0xffff_ffff;
+
+/// The Goldilocks field.
+pub type Goldilocks = Fp64<GoldilocksConfig>;
+
+/// [`FpConfig`] for the Goldilocks field, storing elements in canonical form.
+pub struct GoldilocksConfig;
+
+const fn element(value: u64) -> Goldilocks {
+    ark_ff::Fp(BigInt([value]), PhantomData)
+}
+
+#[inline(always)]
+fn add(a: u64, b: u64) -> u64 {
+    let (sum, over) = a.overflowing_add(b);
+    // a, b < p, so adding 2^64 - p after an overflow cannot overflow again
+    let sum = if over { sum + EPSILON } else { sum };
+    if sum >= MODULUS {
+        sum - MODULUS
+    } else {
+        sum
+    }
+}
+
+#[inline(always)]
+fn sub(a: u64, b: u64) -> u64 {
+    let (diff, under) = a.overflowing_sub(b);
+    if under {
+        diff - EPSILON
+    } else {
+        diff
+    }
+}
+
+/// Reduces a 128-bit integer modulo p to its canonical representative.
+#[inline(always)]
+pub fn reduce128(x: u128) -> u64 {
+    let lo = x as u64;
+    let hi = (x >> 64) as u64;
+    let hi_hi = hi >> 32;
+    let hi_lo = hi & EPSILON;
+
+    // lo - hi_hi * 2^96 = lo - hi_hi
+    let (mut t0, under) = lo.overflowing_sub(hi_hi);
+    if under {
+        t0 = t0.wrapping_sub(EPSILON);
+    }
+    // hi_lo * 2^64 = hi_lo * (2^32 - 1) < 2^64
+    let t1 = hi_lo * EPSILON;
+    let (res, over) = t0.overflowing_add(t1);
+    let res = if over { res + EPSILON } else { res };
+    if res >= MODULUS {
+        res - MODULUS
+    } else {
+        res
+    }
+}
+
+#[inline(always)]
+fn mul(a: u64, b: u64) -> u64 {
+    reduce128(a as u128 * b as u128)
+}
+
+fn pow(mut base: u64, mut exp: u64) -> u64 {
+    let mut res = 1;
+    while exp != 0 {
+        if exp & 1 == 1 {
+            res = mul(res, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    res
+}
+
+impl FpConfig<1> for GoldilocksConfig {
+    const MODULUS: BigInt<1> = BigInt([MODULUS]);
+    const GENERATOR: Goldilocks = element(7);
+    const ZERO: Goldilocks = element(0);
+    const ONE: Goldilocks = element(1);
+    const TWO_ADICITY: u32 = 32;
+    // 7^((p - 1) / 2^32)
+    const TWO_ADIC_ROOT_OF_UNITY: Goldilocks = element(1_753_635_133_440_165_772);
+    const SQRT_PRECOMP: Option<SqrtPrecomputation<Goldilocks>> =
+        Some(SqrtPrecomputation::TonelliShanks {
+            two_adicity: 32,
+            quadratic_nonresidue_to_trace: element(1_753_635_133_440_165_772),
+            trace_of_modulus_minus_one_div_two: &[0x7fff_ffff],
+        });
+
+    #[inline(always)]
+    fn add_assign(a: &mut Goldilocks, b: &Goldilocks) {
+        a.0 .0[0] = add(a.0 .0[0], b.0 .0[0]);
+    }
+
+    #[inline(always)]
+    fn sub_assign(a: &mut Goldilocks, b: &Goldilocks) {
+        a.0 .0[0] = sub(a.0 .0[0], b.0 .0[0]);
+    }
+
+    #[inline(always)]
+    fn double_in_place(a: &mut Goldilocks) {
+        a.0 .0[0] = add(a.0 .0[0], a.0 .0[0]);
+    }
+
+    #[inline(always)]
+    fn neg_in_place(a: &mut Goldilocks) {
+        a.0 .0[0] = sub(0, a.0 .0[0]);
+    }
+
+    #[inline(always)]
+    fn mul_assign(a: &mut Goldilocks, b: &Goldilocks) {
+        a.0 .0[0] = mul(a.0 .0[0], b.0 .0[0]);
+    }
+
+    fn sum_of_products<const T: usize>(a: &[Goldilocks; T], b: &[Goldilocks; T]) -> Goldilocks {
+        let sum = a
+            .iter()
+            .zip(b.iter())
+            .fold(0, |acc, (a, b)| add(acc, mul(a.0 .0[0], b.0 .0[0])));
+        element(sum)
+    }
+
+    #[inline(always)]
+    fn square_in_place(a: &mut Goldilocks) {
+        a.0 .0[0] = mul(a.0 .0[0], a.0 .0[0]);
+    }
+
+    fn inverse(a: &Goldilocks) -> Option<Goldilocks> {
+        match a.0 .0[0] {
+            0 => None,
+            a => Some(element(pow(a, MODULUS - 2))),
+        }
+    }
+
+    fn from_bigint(other: BigInt<1>) -> Option<Goldilocks> {
+        (other.0[0] < MODULUS).then(|| element(other.0[0]))
+    }
+
+    #[inline(always)]
+    fn into_bigint(other: Goldilocks) -> BigInt<1> {
+        other.0
+    }
+}
+
+#[cfg(test)]
+mod goldilocks_field_tests {
+    #![allow(non_local_definitions)]
+
+    use super::{reduce128, Goldilocks, MODULUS};
+    use ark_ff::{
+        fields::{Fp64, MontBackend, MontConfig},
+        FftField, Field, PrimeField, UniformRand,
+    };
+    use rand::thread_rng;
+
+    #[derive(MontConfig)]
+    #[modulus = "18446744069414584321"]
+    #[generator = "7"]
+    struct GoldilocksMontConfig;
+    type Reference = Fp64<MontBackend<GoldilocksMontConfig, 1>>;
+
+    fn to_ref(x: Goldilocks) -> Reference {
+        Reference::from(x.into_bigint().0[0])
+    }
+
+    #[test]
+    fn reduction() {
+        let p = MODULUS as u128;
+        for x in [0, 1, p - 1, p, p + 1, u64::MAX as u128, p * p, u128::MAX] {
+            assert_eq!(reduce128(x) as u128, x % p);
+        }
+    }
+
+    #[test]
+    fn matches_montgomery() {
+        let mut rng = thread_rng();
+        let edge = [0, 1, 2, MODULUS - 1, MODULUS - 2, 1 << 32, u32::MAX as u64];
+        let mut values: Vec<Goldilocks> = edge.into_iter().map(Goldilocks::from).collect();
+        values.extend((0..20).map(|_| Goldilocks::rand(&mut rng)));
+        for a in values.iter() {
+            assert_eq!(to_ref(-*a), -to_ref(*a));
+            assert_eq!(to_ref(a.double()), to_ref(*a).double());
+            assert_eq!(to_ref(a.square()), to_ref(*a).square());
+            assert_eq!(a.inverse().map(to_ref), to_ref(*a).inverse());
+            assert_eq!(a.sqrt().map(|s| s.square()), to_ref(*a).sqrt().map(|_| *a));
+            for b in values.iter() {
+                assert_eq!(to_ref(*a + b), to_ref(*a) + to_ref(*b));
+                assert_eq!(to_ref(*a - b), to_ref(*a) - to_ref(*b));
+                assert_eq!(to_ref(*a * b), to_ref(*a) * to_ref(*b));
+            }
+        }
+        assert_eq!(
+            to_ref(Goldilocks::TWO_ADIC_ROOT_OF_UNITY),
+            Reference::TWO_ADIC_ROOT_OF_UNITY
+        );
+        assert_eq!(Goldilocks::GENERATOR.pow([MODULUS - 1]), Goldilocks::ONE);
+    }
+}
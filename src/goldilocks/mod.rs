@@ -0,0 +1,153 @@
+//! # Goldilocks
+//! Contains Poseidon parameters for the 64-bit Goldilocks field used by Plonky2-style proof
+//! systems, with the sbox x^7 and the round numbers R_F = 8, R_P = 22 for t = 8 and t = 12.
+//!
+//! The round constants are generated by the Grain LFSR of the Poseidon paper. As in the reference
+//! script, Cauchy matrices are sampled from the LFSR until one passes
+//! [`PoseidonParams::validate_security`](crate::parameters::PoseidonParams::validate_security).
+//! Note that Plonky2 itself uses a hand-picked circulant MDS matrix with different round
+//! constants, hence the hashes of these instances differ from Plonky2's.
+//!
+//! Plonky2's own instance (t = 12, same round numbers) is contained in
+//! [`plonky2_t12`](plonky2_t12::POSEIDON_PLONKY2_12_PARAMS), with the round constants and the
+//! circulant-plus-diagonal MDS matrix of `plonky2::hash::poseidon`. Together with
+//! [`Padding::Overwrite(4)`](crate::sponge::Padding::Overwrite), the sponge reproduces Plonky2's
+//! `PoseidonHash::hash_no_pad` and `hash_n_to_m_no_pad`.
+
+pub mod field;
+pub mod plonky2_t12;
+pub mod t12;
+pub mod t8;
+
+#[cfg(test)]
+mod goldilocks_tests {
+    use super::{
+        field::Goldilocks, plonky2_t12::POSEIDON_PLONKY2_12_PARAMS,
+        t12::POSEIDON_GOLDILOCKS_12_PARAMS, t8::POSEIDON_GOLDILOCKS_8_PARAMS,
+    };
+    use crate::{field_from_hex_string, poseidon::Poseidon};
+
+    fn check_kat(poseidon: &Poseidon<Goldilocks>, expected: &[&str]) {
+        let input: Vec<Goldilocks> = (0..expected.len() as u64).map(Goldilocks::from).collect();
+        let perm = poseidon.permutation(input.to_owned()).unwrap();
+        let expected: Vec<Goldilocks> = expected
+            .iter()
+            .map(|e| field_from_hex_string(e).unwrap())
+            .collect();
+        assert_eq!(perm, expected);
+        assert_eq!(poseidon.permutation_not_opt(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn kats() {
+        check_kat(
+            &Poseidon::new(&POSEIDON_GOLDILOCKS_8_PARAMS),
+            &[
+                "0xf533635a5ef2ccc1",
+                "0x8e047e3bdb6e0749",
+                "0x34b5f58f92bab302",
+                "0x2221ed296798fb59",
+                "0x967aeb7b96faab9a",
+                "0xdf94d95c2be1fbb1",
+                "0x918df8c207450abd",
+                "0xe27ac1a8d768814b",
+            ],
+        );
+        check_kat(
+            &Poseidon::new(&POSEIDON_GOLDILOCKS_12_PARAMS),
+            &[
+                "0x5ca1a759ae282a06",
+                "0x467d88acd22b429f",
+                "0x72a1c21d5bf7865a",
+                "0x5ff8d8f7afe5e998",
+                "0x192243d8fbd9ae55",
+                "0x8c611556cd87a648",
+                "0x43517b38aaa39b9d",
+                "0x159416d87c6c0629",
+                "0xcc8df76633e01caf",
+                "0x0237b66de5cd55a8",
+                "0xf6c1681cce137cf8",
+                "0xf4a0bacea3e72dcc",
+            ],
+        );
+        check_kat(
+            &Poseidon::new(&POSEIDON_PLONKY2_12_PARAMS),
+            &[
+                "0xd64e1e3efc5b8e9e",
+                "0x53666633020aaa47",
+                "0xd40285597c6a8825",
+                "0x613a4f81e81231d2",
+                "0x414754bfebd051f0",
+                "0xcb1f8980294a023f",
+                "0x6eb2a9e4d54a9d0f",
+                "0x1902bc3af467e056",
+                "0xf045d5eafdc6021f",
+                "0xe4150f77caaa3be5",
+                "0xc9bfd01d39b50cce",
+                "0x5c0a27fcb0e1459b",
+            ],
+        );
+    }
+
+    // The `test_vectors` of `plonky2::hash::poseidon_goldilocks`, which are not covered by the
+    // files in `test-vectors/`.
+    #[test]
+    fn plonky2_test_suite() {
+        let input = [
+            "0x8ccbbbea4fe5d2b7",
+            "0xc2af59ee9ec49970",
+            "0x90f7e1a9e658446a",
+            "0xdcc0630a3ab8b1b8",
+            "0x7ff8256bca20588c",
+            "0x5d99a7ca0c44ecfb",
+            "0x48452b17a70fbee3",
+            "0xeb09d654690b6c88",
+            "0x4a55d3a39c676a88",
+            "0xc0407a38d2285139",
+            "0xa234bac9356386d1",
+            "0xe1633f2bad98a52f",
+        ];
+        let expected = [
+            "0xa89280105650c4ec",
+            "0xab542d53860d12ed",
+            "0x5704148e9ccab94f",
+            "0xd3a826d4b62da9f5",
+            "0x8a7a6ca87892574f",
+            "0xc7017e1cad1a674e",
+            "0x1f06668922318e34",
+            "0xa3b203bc8102676f",
+            "0xfcc781b0ce382bf2",
+            "0x934c69ff3ed14ba5",
+            "0x504688a5996e8f13",
+            "0x401f3f2ed524a2ba",
+        ];
+        let from_hex = |hex: &[&str]| -> Vec<Goldilocks> {
+            hex.iter()
+                .map(|e| field_from_hex_string(e).unwrap())
+                .collect()
+        };
+        let poseidon = Poseidon::new(&POSEIDON_PLONKY2_12_PARAMS);
+        let expected = from_hex(&expected);
+        assert_eq!(poseidon.permutation(from_hex(&input)).unwrap(), expected);
+        assert_eq!(
+            poseidon.permutation_not_opt(from_hex(&input)).unwrap(),
+            expected
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn matches_generation() {
+        use crate::parameters::generate::{generate_with_rounds, round_numbers};
+
+        for params in [
+            &*POSEIDON_GOLDILOCKS_8_PARAMS,
+            &*POSEIDON_GOLDILOCKS_12_PARAMS,
+        ] {
+            assert_eq!(round_numbers::<Goldilocks>(params.t, 7, 128), (8, 22));
+            let generated = generate_with_rounds::<Goldilocks>(params.t, 7, 8, 22).unwrap();
+            assert_eq!(generated.round_constants, params.round_constants);
+            params.validate_security(128).unwrap();
+        }
+    }
+}
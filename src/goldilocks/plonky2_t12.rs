@@ -0,0 +1,24 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]) = [0xd64e1e3efc5b8e9e, 0x53666633020aaa47, 0xd40285597c6a8825, 0x613a4f81e81231d2, 0x414754bfebd051f0, 0xcb1f8980294a023f, 0x6eb2a9e4d54a9d0f, 0x1902bc3af467e056, 0xf045d5eafdc6021f, 0xe4150f77caaa3be5, 0xc9bfd01d39b50cce, 0x5c0a27fcb0e1459b];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::goldilocks::field::Goldilocks;
+
+lazy_static! {
+    pub static ref POSEIDON_PLONKY2_12_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            12,
+            7,
+            8,
+            22,
+            include_bytes!(concat!(
+                env!("OUT_DIR"),
+                "/params/goldilocks/plonky2_t12.bin"
+            )),
+        )
+        .unwrap()
+    );
+}
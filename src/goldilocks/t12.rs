@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]) = [0x5ca1a759ae282a06, 0x467d88acd22b429f, 0x72a1c21d5bf7865a, 0x5ff8d8f7afe5e998, 0x192243d8fbd9ae55, 0x8c611556cd87a648, 0x43517b38aaa39b9d, 0x159416d87c6c0629, 0xcc8df76633e01caf, 0x0237b66de5cd55a8, 0xf6c1681cce137cf8, 0xf4a0bacea3e72dcc];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::goldilocks::field::Goldilocks;
+
+lazy_static! {
+    pub static ref POSEIDON_GOLDILOCKS_12_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            12,
+            7,
+            8,
+            22,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/goldilocks/t12.bin")),
+        )
+        .unwrap()
+    );
+}
@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4, 5, 6, 7]) = [0xf533635a5ef2ccc1, 0x8e047e3bdb6e0749, 0x34b5f58f92bab302, 0x2221ed296798fb59, 0x967aeb7b96faab9a, 0xdf94d95c2be1fbb1, 0x918df8c207450abd, 0xe27ac1a8d768814b];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::goldilocks::field::Goldilocks;
+
+lazy_static! {
+    pub static ref POSEIDON_GOLDILOCKS_8_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            8,
+            7,
+            8,
+            22,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/goldilocks/t8.bin")),
+        )
+        .unwrap()
+    );
+}
@@ -0,0 +1,197 @@
+//! # Griffin
+//! Contains the Griffin permutation, whose nonlinear layer combines the inverse sbox
+//! x^(1/d) on element 0, the sbox x^d on element 1, and, for the remaining elements, a
+//! multiplication with a quadratic polynomial in a linear combination of the previous elements:
+//!
+//! - y_0 = x_0^(1/d), y_1 = x_1^d,
+//! - y_2 = x_2 * (L_2^2 + alpha_2 * L_2 + beta_2) with L_2 = y_0 + y_1,
+//! - y_i = x_i * (L_i^2 + alpha_i * L_i + beta_i) with L_i = (i - 1) * y_0 + y_1 + x_(i-1).
+//!
+//! The permutation starts with the linear layer, and every round applies the nonlinear layer,
+//! the linear layer, and, except in the last round, the round constants. The parameters are
+//! given explicitly. No instances are shipped: the constants of the published instances
+//! would have to be checked against known answers of the reference implementation, which is
+//! not available as a Rust crate, so the permutation is only tested against the specification.
+
+pub mod parameters;
+
+use crate::{error::Error, poseidon::pow_sbox};
+use alloc::{sync::Arc, vec, vec::Vec};
+use ark_ff::PrimeField;
+use parameters::GriffinParams;
+
+#[derive(Clone, Debug)]
+pub struct Griffin<F: PrimeField> {
+    pub(crate) params: Arc<GriffinParams<F>>,
+}
+
+impl<F: PrimeField> Griffin<F> {
+    pub fn new(params: &Arc<GriffinParams<F>>) -> Self {
+        Griffin {
+            params: params.clone(),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.params.t
+    }
+
+    pub fn permutation(&self, input: Vec<F>) -> Result<Vec<F>, Error> {
+        let mut state = input;
+        self.permutation_in_place(&mut state)?;
+        Ok(state)
+    }
+
+    /// Applies the permutation to the given state in place.
+    pub fn permutation_in_place(&self, state: &mut [F]) -> Result<(), Error> {
+        let params = &self.params;
+        let t = params.t;
+        if state.len() != t {
+            return Err(Error::WrongInputLength {
+                expected: t,
+                got: state.len(),
+            });
+        }
+        let mut scratch = vec![F::zero(); t];
+        params.mds.mat_vec_mul_into(state, &mut scratch);
+        state.copy_from_slice(&scratch);
+        for r in 0..params.rounds {
+            self.non_linear(state);
+            match params.round_constants.get(r) {
+                Some(rc) => params.mds.mat_vec_mul_add_into(state, rc, &mut scratch),
+                None => params.mds.mat_vec_mul_into(state, &mut scratch),
+            }
+            state.copy_from_slice(&scratch);
+        }
+        Ok(())
+    }
+
+    fn non_linear(&self, state: &mut [F]) {
+        let params = &self.params;
+        let y0 = state[0].pow(&params.d_inv);
+        let y1 = pow_sbox(state[1], params.d);
+        // (i - 1) * y_0 + y_1, updated incrementally
+        let mut y01 = y0 + y1;
+        let mut previous = state[1];
+        for (i, (el, (alpha, beta))) in state[2..]
+            .iter_mut()
+            .zip(params.alpha_beta.iter())
+            .enumerate()
+        {
+            let l = if i == 0 {
+                y01
+            } else {
+                y01 += y0;
+                y01 + previous
+            };
+            previous = *el;
+            el.mul_assign(l.square() + *alpha * l + beta);
+        }
+        state[0] = y0;
+        state[1] = y1;
+    }
+}
+
+#[cfg(test)]
+mod griffin_tests {
+    use super::*;
+    use crate::{field_hasher::FieldHasher, merkle::tree::MerkleTree};
+    use ark_ff::{Field, UniformRand};
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+
+    // circ(2, 1, 1) for t = 3, as in the Griffin paper
+    fn params(t: usize, rounds: usize) -> Arc<GriffinParams<Scalar>> {
+        let mut rng = thread_rng();
+        let mds = (0..t)
+            .map(|i| {
+                (0..t)
+                    .map(|j| Scalar::from(if i == j { 2 } else { 1 }))
+                    .collect()
+            })
+            .collect();
+        let rc = (0..rounds - 1)
+            .map(|_| (0..t).map(|_| Scalar::rand(&mut rng)).collect())
+            .collect();
+        // 5 is a non-square modulo the BN254 scalar field, so is alpha^2 - 4 beta = 4 - 4 * -1/4
+        let (alpha, beta) = (Scalar::from(2), -Scalar::from(1) / Scalar::from(4));
+        Arc::new(GriffinParams::new(t, 5, rounds, mds, alpha, beta, rc).unwrap())
+    }
+
+    // Inverts the nonlinear layer element by element
+    fn invert_non_linear(params: &GriffinParams<Scalar>, state: &[Scalar]) -> Vec<Scalar> {
+        let mut x = vec![pow_sbox(state[0], 5), state[1].pow(&params.d_inv)];
+        let (y0, y1) = (state[0], state[1]);
+        for i in 2..state.len() {
+            let mut l = Scalar::from((i - 1) as u64) * y0 + y1;
+            if i > 2 {
+                l += x[i - 1];
+            }
+            let (alpha, beta) = params.alpha_beta[i - 2];
+            x.push(state[i] / (l.square() + alpha * l + beta));
+        }
+        x
+    }
+
+    #[test]
+    fn non_linear_layer() {
+        let mut rng = thread_rng();
+        for t in [3, 4, 8] {
+            let griffin = Griffin::new(&params(t, 10));
+            let input: Vec<Scalar> = (0..t).map(|_| Scalar::rand(&mut rng)).collect();
+            let mut state = input.to_owned();
+            griffin.non_linear(&mut state);
+            assert_eq!(state[0].pow([5]), input[0]);
+            assert_eq!(state[1], input[1].pow([5]));
+            assert_eq!(invert_non_linear(&griffin.params, &state), input);
+        }
+    }
+
+    #[test]
+    fn permutation() {
+        let mut rng = thread_rng();
+        let griffin = Griffin::new(&params(3, 12));
+        let input: Vec<Scalar> = (0..3).map(|_| Scalar::rand(&mut rng)).collect();
+        let output = griffin.permutation(input.to_owned()).unwrap();
+        assert_ne!(output, input);
+        assert!(griffin.permutation(vec![Scalar::from(0); 4]).is_err());
+
+        let leaves: Vec<Scalar> = (0..4).map(Scalar::from).collect();
+        let tree = MerkleTree::with_hasher(
+            griffin.to_owned(),
+            crate::merkle::store::MemoryStore::new(),
+            leaves.to_owned(),
+        )
+        .unwrap();
+        let proof = tree.proof(1).unwrap();
+        assert!(
+            MerkleTree::verify_proof(&griffin, &tree.root(), tree.depth(), leaves[1], &proof)
+                .unwrap()
+        );
+        assert_eq!(
+            griffin.compress(leaves[0], leaves[1]).unwrap(),
+            griffin
+                .permutation(vec![Scalar::from(0), leaves[0], leaves[1]])
+                .unwrap()[0]
+        );
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let mds = vec![vec![Scalar::from(1); 3]; 3];
+        let rc = vec![vec![Scalar::from(1); 3]; 2];
+        let beta = -Scalar::from(1) / Scalar::from(4);
+        let new = |alpha, beta, rc| GriffinParams::new(3, 5, 3, mds.to_owned(), alpha, beta, rc);
+        assert!(new(Scalar::from(2), beta, rc.to_owned()).is_ok());
+        // alpha^2 - 4 beta = 4 is a square
+        assert!(new(Scalar::from(2), Scalar::from(0), rc.to_owned()).is_err());
+        assert!(matches!(
+            new(Scalar::from(2), beta, vec![vec![Scalar::from(1); 3]; 3]),
+            Err(Error::RoundConstantShape {
+                expected: (2, 3),
+                got: (3, 3)
+            })
+        ));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    error::Error,
+    parameters::{check_mds, check_round_constants, inverse_exponent, matrix::Matrix},
+};
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+
+#[derive(Clone, Debug)]
+pub struct GriffinParams<F: PrimeField> {
+    pub(crate) t: usize,        // statesize
+    pub(crate) d: usize,        // sbox degree
+    pub(crate) d_inv: Vec<u64>, // exponent of the inverse sbox, 1 / d mod p - 1
+    pub(crate) rounds: usize,
+    pub(crate) mds: Matrix<F>,
+    pub(crate) alpha_beta: Vec<(F, F)>, // (alpha_i, beta_i) for the elements 2..t
+    pub(crate) round_constants: Vec<Vec<F>>, // one row for all but the last round
+}
+
+impl<F: PrimeField> GriffinParams<F> {
+    /// Creates a new parameter set. The sbox x^d has to be a permutation, and alpha^2 - 4 beta
+    /// has to be a non-square, such that the quadratic polynomials of the elements 2..t have no
+    /// roots. The constants of element i are alpha_i = (i - 1) alpha and beta_i = (i - 1)^2 beta.
+    /// The round constants contain one row of length t for every round except the last one.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        t: usize,
+        d: usize,
+        rounds: usize,
+        mds: Vec<Vec<F>>,
+        alpha: F,
+        beta: F,
+        round_constants: Vec<Vec<F>>,
+    ) -> Result<Self, Error> {
+        if t < 3 || d < 3 || rounds == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        check_mds(t, &mds)?;
+        check_round_constants(rounds - 1, t, &round_constants)?;
+        let d_inv = inverse_exponent::<F>(d)?;
+        let discriminant = alpha.square() - beta.double().double();
+        if discriminant.legendre().is_qr() || discriminant.is_zero() {
+            return Err(Error::InvalidParameters);
+        }
+        let alpha_beta = (2..t)
+            .map(|i| {
+                let factor = F::from((i - 1) as u64);
+                (alpha * factor, beta * factor.square())
+            })
+            .collect();
+
+        Ok(GriffinParams {
+            t,
+            d,
+            d_inv,
+            rounds,
+            mds: Matrix::new(mds)?,
+            alpha_beta,
+            round_constants,
+        })
+    }
+
+    /// The statesize.
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// The degree of the sbox of element 1, element 0 uses its inverse.
+    pub fn d(&self) -> usize {
+        self.d
+    }
+
+    /// The number of rounds, the permutation applies the linear layer once more before them.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    pub fn mds(&self) -> &Matrix<F> {
+        &self.mds
+    }
+}
@@ -0,0 +1,71 @@
+//! # Grumpkin
+//! Grumpkin forms a cycle of curves with BN254: the base field of Grumpkin is the scalar field of
+//! BN254, and the scalar field of Grumpkin is the base field of BN254. Hashing over the base field
+//! of Grumpkin is therefore covered by the circom parameters in [`bn254`](crate::bn254).
+//!
+//! This module contains Poseidon parameters over the scalar field of Grumpkin for t = 3 and t = 4
+//! (x^5, R_F = 8, R_P = 56). The round constants are generated by the Grain LFSR, and Cauchy
+//! matrices are sampled until one passes
+//! [`PoseidonParams::validate_security`](crate::parameters::PoseidonParams::validate_security).
+
+pub mod t3;
+pub mod t4;
+
+/// The scalar field of Grumpkin, which is the base field of BN254.
+pub type Fr = ark_bn254::Fq;
+/// The base field of Grumpkin, which is the scalar field of BN254.
+pub type Fq = ark_bn254::Fr;
+
+#[cfg(test)]
+mod grumpkin_tests {
+    use super::{t3::POSEIDON_GRUMPKIN_3_PARAMS, t4::POSEIDON_GRUMPKIN_4_PARAMS, Fr};
+    use crate::{field_from_hex_string, poseidon::Poseidon};
+
+    fn check_kat(poseidon: &Poseidon<Fr>, expected: &[&str]) {
+        let input: Vec<Fr> = (0..expected.len() as u64).map(Fr::from).collect();
+        let perm = poseidon.permutation(input.to_owned()).unwrap();
+        let expected: Vec<Fr> = expected
+            .iter()
+            .map(|e| field_from_hex_string(e).unwrap())
+            .collect();
+        assert_eq!(perm, expected);
+        assert_eq!(poseidon.permutation_not_opt(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn kats() {
+        check_kat(
+            &Poseidon::new(&POSEIDON_GRUMPKIN_3_PARAMS),
+            &[
+                "0x192b8ca187c4ca8349dbf7c151edea86c9ae19d97a483dd9dda048216d850b9b",
+                "0x1791a6ee27025e8126795cc606aeeaac51fc874a39be483bab3c5f7ca1cd91f3",
+                "0x24c433e7aa6f759314eda7d63c3b1d70f4af6beecebf266553e990057e9c9417",
+            ],
+        );
+        check_kat(
+            &Poseidon::new(&POSEIDON_GRUMPKIN_4_PARAMS),
+            &[
+                "0x20e738c45962a618dfc797b866108ea5a5c5b0afb2042bda16b16270b15e984e",
+                "0x26ee08ac10e2ceda3b8e80bb2bccce6bad9500dc7339149c21360040bc6f3644",
+                "0x1921dd2d7d4be4f64723b705cfdc64825def6a3856955c7394842259a3a89d8f",
+                "0x2cd8a77450f3493deede95bd944c0d68654bb06ba027f4fc30a0340f43ff58f8",
+            ],
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn matches_generation() {
+        use crate::parameters::generate::{generate_with_rounds, round_numbers};
+
+        for (t, params) in [
+            (3, &*POSEIDON_GRUMPKIN_3_PARAMS),
+            (4, &*POSEIDON_GRUMPKIN_4_PARAMS),
+        ] {
+            assert_eq!(round_numbers::<Fr>(t, 5, 128), (8, 56));
+            let generated = generate_with_rounds::<Fr>(t, 5, 8, 56).unwrap();
+            assert_eq!(generated.round_constants, params.round_constants);
+            params.validate_security(128).unwrap();
+        }
+    }
+}
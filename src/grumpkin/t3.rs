@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2]) = [0x192b8ca187c4ca8349dbf7c151edea86c9ae19d97a483dd9dda048216d850b9b, 0x1791a6ee27025e8126795cc606aeeaac51fc874a39be483bab3c5f7ca1cd91f3, 0x24c433e7aa6f759314eda7d63c3b1d70f4af6beecebf266553e990057e9c9417];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::grumpkin::Fr;
+
+lazy_static! {
+    pub static ref POSEIDON_GRUMPKIN_3_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            3,
+            5,
+            8,
+            56,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/grumpkin/t3.bin")),
+        )
+        .unwrap()
+    );
+}
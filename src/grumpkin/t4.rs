@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3]) = [0x20e738c45962a618dfc797b866108ea5a5c5b0afb2042bda16b16270b15e984e, 0x26ee08ac10e2ceda3b8e80bb2bccce6bad9500dc7339149c21360040bc6f3644, 0x1921dd2d7d4be4f64723b705cfdc64825def6a3856955c7394842259a3a89d8f, 0x2cd8a77450f3493deede95bd944c0d68654bb06ba027f4fc30a0340f43ff58f8];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::grumpkin::Fr;
+
+lazy_static! {
+    pub static ref POSEIDON_GRUMPKIN_4_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            4,
+            5,
+            8,
+            56,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/grumpkin/t4.bin")),
+        )
+        .unwrap()
+    );
+}
@@ -0,0 +1,351 @@
+//! # Guessing Game
+//! Contains the typed commitment of the guessing game. The commitment is the Poseidon hash (circom
+//! layout, statesize t = 4) of the guess, the address of the player, and the randomness.
+
+use crate::{
+    bn254::circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS, error::Error, field_from_hex_string_with,
+    parameters::PoseidonParams, poseidon::Poseidon, FieldDecode,
+};
+use alloc::{format, sync::Arc};
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use core::{fmt, str::FromStr};
+#[cfg(feature = "eip55")]
+use sha3::{Digest, Keccak256};
+
+/// The size of an Ethereum address in bytes.
+pub const ADDRESS_SIZE: usize = 20;
+
+/// An Ethereum address. Parsing requires exactly 40 hex digits (with optional `0x` prefix). With
+/// the `eip55` feature, mixed-case addresses additionally have to match their EIP-55 checksum,
+/// whereas all-lowercase and all-uppercase addresses carry no checksum and are accepted as is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EthAddress(pub [u8; ADDRESS_SIZE]);
+
+impl EthAddress {
+    pub fn as_bytes(&self) -> &[u8; ADDRESS_SIZE] {
+        &self.0
+    }
+
+    /// The address as field element, i.e., the big-endian integer of its bytes. As addresses
+    /// have 160 bits, this is always canonical.
+    pub fn to_fr(&self) -> Fr {
+        Fr::from_be_bytes_mod_order(&self.0)
+    }
+
+    /// Parses an address like [`FromStr`], but additionally requires mixed-case hex digits
+    /// matching the EIP-55 checksum.
+    #[cfg(feature = "eip55")]
+    pub fn parse_checksummed(str: &str) -> Result<Self, Error> {
+        let address: EthAddress = str.parse()?;
+        let digits = str.strip_prefix("0x").unwrap_or(str);
+        if digits != &address.to_checksum_string()[2..] {
+            return Err(Error::AddressChecksum);
+        }
+        Ok(address)
+    }
+
+    /// Returns the address with `0x` prefix and the EIP-55 checksum encoded in the case of the
+    /// hex digits.
+    #[cfg(feature = "eip55")]
+    pub fn to_checksum_string(&self) -> alloc::string::String {
+        let lower = format!("{self:x}");
+        let hash = Keccak256::digest(lower.as_bytes());
+        let digits: alloc::string::String = lower
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                // uppercase if the corresponding nibble of the hash is at least 8
+                let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0xf;
+                if nibble >= 8 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect();
+        format!("0x{digits}")
+    }
+}
+
+impl FromStr for EthAddress {
+    type Err = Error;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let digits = str.strip_prefix("0x").unwrap_or(str);
+        if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(Error::InvalidAddress(format!("{c:?} is not a hex digit")));
+        }
+        if digits.len() != 2 * ADDRESS_SIZE {
+            return Err(Error::InvalidAddress(format!(
+                "expected {} hex digits, got {}",
+                2 * ADDRESS_SIZE,
+                digits.len()
+            )));
+        }
+        let mut bytes = [0u8; ADDRESS_SIZE];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&digits[2 * i..2 * i + 2], 16)
+                .expect("the digits are checked to be hex");
+        }
+        let address = EthAddress(bytes);
+
+        #[cfg(feature = "eip55")]
+        {
+            let has_lower = digits.chars().any(|c| c.is_ascii_lowercase());
+            let has_upper = digits.chars().any(|c| c.is_ascii_uppercase());
+            if has_lower && has_upper && digits != &address.to_checksum_string()[2..] {
+                return Err(Error::AddressChecksum);
+            }
+        }
+        Ok(address)
+    }
+}
+
+/// Lowercase hex with `0x` prefix, see [`EthAddress::to_checksum_string`] for the EIP-55 form.
+impl fmt::Display for EthAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{self:x}")
+    }
+}
+
+impl fmt::LowerHex for EthAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl From<[u8; ADDRESS_SIZE]> for EthAddress {
+    fn from(bytes: [u8; ADDRESS_SIZE]) -> Self {
+        EthAddress(bytes)
+    }
+}
+
+/// Builder for the commitment of the guessing game. All three of the guess, the address, and the
+/// randomness have to be set before calling [`Commitment::commit`] or [`Commitment::verify`].
+#[derive(Clone, Debug)]
+pub struct Commitment<F: PrimeField> {
+    poseidon: Poseidon<F>,
+    guess: Option<F>,
+    address: Option<F>,
+    randomness: Option<F>,
+}
+
+impl Default for Commitment<Fr> {
+    /// Uses the circom parameters over BN254, as the deployed game does.
+    fn default() -> Self {
+        Commitment {
+            poseidon: Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS),
+            guess: None,
+            address: None,
+            randomness: None,
+        }
+    }
+}
+
+impl<F: PrimeField> Commitment<F> {
+    /// Creates a builder for the given parameters, which must have statesize t = 4.
+    pub fn with_params(params: &Arc<PoseidonParams<F>>) -> Result<Self, Error> {
+        if params.t != 4 {
+            return Err(Error::WrongStatesize {
+                expected: 4,
+                got: params.t,
+            });
+        }
+        Ok(Commitment {
+            poseidon: Poseidon::new(params),
+            guess: None,
+            address: None,
+            randomness: None,
+        })
+    }
+
+    pub fn guess(mut self, guess: F) -> Self {
+        self.guess = Some(guess);
+        self
+    }
+
+    /// Sets the address, which is interpreted as big-endian integer.
+    pub fn address(mut self, address: &[u8; ADDRESS_SIZE]) -> Self {
+        self.address = Some(F::from_be_bytes_mod_order(address));
+        self
+    }
+
+    pub fn randomness(mut self, randomness: F) -> Self {
+        self.randomness = Some(randomness);
+        self
+    }
+
+    /// Computes the commitment, fails if one of the inputs is not set.
+    pub fn commit(&self) -> Result<F, Error> {
+        let guess = self.guess.ok_or(Error::MissingField("guess"))?;
+        let address = self.address.ok_or(Error::MissingField("address"))?;
+        let randomness = self.randomness.ok_or(Error::MissingField("randomness"))?;
+        self.poseidon.hash(&[guess, address, randomness])
+    }
+
+    /// Checks whether the inputs open the given commitment.
+    pub fn verify(&self, commitment: &F) -> Result<bool, Error> {
+        Ok(self.commit()? == *commitment)
+    }
+}
+
+/// Parses the randomness or a commitment of the guessing game, given as hex string with or
+/// without `0x` prefix (ignoring surrounding whitespace). This is the encoding of the library
+/// functions, the binaries, and the wasm bindings, so unprefixed input such as `10` is 0x10.
+/// Values which are not smaller than the modulus are rejected, so every opening has a unique
+/// encoding.
+pub fn parse_hex(str: &str) -> Result<Fr, Error> {
+    field_from_hex_string_with(str.trim(), FieldDecode::Strict)
+}
+
+/// Checks whether the guess, the address, and the randomness open the commitment of the guessing
+/// game over BN254.
+pub fn verify_commitment(
+    commitment: &Fr,
+    guess: Fr,
+    address: &[u8; ADDRESS_SIZE],
+    randomness: Fr,
+) -> Result<bool, Error> {
+    Commitment::default()
+        .guess(guess)
+        .address(address)
+        .randomness(randomness)
+        .verify(commitment)
+}
+
+#[cfg(test)]
+mod guessing_game_tests {
+    use super::*;
+    use crate::{
+        field_from_hex_string, guessing_game_commit, pasta::fp_t3::POSEIDON_PASTA_FP_3_PARAMS,
+    };
+
+    const ADDRESS: [u8; ADDRESS_SIZE] = [
+        0x70, 0x99, 0x79, 0x70, 0xc5, 0x18, 0x12, 0xdc, 0x3a, 0x01, 0x0c, 0x7d, 0x01, 0xb5, 0x0e,
+        0x0d, 0x17, 0xdc, 0x79, 0xc8,
+    ];
+
+    #[test]
+    fn known_commitment() {
+        let expected: Fr = field_from_hex_string(
+            "0x2346b3b208c9e65959af9824ccab4da69ae27d222204fcf0ace7f725e02e512d",
+        )
+        .unwrap();
+        let commitment = Commitment::default()
+            .guess(Fr::from(5))
+            .address(&ADDRESS)
+            .randomness(Fr::from(10))
+            .commit()
+            .unwrap();
+        assert_eq!(commitment, expected);
+        assert_eq!(
+            commitment,
+            guessing_game_commit(5, "0x70997970c51812dc3a010c7d01b50e0d17dc79c8", "0xa").unwrap()
+        );
+
+        assert!(verify_commitment(&expected, Fr::from(5), &ADDRESS, Fr::from(10)).unwrap());
+        assert!(!verify_commitment(&expected, Fr::from(6), &ADDRESS, Fr::from(10)).unwrap());
+        assert!(!verify_commitment(&expected, Fr::from(5), &ADDRESS, Fr::from(11)).unwrap());
+    }
+
+    #[test]
+    fn eth_address() {
+        let address: EthAddress = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8"
+            .parse()
+            .unwrap();
+        assert_eq!(address.as_bytes(), &ADDRESS);
+        assert_eq!(
+            address.to_string(),
+            "0x70997970c51812dc3a010c7d01b50e0d17dc79c8"
+        );
+        assert_eq!(
+            address.to_fr(),
+            field_from_hex_string("0x70997970c51812dc3a010c7d01b50e0d17dc79c8").unwrap()
+        );
+        let commitment = Commitment::default()
+            .guess(Fr::from(5))
+            .address(address.as_bytes())
+            .randomness(Fr::from(10))
+            .commit()
+            .unwrap();
+        assert_eq!(
+            commitment,
+            guessing_game_commit(5, "0x70997970c51812dc3a010c7d01b50e0d17dc79c8", "0xa").unwrap()
+        );
+        assert_eq!(
+            "70997970C51812DC3A010C7D01B50E0D17DC79C8"
+                .parse::<EthAddress>()
+                .unwrap(),
+            address
+        );
+
+        for (input, reason) in [
+            ("0x70997970c51812dc3a010c7d01b50e0d17dc79", "got 38"),
+            ("0x70997970c51812dc3a010c7d01b50e0d17dc79c800", "got 42"),
+            ("0x70997970c51812dc3a010c7d01b50e0d17dc79cg", "'g'"),
+            ("0x", "got 0"),
+        ] {
+            match input.parse::<EthAddress>() {
+                Err(Error::InvalidAddress(msg)) => assert!(msg.contains(reason), "{msg}"),
+                res => panic!("unexpected {res:?} for {input}"),
+            }
+        }
+    }
+
+    #[cfg(feature = "eip55")]
+    #[test]
+    fn eip55() {
+        // the test vectors of EIP-55
+        for checksummed in [
+            "0x52908400098527886E0F7030069857D2E4169EE7",
+            "0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+            "0xde709f2102306220921060314715629080e2fb77",
+            "0x27b1fdb04752bbc536007a920d24acb045561c26",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let address = EthAddress::parse_checksummed(checksummed).unwrap();
+            assert_eq!(address.to_checksum_string(), checksummed);
+        }
+
+        let checksummed = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+        assert_eq!(
+            EthAddress::parse_checksummed(checksummed)
+                .unwrap()
+                .as_bytes(),
+            &ADDRESS
+        );
+        // mixed case with a wrong checksum
+        assert!(matches!(
+            "0x70997970c51812dc3A010C7d01b50e0d17dc79C8".parse::<EthAddress>(),
+            Err(Error::AddressChecksum)
+        ));
+        // no checksum at all
+        assert!("0x70997970c51812dc3a010c7d01b50e0d17dc79c8"
+            .parse::<EthAddress>()
+            .is_ok());
+        assert!(matches!(
+            EthAddress::parse_checksummed("0x70997970c51812dc3a010c7d01b50e0d17dc79c8"),
+            Err(Error::AddressChecksum)
+        ));
+    }
+
+    #[test]
+    fn incomplete() {
+        let builder = Commitment::default().guess(Fr::from(5)).address(&ADDRESS);
+        assert!(matches!(
+            builder.commit(),
+            Err(Error::MissingField("randomness"))
+        ));
+        assert!(matches!(
+            Commitment::with_params(&POSEIDON_PASTA_FP_3_PARAMS),
+            Err(Error::WrongStatesize {
+                expected: 4,
+                got: 3
+            })
+        ));
+    }
+}
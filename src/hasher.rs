@@ -0,0 +1,216 @@
+//! # Hasher
+//! Contains a streaming byte hasher over the BN254 circom t=3 instance, which implements the
+//! RustCrypto `digest` traits when the `digest` feature is enabled.
+
+use crate::{
+    bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+    poseidon::{bytes_chunk_size, Poseidon, SegmentAbsorber},
+};
+use alloc::vec::Vec;
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField, Zero};
+
+/// A streaming hasher for byte strings. The result is identical to [`Poseidon::hash_bytes`] on
+/// the concatenation of all inputs, i.e., the bytes are injectively encoded as chunks of 31
+/// bytes (little-endian) followed by the number of chunks and the byte length. The 32-byte
+/// digest is the little-endian encoding of the resulting field element.
+#[derive(Clone, Debug)]
+pub struct PoseidonHasher {
+    absorber: SegmentAbsorber<Fr>,
+    buffer: Vec<u8>, // bytes of the incomplete chunk
+    num_chunks: usize,
+    num_bytes: usize,
+}
+
+impl Default for PoseidonHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PoseidonHasher {
+    pub fn new() -> Self {
+        Self::with_domain(Fr::zero())
+    }
+
+    /// Creates a hasher whose outputs are separated from the ones of all other tags and of
+    /// [`Self::new`], by encoding [`Poseidon::domain_separator`] of the tag into the capacity
+    /// element (see [`Poseidon::hash_segments_with_domain`]).
+    pub fn new_with_domain(tag: &str) -> Self {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let domain = poseidon
+            .domain_separator(tag)
+            .expect("t = 3 is a valid statesize");
+        Self::with_domain(domain)
+    }
+
+    fn with_domain(domain: Fr) -> Self {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        PoseidonHasher {
+            absorber: SegmentAbsorber::new(&poseidon, 2, domain)
+                .expect("t = 3 is a valid statesize"),
+            buffer: Vec::new(),
+            num_chunks: 0,
+            num_bytes: 0,
+        }
+    }
+
+    fn chunk_size() -> usize {
+        bytes_chunk_size::<Fr>().expect("BN254 packs 31 bytes per element")
+    }
+
+    fn absorb_chunk(&mut self, chunk: &[u8]) {
+        self.absorber
+            .absorb(Fr::from_le_bytes_mod_order(chunk))
+            .expect("t = 3 is a valid statesize");
+        self.num_chunks += 1;
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        let chunk_size = Self::chunk_size();
+        self.num_bytes += data.len();
+        if !self.buffer.is_empty() {
+            let missing = (chunk_size - self.buffer.len()).min(data.len());
+            self.buffer.extend_from_slice(&data[..missing]);
+            data = &data[missing..];
+            if self.buffer.len() < chunk_size {
+                return;
+            }
+            let chunk = core::mem::take(&mut self.buffer);
+            self.absorb_chunk(&chunk);
+        }
+        let mut chunks = data.chunks_exact(chunk_size);
+        for chunk in chunks.by_ref() {
+            self.absorb_chunk(chunk);
+        }
+        self.buffer.extend_from_slice(chunks.remainder());
+    }
+
+    /// Returns the hash as field element.
+    pub fn finalize_field(mut self) -> Fr {
+        if !self.buffer.is_empty() {
+            let chunk = core::mem::take(&mut self.buffer);
+            self.absorb_chunk(&chunk);
+        }
+        let mut absorber = self.absorber;
+        let result = absorber
+            .end_segment(self.num_chunks)
+            .and_then(|_| absorber.absorb(Fr::from(self.num_bytes as u64)))
+            .and_then(|_| absorber.end_segment(1))
+            .and_then(|_| absorber.finish());
+        result.expect("t = 3 is a valid statesize")
+    }
+
+    /// Returns the hash as little-endian encoding of the field element.
+    pub fn finalize_bytes(self) -> [u8; 32] {
+        let bytes = self.finalize_field().into_bigint().to_bytes_le();
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&bytes);
+        out
+    }
+}
+
+#[cfg(feature = "digest")]
+mod digest_impl {
+    use super::PoseidonHasher;
+    use digest::{
+        consts::U32, FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset,
+        Update,
+    };
+
+    impl HashMarker for PoseidonHasher {}
+
+    impl OutputSizeUser for PoseidonHasher {
+        type OutputSize = U32;
+    }
+
+    impl Update for PoseidonHasher {
+        fn update(&mut self, data: &[u8]) {
+            PoseidonHasher::update(self, data)
+        }
+    }
+
+    impl FixedOutput for PoseidonHasher {
+        fn finalize_into(self, out: &mut Output<Self>) {
+            out.copy_from_slice(&self.finalize_bytes());
+        }
+    }
+
+    impl Reset for PoseidonHasher {
+        fn reset(&mut self) {
+            *self = PoseidonHasher::new();
+        }
+    }
+
+    impl FixedOutputReset for PoseidonHasher {
+        fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+            let hasher = core::mem::take(self);
+            out.copy_from_slice(&hasher.finalize_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+mod hasher_tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn matches_hash_bytes() {
+        let mut rng = thread_rng();
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        for len in [0, 1, 30, 31, 32, 62, 100] {
+            let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            let expected = poseidon.hash_bytes(&data).unwrap();
+
+            let mut hasher = PoseidonHasher::new();
+            hasher.update(&data);
+            assert_eq!(hasher.finalize_field(), expected);
+
+            // streamed in uneven pieces
+            let mut hasher = PoseidonHasher::new();
+            for piece in data.chunks(7) {
+                hasher.update(piece);
+            }
+            hasher.update(&[]);
+            assert_eq!(hasher.finalize_field(), expected);
+        }
+    }
+
+    #[test]
+    fn domain_separation() {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let hash = |mut hasher: PoseidonHasher| {
+            hasher.update(b"message");
+            hasher.finalize_field()
+        };
+        let plain = hash(PoseidonHasher::new());
+        let tagged = hash(PoseidonHasher::new_with_domain("protocol-a"));
+        assert_ne!(plain, tagged);
+        assert_ne!(tagged, hash(PoseidonHasher::new_with_domain("protocol-b")));
+
+        let domain = poseidon.domain_separator("protocol-a").unwrap();
+        let chunks = [Fr::from_le_bytes_mod_order(b"message")];
+        let expected = poseidon
+            .hash_segments_with_domain(domain, &[&chunks, &[Fr::from(7)]])
+            .unwrap();
+        assert_eq!(tagged, expected);
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn digest_traits() {
+        use digest::Digest;
+
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let expected = poseidon.hash_bytes(b"hello world").unwrap();
+        let digest = PoseidonHasher::digest(b"hello world");
+        assert_eq!(digest.as_slice(), expected.into_bigint().to_bytes_le());
+
+        let mut hasher = PoseidonHasher::new();
+        Digest::update(&mut hasher, b"hello ");
+        Digest::update(&mut hasher, b"world");
+        assert_eq!(hasher.finalize_reset(), digest);
+        assert_eq!(hasher.finalize(), PoseidonHasher::digest(b""));
+    }
+}
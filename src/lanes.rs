@@ -0,0 +1,170 @@
+//! # Multi-Instance Permutation
+//! Applies the Poseidon permutation to several independent states at once, e.g., to all pairs
+//! of a Merkle tree layer. The states are transposed into lanes, such that every operation of a
+//! round (round constant addition, sbox, linear layer) is executed for all lanes before moving
+//! on. The field multiplications of different lanes are independent of each other, which allows
+//! the CPU to overlap them. The lanes implement [`PoseidonField`], so they are permuted by the
+//! same round core as single states.
+//!
+//! This is not a SIMD implementation: the lanes use the same field arithmetic as the scalar path,
+//! and there are no packed (e.g., AVX2) Montgomery multiplication kernels.
+
+use crate::{
+    error::Error,
+    mpc::PoseidonField,
+    parameters::matrix::Matrix,
+    poseidon::{pow_sbox, Poseidon},
+};
+use alloc::{vec, vec::Vec};
+use ark_ff::PrimeField;
+use itertools::izip;
+
+/// The number of lanes used by [`Poseidon::permutation_many`].
+pub const LANES: usize = 4;
+
+impl<F: PrimeField> Poseidon<F> {
+    /// Applies the permutation to N states of size t in parallel lanes.
+    pub fn permutation_lanes<const N: usize>(&self, states: &mut [Vec<F>; N]) -> Result<(), Error> {
+        let t = self.params.t;
+        if let Some(state) = states.iter().find(|state| state.len() != t) {
+            return Err(Error::WrongInputLength {
+                expected: t,
+                got: state.len(),
+            });
+        }
+        // transpose into the lanes
+        let mut lanes = vec![[F::zero(); N]; t];
+        for (l, state) in states.iter().enumerate() {
+            for (lane, el) in izip!(lanes.iter_mut(), state.iter()) {
+                lane[l] = *el;
+            }
+        }
+        let mut scratch = vec![[F::zero(); N]; t];
+        self.permute_generic(&mut lanes, &mut scratch, &mut ())?;
+        for (l, state) in states.iter_mut().enumerate() {
+            for (lane, el) in izip!(lanes.iter(), state.iter_mut()) {
+                *el = lane[l];
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the permutation to all states, [`LANES`] at a time. The remaining states are
+    /// permuted with the scalar path.
+    pub fn permutation_many(&self, states: &mut [Vec<F>]) -> Result<(), Error> {
+        let mut chunks = states.chunks_exact_mut(LANES);
+        for chunk in chunks.by_ref() {
+            let chunk: &mut [Vec<F>; LANES] = chunk.try_into().expect("chunk has LANES states");
+            self.permutation_lanes(chunk)?;
+        }
+        for state in chunks.into_remainder() {
+            self.permutation_in_place(state)?;
+        }
+        Ok(())
+    }
+}
+
+/// N lanes of a state element, the operations are applied lane-wise.
+impl<F: PrimeField, const N: usize> PoseidonField<F> for [F; N] {
+    type Context = ();
+
+    fn add(&self, other: &Self) -> Self {
+        core::array::from_fn(|l| self[l] + other[l])
+    }
+
+    fn add_public(&self, constant: &F, _ctx: &()) -> Self {
+        self.map(|el| el + constant)
+    }
+
+    fn mul_public(&self, constant: &F) -> Self {
+        self.map(|el| el * constant)
+    }
+
+    fn pow_batch(values: &mut [Self], d: usize, _ctx: &mut ()) -> Result<(), Error> {
+        for lane in values.iter_mut() {
+            *lane = lane.map(|el| pow_sbox(el, d));
+        }
+        Ok(())
+    }
+
+    fn mat_vec_mul(matrix: &Matrix<F>, input: &[Self], out: &mut [Self]) {
+        for (row, out) in izip!(matrix.rows(), out.iter_mut()) {
+            *out = [F::zero(); N];
+            for (mat, inp) in izip!(row.iter(), input.iter()) {
+                for (out, inp) in izip!(out.iter_mut(), inp.iter()) {
+                    *out += *inp * mat;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod lanes_tests {
+    use super::*;
+    use crate::{
+        bn254::{circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS},
+        goldilocks::{field::Goldilocks, t8::POSEIDON_GOLDILOCKS_8_PARAMS},
+        parameters::PoseidonParams,
+    };
+    use alloc::sync::Arc;
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    fn check<F: PrimeField, const N: usize>(poseidon: &Poseidon<F>) {
+        let mut rng = thread_rng();
+        let t = poseidon.get_t();
+        let mut states: [Vec<F>; N] =
+            core::array::from_fn(|_| (0..t).map(|_| F::rand(&mut rng)).collect());
+        let expected: Vec<Vec<F>> = states
+            .iter()
+            .map(|state| poseidon.permutation(state.to_owned()).unwrap())
+            .collect();
+        poseidon.permutation_lanes(&mut states).unwrap();
+        assert_eq!(states.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn matches_scalar() {
+        let bn254_3 = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let bn254_5 = Poseidon::new(&POSEIDON_CIRCOM_BN_5_PARAMS);
+        let goldilocks = Poseidon::new(&POSEIDON_GOLDILOCKS_8_PARAMS);
+        check::<_, 1>(&bn254_3);
+        check::<_, 4>(&bn254_3);
+        check::<_, 4>(&bn254_5);
+        check::<Goldilocks, 8>(&goldilocks);
+
+        let base = &*POSEIDON_CIRCOM_BN_3_PARAMS;
+        let full_rounds_only = PoseidonParams::new(
+            base.t,
+            base.d,
+            8,
+            0,
+            base.mds.to_vec(),
+            base.round_constants.to_vec()[..8].to_vec(),
+        )
+        .unwrap();
+        check::<_, 4>(&Poseidon::new(&Arc::new(full_rounds_only)));
+    }
+
+    #[test]
+    fn many() {
+        let mut rng = thread_rng();
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let mut states: Vec<Vec<ark_bn254::Fr>> = (0..2 * LANES + 3)
+            .map(|_| (0..3).map(|_| ark_bn254::Fr::rand(&mut rng)).collect())
+            .collect();
+        let expected: Vec<_> = states
+            .iter()
+            .map(|state| poseidon.permutation(state.to_owned()).unwrap())
+            .collect();
+        poseidon.permutation_many(&mut states).unwrap();
+        assert_eq!(states, expected);
+
+        states[5].pop();
+        assert!(matches!(
+            poseidon.permutation_many(&mut states),
+            Err(Error::WrongInputLength { .. })
+        ));
+    }
+}
@@ -1,52 +1,375 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "anemoi")]
+pub mod anemoi;
+#[cfg(feature = "bls12-381")]
+pub mod bls12_381;
 pub mod bn254;
+pub mod chain;
+pub mod circom;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod codegen;
+pub mod commitment;
+pub mod driver;
+#[cfg(feature = "eddsa")]
+pub mod eddsa;
+pub mod encryption;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod field_hasher;
+pub mod goldilocks;
+#[cfg(feature = "griffin")]
+pub mod griffin;
+pub mod grumpkin;
+pub mod guessing_game;
+pub mod hasher;
+#[cfg(feature = "lanes")]
+pub mod lanes;
+pub mod light_poseidon;
+pub mod mac;
+pub mod merkle;
+#[cfg(feature = "mimc")]
+pub mod mimc;
+#[cfg(feature = "monolith")]
+pub mod monolith;
+pub mod mpc;
+pub mod output;
 pub mod parameters;
+pub mod pasta;
 pub mod poseidon;
+pub mod poseidon2;
+pub mod precompile;
+pub mod prf;
+#[cfg(feature = "std")]
+pub mod registry;
+pub mod rescue;
+#[cfg(feature = "safe")]
+pub mod safe;
+#[cfg(feature = "semaphore")]
+pub mod semaphore;
+pub mod small_fields;
+pub mod sponge;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod transcript;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 use crate::error::Error;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 use ark_bn254::Fr;
-use ark_ff::{PrimeField, Zero};
+use ark_ff::PrimeField;
 use bn254::{circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS};
+use chain::HashChain;
 use num_bigint::BigUint;
 use num_traits::Num;
+use parameters::PoseidonParams;
 use poseidon::Poseidon;
 
+/// Whether the x86_64 assembly of ark-ff is compiled in, i.e., the `asm` feature is enabled and
+/// the target supports bmi2 and adx. It applies to the fields defined in this crate; the BN254
+/// scalar field of ark-bn254 0.4 always uses the portable multiplication.
+pub const ASM_BACKEND: bool = cfg!(all(
+    feature = "asm",
+    target_arch = "x86_64",
+    target_feature = "bmi2",
+    target_feature = "adx"
+));
+
+/// How the parsing helpers treat values which are not smaller than the field modulus.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FieldDecode {
+    /// Rejects the value with [`Error::NonCanonical`], as required wherever an encoding has to be
+    /// unique, e.g., for commitment openings.
+    #[default]
+    Strict,
+    /// Reduces the value modulo p.
+    Reduce,
+}
+
+impl FieldDecode {
+    fn decode<F: PrimeField>(self, value: BigUint) -> Result<F, Error> {
+        if self == FieldDecode::Strict && value >= F::MODULUS.into() {
+            return Err(Error::NonCanonical);
+        }
+        Ok(value.into())
+    }
+}
+
+/// Parses a field element from a hex string with or without `0x` prefix. Values which are not
+/// smaller than the modulus are reduced, which is intended for trusted constants only, see
+/// [`field_from_str`] for user input and [`field_from_hex_string_with`] for strict parsing.
 pub fn field_from_hex_string<F: PrimeField>(str: &str) -> Result<F, Error> {
-    let tmp = match str.strip_prefix("0x") {
-        Some(t) => BigUint::from_str_radix(t, 16),
-        None => BigUint::from_str_radix(str, 16),
-    };
+    field_from_hex_string_with(str, FieldDecode::Reduce)
+}
+
+/// Parses a field element from a hex string with or without `0x` prefix in the given mode.
+pub fn field_from_hex_string_with<F: PrimeField>(
+    str: &str,
+    decode: FieldDecode,
+) -> Result<F, Error> {
+    let str = str.strip_prefix("0x").unwrap_or(str);
+    let tmp = BigUint::from_str_radix(str, 16).map_err(|_| Error::ParseString)?;
+    decode.decode(tmp)
+}
+
+/// Parses a field element given in decimal or in hex with `0x` prefix, ignoring surrounding
+/// whitespace. Values which are not smaller than the field modulus are rejected with
+/// [`Error::NonCanonical`] instead of being reduced.
+pub fn field_from_str<F: PrimeField>(str: &str) -> Result<F, Error> {
+    field_from_str_with(str, FieldDecode::Strict)
+}
+
+/// Parses a field element like [`field_from_str`] in the given mode.
+pub fn field_from_str_with<F: PrimeField>(str: &str, decode: FieldDecode) -> Result<F, Error> {
+    let str = str.trim();
+    match str.strip_prefix("0x") {
+        Some(hex) => field_from_str_radix_with(hex, 16, decode),
+        None => field_from_str_radix_with(str, 10, decode),
+    }
+}
+
+/// Parses a field element from a string in the given radix (2..=16), without any prefix. Values
+/// which are not smaller than the field modulus are rejected.
+pub fn field_from_str_radix<F: PrimeField>(str: &str, radix: u32) -> Result<F, Error> {
+    field_from_str_radix_with(str, radix, FieldDecode::Strict)
+}
 
-    let tmp = tmp.map_err(|_| Error::ParseString)?;
-    Ok(tmp.into())
+/// Parses a field element like [`field_from_str_radix`] in the given mode.
+pub fn field_from_str_radix_with<F: PrimeField>(
+    str: &str,
+    radix: u32,
+    decode: FieldDecode,
+) -> Result<F, Error> {
+    if !(2..=16).contains(&radix) {
+        return Err(Error::InvalidRadix(radix));
+    }
+    let tmp = BigUint::from_str_radix(str, radix).map_err(|_| Error::ParseString)?;
+    decode.decode(tmp)
 }
 
-fn commitment(input: Vec<Fr>) -> Result<Fr, Error> {
-    let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS);
-    let perm = poseidon.permutation(input)?;
-    Ok(perm[0])
+/// Hashes an arbitrary byte string with [`Poseidon::hash_bytes`] over the circom t=3 instance.
+/// Like all `try_` functions, it never panics, which makes it a direct fuzzing entry point.
+pub fn try_hash_bytes(bytes: &[u8]) -> Result<Fr, Error> {
+    Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS).hash_bytes(bytes)
+}
+
+/// Loads parameters from untrusted JSON without panicking, either in the format of the parameter
+/// files in `params/` (see [`PoseidonParams::from_params_json`]), which is detected by the key
+/// `t`, or in the format of circomlib (see [`PoseidonParams::from_circom_json`]).
+pub fn try_params_from_json<F: PrimeField>(json: &str) -> Result<PoseidonParams<F>, Error> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    if value.get("t").is_some() {
+        PoseidonParams::from_params_json(json)
+    } else {
+        PoseidonParams::from_circom_json(json)
+    }
 }
 
+/// Returns the hex string (with `0x` prefix) of a field element, zero-padded to the byte size of
+/// the modulus.
+pub fn field_to_hex_string<F: PrimeField>(el: &F) -> String {
+    let biguint: BigUint = el.into_bigint().into();
+    let width = (F::MODULUS_BIT_SIZE as usize).div_ceil(8) * 2;
+    format!("0x{:0>width$}", biguint.to_str_radix(16))
+}
+
+/// Computes the commitment of the guessing game from the address (see
+/// [`guessing_game::EthAddress`]) and the randomness as hex string (see
+/// [`guessing_game::parse_hex`]), see [`guessing_game::Commitment`] for the typed builder.
 pub fn guessing_game_commit(guess: u16, address: &str, r: &str) -> Result<Fr, Error> {
     let guess = Fr::from(guess);
-    let address = field_from_hex_string(address)?;
-    let r = field_from_hex_string(r)?;
+    let address = address.trim().parse::<guessing_game::EthAddress>()?.to_fr();
+    // the opening must be unique, so r + p is not accepted for r
+    let r = guessing_game::parse_hex(r)?;
+
+    Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS).hash(&[guess, address, r])
+}
 
-    commitment(vec![Fr::zero(), guess, address, r])
+/// Checks whether the guess, the address, and the randomness open the claimed commitment of the
+/// guessing game, where all values are parsed like in [`guessing_game_commit`].
+pub fn guessing_game_verify(
+    guess: u16,
+    address: &str,
+    r: &str,
+    commitment: &str,
+) -> Result<bool, Error> {
+    let commitment = guessing_game::parse_hex(commitment)?;
+    Ok(guessing_game_commit(guess, address, r)? == commitment)
 }
 
+/// Computes the commitment of the guessing game like [`guessing_game_commit`] from typed inputs:
+/// the guess is embedded as the integer it is, the 20 bytes of the address are read as
+/// big-endian integer (see [`guessing_game::EthAddress::to_fr`]), and `r` is used as is. The
+/// C interface passes `r` as 32 canonical little-endian bytes.
+pub fn guessing_game_commit_bytes(guess: u16, address: &[u8; 20], r: Fr) -> Result<Fr, Error> {
+    guessing_game::Commitment::default()
+        .guess(Fr::from(guess))
+        .address(address)
+        .randomness(r)
+        .commit()
+}
+
+/// Folds the inputs with the circom t=3 compression, see [`HashChain`] for a streaming variant.
 pub fn poseidon_hash_chain(input: Vec<Fr>) -> Result<Fr, Error> {
-    let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
-
-    let mut state_vec = vec![Fr::zero(); 3];
-    for inp in input {
-        state_vec[1] = state_vec[0]; // output of the hash chain
-        state_vec[0] = Fr::zero(); // Reset capacity part
-        state_vec[2] = inp; // first input part
-        state_vec = poseidon.permutation(state_vec)?;
+    let mut chain = HashChain::new(&POSEIDON_CIRCOM_BN_3_PARAMS)?;
+    chain.update_many(&input)?;
+    Ok(chain.finalize())
+}
+
+/// Computes the hash chain like [`poseidon_hash_chain`], but returns the intermediate value after
+/// every input. The last element is the result of the chain, the trace of the empty chain is
+/// empty.
+pub fn poseidon_hash_chain_with_trace(input: Vec<Fr>) -> Result<Vec<Fr>, Error> {
+    let mut chain = HashChain::new(&POSEIDON_CIRCOM_BN_3_PARAMS)?;
+    chain.update_many_with_trace(&input)
+}
+
+/// Checks whether the inputs lead the hash chain from the start state to the end state, e.g.,
+/// to verify a segment between two intermediate values of [`poseidon_hash_chain_with_trace`].
+pub fn verify_chain(start_state: Fr, inputs: &[Fr], end_state: Fr) -> Result<bool, Error> {
+    let mut chain = HashChain::resume(&POSEIDON_CIRCOM_BN_3_PARAMS, start_state)?;
+    chain.update_many(inputs)?;
+    Ok(chain.finalize() == end_state)
+}
+
+#[cfg(test)]
+mod parse_test {
+    use super::*;
+
+    #[test]
+    fn str_radix() {
+        let expected = Fr::from(1234567890u64);
+        let hex: Fr = field_from_str_radix("499602d2", 16).unwrap();
+        let dec: Fr = field_from_str_radix("1234567890", 10).unwrap();
+        let bin: Fr = field_from_str_radix("1001001100101100000001011010010", 2).unwrap();
+        assert_eq!(hex, expected);
+        assert_eq!(dec, expected);
+        assert_eq!(bin, expected);
+    }
+
+    #[test]
+    fn from_str() {
+        let expected = Fr::from(42u64);
+        assert_eq!(field_from_str::<Fr>("42").unwrap(), expected);
+        assert_eq!(field_from_str::<Fr>("0x2a").unwrap(), expected);
+        assert_eq!(field_from_str::<Fr>(" 0x002A\n").unwrap(), expected);
+
+        let modulus: BigUint = Fr::MODULUS.into();
+        for str in [
+            modulus.to_string(),
+            format!("0x{}", modulus.to_str_radix(16)),
+        ] {
+            assert!(matches!(
+                field_from_str::<Fr>(&str),
+                Err(Error::NonCanonical)
+            ));
+            // the hex parser for trusted constants reduces instead
+            assert!(field_from_hex_string::<Fr>(&modulus.to_str_radix(16)).is_ok());
+        }
+        for str in ["", "0x", "2a", "0xzz", "-1", "1.5"] {
+            assert!(matches!(field_from_str::<Fr>(str), Err(Error::ParseString)));
+        }
+    }
+
+    #[test]
+    fn decode_modes() {
+        let modulus: BigUint = Fr::MODULUS.into();
+        let above = format!("0x{}", (&modulus + 5u32).to_str_radix(16));
+        assert!(matches!(
+            field_from_hex_string_with::<Fr>(&above, FieldDecode::Strict),
+            Err(Error::NonCanonical)
+        ));
+        assert_eq!(
+            field_from_hex_string_with::<Fr>(&above, FieldDecode::Reduce).unwrap(),
+            Fr::from(5u64)
+        );
+        assert_eq!(field_from_hex_string::<Fr>(&above).unwrap(), Fr::from(5u64));
+        assert_eq!(
+            field_from_str_with::<Fr>(&modulus.to_string(), FieldDecode::Reduce).unwrap(),
+            Fr::from(0u64)
+        );
+        assert!(
+            field_from_str_radix_with::<Fr>(&modulus.to_str_radix(2), 2, FieldDecode::Strict)
+                .is_err()
+        );
+
+        // commitment openings are strict
+        let address = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8";
+        let r = format!("0x{}", (&modulus + 10u32).to_str_radix(16));
+        assert!(matches!(
+            guessing_game_commit(5, address, &r),
+            Err(Error::NonCanonical)
+        ));
+    }
+
+    #[test]
+    fn str_radix_rejects() {
+        let modulus =
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+        let below = "21888242871839275222246405745257275088548364400416034343698204186575808495616";
+        assert_eq!(field_from_str_radix::<Fr>(below, 10).unwrap(), -Fr::from(1));
+        assert!(matches!(
+            field_from_str_radix::<Fr>(modulus, 10),
+            Err(Error::NonCanonical)
+        ));
+        assert!(matches!(
+            field_from_str_radix::<Fr>("12", 17),
+            Err(Error::InvalidRadix(17))
+        ));
+        assert!(matches!(
+            field_from_str_radix::<Fr>("0x12", 16),
+            Err(Error::ParseString)
+        ));
+        assert!(matches!(
+            field_from_str_radix::<Fr>("2", 2),
+            Err(Error::ParseString)
+        ));
+    }
+
+    #[test]
+    fn malformed_params_json() {
+        let params = &POSEIDON_CIRCOM_BN_3_PARAMS;
+        let loaded = try_params_from_json::<Fr>(&params.to_params_json()).unwrap();
+        assert_eq!(loaded.to_bytes(), params.to_bytes());
+        let loaded = try_params_from_json::<Fr>(&params.to_circomlib_json()).unwrap();
+        assert_eq!(loaded.to_bytes(), params.to_bytes());
+
+        let inputs = [
+            "",
+            "[1, 2",
+            r#"{"t": 0, "d": 5, "rounds_f": 0, "rounds_p": 0, "M": [], "C": []}"#,
+            r#"{"t": 1, "d": 5, "rounds_f": 18446744073709551615, "rounds_p": 1, "M": [["0x1"]]}"#,
+            r#"{"t": 2, "d": 5, "rounds_f": 2, "rounds_p": 0, "M": [["0x1"], []], "C": []}"#,
+            r#"{"C": ["0x1"], "M": [[1]]}"#,
+            r#"{"C": [], "M": [["0xzz"]]}"#,
+        ];
+        for input in inputs {
+            assert!(try_params_from_json::<Fr>(input).is_err(), "{input}");
+        }
     }
 
-    Ok(state_vec[0])
+    #[test]
+    fn hash_bytes() {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        for bytes in [&b""[..], b"poseidon", &[0xff; 100]] {
+            assert_eq!(
+                try_hash_bytes(bytes).unwrap(),
+                poseidon.hash_bytes(bytes).unwrap()
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +385,28 @@ mod commitment_test {
 
         let result = guessing_game_commit(guess, address, r).unwrap();
         assert_eq!(result, field_from_hex_string(expected).unwrap());
+
+        assert!(guessing_game_verify(guess, address, r, expected).unwrap());
+        assert!(!guessing_game_verify(guess + 1, address, r, expected).unwrap());
+        assert!(!guessing_game_verify(guess, address, "0xb", expected).unwrap());
+        assert!(guessing_game_verify(guess, address, r, "0xzz").is_err());
+    }
+
+    #[test]
+    fn hex_randomness() {
+        // unprefixed randomness is hex, as in the binaries and the wasm bindings
+        let address = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8";
+        let expected = guessing_game_commit(5, address, "0x10").unwrap();
+        assert_eq!(guessing_game_commit(5, address, "10").unwrap(), expected);
+        assert_eq!(guessing_game::parse_hex(" 10 ").unwrap(), Fr::from(16));
+        let bytes = address.parse::<guessing_game::EthAddress>().unwrap();
+        assert_eq!(
+            guessing_game_commit_bytes(5, bytes.as_bytes(), Fr::from(16)).unwrap(),
+            expected
+        );
+        assert!(
+            guessing_game_verify(5, address, "10", &field_to_hex_string(&expected)[2..]).unwrap()
+        );
     }
 
     #[test]
@@ -74,4 +419,23 @@ mod commitment_test {
         let result = guessing_game_commit(guess, address, r).unwrap();
         assert_eq!(result, field_from_hex_string(expected).unwrap());
     }
+
+    #[test]
+    fn known_commitment_bytes() {
+        let guess = 5;
+        let address = [
+            0x70, 0x99, 0x79, 0x70, 0xc5, 0x18, 0x12, 0xdc, 0x3a, 0x01, 0x0c, 0x7d, 0x01, 0xb5,
+            0x0e, 0x0d, 0x17, 0xdc, 0x79, 0xc8,
+        ];
+        let r = Fr::from(10);
+        let expected = "0x2346b3b208c9e65959af9824ccab4da69ae27d222204fcf0ace7f725e02e512d";
+
+        let result = guessing_game_commit_bytes(guess, &address, r).unwrap();
+        assert_eq!(result, field_from_hex_string(expected).unwrap());
+        assert_eq!(
+            result,
+            guessing_game_commit(guess, "0x70997970c51812dc3a010c7d01b50e0d17dc79c8", "0xa")
+                .unwrap()
+        );
+    }
 }
@@ -0,0 +1,121 @@
+//! # Light Poseidon
+//! Contains a byte interface compatible with the `light-poseidon` crate, which backs the Poseidon
+//! syscall of Solana. The hash is circom's Poseidon over the BN254 scalar field (see
+//! [`circom::poseidon_hash`]) for 1 to 12 inputs, where each input is given as at most 32 bytes
+//! encoding a canonical field element and the output is encoded as 32 bytes.
+
+use crate::{circom, error::Error};
+use alloc::vec::Vec;
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use num_bigint::BigUint;
+
+/// The maximum number of inputs supported by light-poseidon and the Solana syscall.
+pub const MAX_INPUTS: usize = 12;
+const FIELD_SIZE: usize = 32;
+
+fn bytes_to_field(bytes: &[u8], big_endian: bool) -> Result<Fr, Error> {
+    if bytes.is_empty() || bytes.len() > FIELD_SIZE {
+        return Err(Error::InvalidInputLength(bytes.len()));
+    }
+    let value = if big_endian {
+        BigUint::from_bytes_be(bytes)
+    } else {
+        BigUint::from_bytes_le(bytes)
+    };
+    if value >= Fr::MODULUS.into() {
+        return Err(Error::NonCanonical);
+    }
+    Ok(value.into())
+}
+
+fn hash_bytes(inputs: &[&[u8]], big_endian: bool) -> Result<[u8; FIELD_SIZE], Error> {
+    if inputs.is_empty() || inputs.len() > MAX_INPUTS {
+        return Err(Error::UnsupportedArity(inputs.len()));
+    }
+    let inputs = inputs
+        .iter()
+        .map(|bytes| bytes_to_field(bytes, big_endian))
+        .collect::<Result<Vec<_>, _>>()?;
+    let hash = circom::poseidon_hash(&inputs)?.into_bigint();
+    let bytes = if big_endian {
+        hash.to_bytes_be()
+    } else {
+        hash.to_bytes_le()
+    };
+    let mut res = [0u8; FIELD_SIZE];
+    res.copy_from_slice(&bytes);
+    Ok(res)
+}
+
+/// Hashes the inputs like light-poseidon's `hash_bytes_be`. Each input consists of 1 to 32
+/// big-endian bytes encoding a field element smaller than the modulus, and the hash is returned
+/// as 32 big-endian bytes.
+pub fn hash_bytes_be(inputs: &[&[u8]]) -> Result<[u8; FIELD_SIZE], Error> {
+    hash_bytes(inputs, true)
+}
+
+/// Hashes the inputs like light-poseidon's `hash_bytes_le`, i.e., like [`hash_bytes_be`] with
+/// little-endian inputs and output.
+pub fn hash_bytes_le(inputs: &[&[u8]]) -> Result<[u8; FIELD_SIZE], Error> {
+    hash_bytes(inputs, false)
+}
+
+#[cfg(test)]
+mod light_poseidon_tests {
+    use super::*;
+    use crate::field_from_hex_string;
+
+    #[test]
+    fn kats() {
+        // test vector of the light-poseidon documentation
+        let hash = hash_bytes_be(&[&[1u8; 32], &[2u8; 32]]).unwrap();
+        assert_eq!(
+            hash,
+            [
+                13, 84, 225, 147, 143, 138, 140, 28, 125, 235, 94, 3, 85, 242, 99, 25, 32, 123,
+                132, 254, 156, 162, 206, 27, 38, 231, 53, 200, 41, 130, 25, 144
+            ]
+        );
+
+        // circomlibjs' poseidon([1, 2]), with short inputs
+        let expected: Fr = field_from_hex_string(
+            "0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a",
+        )
+        .unwrap();
+        let hash = hash_bytes_be(&[&[1], &[0, 2]]).unwrap();
+        assert_eq!(hash.to_vec(), expected.into_bigint().to_bytes_be());
+        let hash = hash_bytes_le(&[&[1, 0], &[2]]).unwrap();
+        assert_eq!(hash.to_vec(), expected.into_bigint().to_bytes_le());
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        assert!(matches!(
+            hash_bytes_be(&[]),
+            Err(Error::UnsupportedArity(0))
+        ));
+        assert!(matches!(
+            hash_bytes_be(&[&[1u8][..]; 13]),
+            Err(Error::UnsupportedArity(13))
+        ));
+        assert!(hash_bytes_be(&[&[1u8][..]; 12]).is_ok());
+        assert!(matches!(
+            hash_bytes_be(&[&[]]),
+            Err(Error::InvalidInputLength(0))
+        ));
+        assert!(matches!(
+            hash_bytes_be(&[&[1; 33]]),
+            Err(Error::InvalidInputLength(33))
+        ));
+        let modulus = Fr::MODULUS.to_bytes_be();
+        assert!(matches!(
+            hash_bytes_be(&[&modulus]),
+            Err(Error::NonCanonical)
+        ));
+        assert!(matches!(
+            hash_bytes_be(&[&[0xff; 32]]),
+            Err(Error::NonCanonical)
+        ));
+    }
+}
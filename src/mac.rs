@@ -0,0 +1,126 @@
+//! # MAC
+//! Contains a message authentication code based on the Poseidon sponge. The key is placed in the
+//! capacity element of the initial state (an inner-keyed sponge), the message is absorbed through
+//! the rate with the 10* padding of [`PoseidonSponge`], and the tag is the first squeezed element.
+//!
+//! Since the key is a single field element, the construction is only offered for fields of at
+//! least 128 bits.
+
+use crate::{error::Error, parameters::PoseidonParams, sponge::PoseidonSponge};
+use alloc::{borrow::ToOwned, sync::Arc};
+use ark_ff::PrimeField;
+
+/// The minimal size of the field in bits, such that the key has 128 bits of entropy.
+pub const MIN_KEY_BITS: u32 = 128;
+
+/// A keyed MAC over the Poseidon sponge, which absorbs the message incrementally.
+#[derive(Clone, Debug)]
+pub struct PoseidonMac<F: PrimeField> {
+    sponge: PoseidonSponge<F>,
+}
+
+impl<F: PrimeField> PoseidonMac<F> {
+    /// Creates the MAC for the given key, fails for fields smaller than [`MIN_KEY_BITS`] bits.
+    pub fn new(params: &Arc<PoseidonParams<F>>, key: F) -> Result<Self, Error> {
+        if F::MODULUS_BIT_SIZE < MIN_KEY_BITS {
+            return Err(Error::InsecureParameters(
+                "the field is too small for a single element key".to_owned(),
+            ));
+        }
+        Ok(PoseidonMac {
+            sponge: PoseidonSponge::with_capacity(params, key)?,
+        })
+    }
+
+    pub fn update(&mut self, message: &[F]) -> Result<(), Error> {
+        self.sponge.absorb(message)
+    }
+
+    /// Absorbs an arbitrary byte string, see [`PoseidonSponge::absorb_bytes`].
+    pub fn update_bytes(&mut self, message: &[u8]) -> Result<(), Error> {
+        self.sponge.absorb_bytes(message)
+    }
+
+    /// Consumes the MAC and returns the tag of the absorbed message.
+    pub fn finalize(self) -> Result<F, Error> {
+        self.sponge.finalize()
+    }
+
+    /// Consumes the MAC and checks the tag of the absorbed message. The comparison does not
+    /// exit early, such that the running time does not depend on the position of a mismatch.
+    pub fn verify(self, tag: &F) -> Result<bool, Error> {
+        Ok(ct_eq(&self.finalize()?, tag))
+    }
+}
+
+// Compares the canonical representations limb by limb without branching on the limbs
+fn ct_eq<F: PrimeField>(a: &F, b: &F) -> bool {
+    let a = a.into_bigint();
+    let b = b.into_bigint();
+    let diff = a
+        .as_ref()
+        .iter()
+        .zip(b.as_ref())
+        .fold(0u64, |acc, (a, b)| acc | (a ^ b));
+    core::hint::black_box(diff) == 0
+}
+
+#[cfg(test)]
+mod mac_tests {
+    use super::*;
+    use crate::{
+        bn254::{circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS},
+        goldilocks::t8::POSEIDON_GOLDILOCKS_8_PARAMS,
+    };
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+
+    fn tag(key: Scalar, message: &[Scalar]) -> Scalar {
+        let mut mac = PoseidonMac::new(&POSEIDON_CIRCOM_BN_5_PARAMS, key).unwrap();
+        mac.update(message).unwrap();
+        mac.finalize().unwrap()
+    }
+
+    #[test]
+    fn tag_and_verify() {
+        let mut rng = thread_rng();
+        let key = Scalar::rand(&mut rng);
+        let message: Vec<Scalar> = (0..10).map(|_| Scalar::rand(&mut rng)).collect();
+        let expected = tag(key, &message);
+
+        let mut mac = PoseidonMac::new(&POSEIDON_CIRCOM_BN_5_PARAMS, key).unwrap();
+        mac.update(&message[..3]).unwrap();
+        mac.update(&message[3..]).unwrap();
+        assert!(mac.clone().verify(&expected).unwrap());
+        assert!(!mac.verify(&(expected + Scalar::from(1))).unwrap());
+
+        assert_ne!(tag(key + Scalar::from(1), &message), expected);
+        assert_ne!(tag(key, &message[..9]), expected);
+
+        // with the zero key, the MAC is the plain sponge hash
+        let mut sponge = PoseidonSponge::new(&POSEIDON_CIRCOM_BN_5_PARAMS).unwrap();
+        sponge.absorb(&message).unwrap();
+        assert_eq!(tag(Scalar::from(0), &message), sponge.finalize().unwrap());
+    }
+
+    #[test]
+    fn bytes() {
+        let key = Scalar::from(42);
+        let mut mac1 = PoseidonMac::new(&POSEIDON_CIRCOM_BN_3_PARAMS, key).unwrap();
+        mac1.update_bytes(b"hello").unwrap();
+        let mut mac2 = PoseidonMac::new(&POSEIDON_CIRCOM_BN_3_PARAMS, key).unwrap();
+        mac2.update_bytes(b"hellO").unwrap();
+        assert_ne!(mac1.finalize().unwrap(), mac2.finalize().unwrap());
+    }
+
+    #[test]
+    fn small_field() {
+        let key = crate::goldilocks::field::Goldilocks::from(42u64);
+        assert!(matches!(
+            PoseidonMac::new(&POSEIDON_GOLDILOCKS_8_PARAMS, key),
+            Err(Error::InsecureParameters(_))
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+//! # Incremental Merkle Tree
+//! Contains an append-only Merkle tree of fixed depth which only stores its frontier, as used by
+//! the tornado-cash and semaphore contracts.
+
+use crate::{
+    error::Error, field_hasher::FieldHasher, parameters::PoseidonParams, poseidon::Poseidon,
+};
+use alloc::{collections::VecDeque, sync::Arc, vec::Vec};
+use ark_ff::PrimeField;
+
+/// An append-only Merkle tree of fixed depth. Leaves are inserted from left to right, all
+/// positions which were not yet filled are zero. Besides the zero hashes, only the frontier (the
+/// last filled left child on each level) and a bounded history of roots is kept in memory.
+#[derive(Clone, Debug)]
+pub struct IncrementalMerkleTree<F: PrimeField, H = Poseidon<F>> {
+    hasher: H,
+    depth: usize,
+    zeros: Vec<F>,
+    frontier: Vec<F>,
+    next_index: usize,
+    roots: VecDeque<F>, // the most recent root is the last one
+    root_history_size: usize,
+}
+
+impl<F: PrimeField> IncrementalMerkleTree<F> {
+    /// Creates an empty tree of the given depth which remembers the last `root_history_size`
+    /// roots (at least 1). The parameters must have statesize t = 3.
+    pub fn new(
+        params: &Arc<PoseidonParams<F>>,
+        depth: usize,
+        root_history_size: usize,
+    ) -> Result<Self, Error> {
+        Self::with_hasher(Poseidon::new(params), depth, root_history_size)
+    }
+
+    pub fn poseidon(&self) -> &Poseidon<F> {
+        &self.hasher
+    }
+}
+
+impl<F: PrimeField, H: FieldHasher<F>> IncrementalMerkleTree<F, H> {
+    /// Creates an empty tree like [`IncrementalMerkleTree::new`], using the compression of the
+    /// given hasher.
+    pub fn with_hasher(hasher: H, depth: usize, root_history_size: usize) -> Result<Self, Error> {
+        if depth >= usize::BITS as usize || root_history_size == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        let zeros = hasher.zero_hashes(depth)?;
+        let frontier = zeros[..depth].to_vec();
+        let mut roots = VecDeque::with_capacity(root_history_size);
+        roots.push_back(zeros[depth]);
+        Ok(IncrementalMerkleTree {
+            hasher,
+            depth,
+            zeros,
+            frontier,
+            next_index: 0,
+            roots,
+            root_history_size,
+        })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn num_leaves(&self) -> usize {
+        self.next_index
+    }
+
+    pub fn capacity(&self) -> usize {
+        1 << self.depth
+    }
+
+    pub fn root(&self) -> F {
+        *self.roots.back().expect("the history is never empty")
+    }
+
+    /// Returns the remembered roots, ordered from the oldest to the current one.
+    pub fn roots(&self) -> impl Iterator<Item = &F> {
+        self.roots.iter()
+    }
+
+    /// Returns whether the given root is the current or one of the remembered historical roots.
+    pub fn is_known_root(&self, root: &F) -> bool {
+        self.roots.contains(root)
+    }
+
+    /// Appends a leaf and returns its index.
+    pub fn append(&mut self, leaf: F) -> Result<usize, Error> {
+        let index = self.next_index;
+        if index >= self.capacity() {
+            return Err(Error::TreeFull);
+        }
+
+        let mut current = leaf;
+        for level in 0..self.depth {
+            current = if (index >> level) & 1 == 0 {
+                self.frontier[level] = current;
+                self.hasher.compress(current, self.zeros[level])?
+            } else {
+                self.hasher.compress(self.frontier[level], current)?
+            };
+        }
+
+        self.next_index += 1;
+        if self.roots.len() == self.root_history_size {
+            self.roots.pop_front();
+        }
+        self.roots.push_back(current);
+        Ok(index)
+    }
+
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+}
+
+#[cfg(test)]
+mod incremental_merkle_tree_tests {
+    use super::*;
+    use crate::{bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, merkle::tree::MerkleTree};
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+    const DEPTH: usize = 4;
+
+    #[test]
+    fn matches_full_tree() {
+        let mut rng = thread_rng();
+        let mut tree = IncrementalMerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, DEPTH, 3).unwrap();
+        let empty = MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, vec![Scalar::from(0); 16]);
+        assert_eq!(tree.root(), empty.unwrap().root());
+
+        let mut leaves = Vec::new();
+        for i in 0..tree.capacity() {
+            let leaf = Scalar::rand(&mut rng);
+            assert_eq!(tree.append(leaf).unwrap(), i);
+            leaves.push(leaf);
+
+            let mut padded = leaves.to_owned();
+            padded.resize(tree.capacity(), Scalar::from(0));
+            let full = MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, padded).unwrap();
+            assert_eq!(tree.root(), full.root());
+        }
+        assert_eq!(tree.num_leaves(), 16);
+        assert!(matches!(tree.append(Scalar::from(1)), Err(Error::TreeFull)));
+    }
+
+    #[test]
+    fn root_history() {
+        let mut tree = IncrementalMerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, DEPTH, 2).unwrap();
+        let root0 = tree.root();
+        tree.append(Scalar::from(1)).unwrap();
+        let root1 = tree.root();
+        assert!(tree.is_known_root(&root0));
+        tree.append(Scalar::from(2)).unwrap();
+        let root2 = tree.root();
+        assert!(!tree.is_known_root(&root0));
+        assert!(tree.is_known_root(&root1));
+        assert_eq!(
+            tree.roots().copied().collect::<Vec<_>>(),
+            vec![root1, root2]
+        );
+
+        assert!(IncrementalMerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, DEPTH, 0).is_err());
+    }
+}
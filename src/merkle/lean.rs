@@ -0,0 +1,251 @@
+//! # Lean Incremental Merkle Tree
+//! Contains the LeanIMT of zk-kit, the group tree of Semaphore v4. In contrast to the
+//! [`IncrementalMerkleTree`](super::incremental::IncrementalMerkleTree), the depth grows with the
+//! number of leaves and there are no zero leaves: a node without right sibling is moved to the
+//! next level unchanged instead of being hashed with a zero hash. Thus, the root of a single leaf
+//! is the leaf itself, and proofs only contain the siblings which actually exist.
+
+use crate::{
+    error::Error, field_hasher::FieldHasher, parameters::PoseidonParams, poseidon::Poseidon,
+};
+use alloc::{sync::Arc, vec, vec::Vec};
+use ark_ff::PrimeField;
+
+/// A LeanIMT over the compression of a hasher with statesize t = 3, by default [`Poseidon`].
+#[derive(Clone, Debug)]
+pub struct LeanMerkleTree<F: PrimeField, H = Poseidon<F>> {
+    hasher: H,
+    nodes: Vec<Vec<F>>, // nodes[0] are the leaves, nodes[depth] contains the root
+}
+
+/// An inclusion proof for a leaf of a [`LeanMerkleTree`], in the format of zk-kit. Bit i of the
+/// index is set if the i-th sibling is the left child, levels without sibling are skipped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeanMerkleProof<F: PrimeField> {
+    pub root: F,
+    pub leaf: F,
+    pub index: usize,
+    pub siblings: Vec<F>, // ordered from the leaf level to the root
+}
+
+impl<F: PrimeField> LeanMerkleTree<F> {
+    /// Creates an empty tree. The parameters must have statesize t = 3.
+    pub fn new(params: &Arc<PoseidonParams<F>>) -> Result<Self, Error> {
+        Self::with_hasher(Poseidon::new(params))
+    }
+
+    pub fn poseidon(&self) -> &Poseidon<F> {
+        &self.hasher
+    }
+
+    /// Verifies the proof against the root it contains. The caller has to check that the root
+    /// is the one of the expected tree.
+    pub fn verify_proof(
+        hasher: &impl FieldHasher<F>,
+        proof: &LeanMerkleProof<F>,
+    ) -> Result<bool, Error> {
+        let depth = proof.siblings.len() as u32;
+        if proof.index.checked_shr(depth).unwrap_or(0) != 0 {
+            return Ok(false);
+        }
+        let mut node = proof.leaf;
+        for (i, sibling) in proof.siblings.iter().enumerate() {
+            node = if (proof.index >> i) & 1 == 1 {
+                hasher.compress(*sibling, node)?
+            } else {
+                hasher.compress(node, *sibling)?
+            };
+        }
+        Ok(node == proof.root)
+    }
+}
+
+impl<F: PrimeField, H: FieldHasher<F>> LeanMerkleTree<F, H> {
+    /// Creates an empty tree over the compression of the given hasher, whose statesize must be
+    /// t = 3.
+    pub fn with_hasher(hasher: H) -> Result<Self, Error> {
+        let t = hasher.state_size();
+        if t != 3 {
+            return Err(Error::WrongStatesize {
+                expected: 3,
+                got: t,
+            });
+        }
+        Ok(LeanMerkleTree {
+            hasher,
+            nodes: vec![Vec::new()],
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.nodes[0].len()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    pub fn leaves(&self) -> &[F] {
+        &self.nodes[0]
+    }
+
+    /// Returns the root, or `None` if the tree is empty.
+    pub fn root(&self) -> Option<F> {
+        self.nodes[self.depth()].first().copied()
+    }
+
+    /// Returns the index of the first occurrence of the leaf.
+    pub fn index_of(&self, leaf: &F) -> Option<usize> {
+        self.nodes[0].iter().position(|el| el == leaf)
+    }
+
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Appends a leaf and returns its index.
+    pub fn insert(&mut self, leaf: F) -> Result<usize, Error> {
+        let index = self.size();
+        if index + 1 > 1 << self.depth() {
+            self.nodes.push(Vec::new());
+        }
+        self.nodes[0].push(leaf);
+        self.update_path(index)?;
+        Ok(index)
+    }
+
+    pub fn insert_many(&mut self, leaves: impl IntoIterator<Item = F>) -> Result<(), Error> {
+        for leaf in leaves {
+            self.insert(leaf)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the leaf at the given index. Semaphore removes members by setting their leaf to
+    /// zero.
+    pub fn update(&mut self, index: usize, leaf: F) -> Result<(), Error> {
+        let Some(el) = self.nodes[0].get_mut(index) else {
+            return Err(Error::IndexOutOfBounds(index));
+        };
+        *el = leaf;
+        self.update_path(index)
+    }
+
+    // Recomputes the ancestors of the leaf at the given index
+    fn update_path(&mut self, mut index: usize) -> Result<(), Error> {
+        let mut node = self.nodes[0][index];
+        for level in 0..self.depth() {
+            let layer = &self.nodes[level];
+            if index & 1 == 1 {
+                node = self.hasher.compress(layer[index - 1], node)?;
+            } else if let Some(sibling) = layer.get(index + 1) {
+                node = self.hasher.compress(node, *sibling)?;
+            }
+            index >>= 1;
+            let parents = &mut self.nodes[level + 1];
+            if index < parents.len() {
+                parents[index] = node;
+            } else {
+                parents.push(node);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the inclusion proof for the leaf at the given index.
+    pub fn proof(&self, index: usize) -> Result<LeanMerkleProof<F>, Error> {
+        let Some(leaf) = self.nodes[0].get(index) else {
+            return Err(Error::IndexOutOfBounds(index));
+        };
+        let mut path = 0;
+        let mut siblings = Vec::new();
+        let mut current = index;
+        for layer in &self.nodes[..self.depth()] {
+            let is_right = current & 1;
+            if let Some(sibling) = layer.get(current ^ 1) {
+                path |= is_right << siblings.len();
+                siblings.push(*sibling);
+            }
+            current >>= 1;
+        }
+        Ok(LeanMerkleProof {
+            root: self.nodes[self.depth()][0],
+            leaf: *leaf,
+            index: path,
+            siblings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod lean_tests {
+    use super::*;
+    use crate::bn254::{
+        circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS,
+    };
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn structure() {
+        let mut tree = LeanMerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+        assert_eq!(tree.root(), None);
+        tree.insert(Scalar::from(1)).unwrap();
+        assert_eq!(tree.depth(), 0);
+        assert_eq!(tree.root(), Some(Scalar::from(1)));
+
+        let poseidon = tree.poseidon().to_owned();
+        let h = |a: Scalar, b: Scalar| poseidon.compress(a, b).unwrap();
+        tree.insert_many((2..=5).map(Scalar::from)).unwrap();
+        assert_eq!(tree.size(), 5);
+        assert_eq!(tree.depth(), 3);
+        // the fifth leaf has no siblings and is moved up to the root level unchanged
+        let left = h(
+            h(Scalar::from(1), Scalar::from(2)),
+            h(Scalar::from(3), Scalar::from(4)),
+        );
+        assert_eq!(tree.root(), Some(h(left, Scalar::from(5))));
+
+        let proof = tree.proof(4).unwrap();
+        assert_eq!(proof.siblings, vec![left]);
+        assert_eq!(proof.index, 1);
+        let proof = tree.proof(2).unwrap();
+        assert_eq!(proof.siblings.len(), 3);
+        assert_eq!(proof.index, 0b010);
+
+        // removing a member sets its leaf to zero
+        tree.update(4, Scalar::from(0)).unwrap();
+        assert_eq!(tree.root(), Some(h(left, Scalar::from(0))));
+        assert_eq!(tree.index_of(&Scalar::from(3)), Some(2));
+        assert!(matches!(
+            tree.update(5, Scalar::from(1)),
+            Err(Error::IndexOutOfBounds(5))
+        ));
+        assert!(LeanMerkleTree::<Scalar>::new(&POSEIDON_CIRCOM_BN_4_PARAMS).is_err());
+    }
+
+    #[test]
+    fn proofs() {
+        let mut rng = thread_rng();
+        let mut tree = LeanMerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+        for size in 1..=17 {
+            tree.insert(Scalar::rand(&mut rng)).unwrap();
+            for index in 0..size {
+                let proof = tree.proof(index).unwrap();
+                assert_eq!(Some(proof.root), tree.root());
+                assert!(LeanMerkleTree::verify_proof(tree.poseidon(), &proof).unwrap());
+            }
+        }
+        let mut proof = tree.proof(6).unwrap();
+        proof.leaf = Scalar::rand(&mut rng);
+        assert!(!LeanMerkleTree::verify_proof(tree.poseidon(), &proof).unwrap());
+        let mut proof = tree.proof(6).unwrap();
+        proof.index ^= 1;
+        assert!(!LeanMerkleTree::verify_proof(tree.poseidon(), &proof).unwrap());
+        proof.index = 1 << proof.siblings.len();
+        assert!(!LeanMerkleTree::verify_proof(tree.poseidon(), &proof).unwrap());
+        assert!(tree.proof(17).is_err());
+    }
+}
@@ -0,0 +1,6 @@
+pub mod incremental;
+pub mod lean;
+pub mod nary;
+pub mod sparse;
+pub mod store;
+pub mod tree;
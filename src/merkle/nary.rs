@@ -0,0 +1,294 @@
+//! # N-ary Merkle Tree
+//! Contains a Merkle tree with configurable arity, where every inner node is the Poseidon hash
+//! (circom layout) of its children. The arity is t - 1 of the parameter set, e.g., the circom
+//! parameters with t = 5 and t = 9 give quad and octal trees. A larger arity reduces the depth of
+//! the tree, and thus the number of hashes a circuit has to verify for an inclusion proof, at the
+//! cost of larger sibling groups per level.
+
+use crate::{
+    error::Error, field_hasher::FieldHasher, parameters::PoseidonParams, poseidon::Poseidon,
+};
+use alloc::{sync::Arc, vec, vec::Vec};
+use ark_ff::PrimeField;
+
+/// A Merkle tree with arity t - 1. The leaves are padded with zeros to the next power of the
+/// arity.
+#[derive(Clone, Debug)]
+pub struct NaryMerkleTree<F: PrimeField, H = Poseidon<F>> {
+    hasher: H,
+    arity: usize,
+    num_leaves: usize,
+    layers: Vec<Vec<F>>, // layers[0] are the padded leaves, the last layer is the root
+}
+
+/// An inclusion proof for a leaf of a [`NaryMerkleTree`]. For every level from the leaves to the
+/// root, the proof contains the position of the node among its siblings and the arity - 1
+/// siblings in their order in the tree, i.e., without the node itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NaryMerkleProof<F: PrimeField> {
+    pub index: usize,
+    pub path_indices: Vec<usize>,
+    pub siblings: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> NaryMerkleTree<F> {
+    /// Builds the tree over the given leaves. The parameters must have statesize t >= 3.
+    pub fn new(params: &Arc<PoseidonParams<F>>, leaves: Vec<F>) -> Result<Self, Error> {
+        Self::with_hasher(Poseidon::new(params), leaves)
+    }
+
+    pub fn poseidon(&self) -> &Poseidon<F> {
+        &self.hasher
+    }
+
+    /// Verifies that the leaf is included in the tree with the given root and depth. The arity is
+    /// given by the statesize of the hasher, which must be at least 3. The depth has to be known
+    /// to the verifier, since a shorter proof would prove an inner node as leaf.
+    pub fn verify_proof(
+        hasher: &impl FieldHasher<F>,
+        root: &F,
+        depth: usize,
+        leaf: F,
+        proof: &NaryMerkleProof<F>,
+    ) -> Result<bool, Error> {
+        let t = hasher.state_size();
+        if t < 3 {
+            return Err(Error::InvalidParameters);
+        }
+        let arity = t - 1;
+        if proof.siblings.len() != depth || proof.path_indices.len() != depth {
+            return Ok(false);
+        }
+        let mut index = proof.index;
+        let mut current = leaf;
+        let mut children = Vec::with_capacity(arity);
+        for (position, group) in proof.path_indices.iter().zip(proof.siblings.iter()) {
+            // the path indices have to match the leaf index
+            if *position >= arity || index % arity != *position || group.len() != arity - 1 {
+                return Ok(false);
+            }
+            children.clear();
+            children.extend_from_slice(&group[..*position]);
+            children.push(current);
+            children.extend_from_slice(&group[*position..]);
+            current = hasher.hash(&children)?;
+            index /= arity;
+        }
+        Ok(index == 0 && current == *root)
+    }
+}
+
+impl<F: PrimeField, H: FieldHasher<F>> NaryMerkleTree<F, H> {
+    /// Builds the tree like [`NaryMerkleTree::new`], using the given hasher, whose statesize
+    /// must be at least 3.
+    pub fn with_hasher(hasher: H, leaves: Vec<F>) -> Result<Self, Error> {
+        let t = hasher.state_size();
+        if t < 3 || leaves.is_empty() {
+            return Err(Error::InvalidParameters);
+        }
+        let arity = t - 1;
+        let num_leaves = leaves.len();
+
+        let mut width = 1;
+        while width < num_leaves {
+            width = width.checked_mul(arity).ok_or(Error::InvalidParameters)?;
+        }
+        let mut layer = leaves;
+        layer.resize(width, F::zero());
+        let mut layers = vec![layer];
+        // each layer is hashed as one buffer of states (zero capacity element and the children)
+        let mut states = Vec::with_capacity(width / arity * t);
+        while layers[layers.len() - 1].len() > 1 {
+            states.clear();
+            for children in layers[layers.len() - 1].chunks_exact(arity) {
+                states.push(F::zero());
+                states.extend_from_slice(children);
+            }
+            hasher.permute_strided(&mut states, t)?;
+            layers.push(states.iter().step_by(t).copied().collect());
+        }
+        Ok(NaryMerkleTree {
+            hasher,
+            arity,
+            num_leaves,
+            layers,
+        })
+    }
+
+    pub fn root(&self) -> F {
+        self.layers[self.layers.len() - 1][0]
+    }
+
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    pub fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    pub fn leaves(&self) -> &[F] {
+        &self.layers[0][..self.num_leaves]
+    }
+
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Returns the inclusion proof for the leaf at the given index.
+    pub fn proof(&self, index: usize) -> Result<NaryMerkleProof<F>, Error> {
+        if index >= self.num_leaves {
+            return Err(Error::IndexOutOfBounds(index));
+        }
+        let mut path_indices = Vec::with_capacity(self.depth());
+        let mut siblings = Vec::with_capacity(self.depth());
+        let mut current = index;
+        for layer in &self.layers[..self.depth()] {
+            let position = current % self.arity;
+            let start = current - position;
+            let group = layer[start..start + self.arity]
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != position)
+                .map(|(_, node)| *node)
+                .collect();
+            path_indices.push(position);
+            siblings.push(group);
+            current /= self.arity;
+        }
+        Ok(NaryMerkleProof {
+            index,
+            path_indices,
+            siblings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod nary_tests {
+    use super::*;
+    use crate::{
+        bn254::{
+            circom_t2::POSEIDON_CIRCOM_BN_2_PARAMS, circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+            circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS, circom_t9::POSEIDON_CIRCOM_BN_9_PARAMS,
+        },
+        merkle::tree::MerkleTree,
+    };
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+
+    fn check_proofs(params: &Arc<PoseidonParams<Scalar>>, num_leaves: usize, depth: usize) {
+        let mut rng = thread_rng();
+        let leaves: Vec<Scalar> = (0..num_leaves).map(|_| Scalar::rand(&mut rng)).collect();
+        let tree = NaryMerkleTree::new(params, leaves.to_owned()).unwrap();
+        assert_eq!(tree.depth(), depth);
+        assert_eq!(tree.leaves(), &leaves);
+        let root = tree.root();
+        let poseidon = tree.poseidon();
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert_eq!(proof.siblings.len(), depth);
+            assert!(proof.siblings.iter().all(|g| g.len() == tree.arity() - 1));
+            assert!(NaryMerkleTree::verify_proof(poseidon, &root, depth, *leaf, &proof).unwrap());
+            let wrong = Scalar::rand(&mut rng);
+            assert!(!NaryMerkleTree::verify_proof(poseidon, &root, depth, wrong, &proof).unwrap());
+        }
+        assert!(matches!(
+            tree.proof(num_leaves),
+            Err(Error::IndexOutOfBounds(_))
+        ));
+    }
+
+    #[test]
+    fn proofs() {
+        check_proofs(&POSEIDON_CIRCOM_BN_5_PARAMS, 13, 2);
+        check_proofs(&POSEIDON_CIRCOM_BN_5_PARAMS, 16, 2);
+        check_proofs(&POSEIDON_CIRCOM_BN_9_PARAMS, 70, 3);
+        check_proofs(&POSEIDON_CIRCOM_BN_9_PARAMS, 1, 0);
+    }
+
+    #[test]
+    fn matches_binary_tree() {
+        let leaves: Vec<Scalar> = (0..11).map(Scalar::from).collect();
+        let binary = MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, leaves.to_owned()).unwrap();
+        let nary = NaryMerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, leaves).unwrap();
+        assert_eq!(nary.root(), binary.root());
+        let proof = nary.proof(6).unwrap();
+        let flat: Vec<Scalar> = proof.siblings.iter().flatten().copied().collect();
+        assert_eq!(flat, binary.proof(6).unwrap().siblings);
+    }
+
+    #[test]
+    fn quad_root_matches_manual() {
+        let leaves: Vec<Scalar> = (0..5).map(Scalar::from).collect();
+        let tree = NaryMerkleTree::new(&POSEIDON_CIRCOM_BN_5_PARAMS, leaves.to_owned()).unwrap();
+        let poseidon = tree.poseidon();
+        let zero = Scalar::from(0);
+        let first = poseidon.hash(&leaves[..4]).unwrap();
+        let second = poseidon.hash(&[leaves[4], zero, zero, zero]).unwrap();
+        let empty = poseidon.hash(&[zero; 4]).unwrap();
+        assert_eq!(
+            tree.root(),
+            poseidon.hash(&[first, second, empty, empty]).unwrap()
+        );
+        let proof = tree.proof(4).unwrap();
+        assert_eq!(proof.path_indices, vec![0, 1]);
+        assert_eq!(proof.siblings[1], vec![first, empty, empty]);
+    }
+
+    #[test]
+    fn tampered_proofs() {
+        let leaves: Vec<Scalar> = (0..20).map(Scalar::from).collect();
+        let tree = NaryMerkleTree::new(&POSEIDON_CIRCOM_BN_5_PARAMS, leaves.to_owned()).unwrap();
+        let root = tree.root();
+        let poseidon = tree.poseidon();
+        let proof = tree.proof(9).unwrap();
+
+        let mut tampered = proof.to_owned();
+        tampered.path_indices[0] = 2;
+        assert!(!NaryMerkleTree::verify_proof(poseidon, &root, 3, leaves[9], &tampered).unwrap());
+        let mut tampered = proof.to_owned();
+        tampered.index = 9 + 64;
+        assert!(!NaryMerkleTree::verify_proof(poseidon, &root, 3, leaves[9], &tampered).unwrap());
+        let mut tampered = proof.to_owned();
+        tampered.siblings[1].pop();
+        assert!(!NaryMerkleTree::verify_proof(poseidon, &root, 3, leaves[9], &tampered).unwrap());
+        let mut tampered = proof;
+        tampered.path_indices.pop();
+        assert!(!NaryMerkleTree::verify_proof(poseidon, &root, 3, leaves[9], &tampered).unwrap());
+
+        assert!(NaryMerkleTree::<Scalar>::new(&POSEIDON_CIRCOM_BN_5_PARAMS, vec![]).is_err());
+        assert!(NaryMerkleTree::new(&POSEIDON_CIRCOM_BN_2_PARAMS, leaves).is_err());
+    }
+
+    #[test]
+    fn truncated_proof() {
+        let leaves: Vec<Scalar> = (0..20).map(Scalar::from).collect();
+        let tree = NaryMerkleTree::new(&POSEIDON_CIRCOM_BN_5_PARAMS, leaves.to_owned()).unwrap();
+        let root = tree.root();
+        let poseidon = tree.poseidon();
+        assert_eq!(tree.depth(), 3);
+
+        // without the lowest level, the proof claims the parent of leaf 9 as leaf 2
+        let proof = tree.proof(9).unwrap();
+        let parent = poseidon.hash(&leaves[8..12]).unwrap();
+        let truncated = NaryMerkleProof {
+            index: 2,
+            path_indices: proof.path_indices[1..].to_vec(),
+            siblings: proof.siblings[1..].to_vec(),
+        };
+        assert!(NaryMerkleTree::verify_proof(poseidon, &root, 2, parent, &truncated).unwrap());
+        assert!(!NaryMerkleTree::verify_proof(poseidon, &root, 3, parent, &truncated).unwrap());
+        assert!(NaryMerkleTree::verify_proof(poseidon, &root, 3, leaves[9], &proof).unwrap());
+
+        // a statesize of 2 would give arity 1
+        let t2 = Poseidon::new(&POSEIDON_CIRCOM_BN_2_PARAMS);
+        assert!(matches!(
+            NaryMerkleTree::verify_proof(&t2, &root, 3, leaves[9], &proof),
+            Err(Error::InvalidParameters)
+        ));
+    }
+}
@@ -0,0 +1,340 @@
+//! # Sparse Merkle Tree
+//! Contains a fixed-depth sparse Merkle tree which stores key-value pairs and supports proofs of
+//! membership as well as non-membership.
+
+use crate::{
+    error::Error, field_hasher::FieldHasher, parameters::PoseidonParams, poseidon::Poseidon,
+};
+use alloc::{borrow::ToOwned, collections::BTreeMap, sync::Arc, vec::Vec};
+use ark_ff::{BigInteger, PrimeField};
+
+/// A sparse Merkle tree of fixed depth. The position of a leaf is given by the bits of its key,
+/// which therefore has to be smaller than 2^`depth`. A leaf with value zero is considered empty.
+/// Only the nodes which differ from the corresponding all-zero subtree are stored.
+#[derive(Clone, Debug)]
+pub struct SparseMerkleTree<F: PrimeField, H = Poseidon<F>> {
+    hasher: H,
+    depth: usize,
+    zeros: Vec<F>,
+    nodes: BTreeMap<Vec<bool>, F>, // keyed by the path from the node to the root
+}
+
+/// A proof for the value stored at a key of a [`SparseMerkleTree`]. Depending on the value the
+/// proof is verified against, it proves membership (non-zero value) or non-membership (zero value).
+/// The verifier has to know the depth of the tree, since a shorter proof would prove an inner node
+/// as leaf.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseMerkleProof<F: PrimeField> {
+    pub key: F,
+    pub siblings: Vec<F>, // ordered from the leaf level to the root
+}
+
+fn key_to_path<F: PrimeField>(key: &F, depth: usize) -> Result<Vec<bool>, Error> {
+    let mut path = key.into_bigint().to_bits_le();
+    // Keys which only differ above the depth would share a leaf, so they are rejected
+    if path.iter().skip(depth).any(|bit| *bit) {
+        return Err(Error::KeyOutOfRange(depth));
+    }
+    path.resize(depth, false);
+    Ok(path)
+}
+
+impl<F: PrimeField> SparseMerkleTree<F> {
+    /// Creates an empty tree of the given depth. The parameters must have statesize t = 3.
+    pub fn new(params: &Arc<PoseidonParams<F>>, depth: usize) -> Result<Self, Error> {
+        Self::with_hasher(Poseidon::new(params), depth)
+    }
+}
+
+impl<F: PrimeField, H: FieldHasher<F>> SparseMerkleTree<F, H> {
+    /// Creates an empty tree like [`SparseMerkleTree::new`], using the compression of the given
+    /// hasher.
+    pub fn with_hasher(hasher: H, depth: usize) -> Result<Self, Error> {
+        if depth > F::MODULUS_BIT_SIZE as usize {
+            return Err(Error::InvalidParameters);
+        }
+        let zeros = hasher.zero_hashes(depth)?;
+        Ok(SparseMerkleTree {
+            hasher,
+            depth,
+            zeros,
+            nodes: BTreeMap::new(),
+        })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn root(&self) -> F {
+        self.node(&[])
+    }
+
+    /// Returns the value stored at the given key, which is zero if the key was never set.
+    pub fn get(&self, key: &F) -> Result<F, Error> {
+        let path = key_to_path(key, self.depth)?;
+        Ok(self.node(&path))
+    }
+
+    /// Sets the value stored at the given key. Setting the value to zero removes the key.
+    pub fn update(&mut self, key: &F, value: F) -> Result<(), Error> {
+        let path = key_to_path(key, self.depth)?;
+        self.set_node(&path, value);
+
+        let mut current = value;
+        for level in 0..self.depth {
+            let sibling = self.sibling(&path[level..]);
+            current = if path[level] {
+                self.hasher.compress(sibling, current)?
+            } else {
+                self.hasher.compress(current, sibling)?
+            };
+            self.set_node(&path[level + 1..], current);
+        }
+        Ok(())
+    }
+
+    /// Returns a proof for the value stored at the given key.
+    pub fn prove(&self, key: &F) -> Result<SparseMerkleProof<F>, Error> {
+        let path = key_to_path(key, self.depth)?;
+        let siblings = (0..self.depth)
+            .map(|level| self.sibling(&path[level..]))
+            .collect();
+        Ok(SparseMerkleProof {
+            key: key.to_owned(),
+            siblings,
+        })
+    }
+
+    fn node(&self, path: &[bool]) -> F {
+        match self.nodes.get(path) {
+            Some(node) => *node,
+            None => self.zeros[self.depth - path.len()],
+        }
+    }
+
+    fn sibling(&self, path: &[bool]) -> F {
+        let mut sibling_path = path.to_vec();
+        sibling_path[0] = !sibling_path[0];
+        self.node(&sibling_path)
+    }
+
+    fn set_node(&mut self, path: &[bool], value: F) {
+        if value == self.zeros[self.depth - path.len()] {
+            self.nodes.remove(path);
+        } else {
+            self.nodes.insert(path.to_vec(), value);
+        }
+    }
+}
+
+impl<F: PrimeField> SparseMerkleProof<F> {
+    /// Recomputes the root of the tree of the given depth from the proof, assuming the given
+    /// value is stored at the key. Fails with [`Error::WrongProofLength`] if the proof does not
+    /// have `depth` siblings.
+    pub fn compute_root(
+        &self,
+        hasher: &impl FieldHasher<F>,
+        depth: usize,
+        value: F,
+    ) -> Result<F, Error> {
+        if self.siblings.len() != depth {
+            return Err(Error::WrongProofLength {
+                expected: depth,
+                got: self.siblings.len(),
+            });
+        }
+        let path = key_to_path(&self.key, depth)?;
+        let mut current = value;
+        for (bit, sibling) in path.into_iter().zip(self.siblings.iter()) {
+            current = if bit {
+                hasher.compress(*sibling, current)?
+            } else {
+                hasher.compress(current, *sibling)?
+            };
+        }
+        Ok(current)
+    }
+
+    /// Verifies that the non-zero value is stored at the key in the tree with the given root and
+    /// depth.
+    pub fn verify_membership(
+        &self,
+        hasher: &impl FieldHasher<F>,
+        root: &F,
+        depth: usize,
+        value: F,
+    ) -> Result<bool, Error> {
+        if value.is_zero() {
+            return Ok(false);
+        }
+        Ok(self.compute_root(hasher, depth, value)? == *root)
+    }
+
+    /// Verifies that the key is not set in the tree with the given root and depth.
+    pub fn verify_non_membership(
+        &self,
+        hasher: &impl FieldHasher<F>,
+        root: &F,
+        depth: usize,
+    ) -> Result<bool, Error> {
+        Ok(self.compute_root(hasher, depth, F::zero())? == *root)
+    }
+}
+
+#[cfg(test)]
+mod sparse_merkle_tree_tests {
+    use super::*;
+    use crate::bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS;
+    use ark_ff::{UniformRand, Zero};
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+    const DEPTH: usize = 16;
+
+    #[test]
+    fn insert_update_remove() {
+        let mut rng = thread_rng();
+        let mut tree = SparseMerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, DEPTH).unwrap();
+        let empty_root = tree.root();
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        assert_eq!(empty_root, poseidon.zero_hashes(DEPTH).unwrap()[DEPTH]);
+
+        let key1 = Scalar::from(5);
+        let key2 = Scalar::from(1234);
+        let value1 = Scalar::rand(&mut rng);
+        let value2 = Scalar::rand(&mut rng);
+
+        // insert
+        tree.update(&key1, value1).unwrap();
+        let root1 = tree.root();
+        assert_ne!(root1, empty_root);
+        assert_eq!(tree.get(&key1).unwrap(), value1);
+        tree.update(&key2, value2).unwrap();
+        let root2 = tree.root();
+        let proof = tree.prove(&key1).unwrap();
+        assert!(proof
+            .verify_membership(&poseidon, &root2, DEPTH, value1)
+            .unwrap());
+        assert!(!proof
+            .verify_membership(&poseidon, &root2, DEPTH, value2)
+            .unwrap());
+        assert!(!proof
+            .verify_non_membership(&poseidon, &root2, DEPTH)
+            .unwrap());
+
+        // update
+        tree.update(&key1, value2).unwrap();
+        assert_ne!(tree.root(), root2);
+        assert_eq!(tree.get(&key1).unwrap(), value2);
+        let proof = tree.prove(&key1).unwrap();
+        assert!(proof
+            .verify_membership(&poseidon, &tree.root(), DEPTH, value2)
+            .unwrap());
+
+        // remove
+        tree.update(&key1, Scalar::zero()).unwrap();
+        tree.update(&key2, Scalar::zero()).unwrap();
+        assert_eq!(tree.root(), empty_root);
+        assert!(tree.nodes.is_empty());
+    }
+
+    #[test]
+    fn non_membership() {
+        let mut rng = thread_rng();
+        let mut tree = SparseMerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, DEPTH).unwrap();
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        for key in 0..10u64 {
+            tree.update(&Scalar::from(key * 3), Scalar::rand(&mut rng))
+                .unwrap();
+        }
+        let root = tree.root();
+
+        let absent = Scalar::from(4);
+        assert_eq!(tree.get(&absent).unwrap(), Scalar::zero());
+        let proof = tree.prove(&absent).unwrap();
+        assert!(proof
+            .verify_non_membership(&poseidon, &root, DEPTH)
+            .unwrap());
+        assert!(!proof
+            .verify_membership(&poseidon, &root, DEPTH, Scalar::rand(&mut rng))
+            .unwrap());
+
+        let present = tree.prove(&Scalar::from(3)).unwrap();
+        assert!(!present
+            .verify_non_membership(&poseidon, &root, DEPTH)
+            .unwrap());
+    }
+
+    #[test]
+    fn aliased_keys() {
+        let mut tree = SparseMerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, DEPTH).unwrap();
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let key = Scalar::from(5);
+        let aliased = key + Scalar::from(1u64 << DEPTH);
+        tree.update(&key, Scalar::from(42)).unwrap();
+        let root = tree.root();
+
+        assert!(matches!(
+            tree.update(&aliased, Scalar::from(43)),
+            Err(Error::KeyOutOfRange(DEPTH))
+        ));
+        assert!(matches!(
+            tree.get(&aliased),
+            Err(Error::KeyOutOfRange(DEPTH))
+        ));
+        assert!(matches!(
+            tree.prove(&aliased),
+            Err(Error::KeyOutOfRange(DEPTH))
+        ));
+        assert_eq!(tree.root(), root);
+
+        // a proof for the key must not be reusable for the aliased key
+        let mut proof = tree.prove(&key).unwrap();
+        proof.key = aliased;
+        assert!(proof
+            .verify_membership(&poseidon, &root, DEPTH, Scalar::from(42))
+            .is_err());
+
+        // the largest key fitting into the depth is accepted
+        let last = Scalar::from((1u64 << DEPTH) - 1);
+        tree.update(&last, Scalar::from(43)).unwrap();
+        assert_eq!(tree.get(&last).unwrap(), Scalar::from(43));
+    }
+
+    #[test]
+    fn truncated_proof() {
+        let mut tree = SparseMerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, DEPTH).unwrap();
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        tree.update(&Scalar::from(5), Scalar::from(42)).unwrap();
+        tree.update(&Scalar::from(6), Scalar::from(43)).unwrap();
+        let root = tree.root();
+
+        // dropping the lowest sibling turns the parent of the leaf (key 5 = 0b101 is a right child)
+        // into the claimed value at key 2
+        let proof = tree.prove(&Scalar::from(5)).unwrap();
+        let parent = poseidon
+            .compress(proof.siblings[0], Scalar::from(42))
+            .unwrap();
+        let truncated = SparseMerkleProof {
+            key: Scalar::from(2),
+            siblings: proof.siblings[1..].to_vec(),
+        };
+        assert_eq!(
+            truncated
+                .compute_root(&poseidon, DEPTH - 1, parent)
+                .unwrap(),
+            root
+        );
+        assert!(matches!(
+            truncated.verify_membership(&poseidon, &root, DEPTH, parent),
+            Err(Error::WrongProofLength {
+                expected: DEPTH,
+                got
+            }) if got == DEPTH - 1
+        ));
+        assert!(proof
+            .verify_membership(&poseidon, &root, DEPTH, Scalar::from(42))
+            .unwrap());
+    }
+}
@@ -0,0 +1,231 @@
+//! # Merkle Store
+//! Contains the storage backends of the nodes of a [`MerkleTree`](super::tree::MerkleTree). A
+//! node is addressed by its level (0 for the leaves) and its index within the level.
+
+use crate::error::Error;
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+
+/// A storage for the nodes of a Merkle tree.
+pub trait MerkleStore<F: PrimeField> {
+    /// Returns the node at the given position, or None if it was never stored.
+    fn get(&self, level: usize, index: usize) -> Result<Option<F>, Error>;
+
+    fn put(&mut self, level: usize, index: usize, node: F) -> Result<(), Error>;
+
+    /// Returns the `count` consecutive nodes of the level starting at `start`, or None if one of
+    /// them was never stored. The default calls [`Self::get`] for each node.
+    fn get_many(&self, level: usize, start: usize, count: usize) -> Result<Option<Vec<F>>, Error> {
+        let end = start
+            .checked_add(count)
+            .ok_or(Error::IndexOutOfBounds(start))?;
+        (start..end).map(|index| self.get(level, index)).collect()
+    }
+
+    /// Stores consecutive nodes of the level starting at `start`. The default calls
+    /// [`Self::put`] for each node.
+    fn put_many(&mut self, level: usize, start: usize, nodes: &[F]) -> Result<(), Error> {
+        for (i, node) in nodes.iter().enumerate() {
+            self.put(level, start + i, *node)?;
+        }
+        Ok(())
+    }
+}
+
+/// Keeps all nodes in memory, one vector per level.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStore<F: PrimeField> {
+    pub(crate) layers: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> MemoryStore<F> {
+    pub fn new() -> Self {
+        MemoryStore { layers: Vec::new() }
+    }
+}
+
+impl<F: PrimeField> MerkleStore<F> for MemoryStore<F> {
+    fn get(&self, level: usize, index: usize) -> Result<Option<F>, Error> {
+        Ok(self
+            .layers
+            .get(level)
+            .and_then(|layer| layer.get(index))
+            .copied())
+    }
+
+    fn put(&mut self, level: usize, index: usize, node: F) -> Result<(), Error> {
+        if self.layers.len() <= level {
+            self.layers.resize(level + 1, Vec::new());
+        }
+        let layer = &mut self.layers[level];
+        if layer.len() <= index {
+            layer.resize(index + 1, F::zero());
+        }
+        layer[index] = node;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+pub use file::FileStore;
+
+#[cfg(feature = "std")]
+mod file {
+    use super::MerkleStore;
+    use crate::{
+        error::Error,
+        parameters::bytes::{decode_elements, element_size, encode_elements},
+    };
+    use ark_ff::PrimeField;
+    use std::{
+        fs::{self, File, OpenOptions},
+        io::{Read, Seek, SeekFrom, Write},
+        marker::PhantomData,
+        path::Path,
+    };
+
+    fn storage_error(e: std::io::Error) -> Error {
+        Error::Storage(e.to_string())
+    }
+
+    // The byte offset of the node with the given index, which fails instead of wrapping around
+    fn offset<F: PrimeField>(index: usize) -> Result<u64, Error> {
+        index
+            .checked_mul(element_size::<F>())
+            .and_then(|offset| u64::try_from(offset).ok())
+            .ok_or_else(|| Error::Storage(format!("the offset of the node {index} overflows")))
+    }
+
+    /// Stores the nodes in a directory with one file `level_<level>.bin` per level, in which the
+    /// node with index i is the canonical little-endian encoding at offset i * element size.
+    /// Positions behind the end of a file were never stored, while gaps inside a file read as
+    /// zero. Consecutive nodes passed to [`MerkleStore::get_many`] and
+    /// [`MerkleStore::put_many`] are read and written with a single call, which
+    /// [`MerkleTree`](crate::merkle::tree::MerkleTree) uses to build the tree in batches.
+    #[derive(Debug)]
+    pub struct FileStore<F: PrimeField> {
+        files: Vec<File>,
+        _field: PhantomData<F>,
+    }
+
+    impl<F: PrimeField> FileStore<F> {
+        /// Opens the store in the given directory for a tree of the given depth, creating the
+        /// directory and the files of the levels if they do not exist.
+        pub fn open(dir: impl AsRef<Path>, depth: usize) -> Result<Self, Error> {
+            let dir = dir.as_ref();
+            fs::create_dir_all(dir).map_err(storage_error)?;
+            let files = (0..=depth)
+                .map(|level| {
+                    OpenOptions::new()
+                        .read(true)
+                        .write(true)
+                        .create(true)
+                        .truncate(false)
+                        .open(dir.join(format!("level_{level}.bin")))
+                        .map_err(storage_error)
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(FileStore {
+                files,
+                _field: PhantomData,
+            })
+        }
+
+        fn file(&self, level: usize) -> Result<&File, Error> {
+            self.files.get(level).ok_or(Error::IndexOutOfBounds(level))
+        }
+    }
+
+    impl<F: PrimeField> MerkleStore<F> for FileStore<F> {
+        fn get(&self, level: usize, index: usize) -> Result<Option<F>, Error> {
+            Ok(self.get_many(level, index, 1)?.map(|nodes| nodes[0]))
+        }
+
+        fn put(&mut self, level: usize, index: usize, node: F) -> Result<(), Error> {
+            self.put_many(level, index, &[node])
+        }
+
+        fn get_many(
+            &self,
+            level: usize,
+            start: usize,
+            count: usize,
+        ) -> Result<Option<Vec<F>>, Error> {
+            let mut file = self.file(level)?;
+            let begin = offset::<F>(start)?;
+            let end = start
+                .checked_add(count)
+                .ok_or_else(|| Error::Storage(format!("the node {start} + {count} overflows")))
+                .and_then(offset::<F>)?;
+            if end > file.metadata().map_err(storage_error)?.len() {
+                return Ok(None);
+            }
+            let mut bytes = vec![0u8; (end - begin) as usize];
+            file.seek(SeekFrom::Start(begin)).map_err(storage_error)?;
+            file.read_exact(&mut bytes).map_err(storage_error)?;
+            Ok(Some(decode_elements(&bytes, count)?))
+        }
+
+        fn put_many(&mut self, level: usize, start: usize, nodes: &[F]) -> Result<(), Error> {
+            let mut file = self.file(level)?;
+            file.seek(SeekFrom::Start(offset::<F>(start)?))
+                .map_err(storage_error)?;
+            file.write_all(&encode_elements(nodes.iter()))
+                .map_err(storage_error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod merkle_store_tests {
+    use super::*;
+
+    type Scalar = ark_bn254::Fr;
+
+    fn check(store: &mut impl MerkleStore<Scalar>) {
+        assert_eq!(store.get(0, 0).unwrap(), None);
+        store.put(0, 0, Scalar::from(1)).unwrap();
+        store.put(0, 3, Scalar::from(4)).unwrap();
+        store.put(2, 0, Scalar::from(5)).unwrap();
+        assert_eq!(store.get(0, 0).unwrap(), Some(Scalar::from(1)));
+        assert_eq!(store.get(0, 1).unwrap(), Some(Scalar::from(0)));
+        assert_eq!(store.get(0, 3).unwrap(), Some(Scalar::from(4)));
+        assert_eq!(store.get(0, 4).unwrap(), None);
+        assert_eq!(store.get(1, 0).unwrap(), None);
+        assert_eq!(store.get(2, 0).unwrap(), Some(Scalar::from(5)));
+        store.put(0, 0, Scalar::from(2)).unwrap();
+        assert_eq!(store.get(0, 0).unwrap(), Some(Scalar::from(2)));
+
+        let nodes: Vec<Scalar> = (10..15).map(Scalar::from).collect();
+        store.put_many(1, 2, &nodes).unwrap();
+        assert_eq!(store.get_many(1, 2, 5).unwrap(), Some(nodes.to_owned()));
+        assert_eq!(store.get(1, 4).unwrap(), Some(nodes[2]));
+        assert_eq!(
+            store.get_many(0, 0, 4).unwrap(),
+            Some(vec![2, 0, 0, 4].into_iter().map(Scalar::from).collect())
+        );
+        assert_eq!(store.get_many(1, 3, 5).unwrap(), None);
+    }
+
+    #[test]
+    fn memory() {
+        check(&mut MemoryStore::new());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn file() {
+        let dir = std::env::temp_dir().join(format!("poseidon-store-{}", std::process::id()));
+        check(&mut FileStore::open(&dir, 2).unwrap());
+        // the nodes are persisted
+        let store = FileStore::<Scalar>::open(&dir, 2).unwrap();
+        assert_eq!(store.get(0, 3).unwrap(), Some(Scalar::from(4)));
+        assert!(store.get(3, 0).is_err());
+        assert!(matches!(store.get(0, usize::MAX), Err(Error::Storage(_))));
+        assert!(matches!(
+            store.get_many(0, 1, usize::MAX),
+            Err(Error::Storage(_))
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
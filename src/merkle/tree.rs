@@ -0,0 +1,563 @@
+//! # Merkle Tree
+//! Contains a binary Merkle tree over a fixed list of leaves, using the circom t=3 compression.
+
+use super::store::{MemoryStore, MerkleStore};
+use crate::{
+    error::Error, field_hasher::FieldHasher, parameters::PoseidonParams, poseidon::Poseidon,
+};
+use alloc::{format, sync::Arc, vec, vec::Vec};
+use ark_ff::PrimeField;
+
+// The number of nodes which are read from or written to the store at once while building the tree
+const BATCH_SIZE: usize = 1 << 12;
+
+/// A binary Merkle tree. The leaves are padded with zeros to the next power of two and inner
+/// nodes are computed with [`FieldHasher::compress`], by default of [`Poseidon`]. The nodes are
+/// kept in a [`MerkleStore`], by default in memory.
+#[derive(Clone, Debug)]
+pub struct MerkleTree<F: PrimeField, S: MerkleStore<F> = MemoryStore<F>, H = Poseidon<F>> {
+    hasher: H,
+    num_leaves: usize,
+    depth: usize,
+    root: F,
+    store: S,
+}
+
+/// An inclusion proof for a leaf of a [`MerkleTree`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleProof<F: PrimeField> {
+    pub index: usize,
+    pub siblings: Vec<F>, // ordered from the leaf level to the root
+}
+
+/// An inclusion proof for several leaves of a [`MerkleTree`]. Nodes which can be computed from
+/// the proven leaves, e.g., the common ancestors, are not part of the proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiProof<F: PrimeField> {
+    pub indices: Vec<usize>, // sorted and without duplicates
+    pub depth: usize,
+    pub nodes: Vec<F>, // ordered by level from the leaves to the root, and by index per level
+}
+
+impl<F: PrimeField> MerkleTree<F> {
+    /// Builds the tree over the given leaves in memory. The parameters must have statesize t = 3.
+    pub fn new(params: &Arc<PoseidonParams<F>>, leaves: Vec<F>) -> Result<Self, Error> {
+        Self::with_store(params, MemoryStore::new(), leaves)
+    }
+
+    pub fn poseidon(&self) -> &Poseidon<F> {
+        &self.hasher
+    }
+
+    /// Verifies that the leaf is included in the tree with the given root and depth. The depth
+    /// has to be known to the verifier, since a shorter proof would prove an inner node as leaf.
+    pub fn verify_proof(
+        hasher: &impl FieldHasher<F>,
+        root: &F,
+        depth: usize,
+        leaf: F,
+        proof: &MerkleProof<F>,
+    ) -> Result<bool, Error> {
+        if proof.siblings.len() != depth {
+            return Ok(false);
+        }
+        let depth = depth as u32;
+        if proof.index.checked_shr(depth).unwrap_or(0) != 0 {
+            return Ok(false);
+        }
+        let mut current = leaf;
+        for (level, sibling) in proof.siblings.iter().enumerate() {
+            current = if (proof.index >> level) & 1 == 1 {
+                hasher.compress(*sibling, current)?
+            } else {
+                hasher.compress(current, *sibling)?
+            };
+        }
+        Ok(current == *root)
+    }
+
+    /// Verifies that the leaves, given in the order of `proof.indices`, are included in the tree
+    /// with the given root and depth. Like for [`Self::verify_proof`], the depth of the proof
+    /// is not trusted but has to match the given one.
+    pub fn verify_multi(
+        hasher: &impl FieldHasher<F>,
+        root: &F,
+        depth: usize,
+        leaves: &[F],
+        proof: &MultiProof<F>,
+    ) -> Result<bool, Error> {
+        if leaves.is_empty() || leaves.len() != proof.indices.len() {
+            return Err(Error::InvalidParameters);
+        }
+        // a tree of depth 0 has a single leaf
+        if proof.depth != depth || (depth == 0 && leaves.len() > 1) {
+            return Ok(false);
+        }
+        let increasing = proof.indices.windows(2).all(|w| w[0] < w[1]);
+        let last = proof.indices[proof.indices.len() - 1];
+        let in_range = u32::try_from(proof.depth)
+            .ok()
+            .and_then(|depth| last.checked_shr(depth))
+            .map_or(true, |rest| rest == 0);
+        if !increasing || !in_range {
+            return Ok(false);
+        }
+
+        let mut current: Vec<(usize, F)> = proof
+            .indices
+            .iter()
+            .copied()
+            .zip(leaves.iter().copied())
+            .collect();
+        let mut nodes = proof.nodes.iter();
+        for _ in 0..proof.depth {
+            let mut next = Vec::with_capacity(current.len());
+            let mut i = 0;
+            while i < current.len() {
+                let (index, node) = current[i];
+                let (left, right) = match current.get(i + 1) {
+                    Some((sibling, right)) if index % 2 == 0 && *sibling == index + 1 => {
+                        i += 1;
+                        (node, *right)
+                    }
+                    _ => {
+                        let Some(sibling) = nodes.next() else {
+                            return Ok(false);
+                        };
+                        if index % 2 == 0 {
+                            (node, *sibling)
+                        } else {
+                            (*sibling, node)
+                        }
+                    }
+                };
+                next.push((index >> 1, hasher.compress(left, right)?));
+                i += 1;
+            }
+            current = next;
+        }
+        Ok(nodes.next().is_none() && current.len() == 1 && current[0].1 == *root)
+    }
+}
+
+impl<F: PrimeField, H: FieldHasher<F>> MerkleTree<F, MemoryStore<F>, H> {
+    pub fn leaves(&self) -> &[F] {
+        &self.store.layers[0][..self.num_leaves]
+    }
+}
+
+impl<F: PrimeField, S: MerkleStore<F>> MerkleTree<F, S> {
+    /// Builds the tree over the given leaves and writes all nodes to the store, layer by layer,
+    /// without keeping the leaves in memory. The parameters must have statesize t = 3.
+    pub fn with_store(
+        params: &Arc<PoseidonParams<F>>,
+        store: S,
+        leaves: impl IntoIterator<Item = F>,
+    ) -> Result<Self, Error> {
+        Self::with_hasher(Poseidon::new(params), store, leaves)
+    }
+
+    /// Opens a tree with the given number of leaves whose nodes were written to the store by
+    /// [`Self::with_store`].
+    pub fn from_store(
+        params: &Arc<PoseidonParams<F>>,
+        store: S,
+        num_leaves: usize,
+    ) -> Result<Self, Error> {
+        Self::from_store_with_hasher(Poseidon::new(params), store, num_leaves)
+    }
+}
+
+impl<F: PrimeField, S: MerkleStore<F>, H: FieldHasher<F>> MerkleTree<F, S, H> {
+    /// Builds the tree over the given leaves like [`MerkleTree::with_store`], using the
+    /// compression of the given hasher.
+    pub fn with_hasher(
+        hasher: H,
+        mut store: S,
+        leaves: impl IntoIterator<Item = F>,
+    ) -> Result<Self, Error> {
+        let mut num_leaves: usize = 0;
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        for leaf in leaves {
+            batch.push(leaf);
+            if batch.len() == BATCH_SIZE {
+                store.put_many(0, num_leaves, &batch)?;
+                num_leaves += batch.len();
+                batch.clear();
+            }
+        }
+        store.put_many(0, num_leaves, &batch)?;
+        num_leaves += batch.len();
+        if num_leaves == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        let width = num_leaves.next_power_of_two();
+        for start in (num_leaves..width).step_by(BATCH_SIZE) {
+            let count = BATCH_SIZE.min(width - start);
+            store.put_many(0, start, &vec![F::zero(); count])?;
+        }
+
+        let depth = width.ilog2() as usize;
+        let mut tree = MerkleTree {
+            hasher,
+            num_leaves,
+            depth,
+            root: F::zero(),
+            store,
+        };
+        for level in 0..depth {
+            let parents = width >> (level + 1);
+            for start in (0..parents).step_by(BATCH_SIZE) {
+                let count = BATCH_SIZE.min(parents - start);
+                let children = tree
+                    .store
+                    .get_many(level, 2 * start, 2 * count)?
+                    .ok_or_else(|| {
+                        Error::Storage(format!("the nodes of level {level} are missing"))
+                    })?;
+                let parents = children
+                    .chunks_exact(2)
+                    .map(|pair| tree.hasher.compress(pair[0], pair[1]))
+                    .collect::<Result<Vec<_>, _>>()?;
+                tree.store.put_many(level + 1, start, &parents)?;
+            }
+        }
+        tree.root = tree.node(depth, 0)?;
+        Ok(tree)
+    }
+
+    /// Opens a tree like [`MerkleTree::from_store`], using the given hasher.
+    pub fn from_store_with_hasher(hasher: H, store: S, num_leaves: usize) -> Result<Self, Error> {
+        if num_leaves == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        let depth = num_leaves.next_power_of_two().ilog2() as usize;
+        let mut tree = MerkleTree {
+            hasher,
+            num_leaves,
+            depth,
+            root: F::zero(),
+            store,
+        };
+        tree.root = tree.node(depth, 0)?;
+        Ok(tree)
+    }
+
+    /// Returns the node at the given level (0 for the leaves) and index.
+    pub fn node(&self, level: usize, index: usize) -> Result<F, Error> {
+        self.store
+            .get(level, index)?
+            .ok_or_else(|| Error::Storage(format!("the node ({level}, {index}) is missing")))
+    }
+
+    pub fn root(&self) -> F {
+        self.root
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the inclusion proof for the leaf at the given index.
+    pub fn proof(&self, index: usize) -> Result<MerkleProof<F>, Error> {
+        if index >= self.num_leaves {
+            return Err(Error::IndexOutOfBounds(index));
+        }
+        let siblings = (0..self.depth)
+            .map(|level| self.node(level, (index >> level) ^ 1))
+            .collect::<Result<_, _>>()?;
+        Ok(MerkleProof { index, siblings })
+    }
+
+    /// Returns the inclusion proof for all leaves at the given indices. The indices are sorted
+    /// and deduplicated, the leaves have to be passed to [`Self::verify_multi`] in this order.
+    pub fn prove_many(&self, indices: &[usize]) -> Result<MultiProof<F>, Error> {
+        let mut known = indices.to_vec();
+        known.sort_unstable();
+        known.dedup();
+        match known.last() {
+            None => return Err(Error::InvalidParameters),
+            Some(last) if *last >= self.num_leaves => return Err(Error::IndexOutOfBounds(*last)),
+            _ => {}
+        }
+        let proven = known.clone();
+
+        let mut nodes = Vec::new();
+        for level in 0..self.depth {
+            let mut i = 0;
+            while i < known.len() {
+                let index = known[i];
+                if index % 2 == 0 && known.get(i + 1) == Some(&(index + 1)) {
+                    // both children are known
+                    i += 2;
+                } else {
+                    nodes.push(self.node(level, index ^ 1)?);
+                    i += 1;
+                }
+            }
+            known = known.iter().map(|index| index >> 1).collect();
+            known.dedup();
+        }
+        Ok(MultiProof {
+            indices: proven,
+            depth: self.depth(),
+            nodes,
+        })
+    }
+
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+}
+
+#[cfg(test)]
+mod merkle_tree_tests {
+    use super::*;
+    use crate::{
+        bn254::{
+            circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS,
+            poseidon2_t3::POSEIDON2_BN_3_PARAMS,
+        },
+        poseidon2::Poseidon2,
+    };
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn proofs() {
+        let mut rng = thread_rng();
+        let leaves: Vec<Scalar> = (0..11).map(|_| Scalar::rand(&mut rng)).collect();
+        let tree = MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, leaves.to_owned()).unwrap();
+        assert_eq!(tree.depth(), 4);
+        assert_eq!(tree.leaves(), &leaves);
+        let root = tree.root();
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert!(
+                MerkleTree::verify_proof(tree.poseidon(), &root, tree.depth(), *leaf, &proof)
+                    .unwrap()
+            );
+            let wrong = Scalar::rand(&mut rng);
+            assert!(
+                !MerkleTree::verify_proof(tree.poseidon(), &root, tree.depth(), wrong, &proof)
+                    .unwrap()
+            );
+        }
+        assert!(tree.proof(11).is_err());
+
+        let mut proof = tree.proof(3).unwrap();
+        proof.index = 2;
+        assert!(
+            !MerkleTree::verify_proof(tree.poseidon(), &root, tree.depth(), leaves[3], &proof)
+                .unwrap()
+        );
+        proof.index = 3 + 16;
+        assert!(
+            !MerkleTree::verify_proof(tree.poseidon(), &root, tree.depth(), leaves[3], &proof)
+                .unwrap()
+        );
+
+        // an inner node with the siblings above it is not a leaf
+        let proof = tree.proof(6).unwrap();
+        let inner = MerkleProof {
+            index: 3,
+            siblings: proof.siblings[1..].to_vec(),
+        };
+        let node = tree.node(1, 3).unwrap();
+        assert!(MerkleTree::verify_proof(tree.poseidon(), &root, 3, node, &inner).unwrap());
+        assert!(
+            !MerkleTree::verify_proof(tree.poseidon(), &root, tree.depth(), node, &inner).unwrap()
+        );
+    }
+
+    #[test]
+    fn root_matches_manual() {
+        let leaves: Vec<Scalar> = (0..3).map(Scalar::from).collect();
+        let tree = MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, leaves).unwrap();
+        let poseidon = tree.poseidon();
+        let left = poseidon.compress(Scalar::from(0), Scalar::from(1)).unwrap();
+        let right = poseidon.compress(Scalar::from(2), Scalar::from(0)).unwrap();
+        assert_eq!(tree.root(), poseidon.compress(left, right).unwrap());
+
+        let single = MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, vec![Scalar::from(7)]).unwrap();
+        assert_eq!(single.depth(), 0);
+        assert_eq!(single.root(), Scalar::from(7));
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        assert!(MerkleTree::<Scalar>::new(&POSEIDON_CIRCOM_BN_3_PARAMS, vec![]).is_err());
+        assert!(MerkleTree::new(&POSEIDON_CIRCOM_BN_4_PARAMS, vec![Scalar::from(1); 2]).is_err());
+    }
+
+    #[test]
+    fn multiproofs() {
+        let mut rng = thread_rng();
+        let leaves: Vec<Scalar> = (0..13).map(|_| Scalar::rand(&mut rng)).collect();
+        let tree = MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, leaves.to_owned()).unwrap();
+        let root = tree.root();
+        let poseidon = tree.poseidon();
+
+        for indices in [
+            vec![0],
+            vec![12, 3, 3, 4],
+            vec![0, 1, 2, 3],
+            (0..13).collect(),
+        ] {
+            let proof = tree.prove_many(&indices).unwrap();
+            let proven: Vec<Scalar> = proof.indices.iter().map(|i| leaves[*i]).collect();
+            assert!(
+                MerkleTree::verify_multi(poseidon, &root, tree.depth(), &proven, &proof).unwrap()
+            );
+
+            // shared nodes are only included once
+            assert!(proof.nodes.len() <= proof.indices.len() * tree.depth());
+            let mut wrong = proven.to_owned();
+            wrong[0] = Scalar::rand(&mut rng);
+            assert!(
+                !MerkleTree::verify_multi(poseidon, &root, tree.depth(), &wrong, &proof).unwrap()
+            );
+        }
+
+        // the siblings of the leaves 0..4 are computed, only the right subtree remains
+        let proof = tree.prove_many(&[0, 1, 2, 3]).unwrap();
+        assert_eq!(
+            proof.nodes,
+            vec![tree.node(2, 1).unwrap(), tree.node(3, 1).unwrap()]
+        );
+        let proof = tree.prove_many(&[12, 3, 3, 4]).unwrap();
+        assert_eq!(proof.indices, vec![3, 4, 12]);
+
+        let proven = [leaves[3], leaves[4], leaves[12]];
+        let mut tampered = proof.to_owned();
+        tampered.nodes.pop();
+        assert!(
+            !MerkleTree::verify_multi(poseidon, &root, tree.depth(), &proven, &tampered).unwrap()
+        );
+        let mut tampered = proof.to_owned();
+        tampered.nodes.push(Scalar::from(0));
+        assert!(
+            !MerkleTree::verify_multi(poseidon, &root, tree.depth(), &proven, &tampered).unwrap()
+        );
+        let mut tampered = proof.to_owned();
+        tampered.indices = vec![3, 4, 13];
+        assert!(
+            !MerkleTree::verify_multi(poseidon, &root, tree.depth(), &proven, &tampered).unwrap()
+        );
+
+        assert!(tree.prove_many(&[]).is_err());
+        assert!(matches!(
+            tree.prove_many(&[2, 13]),
+            Err(Error::IndexOutOfBounds(13))
+        ));
+        assert!(
+            MerkleTree::verify_multi(poseidon, &root, tree.depth(), &proven[..2], &proof).is_err()
+        );
+
+        // the depth of the proof has to match the tree
+        let inner = MultiProof {
+            indices: vec![0, 1],
+            depth: tree.depth() - 1,
+            nodes: vec![tree.node(2, 1).unwrap(), tree.node(3, 1).unwrap()],
+        };
+        let nodes = [tree.node(1, 0).unwrap(), tree.node(1, 1).unwrap()];
+        assert!(
+            MerkleTree::verify_multi(poseidon, &root, tree.depth() - 1, &nodes, &inner).unwrap()
+        );
+        assert!(!MerkleTree::verify_multi(poseidon, &root, tree.depth(), &nodes, &inner).unwrap());
+        let flat = MultiProof {
+            indices: vec![0, 1],
+            depth: 0,
+            nodes: vec![],
+        };
+        assert!(!MerkleTree::verify_multi(poseidon, &root, 0, &[root, root], &flat).unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn file_store() {
+        use crate::merkle::store::FileStore;
+
+        let mut rng = thread_rng();
+        let leaves: Vec<Scalar> = (0..5).map(|_| Scalar::rand(&mut rng)).collect();
+        let memory = MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, leaves.to_owned()).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("poseidon-tree-{}", std::process::id()));
+        let store = FileStore::open(&dir, 3).unwrap();
+        let tree = MerkleTree::with_store(&POSEIDON_CIRCOM_BN_3_PARAMS, store, leaves).unwrap();
+        assert_eq!(tree.root(), memory.root());
+        assert_eq!(tree.proof(4).unwrap(), memory.proof(4).unwrap());
+        drop(tree);
+
+        // reopen the persisted tree
+        let store = FileStore::open(&dir, 3).unwrap();
+        let tree = MerkleTree::from_store(&POSEIDON_CIRCOM_BN_3_PARAMS, store, 5).unwrap();
+        assert_eq!(tree.root(), memory.root());
+        assert_eq!(
+            tree.prove_many(&[1, 4]).unwrap(),
+            memory.prove_many(&[1, 4]).unwrap()
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+
+        // more leaves than a batch
+        let leaves: Vec<Scalar> = (0..BATCH_SIZE as u64 + 3).map(Scalar::from).collect();
+        let dir = std::env::temp_dir().join(format!("poseidon-batch-{}", std::process::id()));
+        let store = FileStore::open(&dir, 13).unwrap();
+        let tree =
+            MerkleTree::with_store(&POSEIDON_CIRCOM_BN_3_PARAMS, store, leaves.to_owned()).unwrap();
+        let poseidon = tree.hasher();
+        let mut layer = leaves;
+        layer.resize(1 << 13, Scalar::from(0));
+        while layer.len() > 1 {
+            layer = layer
+                .chunks(2)
+                .map(|pair| poseidon.compress(pair[0], pair[1]).unwrap())
+                .collect();
+        }
+        assert_eq!(tree.root(), layer[0]);
+        std::fs::remove_dir_all(dir).unwrap();
+
+        let empty = MemoryStore::new();
+        assert!(matches!(
+            MerkleTree::from_store(&POSEIDON_CIRCOM_BN_3_PARAMS, empty, 5),
+            Err(Error::Storage(_))
+        ));
+    }
+
+    #[test]
+    fn poseidon2() {
+        let mut rng = thread_rng();
+        let leaves: Vec<Scalar> = (0..5).map(|_| Scalar::rand(&mut rng)).collect();
+        let hasher = Poseidon2::new(&POSEIDON2_BN_3_PARAMS);
+        let tree =
+            MerkleTree::with_hasher(hasher.to_owned(), MemoryStore::new(), leaves.to_owned())
+                .unwrap();
+        assert_eq!(tree.leaves(), &leaves);
+        let proof = tree.proof(4).unwrap();
+        assert!(
+            MerkleTree::verify_proof(&hasher, &tree.root(), tree.depth(), leaves[4], &proof)
+                .unwrap()
+        );
+        let poseidon = MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, leaves.to_owned()).unwrap();
+        assert_ne!(poseidon.root(), tree.root());
+        assert!(!MerkleTree::verify_proof(
+            poseidon.poseidon(),
+            &tree.root(),
+            tree.depth(),
+            leaves[4],
+            &proof
+        )
+        .unwrap());
+    }
+}
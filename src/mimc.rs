@@ -0,0 +1,203 @@
+//! # MiMC Sponge
+//! Contains the MiMC sponge of circomlib (`MiMCSponge` in circom and `mimcsponge` in circomlibjs),
+//! which older circuits, e.g., the Merkle trees of Tornado Cash, use instead of Poseidon.
+//!
+//! The permutation is the Feistel network MiMC-2n/n with the round function (x_L + k + c_i)^5.
+//! The round constants are derived from the seed by iterating keccak256, where the i-th hash
+//! reduced modulo p is the constant of round i, and the first and the last constant are zero.
+//! [`MIMC_SPONGE_BN254`] is the instance with the seed `"mimcsponge"` and 220 rounds.
+
+use crate::{error::Error, field_hasher::FieldHasher};
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+use lazy_static::lazy_static;
+use sha3::{Digest, Keccak256};
+
+/// The seed of the round constants used by circomlib.
+pub const MIMC_SPONGE_SEED: &str = "mimcsponge";
+/// The number of rounds used by circomlib.
+pub const MIMC_SPONGE_ROUNDS: usize = 220;
+
+lazy_static! {
+    /// The MiMC sponge of circomlib over the BN254 scalar field.
+    pub static ref MIMC_SPONGE_BN254: MiMCSponge<ark_bn254::Fr> =
+        MiMCSponge::new(MIMC_SPONGE_SEED, MIMC_SPONGE_ROUNDS).unwrap();
+}
+
+/// The MiMC sponge with the state (x_L, x_R), absorbing into x_L and squeezing from x_L.
+#[derive(Clone, Debug)]
+pub struct MiMCSponge<F: PrimeField> {
+    round_constants: Vec<F>,
+}
+
+impl<F: PrimeField> MiMCSponge<F> {
+    /// Derives the round constants for the given seed and number of rounds as circomlibjs does.
+    pub fn new(seed: &str, rounds: usize) -> Result<Self, Error> {
+        if rounds < 2 {
+            return Err(Error::InvalidParameters);
+        }
+        let mut round_constants = Vec::with_capacity(rounds);
+        round_constants.push(F::zero());
+        let mut hash = Keccak256::digest(seed.as_bytes());
+        for _ in 1..rounds - 1 {
+            hash = Keccak256::digest(hash);
+            round_constants.push(F::from_be_bytes_mod_order(&hash));
+        }
+        round_constants.push(F::zero());
+        Ok(MiMCSponge { round_constants })
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.round_constants.len()
+    }
+
+    pub fn round_constants(&self) -> &[F] {
+        &self.round_constants
+    }
+
+    /// The Feistel permutation keyed with k (`MiMCFeistel` in circom), returns (x_L, x_R).
+    pub fn feistel(&self, mut xl: F, mut xr: F, k: F) -> (F, F) {
+        let last = self.round_constants.len() - 1;
+        for (i, c) in self.round_constants.iter().enumerate() {
+            let t = xl + k + c;
+            let t2 = t.square();
+            let t5 = t2.square() * t;
+            if i < last {
+                (xl, xr) = (xr + t5, xl);
+            } else {
+                xr += t5;
+            }
+        }
+        (xl, xr)
+    }
+
+    /// Hashes the inputs with the given key into the given number of outputs (`MiMCSponge` in
+    /// circom, `multiHash` in circomlibjs).
+    pub fn multi_hash(&self, inputs: &[F], k: F, outputs: usize) -> Result<Vec<F>, Error> {
+        if inputs.is_empty() || outputs == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        let (mut r, mut c) = (F::zero(), F::zero());
+        for input in inputs {
+            (r, c) = self.feistel(r + input, c, k);
+        }
+        let mut result = Vec::with_capacity(outputs);
+        result.push(r);
+        for _ in 1..outputs {
+            (r, c) = self.feistel(r, c, k);
+            result.push(r);
+        }
+        Ok(result)
+    }
+
+    /// Hashes the inputs with key zero into a single output.
+    pub fn hash(&self, inputs: &[F]) -> Result<F, Error> {
+        Ok(self.multi_hash(inputs, F::zero(), 1)?[0])
+    }
+}
+
+/// The MiMC sponge with key zero as hasher for the Merkle trees. The state is (x_L, x_R), and
+/// [`FieldHasher::compress`] is `hashLeftRight` of Tornado Cash, i.e., `multiHash([left, right])`.
+impl<F: PrimeField> FieldHasher<F> for MiMCSponge<F> {
+    fn state_size(&self) -> usize {
+        2
+    }
+
+    fn permute(&self, state: &mut [F]) -> Result<(), Error> {
+        if state.len() != 2 {
+            return Err(Error::WrongInputLength {
+                expected: 2,
+                got: state.len(),
+            });
+        }
+        (state[0], state[1]) = self.feistel(state[0], state[1], F::zero());
+        Ok(())
+    }
+
+    fn hash(&self, inputs: &[F]) -> Result<F, Error> {
+        if inputs.len() != 1 {
+            return Err(Error::WrongInputLength {
+                expected: 1,
+                got: inputs.len(),
+            });
+        }
+        MiMCSponge::hash(self, inputs)
+    }
+
+    fn compress(&self, left: F, right: F) -> Result<F, Error> {
+        MiMCSponge::hash(self, &[left, right])
+    }
+}
+
+/// Returns the zero leaf of Tornado Cash, keccak256("tornado") modulo p.
+pub fn tornado_zero_leaf() -> ark_bn254::Fr {
+    ark_bn254::Fr::from_be_bytes_mod_order(&Keccak256::digest(b"tornado"))
+}
+
+#[cfg(test)]
+mod mimc_tests {
+    use super::*;
+    use crate::{field_from_hex_string, merkle::tree::MerkleTree};
+    use ark_bn254::Fr;
+    use ark_ff::Zero;
+
+    #[test]
+    fn tornado_zeros() {
+        // zeros(0..3) of Tornado Cash's MerkleTreeWithHistory
+        let expected: Vec<Fr> = [
+            "0x2fe54c60d3acabf3343a35b6eba15db4821b340f76e741e2249685ed4899af6c",
+            "0x256a6135777eee2fd26f54b8b7037a25439d5235caee224154186d2b8a52e31d",
+            "0x1151949895e82ab19924de92c40a3d6f7bcb60d92b00504b8199613683f0c200",
+        ]
+        .iter()
+        .map(|x| field_from_hex_string(x).unwrap())
+        .collect();
+        let mimc = &*MIMC_SPONGE_BN254;
+        assert_eq!(tornado_zero_leaf(), expected[0]);
+        assert_eq!(
+            mimc.compress(expected[0], expected[0]).unwrap(),
+            expected[1]
+        );
+        assert_eq!(
+            mimc.compress(expected[1], expected[1]).unwrap(),
+            expected[2]
+        );
+
+        let tree = MerkleTree::with_hasher(
+            mimc.to_owned(),
+            crate::merkle::store::MemoryStore::new(),
+            vec![expected[0]; 4],
+        )
+        .unwrap();
+        assert_eq!(tree.root(), expected[2]);
+    }
+
+    #[test]
+    fn feistel() {
+        let mimc = &*MIMC_SPONGE_BN254;
+        assert_eq!(mimc.rounds(), 220);
+        assert!(mimc.round_constants()[0].is_zero());
+        assert!(mimc.round_constants()[219].is_zero());
+        let (xl, xr, k) = (Fr::from(1), Fr::from(2), Fr::from(3));
+
+        // the first two rounds by hand
+        let two_rounds = MiMCSponge {
+            round_constants: mimc.round_constants()[..2].to_vec(),
+        };
+        let t0 = xl + k;
+        let l1 = xr + t0 * t0 * t0 * t0 * t0;
+        let t1 = l1 + k + mimc.round_constants()[1];
+        assert_eq!(
+            two_rounds.feistel(xl, xr, k),
+            (l1, xl + t1 * t1 * t1 * t1 * t1)
+        );
+
+        let mut state = [xl, xr];
+        mimc.permute(&mut state).unwrap();
+        assert_eq!((state[0], state[1]), mimc.feistel(xl, xr, Fr::zero()));
+        let outputs = mimc.multi_hash(&[xl, xr], k, 3).unwrap();
+        assert_eq!(outputs.len(), 3);
+        assert_eq!(mimc.multi_hash(&[xl, xr], k, 1).unwrap()[0], outputs[0]);
+        assert!(mimc.multi_hash(&[], k, 1).is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! # Monolith
+//! Contains the Monolith-64 permutation over the Goldilocks field, which uses a lookup-friendly
+//! sbox instead of a power map. Every round applies
+//!
+//! 1. Bars: the first 4 elements are split into 8 bytes, each of which is mapped by the
+//!    chi-like sbox y = (x ^ (!(x <<< 1) & (x <<< 2) & (x <<< 3))) <<< 1,
+//! 2. Bricks: the Feistel layer y_0 = x_0, y_i = x_i + x_(i-1)^2,
+//! 3. Concrete: the multiplication with a circulant matrix, followed by the round constants in all
+//!    but the last round.
+//!
+//! The permutation starts with the Concrete layer. The parameters are given explicitly,
+//! [`plonky2_t12`](plonky2_t12::MONOLITH_PLONKY2_12_PARAMS) contains the instance of
+//! `plonky2_monolith` (t = 12, 6 rounds, 8-bit lookups) with its circulant matrix and round
+//! constants, checked against the vector of its test suite.
+
+pub mod parameters;
+pub mod plonky2_t12;
+
+use crate::{error::Error, goldilocks::field::Goldilocks};
+use alloc::{sync::Arc, vec, vec::Vec};
+use ark_ff::{Field, PrimeField};
+use parameters::MonolithParams;
+
+// number of elements the Bars layer is applied to
+const BARS: usize = 4;
+
+/// Applies the 8-bit sbox of the Bars layer to every byte of the input.
+pub fn bar(limb: u64) -> u64 {
+    // rotations of every byte by 1, 2, and 3 bits to the left
+    let rot1 = ((limb & 0x8080_8080_8080_8080) >> 7) | ((limb & 0x7f7f_7f7f_7f7f_7f7f) << 1);
+    let rot2 = ((limb & 0xc0c0_c0c0_c0c0_c0c0) >> 6) | ((limb & 0x3f3f_3f3f_3f3f_3f3f) << 2);
+    let rot3 = ((limb & 0xe0e0_e0e0_e0e0_e0e0) >> 5) | ((limb & 0x1f1f_1f1f_1f1f_1f1f) << 3);
+    let tmp = limb ^ (!rot1 & rot2 & rot3);
+    ((tmp & 0x8080_8080_8080_8080) >> 7) | ((tmp & 0x7f7f_7f7f_7f7f_7f7f) << 1)
+}
+
+#[derive(Clone, Debug)]
+pub struct Monolith {
+    pub(crate) params: Arc<MonolithParams>,
+}
+
+impl Monolith {
+    pub fn new(params: &Arc<MonolithParams>) -> Self {
+        Monolith {
+            params: params.clone(),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.params.t
+    }
+
+    pub fn permutation(&self, input: Vec<Goldilocks>) -> Result<Vec<Goldilocks>, Error> {
+        let mut state = input;
+        self.permutation_in_place(&mut state)?;
+        Ok(state)
+    }
+
+    /// Applies the permutation to the given state in place.
+    pub fn permutation_in_place(&self, state: &mut [Goldilocks]) -> Result<(), Error> {
+        let params = &self.params;
+        let t = params.t;
+        if state.len() != t {
+            return Err(Error::WrongInputLength {
+                expected: t,
+                got: state.len(),
+            });
+        }
+        let mut scratch = vec![Goldilocks::from(0); t];
+        params.mds.mat_vec_mul_into(state, &mut scratch);
+        state.copy_from_slice(&scratch);
+        for r in 0..params.rounds {
+            Self::bars(state);
+            Self::bricks(state);
+            match params.round_constants.get(r) {
+                Some(rc) => params.mds.mat_vec_mul_add_into(state, rc, &mut scratch),
+                None => params.mds.mat_vec_mul_into(state, &mut scratch),
+            }
+            state.copy_from_slice(&scratch);
+        }
+        Ok(())
+    }
+
+    fn bars(state: &mut [Goldilocks]) {
+        // The sbox maps canonical representatives to canonical representatives, see the Monolith
+        // paper, hence the conversion back never reduces
+        for el in state[..BARS].iter_mut() {
+            *el = Goldilocks::from(bar(el.into_bigint().0[0]));
+        }
+    }
+
+    fn bricks(state: &mut [Goldilocks]) {
+        for i in (1..state.len()).rev() {
+            let square = state[i - 1].square();
+            state[i] += square;
+        }
+    }
+}
+
+#[cfg(test)]
+mod monolith_tests {
+    use super::*;
+    use crate::{field_hasher::FieldHasher, goldilocks::field::MODULUS, sponge::PoseidonSponge};
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    fn params(t: usize) -> Arc<MonolithParams> {
+        let mut rng = thread_rng();
+        let circulant = (0..t as u64).map(|i| Goldilocks::from(i + 2)).collect();
+        let rc = (0..5)
+            .map(|_| (0..t).map(|_| Goldilocks::rand(&mut rng)).collect())
+            .collect();
+        Arc::new(MonolithParams::new(t, 6, circulant, rc).unwrap())
+    }
+
+    #[test]
+    fn bar_sbox() {
+        // a permutation of the bytes, which fixes 0 and 0xff
+        let mut seen = [false; 256];
+        for byte in 0..=255u64 {
+            let out = bar(byte);
+            assert!(out <= 0xff);
+            assert!(!seen[out as usize]);
+            seen[out as usize] = true;
+        }
+        assert_eq!(bar(0), 0);
+        assert_eq!(bar(0xff), 0xff);
+        // bytes are mapped independently
+        assert_eq!(bar(0x0100), bar(1) << 8);
+        // canonical representatives stay canonical
+        assert!(bar(MODULUS - 1) < MODULUS);
+        assert!(bar(0xffff_ffff_0000_0000) < MODULUS);
+    }
+
+    #[test]
+    fn permutation() {
+        let mut rng = thread_rng();
+        for t in [8, 12] {
+            let params = params(t);
+            let monolith = Monolith::new(&params);
+            let input: Vec<Goldilocks> = (0..t).map(|_| Goldilocks::rand(&mut rng)).collect();
+
+            // the layers computed by hand
+            let mut expected = params.mds.mat_vec_mul(&input);
+            for r in 0..6 {
+                for el in expected[..4].iter_mut() {
+                    let bytes = el.into_bigint().0[0]
+                        .to_le_bytes()
+                        .map(|b| bar(b as u64) as u8);
+                    *el = Goldilocks::from(u64::from_le_bytes(bytes));
+                }
+                expected = (0..t)
+                    .map(|i| match i {
+                        0 => expected[0],
+                        _ => expected[i] + expected[i - 1].square(),
+                    })
+                    .collect();
+                expected = params.mds.mat_vec_mul(&expected);
+                if r < 5 {
+                    expected
+                        .iter_mut()
+                        .zip(params.round_constants[r].iter())
+                        .for_each(|(a, b)| *a += b);
+                }
+            }
+            assert_eq!(monolith.permutation(input.to_owned()).unwrap(), expected);
+            assert!(monolith
+                .permutation(vec![Goldilocks::from(0); t - 1])
+                .is_err());
+
+            let mut sponge =
+                PoseidonSponge::with_hasher(monolith.to_owned(), Default::default()).unwrap();
+            sponge.absorb(&input).unwrap();
+            assert_eq!(sponge.squeeze(4).unwrap().len(), 4);
+            assert_eq!(monolith.state_size(), t);
+        }
+    }
+
+    // The `test_vectors` test of plonky2_monolith 0.1.0 with 8-bit lookups
+    #[test]
+    fn plonky2_monolith_kat() {
+        let monolith = Monolith::new(&plonky2_t12::MONOLITH_PLONKY2_12_PARAMS);
+        let input: Vec<Goldilocks> = (0..12).map(Goldilocks::from).collect();
+        let expected: Vec<Goldilocks> = [
+            5867581605548782913u64,
+            588867029099903233,
+            6043817495575026667,
+            805786589926590032,
+            9919982299747097782,
+            6718641691835914685,
+            7951881005429661950,
+            15453177927755089358,
+            974633365445157727,
+            9654662171963364206,
+            6281307445101925412,
+            13745376999934453119,
+        ]
+        .into_iter()
+        .map(Goldilocks::from)
+        .collect();
+        assert_eq!(monolith.permutation(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let row = vec![Goldilocks::from(1); 8];
+        let rc = vec![vec![Goldilocks::from(1); 8]; 5];
+        assert!(MonolithParams::new(8, 6, row.to_owned(), rc.to_owned()).is_ok());
+        assert!(MonolithParams::new(16, 6, vec![Goldilocks::from(1); 16], rc.to_owned()).is_err());
+        assert!(MonolithParams::new(8, 6, row[..7].to_vec(), rc.to_owned()).is_err());
+        assert!(matches!(
+            MonolithParams::new(8, 5, row, rc),
+            Err(Error::RoundConstantShape {
+                expected: (4, 8),
+                got: (5, 8)
+            })
+        ));
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    error::Error,
+    goldilocks::field::Goldilocks,
+    parameters::{bytes::decode_elements, check_round_constants, matrix::Matrix},
+};
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug)]
+pub struct MonolithParams {
+    pub(crate) t: usize, // statesize
+    pub(crate) rounds: usize,
+    pub(crate) mds: Matrix<Goldilocks>,
+    pub(crate) round_constants: Vec<Vec<Goldilocks>>, // one row for all but the last round
+}
+
+impl MonolithParams {
+    /// Creates a new parameter set for Monolith-64 with statesize t = 8 or t = 12. The linear
+    /// layer is the circulant matrix with the given first row, and the round constants contain
+    /// one row of length t for every round except the last one.
+    pub fn new(
+        t: usize,
+        rounds: usize,
+        circulant: Vec<Goldilocks>,
+        round_constants: Vec<Vec<Goldilocks>>,
+    ) -> Result<Self, Error> {
+        if !(t == 8 || t == 12) || rounds == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        if circulant.len() != t {
+            return Err(Error::NonSquareMds {
+                t,
+                got: (1, circulant.len()),
+            });
+        }
+        check_round_constants(rounds - 1, t, &round_constants)?;
+        let mds = (0..t)
+            .map(|i| (0..t).map(|j| circulant[(t + j - i) % t]).collect())
+            .collect();
+
+        Ok(MonolithParams {
+            t,
+            rounds,
+            mds: Matrix::new(mds)?,
+            round_constants,
+        })
+    }
+
+    /// Loads parameters from the binary encoding of the first row of the circulant matrix
+    /// followed by the round constants, as for
+    /// [`PoseidonParams::from_bytes`](crate::parameters::PoseidonParams::from_bytes).
+    pub fn from_bytes(t: usize, rounds: usize, bytes: &[u8]) -> Result<Self, Error> {
+        if t == 0 || rounds == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        let count = rounds.checked_mul(t).ok_or(Error::InvalidParameters)?;
+        let elements = decode_elements::<Goldilocks>(bytes, count)?;
+        let (circulant, round_constants) = elements.split_at(t);
+        let round_constants = round_constants.chunks(t).map(<[_]>::to_vec).collect();
+        Self::new(t, rounds, circulant.to_vec(), round_constants)
+    }
+
+    /// The statesize.
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// The number of rounds, the permutation applies the linear layer once more before them.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    pub fn mds(&self) -> &Matrix<Goldilocks> {
+        &self.mds
+    }
+}
@@ -0,0 +1,17 @@
+// Autogenerated file
+// KAT:
+// Monolith([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]) = [0x516dd661e959f541, 0x082c137169707901, 0x53dff3fd9f0a5beb, 0x0b2ebaa261590650, 0x89aadb57e2969cb6, 0x5d3d6905970259bd, 0x6e5ac1a4c0cfa0fe, 0xd674b7736abfc5ce, 0x0d8697e1cd9a235f, 0x85fc4017c247136e, 0x572bafd76e511424, 0xbec1638e28eae57f];
+use super::parameters::MonolithParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    pub static ref MONOLITH_PLONKY2_12_PARAMS: Arc<MonolithParams> = Arc::new(
+        MonolithParams::from_bytes(
+            12,
+            6,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/monolith/plonky2_t12.bin")),
+        )
+        .unwrap()
+    );
+}
@@ -0,0 +1,201 @@
+//! # MPC
+//! Contains a Poseidon permutation which is generic over the representation of the state
+//! elements, such that it can be evaluated on secret-shared values (e.g., replicated or Shamir
+//! shares). The linear layers, including the sparse matrices of the optimized partial rounds,
+//! only use additions of state elements and additions and multiplications with public constants,
+//! which are local operations for linear secret sharing. The sbox is the only interactive
+//! operation and is applied to all state elements of a full round at once, such that an MPC
+//! protocol needs one batch of communication per round.
+//!
+//! The rounds are implemented once over this abstraction: plain field elements are the instance
+//! with an empty context, which [`Poseidon::permutation_in_place`] uses, and the lanes of
+//! [`Poseidon::permutation_lanes`] are another one. The instances may replace the matrix
+//! multiplication with a specialized kernel.
+
+use crate::{
+    error::Error,
+    parameters::matrix::Matrix,
+    poseidon::{pow_sbox, Poseidon},
+};
+use ark_ff::PrimeField;
+use itertools::izip;
+
+/// The arithmetic required to evaluate the permutation on elements of type Self, where F is the
+/// field of the public parameters.
+pub trait PoseidonField<F: PrimeField>: Clone {
+    /// The context of the arithmetic, e.g., the id and network connection of an MPC party.
+    type Context;
+
+    fn add(&self, other: &Self) -> Self;
+
+    /// Adds a public constant. For additive shares, only one party adds the constant, which is
+    /// why the context is passed.
+    fn add_public(&self, constant: &F, ctx: &Self::Context) -> Self;
+
+    fn mul_public(&self, constant: &F) -> Self;
+
+    /// Raises all elements to the power of d in place.
+    fn pow_batch(values: &mut [Self], d: usize, ctx: &mut Self::Context) -> Result<(), Error>;
+
+    /// Multiplies the public matrix with the input and writes the result to `out`.
+    fn mat_vec_mul(matrix: &Matrix<F>, input: &[Self], out: &mut [Self]) {
+        for (row, out) in izip!(matrix.rows(), out.iter_mut()) {
+            let mut acc = input[0].mul_public(&row[0]);
+            for (inp, mat) in izip!(input.iter(), row.iter()).skip(1) {
+                acc = acc.add(&inp.mul_public(mat));
+            }
+            *out = acc;
+        }
+    }
+}
+
+/// Plain field elements, all operations are local.
+impl<F: PrimeField> PoseidonField<F> for F {
+    type Context = ();
+
+    fn add(&self, other: &Self) -> Self {
+        *self + other
+    }
+
+    fn add_public(&self, constant: &F, _ctx: &()) -> Self {
+        *self + constant
+    }
+
+    fn mul_public(&self, constant: &F) -> Self {
+        *self * constant
+    }
+
+    fn pow_batch(values: &mut [Self], d: usize, _ctx: &mut ()) -> Result<(), Error> {
+        values.iter_mut().for_each(|el| *el = pow_sbox(*el, d));
+        Ok(())
+    }
+
+    fn mat_vec_mul(matrix: &Matrix<F>, input: &[Self], out: &mut [Self]) {
+        matrix.mat_vec_mul_into(input, out);
+    }
+}
+
+impl<F: PrimeField> Poseidon<F> {
+    /// Applies the permutation to a state of t elements of type T, using the optimized
+    /// representation of the partial rounds.
+    pub fn permutation_shared<T: PoseidonField<F>>(
+        &self,
+        state: &mut [T],
+        ctx: &mut T::Context,
+    ) -> Result<(), Error> {
+        let t = self.params.t;
+        if state.len() != t {
+            return Err(Error::WrongInputLength {
+                expected: t,
+                got: state.len(),
+            });
+        }
+        let mut scratch = state.to_vec();
+        self.permute_generic(state, &mut scratch, ctx)
+    }
+}
+
+#[cfg(test)]
+mod mpc_tests {
+    use super::*;
+    use crate::{
+        bn254::{circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS},
+        goldilocks::{field::Goldilocks, t8::POSEIDON_GOLDILOCKS_8_PARAMS},
+    };
+    use rand::{rngs::ThreadRng, thread_rng};
+
+    // Additive sharing between two parties, simulated by a single type holding both shares
+    #[derive(Clone, Debug)]
+    struct Shared<F: PrimeField>([F; 2]);
+
+    // The randomness for resharing and the number of communication rounds
+    struct Network {
+        rng: ThreadRng,
+        rounds: usize,
+    }
+
+    impl<F: PrimeField> PoseidonField<F> for Shared<F> {
+        type Context = Network;
+
+        fn add(&self, other: &Self) -> Self {
+            Shared([self.0[0] + other.0[0], self.0[1] + other.0[1]])
+        }
+
+        fn add_public(&self, constant: &F, _ctx: &Network) -> Self {
+            Shared([self.0[0] + constant, self.0[1]])
+        }
+
+        fn mul_public(&self, constant: &F) -> Self {
+            Shared([self.0[0] * constant, self.0[1] * constant])
+        }
+
+        fn pow_batch(values: &mut [Self], d: usize, ctx: &mut Network) -> Result<(), Error> {
+            ctx.rounds += 1;
+            for el in values.iter_mut() {
+                let opened = pow_sbox(el.0[0] + el.0[1], d);
+                let mask = F::rand(&mut ctx.rng);
+                *el = Shared([opened - mask, mask]);
+            }
+            Ok(())
+        }
+    }
+
+    fn check<F: PrimeField>(poseidon: &Poseidon<F>) {
+        let mut rng = thread_rng();
+        let t = poseidon.get_t();
+        let input: Vec<F> = (0..t).map(|_| F::rand(&mut rng)).collect();
+        let expected = poseidon.permutation(input.to_owned()).unwrap();
+
+        let mut plain = input.to_owned();
+        poseidon.permutation_shared(&mut plain, &mut ()).unwrap();
+        assert_eq!(plain, expected);
+
+        let mut shared: Vec<Shared<F>> = input
+            .iter()
+            .map(|el| {
+                let mask = F::rand(&mut rng);
+                Shared([*el - mask, mask])
+            })
+            .collect();
+        let shared_input = shared.to_owned();
+        let mut network = Network { rng, rounds: 0 };
+        poseidon
+            .permutation_shared(&mut shared, &mut network)
+            .unwrap();
+        let opened: Vec<F> = shared.iter().map(|el| el.0[0] + el.0[1]).collect();
+        assert_eq!(opened, expected);
+        // one batch of sbox evaluations per round
+        assert_eq!(network.rounds, poseidon.params.rounds);
+
+        // the driver on shares, the constants only change the share of the first party
+        let mut driver = poseidon.driver(shared_input).unwrap();
+        while driver.next_linear_layer(&network).unwrap() {
+            driver.apply_sbox(&mut network).unwrap();
+        }
+        let opened: Vec<F> = driver
+            .finish()
+            .unwrap()
+            .iter()
+            .map(|el| el.0[0] + el.0[1])
+            .collect();
+        assert_eq!(opened, expected);
+        assert_eq!(network.rounds, 2 * poseidon.params.rounds);
+    }
+
+    #[test]
+    fn matches_plain() {
+        check(&Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS));
+        check(&Poseidon::new(&POSEIDON_CIRCOM_BN_5_PARAMS));
+        check::<Goldilocks>(&Poseidon::new(&POSEIDON_GOLDILOCKS_8_PARAMS));
+
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let mut short = [ark_bn254::Fr::from(1); 2];
+        assert!(matches!(
+            poseidon.permutation_shared(&mut short, &mut ()),
+            Err(Error::WrongInputLength {
+                expected: 3,
+                got: 2
+            })
+        ));
+    }
+}
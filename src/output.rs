@@ -0,0 +1,193 @@
+//! # Hash Outputs
+//! Deterministic encodings of hash outputs: field elements are encoded as canonical integers
+//! with the byte size of the modulus (e.g., 32 bytes for BN254 and 8 bytes for Goldilocks) in
+//! little- or big-endian order, and decoding rejects other lengths and non-canonical values.
+//! [`Digest`] wraps a hash output with these encodings, the zero-padded hex string as
+//! [`Display`](core::fmt::Display) and [`FromStr`], the order of the integers, and, with the
+//! `serde` feature, (de)serialization as hex string.
+
+use crate::{
+    error::Error,
+    field_from_str, field_to_hex_string,
+    parameters::bytes::{decode_elements, encode_elements},
+};
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+use core::{fmt, str::FromStr};
+
+/// Encodes a field element as canonical little-endian integer with the byte size of the modulus.
+pub fn to_bytes_le<F: PrimeField>(el: &F) -> Vec<u8> {
+    encode_elements(core::iter::once(el))
+}
+
+/// Encodes a field element as canonical big-endian integer with the byte size of the modulus.
+pub fn to_bytes_be<F: PrimeField>(el: &F) -> Vec<u8> {
+    let mut bytes = to_bytes_le(el);
+    bytes.reverse();
+    bytes
+}
+
+/// Decodes a field element from the encoding of [`to_bytes_le`]. Fails with
+/// [`Error::InvalidInputLength`] if the length is not the byte size of the modulus and with
+/// [`Error::NonCanonical`] if the value is not smaller than the modulus.
+pub fn from_bytes_le<F: PrimeField>(bytes: &[u8]) -> Result<F, Error> {
+    Ok(decode_elements(bytes, 1)?[0])
+}
+
+/// Decodes a field element from the encoding of [`to_bytes_be`], see [`from_bytes_le`].
+pub fn from_bytes_be<F: PrimeField>(bytes: &[u8]) -> Result<F, Error> {
+    let mut bytes = bytes.to_vec();
+    bytes.reverse();
+    from_bytes_le(&bytes)
+}
+
+/// A hash output. Digests are ordered like the canonical integers of the field elements, so
+/// sorting them is independent of the internal (e.g., Montgomery) representation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Digest<F: PrimeField>(pub F);
+
+impl<F: PrimeField> Digest<F> {
+    pub fn new(value: F) -> Self {
+        Digest(value)
+    }
+
+    /// The field element of the digest.
+    pub fn value(&self) -> F {
+        self.0
+    }
+
+    /// See [`to_bytes_le`].
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        to_bytes_le(&self.0)
+    }
+
+    /// See [`to_bytes_be`].
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        to_bytes_be(&self.0)
+    }
+
+    /// See [`from_bytes_le`].
+    pub fn from_bytes_le(bytes: &[u8]) -> Result<Self, Error> {
+        from_bytes_le(bytes).map(Digest)
+    }
+
+    /// See [`from_bytes_be`].
+    pub fn from_bytes_be(bytes: &[u8]) -> Result<Self, Error> {
+        from_bytes_be(bytes).map(Digest)
+    }
+}
+
+impl<F: PrimeField> From<F> for Digest<F> {
+    fn from(value: F) -> Self {
+        Digest(value)
+    }
+}
+
+/// The hex string with `0x` prefix, zero-padded to the byte size of the modulus.
+impl<F: PrimeField> fmt::Display for Digest<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&field_to_hex_string(&self.0))
+    }
+}
+
+/// Parses a hex string with `0x` prefix (as printed by [`Display`](core::fmt::Display), but
+/// leading zeros are optional) or a decimal string, see [`field_from_str`].
+impl<F: PrimeField> FromStr for Digest<F> {
+    type Err = Error;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        field_from_str(str).map(Digest)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<F: PrimeField> serde::Serialize for Digest<F> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, F: PrimeField> serde::Deserialize<'de> for Digest<F> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let str = <alloc::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        str.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod output_tests {
+    use super::*;
+    use crate::{goldilocks::field::Goldilocks, guessing_game_commit};
+    use alloc::string::ToString;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn bytes() {
+        let el = Goldilocks::from(0x0102030405060708u64);
+        assert_eq!(to_bytes_le(&el), [8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(to_bytes_be(&el), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(from_bytes_le::<Goldilocks>(&to_bytes_le(&el)).unwrap(), el);
+        assert_eq!(from_bytes_be::<Goldilocks>(&to_bytes_be(&el)).unwrap(), el);
+
+        let one = to_bytes_be(&Fr::from(1u64));
+        assert_eq!(one.len(), 32);
+        assert_eq!(one[31], 1);
+        assert!(matches!(
+            from_bytes_be::<Fr>(&one[1..]),
+            Err(Error::InvalidInputLength(31))
+        ));
+        assert!(matches!(
+            from_bytes_le::<Goldilocks>(&[0xff; 8]),
+            Err(Error::NonCanonical)
+        ));
+    }
+
+    #[test]
+    fn digest() {
+        let commitment =
+            guessing_game_commit(5, "0x70997970c51812dc3a010c7d01b50e0d17dc79c8", "0xa").unwrap();
+        let digest = Digest::new(commitment);
+        let hex = "0x2346b3b208c9e65959af9824ccab4da69ae27d222204fcf0ace7f725e02e512d";
+        assert_eq!(digest.to_string(), hex);
+        assert_eq!(hex.parse::<Digest<Fr>>().unwrap(), digest);
+        assert_eq!(
+            Digest::<Fr>::from_bytes_be(&digest.to_bytes_be()).unwrap(),
+            digest
+        );
+
+        let small = Digest(Goldilocks::from(10u64));
+        assert_eq!(small.to_string(), "0x000000000000000a");
+        assert_eq!("0xa".parse::<Digest<Goldilocks>>().unwrap(), small);
+        assert_eq!("10".parse::<Digest<Goldilocks>>().unwrap(), small);
+        assert!(matches!(
+            "0xffffffffffffffff".parse::<Digest<Goldilocks>>(),
+            Err(Error::NonCanonical)
+        ));
+        assert!("a".parse::<Digest<Goldilocks>>().is_err());
+
+        // ordered like the integers, not like the Montgomery representation
+        let mut digests: Vec<Digest<Fr>> = (0..20u64).rev().map(Fr::from).map(Digest).collect();
+        digests.push(Digest(-Fr::from(1u64)));
+        digests.sort();
+        assert_eq!(digests[0], Digest(Fr::from(0u64)));
+        assert_eq!(digests[19], Digest(Fr::from(19u64)));
+        assert_eq!(digests[20], Digest(-Fr::from(1u64)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let digests = alloc::vec![
+            Digest(Goldilocks::from(10u64)),
+            Digest(-Goldilocks::from(1u64))
+        ];
+        let json = serde_json::to_string(&digests).unwrap();
+        assert_eq!(json, r#"["0x000000000000000a","0xffffffff00000000"]"#);
+        assert_eq!(
+            serde_json::from_str::<Vec<Digest<Goldilocks>>>(&json).unwrap(),
+            digests
+        );
+        assert!(serde_json::from_str::<Digest<Goldilocks>>(r#""0xffffffffffffffff""#).is_err());
+    }
+}
@@ -0,0 +1,292 @@
+//! # Parameter Builder
+//! Contains a builder for custom parameter sets, which accepts the MDS matrix and the round
+//! constants row by row from field elements, hex strings, or byte arrays. Every element is
+//! checked when it is added, and errors report the part, row, and column of the malformed
+//! element.
+
+use super::{bytes::decode_elements, PoseidonParams};
+use crate::{error::Error, field_from_str_radix};
+use alloc::{string::ToString, vec::Vec};
+use ark_ff::PrimeField;
+
+const MDS: &str = "MDS matrix";
+const ROUND_CONSTANTS: &str = "round constants";
+
+/// Builder for [`PoseidonParams`]. Rows are appended in order, i.e., the MDS matrix row by row
+/// and the round constants round by round.
+#[derive(Clone, Debug)]
+pub struct PoseidonParamsBuilder<F: PrimeField> {
+    t: usize,
+    d: usize,
+    rounds_f: usize,
+    rounds_p: usize,
+    mds: Vec<Vec<F>>,
+    round_constants: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> PoseidonParams<F> {
+    /// Starts a [`PoseidonParamsBuilder`] for the given statesize, sbox degree, and round
+    /// numbers.
+    pub fn builder(
+        t: usize,
+        d: usize,
+        rounds_f: usize,
+        rounds_p: usize,
+    ) -> PoseidonParamsBuilder<F> {
+        PoseidonParamsBuilder::new(t, d, rounds_f, rounds_p)
+    }
+}
+
+impl<F: PrimeField> PoseidonParamsBuilder<F> {
+    pub fn new(t: usize, d: usize, rounds_f: usize, rounds_p: usize) -> Self {
+        PoseidonParamsBuilder {
+            t,
+            d,
+            rounds_f,
+            rounds_p,
+            mds: Vec::with_capacity(t),
+            round_constants: Vec::with_capacity(rounds_f + rounds_p),
+        }
+    }
+
+    /// Appends a row of the MDS matrix.
+    pub fn mds_row(mut self, row: impl IntoIterator<Item = F>) -> Result<Self, Error> {
+        let row = row.into_iter().collect();
+        push_row(&mut self.mds, MDS, self.t, self.t, row)?;
+        Ok(self)
+    }
+
+    /// Appends a row of the MDS matrix given as hex strings (with or without `0x` prefix).
+    pub fn mds_row_hex<S: AsRef<str>>(
+        self,
+        row: impl IntoIterator<Item = S>,
+    ) -> Result<Self, Error> {
+        let row = parse_row(MDS, self.mds.len(), row, parse_hex)?;
+        self.mds_row(row)
+    }
+
+    /// Appends a row of the MDS matrix given as canonical little-endian byte arrays with the byte
+    /// size of the modulus.
+    pub fn mds_row_bytes<B: AsRef<[u8]>>(
+        self,
+        row: impl IntoIterator<Item = B>,
+    ) -> Result<Self, Error> {
+        let row = parse_row(MDS, self.mds.len(), row, parse_bytes)?;
+        self.mds_row(row)
+    }
+
+    /// Appends the round constants of the next round.
+    pub fn round_constants(mut self, row: impl IntoIterator<Item = F>) -> Result<Self, Error> {
+        let row = row.into_iter().collect();
+        let rounds = self.rounds_f + self.rounds_p;
+        push_row(
+            &mut self.round_constants,
+            ROUND_CONSTANTS,
+            rounds,
+            self.t,
+            row,
+        )?;
+        Ok(self)
+    }
+
+    /// Appends the round constants of the next round given as hex strings (with or without `0x`
+    /// prefix).
+    pub fn round_constants_hex<S: AsRef<str>>(
+        self,
+        row: impl IntoIterator<Item = S>,
+    ) -> Result<Self, Error> {
+        let row = parse_row(ROUND_CONSTANTS, self.round_constants.len(), row, parse_hex)?;
+        self.round_constants(row)
+    }
+
+    /// Appends the round constants of the next round given as canonical little-endian byte arrays
+    /// with the byte size of the modulus.
+    pub fn round_constants_bytes<B: AsRef<[u8]>>(
+        self,
+        row: impl IntoIterator<Item = B>,
+    ) -> Result<Self, Error> {
+        let row = parse_row(
+            ROUND_CONSTANTS,
+            self.round_constants.len(),
+            row,
+            parse_bytes,
+        )?;
+        self.round_constants(row)
+    }
+
+    /// Creates the parameters, fails if rows are missing.
+    pub fn build(self) -> Result<PoseidonParams<F>, Error> {
+        if self.mds.len() != self.t {
+            return Err(Error::InvalidRowCount {
+                part: MDS,
+                expected: self.t,
+                got: self.mds.len(),
+            });
+        }
+        let rounds = self.rounds_f + self.rounds_p;
+        if self.round_constants.len() != rounds {
+            return Err(Error::InvalidRowCount {
+                part: ROUND_CONSTANTS,
+                expected: rounds,
+                got: self.round_constants.len(),
+            });
+        }
+        PoseidonParams::new(
+            self.t,
+            self.d,
+            self.rounds_f,
+            self.rounds_p,
+            self.mds,
+            self.round_constants,
+        )
+    }
+}
+
+fn push_row<F: PrimeField>(
+    rows: &mut Vec<Vec<F>>,
+    part: &'static str,
+    max_rows: usize,
+    t: usize,
+    row: Vec<F>,
+) -> Result<(), Error> {
+    if rows.len() == max_rows {
+        return Err(Error::InvalidRowCount {
+            part,
+            expected: max_rows,
+            got: max_rows + 1,
+        });
+    }
+    if row.len() != t {
+        return Err(Error::InvalidRowLength {
+            part,
+            row: rows.len(),
+            expected: t,
+            got: row.len(),
+        });
+    }
+    rows.push(row);
+    Ok(())
+}
+
+// Parses all elements of a row, attributing errors to their position
+fn parse_row<F: PrimeField, T>(
+    part: &'static str,
+    row: usize,
+    items: impl IntoIterator<Item = T>,
+    parse: impl Fn(T) -> Result<F, Error>,
+) -> Result<Vec<F>, Error> {
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(column, item)| {
+            parse(item).map_err(|e| Error::InvalidConstant {
+                part,
+                row,
+                column,
+                reason: e.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn parse_hex<F: PrimeField, S: AsRef<str>>(str: S) -> Result<F, Error> {
+    let str = str.as_ref();
+    field_from_str_radix(str.strip_prefix("0x").unwrap_or(str), 16)
+}
+
+fn parse_bytes<F: PrimeField, B: AsRef<[u8]>>(bytes: B) -> Result<F, Error> {
+    Ok(decode_elements(bytes.as_ref(), 1)?[0])
+}
+
+#[cfg(test)]
+mod builder_tests {
+    use super::*;
+    use crate::{
+        bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, field_to_hex_string,
+        parameters::bytes::encode_elements, poseidon::Poseidon,
+    };
+    use alloc::{string::String, vec};
+
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn matches_circom() {
+        let params = &*POSEIDON_CIRCOM_BN_3_PARAMS;
+        let mut builder = PoseidonParams::builder(3, 5, 8, 57);
+        // mix the three input formats
+        builder = builder.mds_row(params.mds().row(0).to_vec()).unwrap();
+        let hex: Vec<String> = params
+            .mds()
+            .row(1)
+            .iter()
+            .map(field_to_hex_string)
+            .collect();
+        builder = builder.mds_row_hex(&hex).unwrap();
+        let bytes = encode_elements(params.mds().row(2).iter());
+        builder = builder.mds_row_bytes(bytes.chunks(32)).unwrap();
+        for round in params.round_constants().rounds() {
+            builder = builder
+                .round_constants_hex(round.iter().map(field_to_hex_string))
+                .unwrap();
+        }
+        let built = builder.build().unwrap();
+
+        let input = vec![Scalar::from(0), Scalar::from(1), Scalar::from(2)];
+        assert_eq!(
+            Poseidon::new(&alloc::sync::Arc::new(built))
+                .permutation(input.to_owned())
+                .unwrap(),
+            Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS)
+                .permutation(input)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn reports_position() {
+        let builder = PoseidonParamsBuilder::<Scalar>::new(3, 5, 8, 57)
+            .mds_row_hex(["0x1", "0x2", "0x3"])
+            .unwrap();
+        let modulus = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+        match builder.to_owned().mds_row_hex(["0x1", modulus, "0x3"]) {
+            Err(Error::InvalidConstant {
+                part, row, column, ..
+            }) => assert_eq!((part, row, column), (MDS, 1, 1)),
+            other => panic!("unexpected result {other:?}"),
+        }
+        match builder.to_owned().mds_row_hex(["0x1", "0x2", "xyz"]) {
+            Err(Error::InvalidConstant { row, column, .. }) => assert_eq!((row, column), (1, 2)),
+            other => panic!("unexpected result {other:?}"),
+        }
+        match builder
+            .to_owned()
+            .mds_row_bytes([[1u8; 32], [2; 32], [0xff; 32]])
+        {
+            Err(Error::InvalidConstant { column, .. }) => assert_eq!(column, 2),
+            other => panic!("unexpected result {other:?}"),
+        }
+        assert!(matches!(
+            builder.to_owned().round_constants([Scalar::from(1); 2]),
+            Err(Error::InvalidRowLength {
+                part: ROUND_CONSTANTS,
+                row: 0,
+                expected: 3,
+                got: 2
+            })
+        ));
+        assert!(matches!(
+            builder.to_owned().build(),
+            Err(Error::InvalidRowCount {
+                part: MDS,
+                expected: 3,
+                got: 1
+            })
+        ));
+
+        let full = (0..2).fold(builder, |b, _| b.mds_row([Scalar::from(1); 3]).unwrap());
+        assert!(matches!(
+            full.mds_row([Scalar::from(1); 3]),
+            Err(Error::InvalidRowCount { part: MDS, .. })
+        ));
+    }
+}
@@ -0,0 +1,118 @@
+//! # Binary Encoding
+//! A compact binary encoding of parameter sets, used to embed the shipped parameters with
+//! `include_bytes!`. Every element is encoded as canonical little-endian integer with the byte
+//! size of the modulus, and the elements are concatenated without any header. For Poseidon, the
+//! MDS matrix (row by row) is followed by the round constants (round by round).
+
+use super::PoseidonParams;
+use crate::error::Error;
+use alloc::vec::Vec;
+use ark_ff::{BigInteger, PrimeField};
+use num_bigint::BigUint;
+
+/// Returns the number of bytes of an encoded element.
+pub(crate) fn element_size<F: PrimeField>() -> usize {
+    (F::MODULUS_BIT_SIZE as usize).div_ceil(8)
+}
+
+/// Encodes the elements as concatenation of canonical little-endian integers.
+pub(crate) fn encode_elements<'a, F: PrimeField>(elements: impl Iterator<Item = &'a F>) -> Vec<u8> {
+    let size = element_size::<F>();
+    let mut res = Vec::new();
+    for el in elements {
+        let bytes = el.into_bigint().to_bytes_le();
+        res.extend_from_slice(&bytes[..size]);
+    }
+    res
+}
+
+/// Decodes exactly `count` elements, rejecting non-canonical encodings.
+pub(crate) fn decode_elements<F: PrimeField>(bytes: &[u8], count: usize) -> Result<Vec<F>, Error> {
+    let size = element_size::<F>();
+    if count.checked_mul(size) != Some(bytes.len()) {
+        return Err(Error::InvalidInputLength(bytes.len()));
+    }
+    let modulus: BigUint = F::MODULUS.into();
+    bytes
+        .chunks_exact(size)
+        .map(|chunk| {
+            let value = BigUint::from_bytes_le(chunk);
+            if value >= modulus {
+                return Err(Error::NonCanonical);
+            }
+            Ok(value.into())
+        })
+        .collect()
+}
+
+impl<F: PrimeField> PoseidonParams<F> {
+    /// Loads parameters from the binary encoding of the MDS matrix and the round constants
+    /// produced by [`Self::to_bytes`].
+    pub fn from_bytes(
+        t: usize,
+        d: usize,
+        rounds_f: usize,
+        rounds_p: usize,
+        bytes: &[u8],
+    ) -> Result<Self, Error> {
+        if t == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        // the sizes come from the caller, so overflows are reported instead of panicking
+        let count = rounds_f
+            .checked_add(rounds_p)
+            .and_then(|rounds| rounds.checked_add(t))
+            .and_then(|rows| rows.checked_mul(t))
+            .ok_or(Error::InvalidParameters)?;
+        let elements = decode_elements::<F>(bytes, count)?;
+        let (mds, round_constants) = elements.split_at(t * t);
+        let mds = mds.chunks(t).map(<[F]>::to_vec).collect();
+        let round_constants = round_constants.chunks(t).map(<[F]>::to_vec).collect();
+        Self::new(t, d, rounds_f, rounds_p, mds, round_constants)
+    }
+
+    /// Returns the binary encoding of the MDS matrix and the round constants, which does not
+    /// include the statesize, the sbox degree, and the round numbers.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mds = self.mds.rows().flatten();
+        encode_elements(mds.chain(self.round_constants.as_flat().iter()))
+    }
+}
+
+#[cfg(test)]
+mod bytes_tests {
+    use super::*;
+    use crate::{bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, goldilocks::field::Goldilocks};
+
+    #[test]
+    fn roundtrip() {
+        let params = &POSEIDON_CIRCOM_BN_3_PARAMS;
+        let bytes = params.to_bytes();
+        assert_eq!(bytes.len(), 32 * (9 + 65 * 3));
+        let loaded = PoseidonParams::from_bytes(3, 5, 8, 57, &bytes).unwrap();
+        assert_eq!(loaded.mds, params.mds);
+        assert_eq!(loaded.round_constants, params.round_constants);
+
+        assert!(matches!(
+            PoseidonParams::<ark_bn254::Fr>::from_bytes(3, 5, 8, 56, &bytes),
+            Err(Error::InvalidInputLength(_))
+        ));
+        assert!(matches!(
+            PoseidonParams::<ark_bn254::Fr>::from_bytes(usize::MAX, 5, 8, 57, &bytes),
+            Err(Error::InvalidParameters)
+        ));
+    }
+
+    #[test]
+    fn strict_decoding() {
+        let elements = [Goldilocks::from(1u64), -Goldilocks::from(1u64)];
+        let mut bytes = encode_elements(elements.iter());
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(decode_elements::<Goldilocks>(&bytes, 2).unwrap(), elements);
+        bytes[8] += 1; // p - 1 becomes p
+        assert!(matches!(
+            decode_elements::<Goldilocks>(&bytes, 2),
+            Err(Error::NonCanonical)
+        ));
+    }
+}
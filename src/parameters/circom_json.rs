@@ -0,0 +1,191 @@
+//! # Circomlib JSON
+//! Conversion of parameters from and to the format of circomlib's `poseidon_constants.json`,
+//! which contains the round constants `C` as flattened hex array and the MDS matrix `M` as
+//! nested hex array. The complete file shipped by circomlibjs contains these arrays for all
+//! statesizes t = 2, 3, ..., i.e., the entry at index t - 2 belongs to statesize t.
+
+use super::PoseidonParams;
+use crate::{error::Error, field_from_hex_string, field_to_hex_string};
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use ark_ff::PrimeField;
+use serde_json::{json, Value};
+
+// Circom always uses x^5 with 8 full rounds
+const CIRCOM_D: usize = 5;
+const CIRCOM_ROUNDS_F: usize = 8;
+
+pub(super) fn parse_json(json: &str) -> Result<Value, Error> {
+    serde_json::from_str(json).map_err(|e| Error::InvalidJson(e.to_string()))
+}
+
+pub(super) fn parse_hex_array<F: PrimeField>(value: &Value) -> Result<Vec<F>, Error> {
+    value
+        .as_array()
+        .ok_or(Error::InvalidJson("expected an array".to_owned()))?
+        .iter()
+        .map(|el| {
+            el.as_str()
+                .ok_or(Error::InvalidJson("expected a hex string".to_owned()))
+                .and_then(field_from_hex_string)
+        })
+        .collect()
+}
+
+impl<F: PrimeField> PoseidonParams<F> {
+    /// Exports the round constants and the MDS matrix in the format used by circomlib's
+    /// `poseidon_constants.json` for a single statesize.
+    pub fn to_circomlib_json(&self) -> String {
+        let c = self
+            .round_constants
+            .as_flat()
+            .iter()
+            .map(field_to_hex_string)
+            .collect::<Vec<_>>();
+        let m = self
+            .mds
+            .rows()
+            .map(|row| row.iter().map(field_to_hex_string).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        json!({ "C": c, "M": m }).to_string()
+    }
+
+    /// Loads parameters from the format used by circomlib's `poseidon_constants.json` for a
+    /// single statesize. The statesize is derived from the MDS matrix and the number of partial
+    /// rounds from the number of round constants, using circom's sbox degree 5 and 8 full rounds.
+    pub fn from_circom_json(json: &str) -> Result<Self, Error> {
+        let value = parse_json(json)?;
+        Self::from_circom_values(&value["C"], &value["M"])
+    }
+
+    /// Loads the parameters for statesize t from the complete `poseidon_constants.json` shipped
+    /// by circomlibjs, which contains the constants of all statesizes.
+    pub fn from_circomlibjs_constants(json: &str, t: usize) -> Result<Self, Error> {
+        let value = parse_json(json)?;
+        let index = t.checked_sub(2).ok_or(Error::InvalidParameters)?;
+        let c = value["C"]
+            .get(index)
+            .ok_or(Error::InvalidJson(format!("no constants for t = {t}")))?;
+        let m = value["M"]
+            .get(index)
+            .ok_or(Error::InvalidJson(format!("no MDS matrix for t = {t}")))?;
+        let params = Self::from_circom_values(c, m)?;
+        if params.t != t {
+            return Err(Error::InvalidParameters);
+        }
+        Ok(params)
+    }
+
+    fn from_circom_values(c: &Value, m: &Value) -> Result<Self, Error> {
+        let mds = m
+            .as_array()
+            .ok_or(Error::InvalidJson("missing MDS matrix M".to_owned()))?
+            .iter()
+            .map(parse_hex_array)
+            .collect::<Result<Vec<Vec<F>>, _>>()?;
+        let constants = parse_hex_array(c)?;
+
+        let t = mds.len();
+        if t == 0 || constants.len() % t != 0 || constants.len() / t < CIRCOM_ROUNDS_F {
+            return Err(Error::InvalidParameters);
+        }
+        let rounds_p = constants.len() / t - CIRCOM_ROUNDS_F;
+        let round_constants = constants.chunks(t).map(|c| c.to_vec()).collect();
+
+        Self::new(t, CIRCOM_D, CIRCOM_ROUNDS_F, rounds_p, mds, round_constants)
+    }
+}
+
+#[cfg(test)]
+mod circom_json_tests {
+    use super::*;
+    use crate::{
+        bn254::{
+            circom_t2::POSEIDON_CIRCOM_BN_2_PARAMS, circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+            circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS,
+        },
+        poseidon::Poseidon,
+    };
+    use alloc::sync::Arc;
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn json_roundtrip() {
+        let mut rng = thread_rng();
+
+        for params in [&*POSEIDON_CIRCOM_BN_3_PARAMS, &*POSEIDON_CIRCOM_BN_4_PARAMS] {
+            let json = params.to_circomlib_json();
+            let loaded = Arc::new(PoseidonParams::<Scalar>::from_circom_json(&json).unwrap());
+            assert_eq!(loaded.t, params.t);
+            assert_eq!(loaded.rounds_p, params.rounds_p);
+            assert_eq!(loaded.to_circomlib_json(), json);
+
+            let poseidon1 = Poseidon::new(params);
+            let poseidon2 = Poseidon::new(&loaded);
+            let input: Vec<Scalar> = (0..params.t).map(|_| Scalar::rand(&mut rng)).collect();
+            assert_eq!(
+                poseidon1.permutation(input.to_owned()).unwrap(),
+                poseidon2.permutation(input).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn circomlib_format() {
+        let json = POSEIDON_CIRCOM_BN_3_PARAMS.to_circomlib_json();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["C"].as_array().unwrap().len(), 3 * 65);
+        assert_eq!(
+            value["C"][0],
+            "0x0ee9a592ba9a9518d05986d656f40c2114c4993c11bb29938d21d47304cd8e6e"
+        );
+        assert_eq!(value["M"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn circomlibjs_constants() {
+        let params = [
+            &*POSEIDON_CIRCOM_BN_2_PARAMS,
+            &*POSEIDON_CIRCOM_BN_3_PARAMS,
+            &*POSEIDON_CIRCOM_BN_4_PARAMS,
+        ];
+        let entries = params
+            .iter()
+            .map(|p| serde_json::from_str::<Value>(&p.to_circomlib_json()).unwrap())
+            .collect::<Vec<_>>();
+        let c = entries
+            .iter()
+            .map(|e| e["C"].to_owned())
+            .collect::<Vec<_>>();
+        let m = entries
+            .iter()
+            .map(|e| e["M"].to_owned())
+            .collect::<Vec<_>>();
+        let json = serde_json::json!({ "C": c, "M": m }).to_string();
+
+        for p in params {
+            let loaded = PoseidonParams::<Scalar>::from_circomlibjs_constants(&json, p.t).unwrap();
+            assert_eq!(loaded.to_circomlib_json(), p.to_circomlib_json());
+        }
+        assert!(PoseidonParams::<Scalar>::from_circomlibjs_constants(&json, 1).is_err());
+        assert!(PoseidonParams::<Scalar>::from_circomlibjs_constants(&json, 5).is_err());
+        assert!(PoseidonParams::<Scalar>::from_circom_json(&json).is_err());
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(PoseidonParams::<Scalar>::from_circom_json("{").is_err());
+        assert!(PoseidonParams::<Scalar>::from_circom_json(r#"{"C": [], "M": []}"#).is_err());
+        assert!(
+            PoseidonParams::<Scalar>::from_circom_json(r#"{"C": ["0x1"], "M": [["0x1"]]}"#)
+                .is_err()
+        );
+    }
+}
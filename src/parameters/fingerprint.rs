@@ -0,0 +1,95 @@
+//! # Fingerprint
+//! Derivation of a compact identifier for parameter sets.
+
+use super::{bytes::encode_elements, PoseidonParams};
+use crate::{
+    bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, output::to_bytes_be, poseidon::Poseidon,
+};
+use alloc::vec::Vec;
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+
+// Packs bytes into BN254 elements, 31 bytes (which are always smaller than the modulus) each.
+fn pack(bytes: &[u8]) -> Vec<Fr> {
+    bytes.chunks(31).map(Fr::from_le_bytes_mod_order).collect()
+}
+
+impl<F: PrimeField> PoseidonParams<F> {
+    /// Returns a fingerprint of the parameter set, such that two parties can cheaply check that
+    /// they use the same instance and proofs can embed the instance it was computed with. It is
+    /// the hash of the statesize, the sbox degree, the round numbers, the modulus of the field,
+    /// the flattened MDS matrix, and the flattened round constants, computed with
+    /// [`Poseidon::hash_segments`] and the fixed circom t=3 instance over BN254, and encoded as
+    /// big-endian integer. It is computed on the first call and cached in the parameters.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        *self.fingerprint.call_once(|| self.compute_fingerprint())
+    }
+
+    fn compute_fingerprint(&self) -> [u8; 32] {
+        let modulus = F::MODULUS.to_bytes_le();
+        let size = (F::MODULUS_BIT_SIZE as usize).div_ceil(8);
+        let header = [
+            Fr::from(self.t as u64),
+            Fr::from(self.d as u64),
+            Fr::from((self.rounds_f_beginning + self.rounds_f_end) as u64),
+            Fr::from(self.rounds_p as u64),
+            Fr::from(size as u64),
+        ];
+        let elements = encode_elements(
+            self.mds
+                .rows()
+                .flatten()
+                .chain(self.round_constants.as_flat()),
+        );
+
+        let hash = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS)
+            .hash_segments(&[&header, &pack(&modulus[..size]), &pack(&elements)])
+            .expect("the fingerprint instance has a valid statesize");
+        to_bytes_be(&hash)
+            .try_into()
+            .expect("BN254 elements are encoded with 32 bytes")
+    }
+}
+
+#[cfg(test)]
+mod fingerprint_tests {
+    use crate::{
+        bn254::{circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS},
+        goldilocks::t8::POSEIDON_GOLDILOCKS_8_PARAMS,
+        output::Digest,
+        parameters::PoseidonParams,
+    };
+    use alloc::string::ToString;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn fingerprints() {
+        let fingerprint3 = POSEIDON_CIRCOM_BN_3_PARAMS.fingerprint();
+        let fingerprint4 = POSEIDON_CIRCOM_BN_4_PARAMS.fingerprint();
+        assert_ne!(fingerprint3, fingerprint4);
+        assert_eq!(fingerprint3, POSEIDON_CIRCOM_BN_3_PARAMS.fingerprint());
+        assert_eq!(
+            POSEIDON_CIRCOM_BN_3_PARAMS.fingerprint.get(),
+            Some(&fingerprint3)
+        );
+        assert_ne!(fingerprint3, POSEIDON_GOLDILOCKS_8_PARAMS.fingerprint());
+        let hex = |fingerprint: [u8; 32]| {
+            Digest::<Fr>::from_bytes_be(&fingerprint)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(
+            hex(fingerprint3),
+            "0x009f71026fdcfa9db6d9ecc104d0216343ccbd963dae74d2110612e19049c1bd"
+        );
+        assert_eq!(
+            hex(fingerprint4),
+            "0x2352e01c7cdd479fccd8d315505f19fa8879d4d19803aaad4b18ab3338a77f44"
+        );
+
+        // only depends on the values of the parameters
+        let params = &POSEIDON_CIRCOM_BN_3_PARAMS;
+        let loaded = PoseidonParams::<Fr>::from_bytes(3, 5, 8, 57, &params.to_bytes()).unwrap();
+        assert_eq!(loaded.fingerprint(), fingerprint3);
+    }
+}
@@ -0,0 +1,249 @@
+//! # Parameter Generation
+//! Generation of Poseidon parameters for arbitrary prime fields, following the reference
+//! implementation of the Poseidon paper (`generate_parameters_grain.sage` and
+//! `calc_round_numbers.py`).
+
+use super::{grain::Grain, mds, PoseidonParams};
+use crate::error::Error;
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+
+// log2 of the modulus as floating point number
+pub(super) fn log2_modulus<F: PrimeField>() -> f64 {
+    let modulus: BigUint = F::MODULUS.into();
+    let bits = modulus.bits();
+    let shift = bits.saturating_sub(64);
+    let top = (&modulus >> shift).iter_u64_digits().next().unwrap_or(0);
+    (top as f64).log2() + shift as f64
+}
+
+fn log2_binomial(n: f64, k: f64) -> f64 {
+    if k < 0.0 || k > n {
+        return f64::NEG_INFINITY;
+    }
+    let k = k.min(n - k) as usize;
+    (1..=k)
+        .map(|i| ((n - k as f64 + i as f64) / i as f64).log2())
+        .sum()
+}
+
+// Checks the round numbers against the statistical and algebraic attacks from the Poseidon paper
+// and the Groebner basis attack from https://eprint.iacr.org/2023/537.pdf
+pub(super) fn sufficient_rounds(
+    log2_p: f64,
+    t: usize,
+    d: usize,
+    security_level: usize,
+    rounds_f: usize,
+    rounds_p: usize,
+) -> bool {
+    let n = log2_p.ceil();
+    let (t_, d_, m) = (t as f64, d as f64, security_level as f64);
+    let (r_f, r_p) = (rounds_f as f64, rounds_p as f64);
+    let log_d = |x: f64| x.ln() / d_.ln();
+
+    let r_f_1 = if m <= (log2_p - (d_ - 1.0) / 2.0).floor() * (t_ + 1.0) {
+        6.0
+    } else {
+        10.0
+    }; // statistical
+    let r_f_2 = 1.0 + (log_d(2.0) * m.min(n)).ceil() + log_d(t_).ceil() - r_p; // interpolation
+    let r_f_3 = log_d(2.0) * m.min(log2_p) - r_p; // Groebner 1
+    let r_f_4 = t_ - 1.0 + log_d(2.0) * (m / (t_ + 1.0)).min(log2_p / 2.0) - r_p; // Groebner 2
+    let r_f_5 = (t_ - 2.0 + m / (2.0 * d_.log2()) - r_p) / (t_ - 1.0); // Groebner 3
+    let r_f_max = [r_f_1, r_f_2, r_f_3, r_f_4, r_f_5]
+        .into_iter()
+        .map(f64::ceil)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    let r_temp = (t / 3) as f64;
+    let over = (r_f - 1.0) * t_ + r_p + r_temp + r_temp * (r_f / 2.0) + r_p + d_;
+    let under = r_temp * (r_f / 2.0) + r_p + d_;
+    let cost_gb4 = (2.0 * log2_binomial(over, under)).ceil();
+
+    r_f >= r_f_max && cost_gb4 >= m
+}
+
+/// Returns the number of full and partial rounds (R_F, R_P) minimizing the number of sboxes
+/// for the given field, statesize, sbox degree, and security level in bits, including the
+/// security margin of the Poseidon paper (+2 full rounds, +7.5% partial rounds).
+pub(crate) fn round_numbers<F: PrimeField>(
+    t: usize,
+    d: usize,
+    security_level: usize,
+) -> (usize, usize) {
+    search_rounds(log2_modulus::<F>(), t, d, security_level)
+}
+
+/// Returns the recommended number of full and partial rounds (R_F, R_P) for a prime field with
+/// `p_bits` bits, statesize `t`, sbox x^d, and the security level in bits, following
+/// `calc_round_numbers.py` of the Poseidon paper: the round numbers minimizing the number of
+/// sboxes among those resisting the statistical and algebraic attacks, plus the security margin
+/// (+2 full rounds, +7.5% partial rounds). Like the script, this uses the bit size instead of
+/// log2(p), which may differ from [`generate`] by a partial round for some fields. Fails with
+/// [`Error::InvalidParameters`] if t < 2, d < 3, or the field has less than 2 bits. Note that
+/// it is not checked that x^d is a permutation of the field.
+pub fn calc_rounds(
+    p_bits: usize,
+    t: usize,
+    d: usize,
+    security_level: usize,
+) -> Result<(usize, usize), Error> {
+    if t < 2 || d < 3 || p_bits < 2 {
+        return Err(Error::InvalidParameters);
+    }
+    Ok(search_rounds(p_bits as f64, t, d, security_level))
+}
+
+fn search_rounds(log2_p: f64, t: usize, d: usize, security_level: usize) -> (usize, usize) {
+    let mut rounds = (0, 0);
+    let mut min_cost = usize::MAX;
+    for r_p in 1..500 {
+        if t * 6 + r_p > min_cost {
+            break; // the cost only grows from here
+        }
+        // as in the reference script, the margin on r_p carries over to larger r_f
+        let mut r_p_t = r_p;
+        for r_f in (4..100).step_by(2) {
+            if t * (r_f + 2) + r_p_t > min_cost {
+                break;
+            }
+            if sufficient_rounds(log2_p, t, d, security_level, r_f, r_p_t) {
+                let r_f_t = r_f + 2;
+                r_p_t = (r_p_t as f64 * 1.075).ceil() as usize;
+                let cost = t * r_f_t + r_p_t;
+                if cost < min_cost || (cost == min_cost && r_f_t < rounds.0) {
+                    rounds = (r_f_t, r_p_t);
+                    min_cost = cost;
+                }
+            }
+        }
+    }
+    rounds
+}
+
+pub(super) fn check_sbox<F: PrimeField>(t: usize, d: usize) -> Result<(), Error> {
+    if t < 2 || d < 3 {
+        return Err(Error::InvalidParameters);
+    }
+    // x^d has to be a permutation, i.e., gcd(d, p - 1) = 1
+    let modulus: BigUint = F::MODULUS.into();
+    let rem = (modulus - 1u32) % d;
+    let (mut a, mut b) = (d as u64, rem.iter_u64_digits().next().unwrap_or(0));
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    if a != 1 {
+        return Err(Error::InvalidParameters);
+    }
+    Ok(())
+}
+
+/// Generates Poseidon parameters for the field `F` with statesize `t`, sbox degree `d`, and the
+/// round numbers derived from the given security level in bits. The round constants and the
+/// Cauchy MDS matrix are derived from the Grain LFSR as in the Poseidon paper. Like the reference
+/// script, matrices which fail its subspace trail algorithms
+/// ([`mds::check_subspace_trail_algorithms`]) are sampled again.
+pub fn generate<F: PrimeField>(
+    t: usize,
+    d: usize,
+    security_level: usize,
+) -> Result<PoseidonParams<F>, Error> {
+    check_sbox::<F>(t, d)?;
+    let (rounds_f, rounds_p) = round_numbers::<F>(t, d, security_level);
+    generate_with_rounds(t, d, rounds_f, rounds_p)
+}
+
+/// Generates Poseidon parameters like [`generate`], but with explicitly given round numbers. For
+/// example, circom uses R_F = 8 and the partial rounds of [`generate`] rounded up to a multiple
+/// of t.
+pub fn generate_with_rounds<F: PrimeField>(
+    t: usize,
+    d: usize,
+    rounds_f: usize,
+    rounds_p: usize,
+) -> Result<PoseidonParams<F>, Error> {
+    check_sbox::<F>(t, d)?;
+    let field_size = F::MODULUS_BIT_SIZE as usize;
+    let mut grain = Grain::new(field_size, t, rounds_f, rounds_p);
+
+    let round_constants = (0..rounds_f + rounds_p)
+        .map(|_| (0..t).map(|_| grain.field_element()).collect())
+        .collect();
+    let mds = cauchy_matrix(|| grain.field_element_reduced(), t);
+
+    PoseidonParams::new(t, d, rounds_f, rounds_p, mds, round_constants)
+}
+
+// Samples 2t values until they are pairwise distinct and yield a Cauchy matrix (see mds::cauchy)
+// which passes the subspace trail algorithms, as the reference script does
+pub(super) fn cauchy_matrix<F: PrimeField>(mut sample: impl FnMut() -> F, t: usize) -> Vec<Vec<F>> {
+    loop {
+        let values: Vec<F> = (0..2 * t).map(|_| sample()).collect();
+        let (xs, ys) = values.split_at(t);
+        if let Ok(mds) = mds::cauchy(xs, ys) {
+            if mds::check_subspace_trail_algorithms(&mds).is_ok() {
+                return mds;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod generate_tests {
+    use super::*;
+    use crate::bn254::{
+        circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS,
+    };
+
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn reproduces_circom() {
+        for (params, rounds_p) in [
+            (&*POSEIDON_CIRCOM_BN_3_PARAMS, 57),
+            (&*POSEIDON_CIRCOM_BN_4_PARAMS, 56),
+        ] {
+            let generated = generate_with_rounds::<Scalar>(params.t, 5, 8, rounds_p).unwrap();
+            assert_eq!(generated.mds, params.mds);
+            assert_eq!(generated.round_constants, params.round_constants);
+        }
+    }
+
+    #[test]
+    fn circom_round_numbers() {
+        // circom uses these round numbers rounded up to a multiple of t
+        let circom = [
+            56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68,
+        ];
+        for (t, expected) in (2..=17).zip(circom) {
+            let (rounds_f, rounds_p) = round_numbers::<Scalar>(t, 5, 128);
+            assert_eq!(rounds_f, 8);
+            assert_eq!(rounds_p.div_ceil(t) * t, expected);
+        }
+    }
+
+    #[test]
+    fn calc_round_numbers() {
+        for t in 2..=17 {
+            assert_eq!(
+                calc_rounds(254, t, 5, 128).unwrap(),
+                round_numbers::<Scalar>(t, 5, 128)
+            );
+        }
+        assert_eq!(calc_rounds(64, 12, 7, 128).unwrap(), (8, 22));
+        // Neptune's BLS12-381 instance for arity 4
+        assert_eq!(calc_rounds(255, 5, 5, 128).unwrap(), (8, 56));
+        assert!(calc_rounds(254, 1, 5, 128).is_err());
+        assert!(calc_rounds(254, 3, 2, 128).is_err());
+    }
+
+    #[test]
+    fn generate_params() {
+        let params = generate::<Scalar>(3, 5, 128).unwrap();
+        assert_eq!(params.rounds, 8 + 56);
+        assert!(generate::<Scalar>(3, 4, 128).is_err());
+        assert!(generate::<Scalar>(3, 3, 128).is_err()); // gcd(3, p - 1) != 1
+        assert!(generate::<Scalar>(1, 5, 128).is_err());
+    }
+}
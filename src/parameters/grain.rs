@@ -0,0 +1,83 @@
+//! # Grain LFSR
+//! The self-shrinking Grain LFSR used by the reference implementation of the Poseidon paper
+//! (`generate_parameters_grain.sage`) to derive round constants and MDS matrices.
+
+use alloc::collections::VecDeque;
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+
+const STATE_SIZE: usize = 80;
+
+pub(crate) struct Grain {
+    state: VecDeque<bool>,
+}
+
+impl Grain {
+    /// Initializes the LFSR for a prime field (`field = 1`) with the sbox x^alpha (`sbox = 0`).
+    pub(crate) fn new(field_size: usize, t: usize, rounds_f: usize, rounds_p: usize) -> Self {
+        let mut state = VecDeque::with_capacity(STATE_SIZE);
+        for (value, width) in [
+            (1, 2),
+            (0, 4),
+            (field_size, 12),
+            (t, 12),
+            (rounds_f, 10),
+            (rounds_p, 10),
+        ] {
+            state.extend((0..width).rev().map(|i| (value >> i) & 1 == 1));
+        }
+        state.extend([true; 30]);
+        debug_assert_eq!(state.len(), STATE_SIZE);
+
+        let mut grain = Grain { state };
+        for _ in 0..160 {
+            grain.update();
+        }
+        grain
+    }
+
+    fn update(&mut self) -> bool {
+        let s = &self.state;
+        let new_bit = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+        self.state.pop_front();
+        self.state.push_back(new_bit);
+        new_bit
+    }
+
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let select = self.update();
+            let bit = self.update();
+            if select {
+                return bit;
+            }
+        }
+    }
+
+    fn next_bits(&mut self, num_bits: usize) -> BigUint {
+        let mut res = BigUint::default();
+        for _ in 0..num_bits {
+            res <<= 1;
+            if self.next_bit() {
+                res.set_bit(0, true);
+            }
+        }
+        res
+    }
+
+    /// Samples a field element by rejection sampling, as used for the round constants.
+    pub(crate) fn field_element<F: PrimeField>(&mut self) -> F {
+        let modulus: BigUint = F::MODULUS.into();
+        loop {
+            let candidate = self.next_bits(F::MODULUS_BIT_SIZE as usize);
+            if candidate < modulus {
+                return candidate.into();
+            }
+        }
+    }
+
+    /// Samples a field element by reducing the sampled bits, as used for the MDS matrix.
+    pub(crate) fn field_element_reduced<F: PrimeField>(&mut self) -> F {
+        self.next_bits(F::MODULUS_BIT_SIZE as usize).into()
+    }
+}
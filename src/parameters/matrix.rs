@@ -0,0 +1,235 @@
+//! # Matrix
+//! Contains the storage of the square matrices used in the linear layers of the permutation.
+
+use crate::error::Error;
+use alloc::{borrow::ToOwned, vec, vec::Vec};
+use ark_ff::PrimeField;
+use itertools::izip;
+
+/// Matrices with a dimension of at least this value are stored in a flat buffer, smaller ones
+/// as nested vectors. In `benches/matrix.rs` the nested layout is faster for t = 3 and on par
+/// for t = 4, while the flat layout is ahead from t = 5 on (e.g. ~1.16us vs ~1.19us for t = 5,
+/// ~2.6us vs ~3.6us for t = 8 and ~5.4us vs ~7.8us for t = 12).
+pub const FLAT_STORAGE_MIN_DIM: usize = 5;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Storage<F: PrimeField> {
+    Nested(Vec<Vec<F>>),
+    Flat(Vec<F>), // row-major
+}
+
+/// A square matrix, stored either as nested vectors or in a flat row-major buffer, depending on
+/// its dimension.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix<F: PrimeField> {
+    dim: usize,
+    storage: Storage<F>,
+}
+
+impl<F: PrimeField> Matrix<F> {
+    /// Creates a matrix from its rows, selecting the storage based on the dimension. Returns an
+    /// error if the matrix is not square.
+    pub fn new(rows: Vec<Vec<F>>) -> Result<Self, Error> {
+        if rows.len() >= FLAT_STORAGE_MIN_DIM {
+            Self::new_flat(rows)
+        } else {
+            Self::new_nested(rows)
+        }
+    }
+
+    /// Creates a matrix stored as nested vectors. Returns an error if the matrix is not square.
+    pub fn new_nested(rows: Vec<Vec<F>>) -> Result<Self, Error> {
+        Self::check_square(&rows)?;
+        Ok(Matrix {
+            dim: rows.len(),
+            storage: Storage::Nested(rows),
+        })
+    }
+
+    /// Creates a matrix stored in a flat row-major buffer. Returns an error if the matrix is not
+    /// square.
+    pub fn new_flat(rows: Vec<Vec<F>>) -> Result<Self, Error> {
+        Self::check_square(&rows)?;
+        let dim = rows.len();
+        let data: Vec<F> = rows.into_iter().flatten().collect();
+        Ok(Matrix {
+            dim,
+            storage: Storage::Flat(data),
+        })
+    }
+
+    fn check_square(rows: &[Vec<F>]) -> Result<(), Error> {
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != rows.len() {
+                return Err(Error::InvalidRowLength {
+                    part: "matrix",
+                    row: i,
+                    expected: rows.len(),
+                    got: row.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn is_flat(&self) -> bool {
+        matches!(self.storage, Storage::Flat(_))
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> &F {
+        match &self.storage {
+            Storage::Nested(rows) => &rows[row][col],
+            Storage::Flat(data) => &data[row * self.dim + col],
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[F] {
+        match &self.storage {
+            Storage::Nested(rows) => &rows[row],
+            Storage::Flat(data) => &data[row * self.dim..(row + 1) * self.dim],
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[F]> {
+        (0..self.dim).map(|row| self.row(row))
+    }
+
+    pub fn to_vec(&self) -> Vec<Vec<F>> {
+        self.rows().map(|row| row.to_vec()).collect()
+    }
+
+    pub fn mat_vec_mul(&self, input: &[F]) -> Vec<F> {
+        let mut out = vec![F::zero(); self.dim];
+        self.mat_vec_mul_into(input, &mut out);
+        out
+    }
+
+    /// Multiplies the matrix with `input` and writes the result to `out`, which allows reusing
+    /// the output buffer across calls.
+    pub fn mat_vec_mul_into(&self, input: &[F], out: &mut [F]) {
+        debug_assert_eq!(self.dim, input.len());
+        debug_assert_eq!(self.dim, out.len());
+        match &self.storage {
+            Storage::Nested(rows) => {
+                for (row, out) in izip!(rows.iter(), out.iter_mut()) {
+                    *out = F::zero();
+                    Self::dot_product(row, input, out);
+                }
+            }
+            Storage::Flat(data) => {
+                for (row, out) in izip!(data.chunks_exact(self.dim), out.iter_mut()) {
+                    *out = F::zero();
+                    Self::dot_product(row, input, out);
+                }
+            }
+        }
+    }
+
+    /// Multiplies the matrix with `input`, adds `add`, and writes the result to `out`. This fuses
+    /// the round constant addition of the next round into the linear layer, such that the state
+    /// is only traversed once.
+    pub fn mat_vec_mul_add_into(&self, input: &[F], add: &[F], out: &mut [F]) {
+        debug_assert_eq!(self.dim, input.len());
+        debug_assert_eq!(self.dim, add.len());
+        debug_assert_eq!(self.dim, out.len());
+        match &self.storage {
+            Storage::Nested(rows) => {
+                for (row, add, out) in izip!(rows.iter(), add.iter(), out.iter_mut()) {
+                    *out = *add;
+                    Self::dot_product(row, input, out);
+                }
+            }
+            Storage::Flat(data) => {
+                for (row, add, out) in
+                    izip!(data.chunks_exact(self.dim), add.iter(), out.iter_mut())
+                {
+                    *out = *add;
+                    Self::dot_product(row, input, out);
+                }
+            }
+        }
+    }
+
+    fn dot_product(row: &[F], input: &[F], out: &mut F) {
+        for (mat, inp) in izip!(row.iter(), input.iter()) {
+            let mut tmp = mat.to_owned();
+            tmp.mul_assign(inp);
+            out.add_assign(tmp);
+        }
+    }
+}
+
+#[cfg(test)]
+mod matrix_tests {
+    use super::*;
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn storage_selection() {
+        let mut rng = thread_rng();
+
+        for dim in [3, 4, 12] {
+            let rows: Vec<Vec<Scalar>> = (0..dim)
+                .map(|_| (0..dim).map(|_| Scalar::rand(&mut rng)).collect())
+                .collect();
+            let input: Vec<Scalar> = (0..dim).map(|_| Scalar::rand(&mut rng)).collect();
+
+            let nested = Matrix::new_nested(rows.to_owned()).unwrap();
+            let flat = Matrix::new_flat(rows.to_owned()).unwrap();
+            assert_eq!(
+                Matrix::new(rows.to_owned()).unwrap().is_flat(),
+                dim >= FLAT_STORAGE_MIN_DIM
+            );
+            assert_eq!(nested.to_vec(), rows);
+            assert_eq!(flat.to_vec(), rows);
+            assert_eq!(nested.get(1, 2), flat.get(1, 2));
+            assert_eq!(nested.mat_vec_mul(&input), flat.mat_vec_mul(&input));
+
+            let mut out = vec![Scalar::rand(&mut rng); dim];
+            flat.mat_vec_mul_into(&input, &mut out);
+            assert_eq!(out, nested.mat_vec_mul(&input));
+
+            let add: Vec<Scalar> = (0..dim).map(|_| Scalar::rand(&mut rng)).collect();
+            let expected: Vec<Scalar> = izip!(nested.mat_vec_mul(&input), add.iter())
+                .map(|(a, b)| a + b)
+                .collect();
+            nested.mat_vec_mul_add_into(&input, &add, &mut out);
+            assert_eq!(out, expected);
+            flat.mat_vec_mul_add_into(&input, &add, &mut out);
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn non_square() {
+        let mut rng = thread_rng();
+        let mut rows: Vec<Vec<Scalar>> = (0..6)
+            .map(|_| (0..6).map(|_| Scalar::rand(&mut rng)).collect())
+            .collect();
+        rows[2].pop();
+        for result in [
+            Matrix::new(rows.to_owned()),
+            Matrix::new_nested(rows.to_owned()),
+            Matrix::new_flat(rows.to_owned()),
+        ] {
+            assert!(matches!(
+                result,
+                Err(Error::InvalidRowLength {
+                    row: 2,
+                    expected: 6,
+                    got: 5,
+                    ..
+                })
+            ));
+        }
+        rows.pop();
+        assert!(Matrix::new_flat(rows).is_err());
+    }
+}
@@ -0,0 +1,604 @@
+//! # MDS Matrices
+//! Construction of Cauchy and circulant matrices over any prime field and the matrix checks of
+//! the reference script of the Poseidon paper (`generate_parameters_grain.sage`): the MDS
+//! property, the sufficient condition excluding infinitely long invariant subspace trails, and
+//! the subspace trail algorithms the script uses to accept matrices.
+
+use crate::error::Error;
+use alloc::{borrow::ToOwned, format, string::String, vec, vec::Vec};
+use ark_ff::{BigInteger, PrimeField};
+
+fn distinct<F: PrimeField>(values: &[F]) -> bool {
+    (1..values.len()).all(|i| !values[..i].contains(&values[i]))
+}
+
+/// Constructs the Cauchy matrix `M[i][j] = 1 / (x_i + y_j)`, which is MDS. Fails with
+/// [`Error::InvalidParameters`] if the inputs have different lengths, the x_i or the y_j are not
+/// pairwise distinct, or some x_i + y_j is zero.
+pub fn cauchy<F: PrimeField>(xs: &[F], ys: &[F]) -> Result<Vec<Vec<F>>, Error> {
+    if xs.len() != ys.len() || !distinct(xs) || !distinct(ys) {
+        return Err(Error::InvalidParameters);
+    }
+    xs.iter()
+        .map(|x| {
+            ys.iter()
+                .map(|y| (*x + y).inverse())
+                .collect::<Option<Vec<_>>>()
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or(Error::InvalidParameters)
+}
+
+/// Constructs the circulant matrix with the given first row, where each row is the previous one
+/// rotated to the right by one position, i.e., `M[i][j] = c[(j - i) mod t]`. Whether the result
+/// is MDS depends on the entries, see [`is_mds`].
+pub fn circulant<F: PrimeField>(first_row: &[F]) -> Vec<Vec<F>> {
+    let t = first_row.len();
+    (0..t)
+        .map(|i| (0..t).map(|j| first_row[(j + t - i) % t]).collect())
+        .collect()
+}
+
+/// Checks the sufficient condition of the reference script against infinitely long invariant
+/// subspace trails: the minimal polynomials of M, M^2, ..., M^(2t) are irreducible of degree t.
+/// In particular, none of these powers has an eigenvalue in the field. Fails with
+/// [`Error::InsecureParameters`] naming the first power violating the condition.
+pub fn check_subspace_trails<F: PrimeField>(mat: &[Vec<F>]) -> Result<(), Error> {
+    let t = mat.len();
+    let mut power = mat.to_owned();
+    for i in 1..=2 * t {
+        if !is_irreducible(&char_poly(&power)) {
+            return Err(Error::InsecureParameters(format!(
+                "the minimal polynomial of M^{i} is not irreducible of degree {t}"
+            )));
+        }
+        power = mat_mul(mat, &power);
+    }
+    Ok(())
+}
+
+/// Runs the three subspace trail algorithms of the reference script (`algorithm_1`,
+/// `algorithm_2`, and `algorithm_3`) for partial rounds with one sbox, which decide whether the
+/// script accepts a matrix. For i < t, they reject if
+/// - M^i is a multiple of the identity,
+/// - M^i has an eigenvector (with eigenvalue in the field) in the subspace S_i of states which
+///   keep the sbox inactive for i rounds,
+/// - S_i is invariant under M^j for some j <= i,
+///
+/// and, for r <= 4t, if the first unit vector does not generate the whole space under M^r.
+/// Fails with [`Error::InsecureParameters`] naming the violated condition. In contrast to
+/// [`check_subspace_trails`], this accepts all matrices the reference script accepts.
+pub fn check_subspace_trail_algorithms<F: PrimeField>(mat: &[Vec<F>]) -> Result<(), Error> {
+    let t = mat.len();
+    let insecure = |reason: String| Err(Error::InsecureParameters(reason));
+
+    // Algorithm 1, the constraints are the first rows of M^0, ..., M^(i-1), whose kernel is S_i
+    let mut constraints = vec![unit(t, 0)];
+    let mut power = mat.to_owned();
+    for i in 1..t {
+        let diagonal = power[0][0];
+        if (0..t).all(|r| (0..t).all(|c| power[r][c] == if r == c { diagonal } else { F::zero() }))
+        {
+            return insecure(format!("M^{i} is a multiple of the identity"));
+        }
+        let inactive = kernel(&constraints, t);
+        if has_eigenvector_in(&power, &inactive) {
+            return insecure(format!(
+                "M^{i} has an eigenvector which keeps the sbox inactive for {i} rounds"
+            ));
+        }
+        let mut power_j = mat.to_owned();
+        for j in 1..=i {
+            if inactive.image(&power_j) == inactive {
+                return insecure(format!(
+                    "the subspace keeping the sbox inactive for {i} rounds is invariant under M^{j}"
+                ));
+            }
+            power_j = mat_mul(mat, &power_j);
+        }
+        constraints.push(power[0].to_owned());
+        power = mat_mul(mat, &power);
+    }
+
+    // Algorithm 2 for M and algorithm 3 for M^2, ..., M^(4t)
+    let mut power = mat.to_owned();
+    for r in 1..=4 * t {
+        let mut generated = Subspace::span([unit(t, 0)]);
+        let mut v = unit(t, 0);
+        loop {
+            v = mat_vec_mul(&power, &v);
+            if !generated.insert(v.to_owned()) {
+                return insecure(format!(
+                    "the first unit vector generates an invariant subspace of M^{r}"
+                ));
+            }
+            if generated.dim() == t {
+                break;
+            }
+        }
+        power = mat_mul(mat, &power);
+    }
+    Ok(())
+}
+
+fn unit<F: PrimeField>(t: usize, i: usize) -> Vec<F> {
+    let mut v = vec![F::zero(); t];
+    v[i] = F::one();
+    v
+}
+
+fn mat_vec_mul<F: PrimeField>(mat: &[Vec<F>], v: &[F]) -> Vec<F> {
+    mat.iter()
+        .map(|row| row.iter().zip(v).map(|(a, b)| *a * b).sum())
+        .collect()
+}
+
+// A subspace of F^t, stored as its basis in reduced row echelon form with the rows sorted by
+// their pivots. This form is unique, hence two subspaces are equal iff their bases are equal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Subspace<F: PrimeField> {
+    basis: Vec<Vec<F>>,
+    pivots: Vec<usize>,
+}
+
+impl<F: PrimeField> Subspace<F> {
+    fn span(vectors: impl IntoIterator<Item = Vec<F>>) -> Self {
+        let mut subspace = Subspace {
+            basis: Vec::new(),
+            pivots: Vec::new(),
+        };
+        for v in vectors {
+            subspace.insert(v);
+        }
+        subspace
+    }
+
+    fn dim(&self) -> usize {
+        self.basis.len()
+    }
+
+    // Adds the vector to the spanning set, returns whether the dimension increased
+    fn insert(&mut self, mut v: Vec<F>) -> bool {
+        for (b, pivot) in self.basis.iter().zip(self.pivots.iter()) {
+            let factor = v[*pivot];
+            if !factor.is_zero() {
+                v.iter_mut().zip(b).for_each(|(x, y)| *x -= factor * y);
+            }
+        }
+        let pivot = match v.iter().position(|x| !x.is_zero()) {
+            Some(pivot) => pivot,
+            None => return false,
+        };
+        let inv = v[pivot].inverse().expect("the pivot is non-zero");
+        v.iter_mut().for_each(|x| *x *= inv);
+        for b in self.basis.iter_mut() {
+            let factor = b[pivot];
+            if !factor.is_zero() {
+                b.iter_mut()
+                    .zip(v.iter())
+                    .for_each(|(x, y)| *x -= factor * y);
+            }
+        }
+        let pos = self.pivots.partition_point(|p| *p < pivot);
+        self.basis.insert(pos, v);
+        self.pivots.insert(pos, pivot);
+        true
+    }
+
+    // The vectors orthogonal to the subspace, i.e., the rows of a matrix with it as kernel
+    fn annihilator(&self, t: usize) -> Vec<Vec<F>> {
+        kernel(&self.basis, t).basis
+    }
+
+    fn image(&self, mat: &[Vec<F>]) -> Self {
+        Self::span(self.basis.iter().map(|b| mat_vec_mul(mat, b)))
+    }
+
+    // {x : mat * x in self}
+    fn preimage(&self, mat: &[Vec<F>], t: usize) -> Self {
+        let rows: Vec<Vec<F>> = self
+            .annihilator(t)
+            .iter()
+            .map(|y| {
+                (0..t)
+                    .map(|j| (0..t).map(|i| y[i] * mat[i][j]).sum())
+                    .collect()
+            })
+            .collect();
+        kernel(&rows, t)
+    }
+
+    fn intersection(&self, other: &Self, t: usize) -> Self {
+        let mut rows = self.annihilator(t);
+        rows.extend(other.annihilator(t));
+        kernel(&rows, t)
+    }
+}
+
+// The right kernel {x : row * x = 0 for all rows}
+fn kernel<F: PrimeField>(rows: &[Vec<F>], t: usize) -> Subspace<F> {
+    let echelon = Subspace::span(rows.iter().cloned());
+    let free = (0..t).filter(|c| !echelon.pivots.contains(c));
+    Subspace::span(free.map(|f| {
+        let mut x = unit(t, f);
+        for (b, pivot) in echelon.basis.iter().zip(echelon.pivots.iter()) {
+            x[*pivot] = -b[f];
+        }
+        x
+    }))
+}
+
+// Whether the matrix has an eigenvector with eigenvalue in the field in the subspace. Such an
+// eigenvector spans an invariant subspace, hence it lies in the largest invariant subspace W
+// contained in the subspace, and it exists iff the restriction of the matrix to W has an
+// eigenvalue in the field, i.e., its characteristic polynomial f has a root, i.e.,
+// gcd(x^p - x, f) != 1.
+fn has_eigenvector_in<F: PrimeField>(mat: &[Vec<F>], subspace: &Subspace<F>) -> bool {
+    let t = mat.len();
+    let mut invariant = subspace.to_owned();
+    loop {
+        let next = invariant.intersection(&invariant.preimage(mat, t), t);
+        if next.dim() == invariant.dim() {
+            break;
+        }
+        invariant = next;
+    }
+    if invariant.dim() == 0 {
+        return false;
+    }
+
+    // Since the basis is in reduced row echelon form, the coordinates of a vector of W are its
+    // entries at the pivots
+    let images: Vec<Vec<F>> = invariant
+        .basis
+        .iter()
+        .map(|b| mat_vec_mul(mat, b))
+        .collect();
+    let restricted: Vec<Vec<F>> = invariant
+        .pivots
+        .iter()
+        .map(|pivot| images.iter().map(|image| image[*pivot]).collect())
+        .collect();
+    let f = char_poly(&restricted);
+    let mut frob = frobenius(&f);
+    frob.resize(frob.len().max(2), F::zero());
+    frob[1] -= F::one();
+    poly_gcd(&frob, &f).len() > 1
+}
+
+fn mat_mul<F: PrimeField>(a: &[Vec<F>], b: &[Vec<F>]) -> Vec<Vec<F>> {
+    let n = a.len();
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| (0..n).map(|k| a[i][k] * b[k][j]).sum())
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether the matrix is a Cauchy matrix `M[i][j] = 1 / (x_i + y_j)` with pairwise distinct
+/// x_i and pairwise distinct y_j, which implies the MDS property. This holds iff all entries are
+/// non-zero, the inverses satisfy `1/M[i][j] = 1/M[i][0] + 1/M[0][j] - 1/M[0][0]`, and the
+/// derived x_i and y_j are pairwise distinct.
+pub fn is_cauchy<F: PrimeField>(mat: &[Vec<F>]) -> bool {
+    let inv = match mat
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|el| el.inverse())
+                .collect::<Option<Vec<_>>>()
+        })
+        .collect::<Option<Vec<_>>>()
+    {
+        Some(inv) => inv,
+        None => return false,
+    };
+    let n = inv.len();
+    let xs: Vec<F> = (0..n).map(|i| inv[i][0]).collect();
+    let ys: Vec<F> = (0..n).map(|j| inv[0][j] - inv[0][0]).collect();
+    let distinct = |v: &[F]| (1..v.len()).all(|i| !v[..i].contains(&v[i]));
+    distinct(&xs) && distinct(&ys) && (0..n).all(|i| (0..n).all(|j| inv[i][j] == xs[i] + ys[j]))
+}
+
+fn determinant<F: PrimeField>(mut mat: Vec<Vec<F>>) -> F {
+    let n = mat.len();
+    let mut det = F::one();
+    for col in 0..n {
+        let pivot = match (col..n).find(|row| !mat[*row][col].is_zero()) {
+            Some(pivot) => pivot,
+            None => return F::zero(),
+        };
+        if pivot != col {
+            mat.swap(pivot, col);
+            det = -det;
+        }
+        det *= mat[col][col];
+        let inv = mat[col][col].inverse().expect("the pivot is non-zero");
+        let (top, bottom) = mat.split_at_mut(col + 1);
+        let pivot_row = &top[col];
+        for row in bottom.iter_mut() {
+            let factor = row[col] * inv;
+            for (el, pivot_el) in row[col..].iter_mut().zip(pivot_row[col..].iter()) {
+                *el -= *pivot_el * factor;
+            }
+        }
+    }
+    det
+}
+
+// Returns all subsets of 0..n of size k
+fn subsets(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+    (k - 1..n)
+        .flat_map(|last| {
+            subsets(last, k - 1).into_iter().map(move |mut s| {
+                s.push(last);
+                s
+            })
+        })
+        .collect()
+}
+
+/// Whether the matrix is MDS, i.e., all its square submatrices are invertible. This computes all
+/// minors, so the cost grows exponentially with the dimension (see [`is_cauchy`] for a cheap
+/// sufficient condition).
+pub fn is_mds<F: PrimeField>(mat: &[Vec<F>]) -> bool {
+    let n = mat.len();
+    (1..=n).all(|k| {
+        let sets = subsets(n, k);
+        sets.iter().all(|rows| {
+            sets.iter().all(|cols| {
+                let minor = rows
+                    .iter()
+                    .map(|r| cols.iter().map(|c| mat[*r][*c]).collect())
+                    .collect();
+                !determinant(minor).is_zero()
+            })
+        })
+    })
+}
+
+// Polynomials are stored as coefficient vectors, starting with the constant coefficient
+
+// Characteristic polynomial det(xI - A) via the Faddeev-LeVerrier algorithm, requires p > n
+fn char_poly<F: PrimeField>(mat: &[Vec<F>]) -> Vec<F> {
+    let n = mat.len();
+    let mut coeffs = vec![F::zero(); n + 1];
+    coeffs[n] = F::one();
+    let mut m = vec![vec![F::zero(); n]; n];
+    for k in 1..=n {
+        m = mat_mul(mat, &m);
+        for (i, row) in m.iter_mut().enumerate() {
+            row[i] += coeffs[n - k + 1];
+        }
+        let am = mat_mul(mat, &m);
+        let trace: F = (0..n).map(|i| am[i][i]).sum();
+        let k_inv = F::from(k as u64).inverse().expect("p > n");
+        coeffs[n - k] = -trace * k_inv;
+    }
+    coeffs
+}
+
+fn poly_trim<F: PrimeField>(mut a: Vec<F>) -> Vec<F> {
+    while a.last().is_some_and(|c| c.is_zero()) {
+        a.pop();
+    }
+    a
+}
+
+// Remainder of a divided by b, where b is non-zero and trimmed
+fn poly_rem<F: PrimeField>(a: &[F], b: &[F]) -> Vec<F> {
+    let mut rem = a.to_vec();
+    let lead_inv = b
+        .last()
+        .expect("b is non-zero")
+        .inverse()
+        .expect("b is trimmed");
+    while rem.len() >= b.len() {
+        let factor = *rem.last().expect("rem is non-empty") * lead_inv;
+        let shift = rem.len() - b.len();
+        for (i, c) in b.iter().enumerate() {
+            rem[shift + i] -= *c * factor;
+        }
+        rem.pop();
+    }
+    poly_trim(rem)
+}
+
+fn poly_mul_mod<F: PrimeField>(a: &[F], b: &[F], modulus: &[F]) -> Vec<F> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut prod = vec![F::zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            prod[i + j] += *x * y;
+        }
+    }
+    poly_rem(&prod, modulus)
+}
+
+// x^p mod f
+fn frobenius<F: PrimeField>(f: &[F]) -> Vec<F> {
+    let x = poly_rem(&[F::zero(), F::one()], f);
+    let mut result = vec![F::one()];
+    for bit in F::MODULUS.to_bits_be() {
+        result = poly_mul_mod(&result, &result, f);
+        if bit {
+            result = poly_mul_mod(&result, &x, f);
+        }
+    }
+    result
+}
+
+// h(g) mod f
+fn poly_compose_mod<F: PrimeField>(h: &[F], g: &[F], f: &[F]) -> Vec<F> {
+    let mut result = vec![];
+    for c in h.iter().rev() {
+        result = poly_mul_mod(&result, g, f);
+        if result.is_empty() {
+            result.push(F::zero());
+        }
+        result[0] += c;
+        result = poly_trim(result);
+    }
+    result
+}
+
+fn poly_gcd<F: PrimeField>(a: &[F], b: &[F]) -> Vec<F> {
+    let (mut a, mut b) = (poly_trim(a.to_vec()), poly_trim(b.to_vec()));
+    while !b.is_empty() {
+        let rem = poly_rem(&a, &b);
+        a = b;
+        b = rem;
+    }
+    a
+}
+
+// Rabin's test: f of degree n is irreducible iff x^(p^n) = x mod f and
+// gcd(x^(p^(n/q)) - x, f) = 1 for all prime divisors q of n
+fn is_irreducible<F: PrimeField>(f: &[F]) -> bool {
+    let n = f.len() - 1;
+    if n <= 1 {
+        return true;
+    }
+    let x = poly_rem(&[F::zero(), F::one()], f);
+    let frob = frobenius(f);
+    let mut powers = vec![x.to_owned(), frob.to_owned()]; // x^(p^k) mod f
+    for k in 2..=n {
+        let next = poly_compose_mod(&powers[k - 1], &frob, f);
+        powers.push(next);
+    }
+    if powers[n] != x {
+        return false;
+    }
+    let prime_divisors = (2..=n).filter(|q| n % q == 0 && (2..*q).all(|r| q % r != 0));
+    for q in prime_divisors {
+        let mut diff = powers[n / q].to_owned();
+        diff.resize(diff.len().max(2), F::zero());
+        diff[1] -= F::one();
+        if poly_gcd(&poly_trim(diff), f).len() != 1 {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod mds_tests {
+    use super::*;
+    use crate::{
+        bn254::{circom_t17::POSEIDON_CIRCOM_BN_17_PARAMS, circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS},
+        goldilocks::field::Goldilocks,
+    };
+    use ark_ff::Field;
+
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn constructions() {
+        let x = |v: u64| Scalar::from(v);
+        let mat = cauchy(&[x(1), x(2), x(3)], &[x(4), x(5), x(6)]).unwrap();
+        assert_eq!(mat[1][2], x(8).inverse().unwrap());
+        assert!(is_cauchy(&mat));
+        assert!(is_mds(&mat));
+        assert!(cauchy(&[x(1), x(1)], &[x(4), x(5)]).is_err());
+        assert!(cauchy(&[x(1), -x(4)], &[x(4), x(5)]).is_err());
+        assert!(cauchy(&[x(1)], &[x(4), x(5)]).is_err());
+
+        let mat = circulant(&[x(2), x(1), x(1)]);
+        assert_eq!(mat[1], [x(1), x(2), x(1)]);
+        assert!(is_mds(&mat));
+        assert!(!is_cauchy(&mat));
+        // [[1, 1], [1, 1]] is singular
+        assert!(!is_mds(&circulant(&[x(1), x(1)])));
+        let small = circulant(&[Goldilocks::from(2u64), Goldilocks::from(1u64)]);
+        assert!(is_mds(&small));
+    }
+
+    #[test]
+    fn subspace_trails() {
+        check_subspace_trails(&POSEIDON_CIRCOM_BN_4_PARAMS.mds.to_vec()).unwrap();
+        // the circulant matrix [[2, 1], [1, 2]] has the eigenvalues 1 and 3
+        let err = check_subspace_trails(&circulant(&[Scalar::from(2u64), Scalar::from(1u64)]));
+        assert!(matches!(err, Err(Error::InsecureParameters(reason)) if reason.contains("M^1")));
+    }
+
+    #[test]
+    fn subspace_trail_algorithms() {
+        let x = |v: u64| Scalar::from(v);
+        // the larger circom matrices fail the sufficient condition, but pass the algorithms
+        let circom = POSEIDON_CIRCOM_BN_17_PARAMS.mds.to_vec();
+        assert!(check_subspace_trails(&circom).is_err());
+        check_subspace_trail_algorithms(&circom).unwrap();
+        check_subspace_trail_algorithms(&POSEIDON_CIRCOM_BN_4_PARAMS.mds.to_vec()).unwrap();
+
+        // the eigenvectors (1, 1) and (1, -1) of [[2, 1], [1, 2]] do not keep the sbox inactive,
+        // hence the algorithms accept it, while it fails the sufficient condition
+        let circulant = circulant(&[x(2), x(1)]);
+        assert!(check_subspace_trails(&circulant).is_err());
+        check_subspace_trail_algorithms(&circulant).unwrap();
+
+        // e_1 is an eigenvector in S_1
+        let triangular = vec![
+            vec![x(1), x(0), x(0)],
+            vec![x(1), x(2), x(0)],
+            vec![x(1), x(1), x(3)],
+        ];
+        let err = check_subspace_trail_algorithms(&triangular);
+        assert!(
+            matches!(err, Err(Error::InsecureParameters(reason)) if reason.contains("eigenvector"))
+        );
+
+        // the identity
+        let identity = vec![vec![x(1), x(0)], vec![x(0), x(1)]];
+        let err = check_subspace_trail_algorithms(&identity);
+        assert!(
+            matches!(err, Err(Error::InsecureParameters(reason)) if reason.contains("identity"))
+        );
+
+        // [[0, 1], [n, 0]] for a non-residue n has no eigenvalues, but M^2 = n * I
+        let n = (2..).map(x).find(|n| n.legendre().is_qnr()).unwrap();
+        let err = check_subspace_trail_algorithms(&[vec![x(0), x(1)], vec![n, x(0)]]);
+        assert!(matches!(err, Err(Error::InsecureParameters(reason)) if reason.contains("M^2")));
+    }
+
+    #[test]
+    fn linear_algebra() {
+        let x = |v: u64| Scalar::from(v);
+        let a = Subspace::span([vec![x(1), x(2), x(3)], vec![x(2), x(4), x(6)]]);
+        assert_eq!(a.dim(), 1);
+        let b = Subspace::span([vec![x(0), x(1), x(0)], vec![x(1), x(0), x(0)]]);
+        assert_eq!(a.intersection(&b, 3).dim(), 0);
+        let c = Subspace::span([vec![x(1), x(2), x(3)], vec![x(0), x(0), x(1)]]);
+        assert_eq!(
+            c.intersection(&b, 3),
+            Subspace::span([vec![x(1), x(2), x(0)]])
+        );
+        assert_eq!(kernel(&[vec![x(1), x(1), x(1)]], 3).dim(), 2);
+        assert_eq!(
+            Subspace::span([vec![x(1), x(1)], vec![x(1), x(2)]]),
+            Subspace::span([vec![x(0), x(1)], vec![x(1), x(0)]])
+        );
+    }
+
+    #[test]
+    fn polynomials() {
+        let x = |v: i64| Scalar::from(v);
+        // x^2 - n is irreducible iff n is a non-residue
+        let non_residue = (2..).map(x).find(|n| n.legendre().is_qnr()).unwrap();
+        assert!(is_irreducible(&[-non_residue, x(0), x(1)]));
+        assert!(!is_irreducible(&[x(-4), x(0), x(1)]));
+        assert!(!is_irreducible(&[x(2), x(-3), x(1)]));
+        assert!(is_irreducible(&[x(0), x(1)]));
+        assert_eq!(
+            char_poly(&[vec![x(2), x(1)], vec![x(1), x(2)]]),
+            vec![x(3), x(-4), x(1)]
+        );
+        assert!(is_cauchy(&POSEIDON_CIRCOM_BN_4_PARAMS.mds.to_vec()));
+        assert!(!is_cauchy(&[vec![x(2), x(1)], vec![x(1), x(2)]]));
+    }
+}
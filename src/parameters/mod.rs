@@ -0,0 +1,544 @@
+pub mod builder;
+pub(crate) mod bytes;
+mod circom_json;
+mod fingerprint;
+#[cfg(feature = "std")]
+pub mod generate;
+#[cfg(feature = "std")]
+mod grain;
+pub mod matrix;
+pub mod mds;
+mod params_json;
+pub mod round_constants;
+#[cfg(feature = "std")]
+mod security;
+#[cfg(feature = "seeded")]
+pub mod seeded;
+
+use alloc::{borrow::ToOwned, vec, vec::Vec};
+use ark_ff::PrimeField;
+use core::fmt;
+#[cfg(feature = "std")]
+pub use generate::calc_rounds;
+use itertools::izip;
+use spin::Once;
+
+use crate::error::Error;
+use matrix::Matrix;
+use round_constants::RoundConstants;
+
+#[derive(Debug)]
+pub struct PoseidonParams<F: PrimeField> {
+    pub(crate) t: usize, // statesize
+    pub(crate) d: usize, // sbox degree
+    pub(crate) rounds_f_beginning: usize,
+    pub(crate) rounds_p: usize,
+    pub(crate) rounds_f_end: usize,
+    pub(crate) rounds: usize,
+    pub(crate) mds: Matrix<F>,
+    pub(crate) round_constants: RoundConstants<F>,
+    // computed on first use, see Self::optimized
+    pub(crate) optimized: Once<Result<OptimizedParams<F>, Error>>,
+    // computed on first use, see Self::fingerprint
+    pub(crate) fingerprint: Once<[u8; 32]>,
+}
+
+/// The equivalent representation of the partial rounds used by the optimized permutation.
+#[derive(Clone, Debug)]
+pub(crate) struct OptimizedParams<F: PrimeField> {
+    pub(crate) round_constants: Vec<Vec<F>>,
+    pub(crate) w_hat: Vec<Vec<F>>,
+    pub(crate) v: Vec<Vec<F>>,
+    pub(crate) m_i: Matrix<F>,
+}
+
+/// Summarizes the parameters, e.g., `t = 3, d = 5, R_F = 8, R_P = 57 over a 254-bit field`.
+impl<F: PrimeField> fmt::Display for PoseidonParams<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "t = {}, d = {}, R_F = {}, R_P = {} over a {}-bit field",
+            self.t,
+            self.d,
+            self.rounds_full(),
+            self.rounds_p,
+            F::MODULUS_BIT_SIZE
+        )
+    }
+}
+
+// Once does not implement Clone, hence the cached values are copied manually
+impl<F: PrimeField> Clone for PoseidonParams<F> {
+    fn clone(&self) -> Self {
+        let optimized = match self.optimized.get() {
+            Some(optimized) => Once::initialized(optimized.clone()),
+            None => Once::new(),
+        };
+        let fingerprint = match self.fingerprint.get() {
+            Some(fingerprint) => Once::initialized(*fingerprint),
+            None => Once::new(),
+        };
+        PoseidonParams {
+            t: self.t,
+            d: self.d,
+            rounds_f_beginning: self.rounds_f_beginning,
+            rounds_p: self.rounds_p,
+            rounds_f_end: self.rounds_f_end,
+            rounds: self.rounds,
+            mds: self.mds.clone(),
+            round_constants: self.round_constants.clone(),
+            optimized,
+            fingerprint,
+        }
+    }
+}
+
+impl<F: PrimeField> PoseidonParams<F> {
+    /// Creates the parameters and precomputes the equivalent representation of the partial
+    /// rounds used by [`Poseidon::permutation`](crate::poseidon::Poseidon::permutation).
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        t: usize,
+        d: usize,
+        rounds_f: usize,
+        rounds_p: usize,
+        mds: Vec<Vec<F>>,
+        round_constants: Vec<Vec<F>>,
+    ) -> Result<Self, Error> {
+        let params = Self::new_unoptimized(t, d, rounds_f, rounds_p, mds, round_constants)?;
+        let optimized = params.compute_optimized()?;
+        params.optimized.call_once(|| Ok(optimized));
+        Ok(params)
+    }
+
+    /// Creates the parameters without precomputing the optimized representation, which
+    /// requires several matrix inversions. It is computed once on the first call of
+    /// [`Poseidon::permutation`](crate::poseidon::Poseidon::permutation) instead, hence this is
+    /// the cheaper constructor for parameters that are only used with
+    /// [`Poseidon::permutation_not_opt`](crate::poseidon::Poseidon::permutation_not_opt) or
+    /// constructed transiently. Since the matrices are not inverted here, a singular one is only
+    /// reported as [`Error::NonInvertibleSubmatrix`] by the optimized permutation.
+    pub fn new_unoptimized(
+        t: usize,
+        d: usize,
+        rounds_f: usize,
+        rounds_p: usize,
+        mds: Vec<Vec<F>>,
+        round_constants: Vec<Vec<F>>,
+    ) -> Result<Self, Error> {
+        if t == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        check_mds(t, &mds)?;
+        let rounds = rounds_f
+            .checked_add(rounds_p)
+            .ok_or(Error::InvalidParameters)?;
+        check_round_constants(rounds, t, &round_constants)?;
+        if rounds_f % 2 != 0 {
+            return Err(Error::OddFullRounds(rounds_f));
+        }
+        let r = rounds_f / 2;
+
+        Ok(PoseidonParams {
+            t,
+            d,
+            rounds_f_beginning: r,
+            rounds_p,
+            rounds_f_end: r,
+            rounds,
+            mds: Matrix::new(mds)?,
+            round_constants: RoundConstants::new(t, round_constants),
+            optimized: Once::new(),
+            fingerprint: Once::new(),
+        })
+    }
+
+    /// The statesize.
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// The degree of the sbox x^d.
+    pub fn d(&self) -> usize {
+        self.d
+    }
+
+    /// The number of full rounds, half of which are applied before the partial rounds.
+    pub fn rounds_full(&self) -> usize {
+        self.rounds_f_beginning + self.rounds_f_end
+    }
+
+    pub fn rounds_partial(&self) -> usize {
+        self.rounds_p
+    }
+
+    /// The total number of rounds.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    pub fn mds(&self) -> &Matrix<F> {
+        &self.mds
+    }
+
+    /// The round constants of the unoptimized permutation, one row of t elements per round.
+    pub fn round_constants(&self) -> &RoundConstants<F> {
+        &self.round_constants
+    }
+
+    /// Returns the optimized representation, computing it on the first call. Fails with
+    /// [`Error::NonInvertibleSubmatrix`] on every call if a required submatrix is singular.
+    pub(crate) fn optimized(&self) -> Result<&OptimizedParams<F>, Error> {
+        self.optimized
+            .call_once(|| self.compute_optimized())
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    fn compute_optimized(&self) -> Result<OptimizedParams<F>, Error> {
+        // Without partial rounds, the optimized representation is not required
+        if self.rounds_p == 0 {
+            return Ok(OptimizedParams {
+                round_constants: Vec::new(),
+                w_hat: Vec::new(),
+                v: Vec::new(),
+                m_i: Matrix::new(Vec::new())?,
+            });
+        }
+        let mds = self.mds.to_vec();
+        let (m_i, v, w_hat) = Self::equivalent_matrices(&mds, self.t, self.rounds_p)?;
+        let round_constants = Self::equivalent_round_constants(
+            &self.round_constants,
+            &mds,
+            self.rounds_f_beginning,
+            self.rounds_p,
+        )?;
+        Ok(OptimizedParams {
+            round_constants,
+            w_hat,
+            v,
+            m_i: Matrix::new(m_i)?,
+        })
+    }
+
+    // Gauss-Jordan elimination with partial pivoting, i.e., if the current pivot is zero, a row
+    // below it with a non-zero element in the pivot column is swapped in. Fails iff the matrix is
+    // singular.
+    pub(crate) fn mat_inverse(mat: &[Vec<F>]) -> Result<Vec<Vec<F>>, Error> {
+        let n = mat.len();
+        debug_assert!(mat.iter().all(|row| row.len() == n));
+
+        let mut m = mat.to_owned();
+        let mut inv = vec![vec![F::zero(); n]; n];
+        for (i, invi) in inv.iter_mut().enumerate() {
+            invi[i] = F::one();
+        }
+
+        for col in 0..n {
+            let pivot = (col..n)
+                .find(|row| !m[*row][col].is_zero())
+                .ok_or(Error::NonInvertibleSubmatrix)?;
+            m.swap(col, pivot);
+            inv.swap(col, pivot);
+
+            // make 1 in diag
+            let el_inv = m[col][col].inverse().ok_or(Error::NonInvertibleSubmatrix)?;
+            m[col].iter_mut().for_each(|el| el.mul_assign(&el_inv));
+            inv[col].iter_mut().for_each(|el| el.mul_assign(&el_inv));
+
+            // eliminate the column in all other rows
+            for row in 0..n {
+                if row == col || m[row][col].is_zero() {
+                    continue;
+                }
+                let factor = m[row][col];
+                for k in 0..n {
+                    let mut tmp = m[col][k];
+                    tmp.mul_assign(&factor);
+                    m[row][k].sub_assign(&tmp);
+                    let mut tmp = inv[col][k];
+                    tmp.mul_assign(&factor);
+                    inv[row][k].sub_assign(&tmp);
+                }
+            }
+        }
+        Ok(inv)
+    }
+
+    fn mat_transpose(mat: &[Vec<F>]) -> Vec<Vec<F>> {
+        let rows = mat.len();
+        let cols = mat[0].len();
+        let mut transpose = vec![vec![F::zero(); rows]; cols];
+
+        for (row, matrow) in mat.iter().enumerate() {
+            debug_assert_eq!(cols, matrow.len());
+            for col in 0..cols {
+                transpose[col][row] = matrow[col];
+            }
+        }
+        transpose
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn equivalent_matrices(
+        mds: &[Vec<F>],
+        t: usize,
+        rounds_p: usize,
+    ) -> Result<(Vec<Vec<F>>, Vec<Vec<F>>, Vec<Vec<F>>), Error> {
+        let mut w_hat = Vec::with_capacity(rounds_p);
+        let mut v = Vec::with_capacity(rounds_p);
+        let mut m_i = vec![vec![F::zero(); t]; t];
+
+        let mds_ = Self::mat_transpose(mds);
+        #[allow(clippy::redundant_clone)] // Seems to be a mistake by clippy?
+        let mut m_mul = mds_.clone();
+
+        for _ in 0..rounds_p {
+            // calc m_hat, w and v
+            let mut m_hat = vec![vec![F::zero(); t - 1]; t - 1];
+            let mut w = vec![F::zero(); t - 1];
+            let mut v_ = vec![F::zero(); t - 1];
+            v_[..(t - 1)].clone_from_slice(&m_mul[0][1..t]);
+            for row in 1..t {
+                for col in 1..t {
+                    m_hat[row - 1][col - 1] = m_mul[row][col];
+                }
+                w[row - 1] = m_mul[row][0];
+            }
+            // calc_w_hat
+            let m_hat_inv = Self::mat_inverse(&m_hat)?;
+            let w_hat_ = Self::mat_vec_mul(&m_hat_inv, &w);
+
+            w_hat.push(w_hat_);
+            v.push(v_);
+
+            // update m_i
+            m_i = m_mul;
+            m_i[0][0] = F::one();
+            m_i[0][1..].iter_mut().for_each(|el| *el = F::zero());
+            for row in m_i.iter_mut().skip(1) {
+                row[0] = F::zero();
+            }
+            m_mul = Self::mat_mat_mul(&mds_, &m_i);
+        }
+
+        Ok((Self::mat_transpose(&m_i), v, w_hat))
+    }
+
+    fn equivalent_round_constants(
+        round_constants: &RoundConstants<F>,
+        mds: &[Vec<F>],
+        rounds_f_beginning: usize,
+        rounds_p: usize,
+    ) -> Result<Vec<Vec<F>>, Error> {
+        let mut opt = vec![Vec::new(); rounds_p];
+        let mds_inv = Self::mat_inverse(mds)?;
+
+        let p_end = rounds_f_beginning + rounds_p - 1;
+        let mut tmp = round_constants.round(p_end).to_vec();
+        for i in (0..rounds_p - 1).rev() {
+            let inv_cip = Self::mat_vec_mul(&mds_inv, &tmp);
+            opt[i + 1] = vec![inv_cip[0]];
+            tmp.copy_from_slice(round_constants.round(rounds_f_beginning + i));
+            for i in 1..inv_cip.len() {
+                tmp[i].add_assign(&inv_cip[i]);
+            }
+        }
+        opt[0] = tmp;
+
+        Ok(opt)
+    }
+
+    pub(crate) fn mat_vec_mul(mat: &[Vec<F>], input: &[F]) -> Vec<F> {
+        let t = mat.len();
+        debug_assert!(t == input.len());
+        let mut out = vec![F::zero(); t];
+        for (mat, out) in izip!(mat.iter(), out.iter_mut()) {
+            debug_assert_eq!(mat.len(), t);
+            for (mat, inp) in izip!(mat.iter(), input.iter()) {
+                let mut tmp = mat.to_owned();
+                tmp.mul_assign(inp);
+                out.add_assign(tmp);
+            }
+        }
+        out
+    }
+
+    fn mat_mat_mul(mat1: &[Vec<F>], mat2: &[Vec<F>]) -> Vec<Vec<F>> {
+        let t = mat1.len();
+        let mut out = vec![vec![F::zero(); t]; t];
+        for (mat1, out) in izip!(mat1.iter(), out.iter_mut()) {
+            for (col1, out) in out.iter_mut().enumerate() {
+                for (mat1, m2) in izip!(mat1.iter(), mat2.iter()) {
+                    let mut tmp = mat1.to_owned();
+                    tmp.mul_assign(&m2[col1]);
+                    out.add_assign(&tmp);
+                }
+            }
+        }
+        out
+    }
+}
+
+// Checks that the MDS matrix has t rows of length t
+pub(crate) fn check_mds<F: PrimeField>(t: usize, mds: &[Vec<F>]) -> Result<(), Error> {
+    if mds.len() != t {
+        let columns = mds.first().map_or(0, Vec::len);
+        return Err(Error::NonSquareMds {
+            t,
+            got: (mds.len(), columns),
+        });
+    }
+    if let Some(row) = mds.iter().find(|row| row.len() != t) {
+        return Err(Error::NonSquareMds {
+            t,
+            got: (t, row.len()),
+        });
+    }
+    Ok(())
+}
+
+// Checks that there are the given number of rows of round constants, each of length t
+pub(crate) fn check_round_constants<F: PrimeField>(
+    rows: usize,
+    t: usize,
+    round_constants: &[Vec<F>],
+) -> Result<(), Error> {
+    let shape = |len| Error::RoundConstantShape {
+        expected: (rows, t),
+        got: (round_constants.len(), len),
+    };
+    if round_constants.len() != rows {
+        return Err(shape(round_constants.first().map_or(0, Vec::len)));
+    }
+    if let Some(row) = round_constants.iter().find(|row| row.len() != t) {
+        return Err(shape(row.len()));
+    }
+    Ok(())
+}
+
+// Returns the exponent 1 / d mod p - 1 of the inverse of the sbox x^d as little-endian limbs,
+// fails if x^d is not a permutation
+pub(crate) fn inverse_exponent<F: PrimeField>(d: usize) -> Result<Vec<u64>, Error> {
+    let modulus: num_bigint::BigUint = F::MODULUS.into();
+    num_bigint::BigUint::from(d)
+        .modinv(&(modulus - 1u32))
+        .map(|inv| inv.to_u64_digits())
+        .ok_or(Error::InvalidParameters)
+}
+
+#[cfg(test)]
+mod parameters_tests {
+    use super::*;
+    use crate::{
+        bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, field_from_hex_string, poseidon::Poseidon,
+    };
+    use alloc::string::ToString;
+    use std::sync::Arc;
+
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn getters() {
+        let params = &*POSEIDON_CIRCOM_BN_3_PARAMS;
+        assert_eq!(params.t(), 3);
+        assert_eq!(params.d(), 5);
+        assert_eq!(params.rounds_full(), 8);
+        assert_eq!(params.rounds_partial(), 57);
+        assert_eq!(params.rounds(), 65);
+        assert_eq!(params.mds().dim(), 3);
+        assert_eq!(params.round_constants().len(), 65);
+        // the first round constant of circomlib's t = 3 instance
+        assert_eq!(
+            params.round_constants().round(0)[0],
+            field_from_hex_string(
+                "0x0ee9a592ba9a9518d05986d656f40c2114c4993c11bb29938d21d47304cd8e6e"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            params.to_string(),
+            "t = 3, d = 5, R_F = 8, R_P = 57 over a 254-bit field"
+        );
+    }
+
+    #[test]
+    fn shape_errors() {
+        let base = &*POSEIDON_CIRCOM_BN_3_PARAMS;
+        let mds = base.mds.to_vec();
+        let rc = base.round_constants.to_vec();
+        let new = |mds: Vec<Vec<Scalar>>, rc: Vec<Vec<Scalar>>, rounds_f| {
+            PoseidonParams::new(3, 5, rounds_f, 65 - rounds_f, mds, rc)
+        };
+
+        assert!(matches!(
+            new(mds[..2].to_vec(), rc.to_owned(), 8),
+            Err(Error::NonSquareMds { t: 3, got: (2, 3) })
+        ));
+        let mut short_row = mds.to_owned();
+        short_row[1].pop();
+        assert!(matches!(
+            new(short_row, rc.to_owned(), 8),
+            Err(Error::NonSquareMds { t: 3, got: (3, 2) })
+        ));
+        assert!(matches!(
+            new(mds.to_owned(), rc[..64].to_vec(), 8),
+            Err(Error::RoundConstantShape {
+                expected: (65, 3),
+                got: (64, 3)
+            })
+        ));
+        assert!(matches!(
+            new(mds.to_owned(), rc.to_owned(), 7),
+            Err(Error::OddFullRounds(7))
+        ));
+
+        // a zero pivot is resolved by swapping rows
+        let swap = vec![
+            vec![Scalar::from(0), Scalar::from(2), Scalar::from(0)],
+            vec![Scalar::from(3), Scalar::from(0), Scalar::from(0)],
+            vec![Scalar::from(0), Scalar::from(0), Scalar::from(5)],
+        ];
+        let inv = PoseidonParams::mat_inverse(&swap).unwrap();
+        let product = PoseidonParams::mat_mat_mul(&swap, &inv);
+        for (i, row) in product.iter().enumerate() {
+            for (j, el) in row.iter().enumerate() {
+                assert_eq!(*el, Scalar::from((i == j) as u64));
+            }
+        }
+        assert!(matches!(
+            PoseidonParams::mat_inverse(&[
+                vec![Scalar::from(1), Scalar::from(2)],
+                vec![Scalar::from(2), Scalar::from(4)],
+            ]),
+            Err(Error::NonInvertibleSubmatrix)
+        ));
+
+        // the all-ones matrix is singular
+        let singular = vec![vec![Scalar::from(1); 3]; 3];
+        assert!(matches!(
+            new(singular.clone(), rc.clone(), 8),
+            Err(Error::NonInvertibleSubmatrix)
+        ));
+        // without precomputation, the optimized permutation reports it on every call
+        let params = Arc::new(PoseidonParams::new_unoptimized(3, 5, 8, 57, singular, rc).unwrap());
+        let poseidon = Poseidon::new(&params);
+        for _ in 0..2 {
+            assert!(matches!(
+                poseidon.permutation(vec![Scalar::from(0); 3]),
+                Err(Error::NonInvertibleSubmatrix)
+            ));
+        }
+        let mut state = vec![Scalar::from(0); 3];
+        assert!(matches!(
+            poseidon.permutation_shared(&mut state, &mut ()),
+            Err(Error::NonInvertibleSubmatrix)
+        ));
+        #[cfg(feature = "lanes")]
+        assert!(matches!(
+            poseidon.permutation_lanes(&mut [state.clone(), state.clone()]),
+            Err(Error::NonInvertibleSubmatrix)
+        ));
+        assert!(poseidon.permutation_not_opt(state).is_ok());
+    }
+}
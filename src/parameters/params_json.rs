@@ -0,0 +1,82 @@
+//! # Parameter Files
+//! Conversion of parameters from and to the JSON format of the parameter files in `params/`,
+//! which `build.rs` translates to the binary encoding. Besides the statesize `t`, the sbox degree
+//! `d`, and the round numbers `rounds_f` and `rounds_p`, a file contains the MDS matrix `M` and
+//! the round constants `C` (one row per round) as nested arrays of hex strings, zero-padded to
+//! the byte size of the modulus.
+
+use super::{
+    circom_json::{parse_hex_array, parse_json},
+    PoseidonParams,
+};
+use crate::{error::Error, field_to_hex_string};
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use ark_ff::PrimeField;
+use serde_json::{json, Value};
+
+fn parse_hex_matrix<F: PrimeField>(value: &Value) -> Result<Vec<Vec<F>>, Error> {
+    value
+        .as_array()
+        .ok_or(Error::InvalidJson("expected an array".to_owned()))?
+        .iter()
+        .map(parse_hex_array)
+        .collect()
+}
+
+fn parse_usize(value: &Value, key: &str) -> Result<usize, Error> {
+    value[key]
+        .as_u64()
+        .map(|v| v as usize)
+        .ok_or(Error::InvalidJson(alloc::format!("missing {key}")))
+}
+
+impl<F: PrimeField> PoseidonParams<F> {
+    /// Exports the parameters in the format of the parameter files in `params/`.
+    pub fn to_params_json(&self) -> String {
+        let hex_rows = |rows: &mut dyn Iterator<Item = &[F]>| {
+            rows.map(|row| row.iter().map(field_to_hex_string).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+        let value = json!({
+            "t": self.t,
+            "d": self.d,
+            "rounds_f": self.rounds_f_beginning + self.rounds_f_end,
+            "rounds_p": self.rounds_p,
+            "M": hex_rows(&mut self.mds.rows()),
+            "C": hex_rows(&mut self.round_constants.rounds()),
+        });
+        serde_json::to_string_pretty(&value).expect("serializing a JSON value cannot fail")
+    }
+
+    /// Loads parameters from the format of the parameter files in `params/`.
+    pub fn from_params_json(json: &str) -> Result<Self, Error> {
+        let value = parse_json(json)?;
+        let t = parse_usize(&value, "t")?;
+        let d = parse_usize(&value, "d")?;
+        let rounds_f = parse_usize(&value, "rounds_f")?;
+        let rounds_p = parse_usize(&value, "rounds_p")?;
+        let mds = parse_hex_matrix(&value["M"])?;
+        let round_constants = parse_hex_matrix(&value["C"])?;
+        Self::new(t, d, rounds_f, rounds_p, mds, round_constants)
+    }
+}
+
+#[cfg(test)]
+mod params_json_tests {
+    use super::*;
+    use crate::goldilocks::{field::Goldilocks, t8::POSEIDON_GOLDILOCKS_8_PARAMS};
+
+    #[test]
+    fn parameter_file() {
+        let json = include_str!("../../params/goldilocks/t8.json");
+        let params = PoseidonParams::<Goldilocks>::from_params_json(json).unwrap();
+        assert_eq!(params.to_bytes(), POSEIDON_GOLDILOCKS_8_PARAMS.to_bytes());
+        assert_eq!(params.rounds_full(), 8);
+
+        let exported = POSEIDON_GOLDILOCKS_8_PARAMS.to_params_json();
+        let loaded = PoseidonParams::<Goldilocks>::from_params_json(&exported).unwrap();
+        assert_eq!(loaded.to_bytes(), params.to_bytes());
+        assert_eq!(loaded.to_params_json(), exported);
+        assert!(PoseidonParams::<Goldilocks>::from_params_json(r#"{"t": 8}"#).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+//! # Round Constants
+//! Contains the storage of the round constants, which are kept in a single contiguous buffer
+//! and indexed with the statesize as stride.
+
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+
+/// The round constants of all rounds, stored round by round in a flat buffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoundConstants<F: PrimeField> {
+    t: usize,
+    data: Vec<F>,
+}
+
+impl<F: PrimeField> RoundConstants<F> {
+    /// Creates the round constants from one row of t elements per round.
+    pub fn new(t: usize, rounds: Vec<Vec<F>>) -> Self {
+        debug_assert!(rounds.iter().all(|round| round.len() == t));
+        RoundConstants {
+            t,
+            data: rounds.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Returns the number of rounds.
+    pub fn len(&self) -> usize {
+        self.data.len().checked_div(self.t).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the t round constants of round r.
+    pub fn round(&self, r: usize) -> &[F] {
+        &self.data[r * self.t..(r + 1) * self.t]
+    }
+
+    pub fn rounds(&self) -> impl Iterator<Item = &[F]> {
+        // the buffer is empty for t = 0
+        self.data.chunks_exact(self.t.max(1))
+    }
+
+    /// Returns the constants of all rounds as one slice.
+    pub fn as_flat(&self) -> &[F] {
+        &self.data
+    }
+
+    pub fn to_vec(&self) -> Vec<Vec<F>> {
+        self.rounds().map(|round| round.to_vec()).collect()
+    }
+}
+
+#[cfg(test)]
+mod round_constants_tests {
+    use super::*;
+    use alloc::vec;
+
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn indexing() {
+        let rounds: Vec<Vec<Scalar>> = (0..5)
+            .map(|r| (0..3).map(|i| Scalar::from(10 * r + i)).collect())
+            .collect();
+        let rc = RoundConstants::new(3, rounds.to_owned());
+        assert_eq!(rc.len(), 5);
+        assert_eq!(rc.round(2), rounds[2].as_slice());
+        assert_eq!(rc.rounds().count(), 5);
+        assert_eq!(rc.as_flat().len(), 15);
+        assert_eq!(rc.as_flat()[7], Scalar::from(21u64));
+        assert_eq!(rc.to_vec(), rounds);
+
+        let empty = RoundConstants::<Scalar>::new(0, vec![]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert_eq!(empty.rounds().count(), 0);
+    }
+}
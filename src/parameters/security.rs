@@ -0,0 +1,169 @@
+//! # Security Validation
+//! Checks of supplied parameters against the attacks considered by the Poseidon paper and the
+//! matrix conditions of the reference script (`generate_parameters_grain.sage`).
+
+use super::{
+    generate::{check_sbox, log2_modulus, sufficient_rounds},
+    mds, PoseidonParams,
+};
+use crate::error::Error;
+use alloc::{borrow::ToOwned, format, string::String};
+use ark_ff::PrimeField;
+
+// The MDS property of matrices which are not of Cauchy form is verified by computing all square
+// minors, which is only feasible for small dimensions
+const MINORS_MAX_DIM: usize = 8;
+
+impl<F: PrimeField> PoseidonParams<F> {
+    /// Validates the parameters for the given security level in bits. This checks that
+    /// - x^d is a permutation of the field,
+    /// - the round numbers resist the statistical and algebraic attacks of the Poseidon paper
+    ///   (without the additional security margin),
+    /// - the matrix is MDS, which is verified via its Cauchy structure or, for t <= 8, by
+    ///   computing all square minors,
+    /// - the matrix passes the subspace trail algorithms of the reference script, which exclude
+    ///   infinitely long subspace trails with inactive sboxes in the partial rounds, see
+    ///   [`mds::check_subspace_trail_algorithms`].
+    pub fn validate_security(&self, security_bits: usize) -> Result<(), Error> {
+        let t = self.t;
+        let insecure = |reason: String| Err(Error::InsecureParameters(reason));
+
+        if check_sbox::<F>(t, self.d).is_err() {
+            return insecure(format!("x^{} is not a permutation of the field", self.d));
+        }
+        let rounds_f = self.rounds_f_beginning + self.rounds_f_end;
+        let log2_p = log2_modulus::<F>();
+        if !sufficient_rounds(log2_p, t, self.d, security_bits, rounds_f, self.rounds_p) {
+            return insecure(format!(
+                "R_F = {rounds_f} and R_P = {} are insufficient for {security_bits} bits of security",
+                self.rounds_p
+            ));
+        }
+
+        let mds = self.mds.to_vec();
+        if !mds::is_cauchy(&mds) {
+            if t > MINORS_MAX_DIM {
+                return insecure(format!(
+                    "the MDS property of the {t}x{t} matrix cannot be verified"
+                ));
+            }
+            if !mds::is_mds(&mds) {
+                return insecure("the matrix is not MDS".to_owned());
+            }
+        }
+        mds::check_subspace_trail_algorithms(&mds)
+    }
+}
+
+#[cfg(test)]
+mod security_tests {
+    use super::*;
+    use crate::{
+        bn254::{
+            circom_t10::POSEIDON_CIRCOM_BN_10_PARAMS, circom_t11::POSEIDON_CIRCOM_BN_11_PARAMS,
+            circom_t12::POSEIDON_CIRCOM_BN_12_PARAMS, circom_t13::POSEIDON_CIRCOM_BN_13_PARAMS,
+            circom_t14::POSEIDON_CIRCOM_BN_14_PARAMS, circom_t15::POSEIDON_CIRCOM_BN_15_PARAMS,
+            circom_t16::POSEIDON_CIRCOM_BN_16_PARAMS, circom_t17::POSEIDON_CIRCOM_BN_17_PARAMS,
+            circom_t2::POSEIDON_CIRCOM_BN_2_PARAMS, circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+            circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS, circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS,
+            circom_t6::POSEIDON_CIRCOM_BN_6_PARAMS, circom_t7::POSEIDON_CIRCOM_BN_7_PARAMS,
+            circom_t8::POSEIDON_CIRCOM_BN_8_PARAMS, circom_t9::POSEIDON_CIRCOM_BN_9_PARAMS,
+        },
+        goldilocks::{t12::POSEIDON_GOLDILOCKS_12_PARAMS, t8::POSEIDON_GOLDILOCKS_8_PARAMS},
+        grumpkin::{t3::POSEIDON_GRUMPKIN_3_PARAMS, t4::POSEIDON_GRUMPKIN_4_PARAMS},
+        pasta::{fp_t3::POSEIDON_PASTA_FP_3_PARAMS, fq_t3::POSEIDON_PASTA_FQ_3_PARAMS},
+        small_fields::{
+            babybear_t16::POSEIDON_BABYBEAR_16_PARAMS, babybear_t24::POSEIDON_BABYBEAR_24_PARAMS,
+            mersenne31_t16::POSEIDON_MERSENNE31_16_PARAMS,
+            mersenne31_t24::POSEIDON_MERSENNE31_24_PARAMS,
+        },
+    };
+    use alloc::{vec, vec::Vec};
+    use ark_ff::Field;
+
+    type Scalar = ark_bn254::Fr;
+
+    fn params(
+        t: usize,
+        d: usize,
+        rounds_p: usize,
+        mds: Vec<Vec<Scalar>>,
+    ) -> PoseidonParams<Scalar> {
+        let round_constants = vec![vec![Scalar::from(0); t]; 8 + rounds_p];
+        PoseidonParams::new(t, d, 8, rounds_p, mds, round_constants).unwrap()
+    }
+
+    #[test]
+    fn shipped_parameters() {
+        for params in [
+            &*POSEIDON_CIRCOM_BN_2_PARAMS,
+            &POSEIDON_CIRCOM_BN_3_PARAMS,
+            &POSEIDON_CIRCOM_BN_4_PARAMS,
+            &POSEIDON_CIRCOM_BN_5_PARAMS,
+            &POSEIDON_CIRCOM_BN_6_PARAMS,
+            &POSEIDON_CIRCOM_BN_7_PARAMS,
+            &POSEIDON_CIRCOM_BN_8_PARAMS,
+            &POSEIDON_CIRCOM_BN_9_PARAMS,
+            &POSEIDON_CIRCOM_BN_10_PARAMS,
+            &POSEIDON_CIRCOM_BN_11_PARAMS,
+            &POSEIDON_CIRCOM_BN_12_PARAMS,
+            &POSEIDON_CIRCOM_BN_13_PARAMS,
+            &POSEIDON_CIRCOM_BN_14_PARAMS,
+            &POSEIDON_CIRCOM_BN_15_PARAMS,
+            &POSEIDON_CIRCOM_BN_16_PARAMS,
+            &POSEIDON_CIRCOM_BN_17_PARAMS,
+        ] {
+            params.validate_security(128).unwrap();
+        }
+        POSEIDON_PASTA_FP_3_PARAMS.validate_security(128).unwrap();
+        POSEIDON_PASTA_FQ_3_PARAMS.validate_security(128).unwrap();
+        POSEIDON_GRUMPKIN_3_PARAMS.validate_security(128).unwrap();
+        POSEIDON_GRUMPKIN_4_PARAMS.validate_security(128).unwrap();
+        POSEIDON_GOLDILOCKS_8_PARAMS.validate_security(128).unwrap();
+        POSEIDON_GOLDILOCKS_12_PARAMS
+            .validate_security(128)
+            .unwrap();
+        POSEIDON_BABYBEAR_16_PARAMS.validate_security(128).unwrap();
+        POSEIDON_BABYBEAR_24_PARAMS.validate_security(128).unwrap();
+        POSEIDON_MERSENNE31_16_PARAMS
+            .validate_security(128)
+            .unwrap();
+        POSEIDON_MERSENNE31_24_PARAMS
+            .validate_security(128)
+            .unwrap();
+    }
+
+    #[test]
+    fn insecure_parameters() {
+        let circom = &*POSEIDON_CIRCOM_BN_3_PARAMS;
+        let mds = circom.mds.to_vec();
+
+        // too few partial rounds
+        let few_rounds = params(3, 5, 10, mds.to_owned());
+        assert!(matches!(
+            few_rounds.validate_security(128),
+            Err(Error::InsecureParameters(_))
+        ));
+
+        // x^3 is not a permutation of the BN254 scalar field
+        assert!(params(3, 3, 57, mds.to_owned())
+            .validate_security(128)
+            .is_err());
+
+        // not MDS since it contains zeros
+        let mut zero_entry = mds.to_owned();
+        zero_entry[0][1] = Scalar::from(0);
+        assert!(params(3, 5, 57, zero_entry).validate_security(128).is_err());
+
+        // MDS, but for n = -1/2 the square of [[1, 2], [n, 1]] is [[0, 4], [-1, 0]], hence
+        // M^4 = -4 * I and e_0 does not generate the space under M^4
+        let n = -Scalar::from(2).inverse().unwrap();
+        let periodic = vec![
+            vec![Scalar::from(1), Scalar::from(2)],
+            vec![n, Scalar::from(1)],
+        ];
+        assert!(mds::is_mds(&periodic));
+        let err = params(2, 5, 56, periodic).validate_security(128);
+        assert!(matches!(err, Err(Error::InsecureParameters(reason)) if reason.contains("M^4")));
+    }
+}
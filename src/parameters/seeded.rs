@@ -0,0 +1,197 @@
+//! # Seeded Parameter Generation
+//! Derivation of round constants and MDS matrices from a user-chosen domain string with SHA-256
+//! or Blake2s-256, as a nothing-up-my-sleeve alternative to the Grain LFSR of
+//! [`generate`](super::generate), which can be reproduced from this specification alone:
+//!
+//! 1. The prefix is the concatenation of the length of the domain string, the domain string,
+//!    and the bit size of the modulus, t, d, R_F, and R_P, where all integers are encoded as
+//!    little-endian u64.
+//! 2. The byte stream is the concatenation of the blocks `H(prefix || i)` for i = 0, 1, 2, ...,
+//!    where i is encoded as little-endian u64.
+//! 3. A field element is sampled by taking the next `ceil(bits / 8)` bytes of the stream as
+//!    big-endian integer, clearing all bits above the bit size of the modulus, and starting
+//!    over if the result is not smaller than the modulus.
+//! 4. The round constants are sampled first, round by round. Afterwards, the MDS matrix is the
+//!    Cauchy matrix `M[i][j] = 1 / (x_i + y_j)` with x_0, ..., x_{t-1}, y_0, ..., y_{t-1}
+//!    sampled next. If the 2t values are not pairwise distinct, all of them are sampled again,
+//!    and if some x_i + y_j is zero or the matrix fails the subspace trail algorithms of the
+//!    reference script
+//!    ([`check_subspace_trail_algorithms`](super::mds::check_subspace_trail_algorithms)), the
+//!    matrix is sampled again.
+
+use super::{
+    generate::{cauchy_matrix, check_sbox, round_numbers},
+    PoseidonParams,
+};
+use crate::error::Error;
+use ark_ff::PrimeField;
+use blake2::Blake2s256;
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+use std::{collections::VecDeque, marker::PhantomData, vec::Vec};
+
+/// The hash function expanding the domain string.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SeedHash {
+    #[default]
+    Sha256,
+    Blake2s256,
+}
+
+struct Stream<D: Digest> {
+    prefix: Vec<u8>,
+    counter: u64,
+    buffer: VecDeque<u8>,
+    hasher: PhantomData<D>,
+}
+
+impl<D: Digest> Stream<D> {
+    fn new(prefix: Vec<u8>) -> Self {
+        Stream {
+            prefix,
+            counter: 0,
+            buffer: VecDeque::new(),
+            hasher: PhantomData,
+        }
+    }
+
+    fn next_byte(&mut self) -> u8 {
+        if self.buffer.is_empty() {
+            let block = D::new()
+                .chain_update(&self.prefix)
+                .chain_update(self.counter.to_le_bytes())
+                .finalize();
+            self.buffer.extend(block);
+            self.counter += 1;
+        }
+        self.buffer.pop_front().expect("the buffer was refilled")
+    }
+
+    fn field_element<F: PrimeField>(&mut self) -> F {
+        let modulus: BigUint = F::MODULUS.into();
+        let bits = F::MODULUS_BIT_SIZE as usize;
+        loop {
+            let mut bytes: Vec<u8> = (0..bits.div_ceil(8)).map(|_| self.next_byte()).collect();
+            if bits % 8 != 0 {
+                bytes[0] &= (1 << (bits % 8)) - 1;
+            }
+            let candidate = BigUint::from_bytes_be(&bytes);
+            if candidate < modulus {
+                return candidate.into();
+            }
+        }
+    }
+}
+
+fn generate_from_stream<F: PrimeField, D: Digest>(
+    domain: &str,
+    t: usize,
+    d: usize,
+    rounds_f: usize,
+    rounds_p: usize,
+) -> Result<PoseidonParams<F>, Error> {
+    let mut prefix = Vec::new();
+    prefix.extend_from_slice(&(domain.len() as u64).to_le_bytes());
+    prefix.extend_from_slice(domain.as_bytes());
+    for value in [F::MODULUS_BIT_SIZE as usize, t, d, rounds_f, rounds_p] {
+        prefix.extend_from_slice(&(value as u64).to_le_bytes());
+    }
+    let mut stream = Stream::<D>::new(prefix);
+
+    let round_constants = (0..rounds_f + rounds_p)
+        .map(|_| (0..t).map(|_| stream.field_element()).collect())
+        .collect();
+    let mds = cauchy_matrix(|| stream.field_element(), t);
+
+    PoseidonParams::new(t, d, rounds_f, rounds_p, mds, round_constants)
+}
+
+/// Generates Poseidon parameters for the field `F` with statesize `t`, sbox degree `d`, and the
+/// round numbers derived from the given security level in bits (see
+/// [`generate`](super::generate::generate)), where the round constants and the MDS matrix are
+/// derived from the domain string as described in the [module](self) documentation.
+pub fn generate_seeded<F: PrimeField>(
+    hash: SeedHash,
+    domain: &str,
+    t: usize,
+    d: usize,
+    security_level: usize,
+) -> Result<PoseidonParams<F>, Error> {
+    check_sbox::<F>(t, d)?;
+    let (rounds_f, rounds_p) = round_numbers::<F>(t, d, security_level);
+    generate_seeded_with_rounds(hash, domain, t, d, rounds_f, rounds_p)
+}
+
+/// Generates Poseidon parameters like [`generate_seeded`], but with explicitly given round
+/// numbers.
+pub fn generate_seeded_with_rounds<F: PrimeField>(
+    hash: SeedHash,
+    domain: &str,
+    t: usize,
+    d: usize,
+    rounds_f: usize,
+    rounds_p: usize,
+) -> Result<PoseidonParams<F>, Error> {
+    check_sbox::<F>(t, d)?;
+    match hash {
+        SeedHash::Sha256 => generate_from_stream::<F, Sha256>(domain, t, d, rounds_f, rounds_p),
+        SeedHash::Blake2s256 => {
+            generate_from_stream::<F, Blake2s256>(domain, t, d, rounds_f, rounds_p)
+        }
+    }
+}
+
+#[cfg(test)]
+mod seeded_tests {
+    use super::*;
+    use crate::{field_from_str, goldilocks::field::Goldilocks};
+
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn reproducible() {
+        let params =
+            generate_seeded::<Scalar>(SeedHash::Sha256, "poseidon-rust", 3, 5, 128).unwrap();
+        assert_eq!(params.rounds_full(), 8);
+        assert_eq!(params.rounds_partial(), 56);
+        params.validate_security(128).unwrap();
+        let again =
+            generate_seeded_with_rounds::<Scalar>(SeedHash::Sha256, "poseidon-rust", 3, 5, 8, 56)
+                .unwrap();
+        assert_eq!(params.mds, again.mds);
+        assert_eq!(params.round_constants, again.round_constants);
+        // the first round constant and the first MDS entry as specified in the module documentation
+        assert_eq!(
+            params.round_constants.as_flat()[0],
+            field_from_str(
+                "3212312926657373000050128721020838781410376742394189013264127268144712524197"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            params.mds.rows().next().unwrap()[0],
+            field_from_str(
+                "7086165528715255529098257689722270793092771144588164981231767003253953754302"
+            )
+            .unwrap()
+        );
+
+        let other_domain =
+            generate_seeded::<Scalar>(SeedHash::Sha256, "poseidon-rust ", 3, 5, 128).unwrap();
+        let blake2 =
+            generate_seeded::<Scalar>(SeedHash::Blake2s256, "poseidon-rust", 3, 5, 128).unwrap();
+        assert_ne!(params.round_constants, other_domain.round_constants);
+        assert_ne!(params.round_constants, blake2.round_constants);
+        assert_ne!(params.mds, blake2.mds);
+    }
+
+    #[test]
+    fn small_field() {
+        let params =
+            generate_seeded::<Goldilocks>(SeedHash::Blake2s256, "goldilocks", 12, 7, 128).unwrap();
+        assert_eq!(params.t(), 12);
+        assert_eq!(params.mds().to_vec().len(), 12);
+        params.validate_security(128).unwrap();
+        assert!(generate_seeded::<Goldilocks>(SeedHash::Sha256, "goldilocks", 12, 5, 128).is_err());
+    }
+}
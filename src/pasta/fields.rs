@@ -0,0 +1,22 @@
+//! # Pasta Fields
+//! Contains the base fields of the Pallas and Vesta curves. The base field of each curve is the
+//! scalar field of the other one.
+
+// The MontConfig derive macro of ark-ff 0.4 implements the trait inside a function
+#![allow(non_local_definitions)]
+
+use ark_ff::fields::{Fp256, MontBackend, MontConfig};
+
+#[derive(MontConfig)]
+#[modulus = "28948022309329048855892746252171976963363056481941560715954676764349967630337"]
+#[generator = "5"]
+pub struct PallasBaseConfig;
+/// The base field of Pallas and the scalar field of Vesta.
+pub type Fp = Fp256<MontBackend<PallasBaseConfig, 4>>;
+
+#[derive(MontConfig)]
+#[modulus = "28948022309329048855892746252171976963363056481941647379679742748393362948097"]
+#[generator = "5"]
+pub struct VestaBaseConfig;
+/// The base field of Vesta and the scalar field of Pallas.
+pub type Fq = Fp256<MontBackend<VestaBaseConfig, 4>>;
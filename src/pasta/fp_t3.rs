@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2]) = [0x2a526acd0b64b45394efb364f966240ff7e69a71d0b642a0aeb1bc024aeca456, 0x13c5d1568b4aa43076ff7dae343d5512dcd42e7fbed9dafe012a3e9628e5b82a, 0x0a49c868c6976544256fcd597984561af7cfdfe1bda42c7b359029a1d34e9ddd];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::pasta::fields::Fp;
+
+lazy_static! {
+    pub static ref POSEIDON_PASTA_FP_3_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            3,
+            5,
+            8,
+            56,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/pasta/fp_t3.bin")),
+        )
+        .unwrap()
+    );
+}
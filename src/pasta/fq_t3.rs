@@ -0,0 +1,21 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2]) = [0x315a1f4cdb942f7ceddd74f22f8f2ff74d43d1973dd336c60eb08ea813bebe59, 0x3be475f2d7642bde642adee0dd13aa48413ee0eb7bbd2198f9f126e61ea165f1, 0x25ab8aece9537168117fdb2420d8ea605019bfd4e0423fa014d542372a7ba0d9];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::pasta::fields::Fq;
+
+lazy_static! {
+    pub static ref POSEIDON_PASTA_FQ_3_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            3,
+            5,
+            8,
+            56,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/pasta/fq_t3.bin")),
+        )
+        .unwrap()
+    );
+}
@@ -0,0 +1,67 @@
+//! # Pasta
+//! Contains Poseidon parameters for the base fields of the Pallas and Vesta curves. They are the
+//! `P128Pow5T3` instances of halo2's Poseidon primitives (t = 3, x^5, R_F = 8, R_P = 56), i.e.,
+//! the instances generated by the Grain LFSR of the Poseidon paper for these fields.
+
+pub mod fields;
+pub mod fp_t3;
+pub mod fq_t3;
+
+#[cfg(test)]
+mod pasta_tests {
+    use super::{
+        fields::{Fp, Fq},
+        fp_t3::POSEIDON_PASTA_FP_3_PARAMS,
+        fq_t3::POSEIDON_PASTA_FQ_3_PARAMS,
+    };
+    use crate::{field_from_hex_string, poseidon::Poseidon};
+    use ark_ff::PrimeField;
+
+    fn check_kat<F: PrimeField>(poseidon: &Poseidon<F>, expected: [&str; 3]) {
+        let input = vec![F::from(0u64), F::from(1u64), F::from(2u64)];
+        let perm = poseidon.permutation(input.to_owned()).unwrap();
+        let expected: Vec<F> = expected
+            .iter()
+            .map(|e| field_from_hex_string(e).unwrap())
+            .collect();
+        assert_eq!(perm, expected);
+        assert_eq!(poseidon.permutation_not_opt(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn kats() {
+        check_kat::<Fp>(
+            &Poseidon::new(&POSEIDON_PASTA_FP_3_PARAMS),
+            [
+                "0x2a526acd0b64b45394efb364f966240ff7e69a71d0b642a0aeb1bc024aeca456",
+                "0x13c5d1568b4aa43076ff7dae343d5512dcd42e7fbed9dafe012a3e9628e5b82a",
+                "0x0a49c868c6976544256fcd597984561af7cfdfe1bda42c7b359029a1d34e9ddd",
+            ],
+        );
+        check_kat::<Fq>(
+            &Poseidon::new(&POSEIDON_PASTA_FQ_3_PARAMS),
+            [
+                "0x315a1f4cdb942f7ceddd74f22f8f2ff74d43d1973dd336c60eb08ea813bebe59",
+                "0x3be475f2d7642bde642adee0dd13aa48413ee0eb7bbd2198f9f126e61ea165f1",
+                "0x25ab8aece9537168117fdb2420d8ea605019bfd4e0423fa014d542372a7ba0d9",
+            ],
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn matches_generation() {
+        use crate::parameters::generate::generate_with_rounds;
+
+        let fp = generate_with_rounds::<Fp>(3, 5, 8, 56).unwrap();
+        assert_eq!(
+            fp.to_circomlib_json(),
+            POSEIDON_PASTA_FP_3_PARAMS.to_circomlib_json()
+        );
+        let fq = generate_with_rounds::<Fq>(3, 5, 8, 56).unwrap();
+        assert_eq!(
+            fq.to_circomlib_json(),
+            POSEIDON_PASTA_FQ_3_PARAMS.to_circomlib_json()
+        );
+    }
+}
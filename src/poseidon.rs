@@ -1,8 +1,50 @@
-use crate::{error::Error, parameters::PoseidonParams};
+use crate::{
+    error::Error,
+    field_hasher::FieldHasher,
+    mpc::PoseidonField,
+    parameters::{OptimizedParams, PoseidonParams},
+};
+use alloc::{borrow::ToOwned, sync::Arc, vec, vec::Vec};
 use ark_ff::PrimeField;
+#[cfg(feature = "ark-serialize")]
+use ark_serialize::CanonicalSerialize;
 use itertools::izip;
-use std::sync::Arc;
 
+/// Computes the sbox x^d, using specialized addition chains for the degrees 3, 5, and 7.
+pub fn pow_sbox<F: PrimeField>(input: F, d: usize) -> F {
+    match d {
+        3 => {
+            let input2 = input.square();
+            let mut out = input2;
+            out.mul_assign(&input);
+            out
+        }
+        5 => {
+            let input2 = input.square();
+            let mut out = input2.square();
+            out.mul_assign(&input);
+            out
+        }
+        7 => {
+            let input2 = input.square();
+            let mut out = input2.square();
+            out.mul_assign(&input2);
+            out.mul_assign(&input);
+            out
+        }
+        _ => input.pow([d as u64]),
+    }
+}
+
+/// The Poseidon permutation.
+///
+/// Each round consists of adding the round constants, applying the sbox (to all state elements
+/// in full rounds and to the first element in partial rounds), and multiplying with the MDS
+/// matrix, in this order. This is the ordering of both circomlib and the reference
+/// implementation of the Poseidon paper (`poseidonperm_x5_254_3.sage` in the hadeshash
+/// repository), so there is no separate convention to select: for the same constants both
+/// produce identical outputs, and the known-answer tests in this crate are the ones emitted by
+/// the reference implementation (see the header of the generated parameter files).
 #[derive(Clone, Debug)]
 pub struct Poseidon<F: PrimeField> {
     pub(crate) params: Arc<PoseidonParams<F>>,
@@ -18,109 +60,375 @@ impl<F: PrimeField> Poseidon<F> {
         self.params.t
     }
 
+    /// Applies the permutation by driving the
+    /// [`PermutationDriver`](crate::driver::PermutationDriver) with local sbox evaluations.
     pub fn permutation(&self, input: Vec<F>) -> Result<Vec<F>, Error> {
+        let mut driver = self.driver(input)?;
+        while driver.next_linear_layer(&())? {
+            driver.apply_sbox(&mut ())?;
+        }
+        driver.finish()
+    }
+
+    /// Applies the permutation to the given state in place. Besides a single scratch buffer of
+    /// size t, which is reused in all rounds, no memory is allocated.
+    pub fn permutation_in_place(&self, state: &mut [F]) -> Result<(), Error> {
         let t = self.params.t;
-        if input.len() != t {
-            return Err(Error::InvalidParameters);
+        if state.len() != t {
+            return Err(Error::WrongInputLength {
+                expected: t,
+                got: state.len(),
+            });
         }
-        let mut current_state = input;
-        for r in 0..self.params.rounds_f_beginning {
-            self.add_rc(&mut current_state, &self.params.round_constants[r]);
-            self.sbox(&mut current_state);
-            current_state = PoseidonParams::mat_vec_mul(&self.params.mds, &current_state);
+        let mut scratch = vec![F::zero(); t];
+        self.permute_generic(state, &mut scratch, &mut ())
+    }
+
+    /// Applies the permutation to every state of a buffer of consecutive states, where the i-th
+    /// state consists of the first t elements of `buf[i * stride..]` and the remaining stride - t
+    /// elements are left untouched. The states are permuted where they are, e.g., a whole Merkle
+    /// layer can be laid out in one buffer and hashed without copying each state. The last chunk
+    /// may be shorter than the stride but has to hold a full state.
+    pub fn permute_strided(&self, buf: &mut [F], stride: usize) -> Result<(), Error> {
+        let t = self.params.t;
+        if stride < t {
+            return Err(Error::WrongInputLength {
+                expected: t,
+                got: stride,
+            });
         }
-        let p_end = self.params.rounds_f_beginning + self.params.rounds_p;
-        self.add_rc(&mut current_state, &self.params.opt_round_constants[0]);
-        current_state = PoseidonParams::mat_vec_mul(&self.params.m_i, &current_state);
-        for r in self.params.rounds_f_beginning..p_end {
-            current_state[0] = self.sbox_p(&current_state[0]);
-            if r < p_end - 1 {
-                current_state[0].add_assign(
-                    &self.params.opt_round_constants[r + 1 - self.params.rounds_f_beginning][0],
-                );
+        let mut scratch = vec![F::zero(); t];
+        for chunk in buf.chunks_mut(stride) {
+            if chunk.len() < t {
+                return Err(Error::WrongInputLength {
+                    expected: t,
+                    got: chunk.len(),
+                });
             }
-            current_state = self.cheap_matmul(&current_state, p_end - r - 1);
+            self.permute_generic(&mut chunk[..t], &mut scratch, &mut ())?;
         }
-        for r in p_end..self.params.rounds {
-            self.add_rc(&mut current_state, &self.params.round_constants[r]);
-            self.sbox(&mut current_state);
-            current_state = PoseidonParams::mat_vec_mul(&self.params.mds, &current_state);
+        Ok(())
+    }
+
+    /// Applies the permutation like [`Self::permutation`] and overwrites the intermediate
+    /// buffer with zeros afterwards, such that only the returned state holds values derived from
+    /// the input.
+    #[cfg(feature = "zeroize")]
+    pub fn permutation_zeroizing(&self, input: Vec<F>) -> Result<Vec<F>, Error> {
+        use zeroize::Zeroize;
+
+        let mut state = input;
+        let t = self.params.t;
+        if state.len() != t {
+            let got = state.len();
+            state.zeroize();
+            return Err(Error::WrongInputLength { expected: t, got });
         }
-        Ok(current_state)
+        let mut scratch = vec![F::zero(); t];
+        let result = self.permute_generic(&mut state, &mut scratch, &mut ());
+        scratch.zeroize();
+        if let Err(err) = result {
+            state.zeroize();
+            return Err(err);
+        }
+        Ok(state)
+    }
+
+    /// The round core shared by all permutations over the optimized representation, i.e., the
+    /// plain one, [`Self::permutation_shared`], and the lanes. Both slices must have length t.
+    pub(crate) fn permute_generic<T: PoseidonField<F>>(
+        &self,
+        state: &mut [T],
+        scratch: &mut [T],
+        ctx: &mut T::Context,
+    ) -> Result<(), Error> {
+        for r in 0..self.params.rounds {
+            self.linear_layer(state, scratch, r, ctx)?;
+            let len = if self.is_full_round(r) {
+                state.len()
+            } else {
+                1
+            };
+            T::pow_batch(&mut state[..len], self.params.d, ctx)?;
+        }
+        self.linear_layer(state, scratch, self.params.rounds, ctx)
+    }
+
+    pub(crate) fn is_full_round(&self, r: usize) -> bool {
+        let params = &self.params;
+        params.rounds_p == 0
+            || r < params.rounds_f_beginning
+            || r >= params.rounds_f_beginning + params.rounds_p
+    }
+
+    /// Applies the linear operations between the sbox layers of round r - 1 and round r, i.e.,
+    /// the linear layer of round r - 1 (if r > 0) followed by the round constants of round r (if
+    /// r < rounds). The partial rounds use the optimized representation, where the round
+    /// constants are moved into the first element and the MDS matrix is split into the dense
+    /// matrix m_i, applied before the first partial round, and sparse matrices. Only additions
+    /// and public constants are used, see [`PoseidonField`]. Fails if the optimized
+    /// representation cannot be computed.
+    pub(crate) fn linear_layer<T: PoseidonField<F>>(
+        &self,
+        state: &mut [T],
+        scratch: &mut [T],
+        r: usize,
+        ctx: &T::Context,
+    ) -> Result<(), Error> {
+        let params = &self.params;
+        let p_end = params.rounds_f_beginning + params.rounds_p;
+        if r > 0 {
+            if self.is_full_round(r - 1) {
+                T::mat_vec_mul(&params.mds, state, scratch);
+                state.swap_with_slice(scratch);
+            } else {
+                let optimized = params.optimized()?;
+                if r < p_end {
+                    let rc = &optimized.round_constants[r - params.rounds_f_beginning][0];
+                    state[0] = state[0].add_public(rc, ctx);
+                }
+                self.cheap_matmul(optimized, state, p_end - r);
+            }
+        }
+        if r < params.rounds {
+            if self.is_full_round(r) {
+                add_public(state, params.round_constants.round(r), ctx);
+            } else if r == params.rounds_f_beginning {
+                let optimized = params.optimized()?;
+                add_public(state, &optimized.round_constants[0], ctx);
+                T::mat_vec_mul(&optimized.m_i, state, scratch);
+                state.swap_with_slice(scratch);
+            }
+        }
+        Ok(())
     }
 
     pub fn permutation_not_opt(&self, input: Vec<F>) -> Result<Vec<F>, Error> {
         let t = self.params.t;
         if input.len() != t {
-            return Err(Error::InvalidParameters);
+            return Err(Error::WrongInputLength {
+                expected: t,
+                got: input.len(),
+            });
         }
         let mut current_state = input;
         for r in 0..self.params.rounds_f_beginning {
-            self.add_rc(&mut current_state, &self.params.round_constants[r]);
+            self.add_rc(&mut current_state, self.params.round_constants.round(r));
             self.sbox(&mut current_state);
-            current_state = PoseidonParams::mat_vec_mul(&self.params.mds, &current_state);
+            current_state = self.params.mds.mat_vec_mul(&current_state);
         }
         let p_end = self.params.rounds_f_beginning + self.params.rounds_p;
         for r in self.params.rounds_f_beginning..p_end {
-            self.add_rc(&mut current_state, &self.params.round_constants[r]);
+            self.add_rc(&mut current_state, self.params.round_constants.round(r));
             current_state[0] = self.sbox_p(&current_state[0]);
-            current_state = PoseidonParams::mat_vec_mul(&self.params.mds, &current_state);
+            current_state = self.params.mds.mat_vec_mul(&current_state);
         }
         for r in p_end..self.params.rounds {
-            self.add_rc(&mut current_state, &self.params.round_constants[r]);
+            self.add_rc(&mut current_state, self.params.round_constants.round(r));
             self.sbox(&mut current_state);
-            current_state = PoseidonParams::mat_vec_mul(&self.params.mds, &current_state);
+            current_state = self.params.mds.mat_vec_mul(&current_state);
         }
         Ok(current_state)
     }
 
+    /// Hashes a list of variable-length segments into a single field element.
+    ///
+    /// The separation scheme is as follows: the capacity element (index 0) is initialized with
+    /// the number of segments. Afterwards, the elements of each segment followed by the length
+    /// of the segment are added to the rate part of the state (indices 1..t), where the
+    /// permutation is applied each time the rate is full. If a partial block remains at the end,
+    /// it is zero-padded and permuted. The output is the first element of the final state.
+    /// Appending each length allows to uniquely split the absorbed stream into its segments, e.g.,
+    /// `[[a], [b]]` and `[[a, b], []]` are absorbed as `a, 1, b, 1` and `a, b, 2, 0`.
+    pub fn hash_segments(&self, segments: &[&[F]]) -> Result<F, Error> {
+        self.hash_segments_with_domain(F::zero(), segments)
+    }
+
+    /// Hashes a list of variable-length segments like [`Self::hash_segments`], but additionally
+    /// encodes the given domain separator (see [`Self::domain_separator`]) into the capacity
+    /// element as `num_segments + domain * 2^64`. The domain zero yields [`Self::hash_segments`].
+    pub fn hash_segments_with_domain(&self, domain: F, segments: &[&[F]]) -> Result<F, Error> {
+        hash_segments_with(self, domain, segments)
+    }
+
+    /// Hashes an arbitrary byte string into a single field element. The bytes are split into
+    /// chunks of `(MODULUS_BIT_SIZE - 1) / 8` bytes, each interpreted as a little-endian
+    /// integer (and thus always smaller than the modulus), and hashed with
+    /// [`Self::hash_segments`] as the two segments `[chunks, [byte length]]`.
+    pub fn hash_bytes(&self, bytes: &[u8]) -> Result<F, Error> {
+        hash_bytes_with(self, bytes)
+    }
+
+    /// Derives the domain separator for a tag as [`Self::hash_bytes`] of its UTF-8 bytes, such
+    /// that different protocols using the same instance can separate their hashes.
+    pub fn domain_separator(&self, tag: &str) -> Result<F, Error> {
+        self.hash_bytes(tag.as_bytes())
+    }
+
+    /// Hashes a list of arkworks-serializable items by concatenating their compressed canonical
+    /// serializations and hashing the result with [`Self::hash_bytes`]. Note that this hashes
+    /// the byte representation, so for field elements the result differs from hashing them
+    /// directly as field elements.
+    #[cfg(feature = "ark-serialize")]
+    pub fn hash_canonical<T: CanonicalSerialize>(&self, items: &[T]) -> Result<F, Error> {
+        use alloc::string::ToString;
+
+        let mut bytes = Vec::new();
+        for item in items {
+            item.serialize_compressed(&mut bytes)
+                .map_err(|e| Error::Other(e.to_string()))?;
+        }
+        self.hash_bytes(&bytes)
+    }
+
+    /// Hashes up to t - 1 inputs with a single permutation. The state consists of the capacity
+    /// element set to the value of the given [`DomainTag`], followed by the inputs and
+    /// zero-padding. The output is the first element of the permuted state, so the
+    /// [`DomainTag::Zero`] tag with t - 1 inputs reproduces the circom layout.
+    pub fn hash_with_tag(&self, tag: DomainTag<F>, inputs: &[F]) -> Result<F, Error> {
+        Ok(self.tagged_permutation(tag, inputs)?[0])
+    }
+
+    /// Hashes up to t - 1 inputs like neptune's `Poseidon::hash`: the state is laid out as for
+    /// [`Self::hash_with_tag`], but the output is the second element of the permuted state. With
+    /// the parameters in [`bls12_381`](crate::bls12_381) and [`DomainTag::MerkleTree`], this
+    /// reproduces the nodes of Filecoin's Merkle trees, and [`DomainTag::ConstantLength`]
+    /// reproduces neptune's `HashType::ConstantLength` for up to t - 1 inputs.
+    pub fn hash_neptune(&self, tag: DomainTag<F>, inputs: &[F]) -> Result<F, Error> {
+        Ok(self.tagged_permutation(tag, inputs)?[1])
+    }
+
+    fn tagged_permutation(&self, tag: DomainTag<F>, inputs: &[F]) -> Result<Vec<F>, Error> {
+        let t = self.params.t;
+        if inputs.len() >= t {
+            return Err(Error::TooManyInputs {
+                max: t - 1,
+                got: inputs.len(),
+            });
+        }
+        let mut state = vec![F::zero(); t];
+        state[0] = tag.value(t, inputs.len());
+        state[1..=inputs.len()].copy_from_slice(inputs);
+        self.permutation_in_place(&mut state)?;
+        Ok(state)
+    }
+
+    /// Hashes exactly t - 1 inputs with the circom layout: the capacity element is set to zero,
+    /// followed by the inputs, and the output is the first element of the permuted state.
+    pub fn hash(&self, inputs: &[F]) -> Result<F, Error> {
+        if inputs.len() + 1 != self.params.t {
+            return Err(Error::WrongInputLength {
+                expected: self.params.t - 1,
+                got: inputs.len(),
+            });
+        }
+        self.hash_with_tag(DomainTag::Zero, inputs)
+    }
+
+    /// Hashes up to t - 1 inputs with the circom layout and returns n_out elements. The outputs
+    /// are the elements of the permuted state in order, if more than t are requested, the state
+    /// is permuted again. Thus, `hash_to_n(inputs, 1)` equals [`Self::hash`], and for n_out <= t
+    /// the result matches circomlib's `PoseidonEx` with nOuts = n_out and an initial state of
+    /// zero.
+    pub fn hash_to_n(&self, inputs: &[F], n_out: usize) -> Result<Vec<F>, Error> {
+        let t = self.params.t;
+        if inputs.len() >= t {
+            return Err(Error::TooManyInputs {
+                max: t - 1,
+                got: inputs.len(),
+            });
+        }
+        let mut state = vec![F::zero(); t];
+        state[1..=inputs.len()].copy_from_slice(inputs);
+        let mut output = Vec::with_capacity(n_out);
+        while output.len() < n_out {
+            self.permutation_in_place(&mut state)?;
+            let remaining = n_out - output.len();
+            output.extend_from_slice(&state[..remaining.min(t)]);
+        }
+        Ok(output)
+    }
+
+    /// Hashes a batch of inputs of RATE = t - 1 elements each with the circom layout (zero
+    /// capacity element, first element of the permuted state as output), e.g., the leaves of a
+    /// large tree. With the `lanes` feature the states are permuted in lanes. Fails with
+    /// [`Error::WrongBatchRate`] if RATE is not t - 1.
+    pub fn hash_many<const RATE: usize>(&self, inputs: &[[F; RATE]]) -> Result<Vec<F>, Error> {
+        let t = self.params.t;
+        if RATE + 1 != t {
+            return Err(Error::WrongBatchRate {
+                expected: t - 1,
+                got: RATE,
+            });
+        }
+        let mut states: Vec<Vec<F>> = inputs
+            .iter()
+            .map(|input| {
+                let mut state = Vec::with_capacity(t);
+                state.push(F::zero());
+                state.extend_from_slice(input);
+                state
+            })
+            .collect();
+        #[cfg(feature = "lanes")]
+        self.permutation_many(&mut states)?;
+        #[cfg(not(feature = "lanes"))]
+        for state in states.iter_mut() {
+            self.permutation_in_place(state)?;
+        }
+        Ok(states.into_iter().map(|state| state[0]).collect())
+    }
+
+    /// Compresses two field elements into one using the circom layout, i.e., the capacity
+    /// element is set to zero and the first element of the permuted state is returned.
+    /// Requires a parameter set with statesize t = 3.
+    pub fn compress(&self, left: F, right: F) -> Result<F, Error> {
+        if self.params.t != 3 {
+            return Err(Error::WrongStatesize {
+                expected: 3,
+                got: self.params.t,
+            });
+        }
+        self.hash(&[left, right])
+    }
+
+    /// Returns the hashes of all-zero subtrees for the levels 0..=depth, where level 0 is the
+    /// zero leaf and level i is the compression of two level i-1 subtrees.
+    pub fn zero_hashes(&self, depth: usize) -> Result<Vec<F>, Error> {
+        let mut zeros = Vec::with_capacity(depth + 1);
+        zeros.push(F::zero());
+        for i in 0..depth {
+            zeros.push(self.compress(zeros[i], zeros[i])?);
+        }
+        Ok(zeros)
+    }
+
     fn sbox(&self, input: &mut [F]) {
         input.iter_mut().for_each(|el| *el = self.sbox_p(el));
     }
 
     fn sbox_p(&self, input: &F) -> F {
-        match self.params.d {
-            3 => {
-                let input2 = input.square();
-                let mut out = input2;
-                out.mul_assign(input);
-                out
-            }
-            5 => {
-                let input2 = input.square();
-                let mut out = input2.square();
-                out.mul_assign(input);
-                out
-            }
-            7 => {
-                let input2 = input.square();
-                let mut out = input2.square();
-                out.mul_assign(&input2);
-                out.mul_assign(input);
-                out
-            }
-            _ => input.pow([self.params.d as u64]),
-        }
+        pow_sbox(*input, self.params.d)
     }
 
-    fn cheap_matmul(&self, input: &[F], r: usize) -> Vec<F> {
-        let v = &self.params.v[r];
-        let w_hat = &self.params.w_hat[r];
-        let t = self.params.t;
-        let mut new_state = vec![F::zero(); t];
-        new_state[0] = self.params.mds[0][0];
-        new_state[0].mul_assign(&input[0]);
-        for (inp, w) in izip!(input.iter().skip(1), w_hat.iter()) {
-            let mut tmp = w.to_owned();
-            tmp.mul_assign(inp);
-            new_state[0].add_assign(&tmp);
+    // the sparse matrix of a partial round
+    fn cheap_matmul<T: PoseidonField<F>>(
+        &self,
+        optimized: &OptimizedParams<F>,
+        state: &mut [T],
+        r: usize,
+    ) {
+        let v = &optimized.v[r];
+        let w_hat = &optimized.w_hat[r];
+        let first = state[0].clone();
+        let mut new_first = first.mul_public(self.params.mds.get(0, 0));
+        for (inp, w) in izip!(state.iter().skip(1), w_hat.iter()) {
+            new_first = new_first.add(&inp.mul_public(w));
         }
-        for (n, inp, v) in izip!(new_state.iter_mut().skip(1), input.iter().skip(1), v.iter()) {
-            input[0].clone_into(n);
-            n.mul_assign(v);
-            n.add_assign(inp);
+        for (n, v) in izip!(state.iter_mut().skip(1), v.iter()) {
+            *n = n.add(&first.mul_public(v));
         }
-        new_state
+        state[0] = new_first;
     }
 
     fn add_rc(&self, input: &mut [F], rc: &[F]) {
@@ -131,15 +439,188 @@ impl<F: PrimeField> Poseidon<F> {
     }
 }
 
+/// The strategy for the capacity element in [`Poseidon::hash_with_tag`] and
+/// [`Poseidon::hash_neptune`]. Besides the zero
+/// capacity of circom, this covers the tags used by neptune to separate Merkle tree hashing from
+/// constant-length hashing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DomainTag<F: PrimeField> {
+    /// The capacity element is zero.
+    Zero,
+    /// The capacity element is 2^arity - 1 with arity = t - 1, as for neptune's Merkle trees.
+    MerkleTree,
+    /// The capacity element is len * 2^64 for len inputs, as for neptune's constant-length
+    /// hashing.
+    ConstantLength,
+    /// The capacity element is the given value, e.g., a [`Poseidon::domain_separator`].
+    Custom(F),
+}
+
+impl<F: PrimeField> DomainTag<F> {
+    /// Returns the value of the capacity element for statesize t and len inputs.
+    pub fn value(&self, t: usize, len: usize) -> F {
+        match self {
+            DomainTag::Zero => F::zero(),
+            DomainTag::MerkleTree => F::from(2u64).pow([t as u64 - 1]) - F::one(),
+            DomainTag::ConstantLength => F::from(len as u64) * F::from(2u64).pow([64]),
+            DomainTag::Custom(value) => *value,
+        }
+    }
+}
+
+/// The Poseidon permutation for a statesize T fixed at compile time. The state is passed as an
+/// array, so the permutation neither checks its length nor allocates memory.
+#[derive(Clone, Debug)]
+pub struct PoseidonFixed<F: PrimeField, const T: usize> {
+    poseidon: Poseidon<F>,
+}
+
+impl<F: PrimeField, const T: usize> PoseidonFixed<F, T> {
+    /// Fails if the statesize of the parameters does not match T.
+    pub fn new(params: &Arc<PoseidonParams<F>>) -> Result<Self, Error> {
+        if params.t != T {
+            return Err(Error::WrongStatesize {
+                expected: T,
+                got: params.t,
+            });
+        }
+        Ok(PoseidonFixed {
+            poseidon: Poseidon::new(params),
+        })
+    }
+
+    /// Fails only if the optimized representation of the parameters cannot be computed.
+    pub fn permutation(&self, input: [F; T]) -> Result<[F; T], Error> {
+        let mut state = input;
+        let mut scratch = [F::zero(); T];
+        self.poseidon
+            .permute_generic(&mut state, &mut scratch, &mut ())?;
+        Ok(state)
+    }
+
+    /// Returns the dynamically sized instance with the same parameters.
+    pub fn as_dynamic(&self) -> &Poseidon<F> {
+        &self.poseidon
+    }
+}
+
+fn add_public<F: PrimeField, T: PoseidonField<F>>(
+    state: &mut [T],
+    constants: &[F],
+    ctx: &T::Context,
+) {
+    debug_assert_eq!(state.len(), constants.len());
+    for (el, c) in izip!(state.iter_mut(), constants.iter()) {
+        *el = el.add_public(c, ctx);
+    }
+}
+
+// The number of bytes which are packed into one field element by [`Poseidon::hash_bytes`]
+pub(crate) fn bytes_chunk_size<F: PrimeField>() -> Result<usize, Error> {
+    let chunk_size = (F::MODULUS_BIT_SIZE as usize - 1) / 8;
+    if chunk_size == 0 {
+        return Err(Error::InvalidParameters);
+    }
+    Ok(chunk_size)
+}
+
+// [`Poseidon::hash_segments_with_domain`] for an arbitrary hasher
+pub(crate) fn hash_segments_with<F: PrimeField, H: FieldHasher<F> + Clone>(
+    hasher: &H,
+    domain: F,
+    segments: &[&[F]],
+) -> Result<F, Error> {
+    let mut absorber = SegmentAbsorber::new(hasher, segments.len(), domain)?;
+    for segment in segments {
+        for el in segment.iter() {
+            absorber.absorb(*el)?;
+        }
+        absorber.end_segment(segment.len())?;
+    }
+    absorber.finish()
+}
+
+// [`Poseidon::hash_bytes`] for an arbitrary hasher
+pub(crate) fn hash_bytes_with<F: PrimeField, H: FieldHasher<F> + Clone>(
+    hasher: &H,
+    bytes: &[u8],
+) -> Result<F, Error> {
+    let chunk_size = bytes_chunk_size::<F>()?;
+    let chunks = bytes
+        .chunks(chunk_size)
+        .map(F::from_le_bytes_mod_order)
+        .collect::<Vec<_>>();
+    hash_segments_with(
+        hasher,
+        F::zero(),
+        &[&chunks, &[F::from(bytes.len() as u64)]],
+    )
+}
+
+// Incremental absorption following the separation scheme of [`Poseidon::hash_segments`]
+#[derive(Clone, Debug)]
+pub(crate) struct SegmentAbsorber<F: PrimeField, H = Poseidon<F>> {
+    hasher: H,
+    state: Vec<F>,
+    pos: usize,
+    permuted: bool,
+}
+
+impl<F: PrimeField, H: FieldHasher<F> + Clone> SegmentAbsorber<F, H> {
+    pub(crate) fn new(hasher: &H, num_segments: usize, domain: F) -> Result<Self, Error> {
+        let t = hasher.state_size();
+        if t < 2 {
+            return Err(Error::InvalidParameters);
+        }
+        let mut state = vec![F::zero(); t];
+        state[0] = domain * F::from(1u128 << 64) + F::from(num_segments as u64);
+        Ok(SegmentAbsorber {
+            hasher: hasher.to_owned(),
+            state,
+            pos: 0,
+            permuted: false,
+        })
+    }
+
+    fn permute(&mut self) -> Result<(), Error> {
+        self.hasher.permute(&mut self.state)?;
+        self.permuted = true;
+        Ok(())
+    }
+
+    pub(crate) fn absorb(&mut self, el: F) -> Result<(), Error> {
+        self.state[self.pos + 1].add_assign(el);
+        self.pos += 1;
+        if self.pos == self.state.len() - 1 {
+            self.permute()?;
+            self.pos = 0;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn end_segment(&mut self, len: usize) -> Result<(), Error> {
+        self.absorb(F::from(len as u64))
+    }
+
+    pub(crate) fn finish(mut self) -> Result<F, Error> {
+        if self.pos != 0 || !self.permuted {
+            self.permute()?;
+        }
+        Ok(self.state[0])
+    }
+}
+
 #[cfg(test)]
 mod poseidon_bn254_tests {
     use super::*;
     use crate::{
         bn254::{circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS},
         field_from_hex_string,
+        parameters::matrix::Matrix,
     };
-    use ark_ff::{One, UniformRand, Zero};
+    use ark_ff::{Field, One, UniformRand, Zero};
     use rand::thread_rng;
+    use spin::Once;
 
     static TESTRUNS: usize = 5;
     type Scalar = ark_bn254::Fr;
@@ -169,6 +650,63 @@ mod poseidon_bn254_tests {
         }
     }
 
+    #[test]
+    fn permutation_in_place() {
+        let mut rng = thread_rng();
+
+        for poseidon in [
+            Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS),
+            Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS),
+        ] {
+            let t = poseidon.get_t();
+            let input: Vec<Scalar> = (0..t).map(|_| Scalar::rand(&mut rng)).collect();
+            let mut state = input.to_owned();
+            poseidon.permutation_in_place(&mut state).unwrap();
+            assert_eq!(state, poseidon.permutation_not_opt(input).unwrap());
+            assert!(poseidon.permutation_in_place(&mut state[1..]).is_err());
+        }
+    }
+
+    #[test]
+    fn permute_strided() {
+        let mut rng = thread_rng();
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let stride = 5;
+        let mut buf: Vec<Scalar> = (0..3 * stride + 3)
+            .map(|_| Scalar::rand(&mut rng))
+            .collect();
+        let expected: Vec<Scalar> = buf
+            .chunks(stride)
+            .flat_map(|chunk| {
+                let mut chunk = chunk.to_vec();
+                let state = poseidon.permutation(chunk[..3].to_vec()).unwrap();
+                chunk[..3].copy_from_slice(&state);
+                chunk
+            })
+            .collect();
+        poseidon.permute_strided(&mut buf, stride).unwrap();
+        assert_eq!(buf, expected);
+
+        assert!(poseidon.permute_strided(&mut buf, 2).is_err());
+        assert!(poseidon.permute_strided(&mut buf[..7], stride).is_err());
+        assert!(poseidon.permute_strided(&mut [], stride).is_ok());
+    }
+
+    #[test]
+    fn fixed_statesize() {
+        let mut rng = thread_rng();
+
+        let fixed = PoseidonFixed::<Scalar, 4>::new(&POSEIDON_CIRCOM_BN_4_PARAMS).unwrap();
+        let input: [Scalar; 4] = std::array::from_fn(|_| Scalar::rand(&mut rng));
+        let perm = fixed.permutation(input).unwrap();
+        assert_eq!(
+            perm.to_vec(),
+            fixed.as_dynamic().permutation(input.to_vec()).unwrap()
+        );
+        assert!(PoseidonFixed::<Scalar, 3>::new(&POSEIDON_CIRCOM_BN_4_PARAMS).is_err());
+    }
+
+    // Also the test vector of the reference implementation for poseidonperm_x5_254_3
     #[test]
     fn kats_t3() {
         let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
@@ -237,6 +775,325 @@ mod poseidon_bn254_tests {
         );
     }
 
+    #[test]
+    fn zero_hashes() {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let zeros = poseidon.zero_hashes(2).unwrap();
+        assert_eq!(zeros.len(), 3);
+        assert_eq!(zeros[0], Scalar::zero());
+
+        let level1 = poseidon.compress(Scalar::zero(), Scalar::zero()).unwrap();
+        let level2 = poseidon.compress(level1, level1).unwrap();
+        assert_eq!(zeros[1], level1);
+        assert_eq!(zeros[2], level2);
+
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS);
+        assert!(poseidon.zero_hashes(1).is_err());
+    }
+
+    #[test]
+    fn sbox() {
+        let mut rng = thread_rng();
+
+        assert_eq!(pow_sbox(Scalar::from(2), 5), Scalar::from(32));
+        for d in [3, 5, 7] {
+            let x = Scalar::rand(&mut rng);
+            assert_eq!(pow_sbox(x, d), x.pow([d as u64]));
+        }
+    }
+
+    #[test]
+    fn hash_segments() {
+        let mut rng = thread_rng();
+        let a = Scalar::rand(&mut rng);
+        let b = Scalar::rand(&mut rng);
+
+        for params in [&*POSEIDON_CIRCOM_BN_3_PARAMS, &*POSEIDON_CIRCOM_BN_4_PARAMS] {
+            let poseidon = Poseidon::new(params);
+            let hash1 = poseidon.hash_segments(&[&[a], &[b]]).unwrap();
+            let hash2 = poseidon.hash_segments(&[&[a, b], &[]]).unwrap();
+            let hash3 = poseidon.hash_segments(&[&[a, b]]).unwrap();
+            let hash4 = poseidon.hash_segments(&[&[], &[a, b]]).unwrap();
+            let hash5 = poseidon.hash_segments(&[]).unwrap();
+            let hash6 = poseidon.hash_segments(&[&[]]).unwrap();
+            let hashes = [hash1, hash2, hash3, hash4, hash5, hash6];
+            for (i, h1) in hashes.iter().enumerate() {
+                for h2 in hashes.iter().skip(i + 1) {
+                    assert_ne!(h1, h2);
+                }
+            }
+            assert_eq!(hash1, poseidon.hash_segments(&[&[a], &[b]]).unwrap());
+        }
+    }
+
+    #[test]
+    fn matrix_storage_equivalence() {
+        let mut rng = thread_rng();
+
+        let params = &*POSEIDON_CIRCOM_BN_4_PARAMS;
+        let optimized = params.optimized().unwrap();
+        let mut nested = params.as_ref().to_owned();
+        nested.mds = Matrix::new_nested(params.mds.to_vec()).unwrap();
+        nested.optimized = Once::initialized(Ok(OptimizedParams {
+            m_i: Matrix::new_nested(optimized.m_i.to_vec()).unwrap(),
+            ..optimized.clone()
+        }));
+        let mut flat = params.as_ref().to_owned();
+        flat.mds = Matrix::new_flat(params.mds.to_vec()).unwrap();
+        flat.optimized = Once::initialized(Ok(OptimizedParams {
+            m_i: Matrix::new_flat(optimized.m_i.to_vec()).unwrap(),
+            ..optimized.clone()
+        }));
+
+        let nested = Poseidon::new(&Arc::new(nested));
+        let flat = Poseidon::new(&Arc::new(flat));
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..params.t).map(|_| Scalar::rand(&mut rng)).collect();
+            let perm1 = nested.permutation(input.to_owned()).unwrap();
+            let perm2 = flat.permutation(input.to_owned()).unwrap();
+            let perm3 = flat.permutation_not_opt(input).unwrap();
+            assert_eq!(perm1, perm2);
+            assert_eq!(perm1, perm3);
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn permutation_zeroizing() {
+        use crate::bn254::circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS;
+
+        let mut rng = thread_rng();
+        for params in [&*POSEIDON_CIRCOM_BN_3_PARAMS, &*POSEIDON_CIRCOM_BN_5_PARAMS] {
+            let poseidon = Poseidon::new(params);
+            let input: Vec<Scalar> = (0..params.t).map(|_| Scalar::rand(&mut rng)).collect();
+            assert_eq!(
+                poseidon.permutation_zeroizing(input.to_owned()).unwrap(),
+                poseidon.permutation(input).unwrap()
+            );
+        }
+        assert!(Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS)
+            .permutation_zeroizing(vec![Scalar::one(); 2])
+            .is_err());
+    }
+
+    #[test]
+    fn hash() {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS);
+        let inputs = [Scalar::from(1), Scalar::from(2), Scalar::from(3)];
+        let expected = poseidon
+            .permutation(vec![Scalar::zero(), inputs[0], inputs[1], inputs[2]])
+            .unwrap()[0];
+        assert_eq!(poseidon.hash(&inputs).unwrap(), expected);
+        assert!(matches!(
+            poseidon.hash(&inputs[..2]),
+            Err(Error::WrongInputLength {
+                expected: 3,
+                got: 2
+            })
+        ));
+        assert!(matches!(
+            poseidon.hash(&[Scalar::one(); 4]),
+            Err(Error::WrongInputLength {
+                expected: 3,
+                got: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn hash_many() {
+        let mut rng = thread_rng();
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let inputs: Vec<[Scalar; 2]> = (0..7)
+            .map(|_| [Scalar::rand(&mut rng), Scalar::rand(&mut rng)])
+            .collect();
+        let hashes = poseidon.hash_many(&inputs).unwrap();
+        assert_eq!(hashes.len(), inputs.len());
+        for (input, hash) in inputs.iter().zip(hashes) {
+            assert_eq!(
+                poseidon.hash_with_tag(DomainTag::Zero, input).unwrap(),
+                hash
+            );
+        }
+        assert!(poseidon.hash_many::<2>(&[]).unwrap().is_empty());
+        assert!(matches!(
+            poseidon.hash_many(&[[Scalar::one(); 3]]),
+            Err(Error::WrongBatchRate {
+                expected: 2,
+                got: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn lazy_optimization() {
+        let mut rng = thread_rng();
+
+        let base = &*POSEIDON_CIRCOM_BN_3_PARAMS;
+        assert!(base.optimized.get().is_some());
+        let params = PoseidonParams::new_unoptimized(
+            base.t,
+            base.d,
+            8,
+            base.rounds_p,
+            base.mds.to_vec(),
+            base.round_constants.to_vec(),
+        )
+        .unwrap();
+        assert!(params.optimized.get().is_none());
+
+        let poseidon = Poseidon::new(&Arc::new(params));
+        let input: Vec<Scalar> = (0..base.t).map(|_| Scalar::rand(&mut rng)).collect();
+        let perm1 = poseidon.permutation_not_opt(input.to_owned()).unwrap();
+        assert!(poseidon.params.optimized.get().is_none());
+        let perm2 = poseidon.permutation(input).unwrap();
+        assert!(poseidon.params.optimized.get().is_some());
+        assert_eq!(perm1, perm2);
+    }
+
+    #[test]
+    fn full_rounds_only() {
+        let mut rng = thread_rng();
+
+        let base = &*POSEIDON_CIRCOM_BN_3_PARAMS;
+        let params = PoseidonParams::new(
+            base.t,
+            base.d,
+            8,
+            0,
+            base.mds.to_vec(),
+            base.round_constants.to_vec()[..8].to_vec(),
+        )
+        .unwrap();
+        let optimized = params.optimized().unwrap();
+        assert!(optimized.round_constants.is_empty());
+        assert!(optimized.w_hat.is_empty());
+        assert!(optimized.v.is_empty());
+        assert_eq!(optimized.m_i.dim(), 0);
+
+        let poseidon = Poseidon::new(&Arc::new(params));
+        for _ in 0..TESTRUNS {
+            let input: Vec<Scalar> = (0..base.t).map(|_| Scalar::rand(&mut rng)).collect();
+            let perm1 = poseidon.permutation(input.to_owned()).unwrap();
+            let perm2 = poseidon.permutation_not_opt(input).unwrap();
+            assert_eq!(perm1, perm2);
+        }
+    }
+
+    #[test]
+    fn hash_segments_with_domain() {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let input = [Scalar::from(1), Scalar::from(2)];
+        let plain = poseidon.hash_segments(&[&input]).unwrap();
+        assert_eq!(
+            poseidon
+                .hash_segments_with_domain(Scalar::zero(), &[&input])
+                .unwrap(),
+            plain
+        );
+
+        let domain1 = poseidon.domain_separator("protocol-a").unwrap();
+        let domain2 = poseidon.domain_separator("protocol-b").unwrap();
+        let hash1 = poseidon
+            .hash_segments_with_domain(domain1, &[&input])
+            .unwrap();
+        let hash2 = poseidon
+            .hash_segments_with_domain(domain2, &[&input])
+            .unwrap();
+        assert_ne!(hash1, plain);
+        assert_ne!(hash1, hash2);
+    }
+
+    #[test]
+    fn hash_with_tag() {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS);
+        let (a, b) = (Scalar::from(1), Scalar::from(2));
+        let perm = |state: Vec<Scalar>| poseidon.permutation(state).unwrap()[0];
+
+        let zero = poseidon.hash_with_tag(DomainTag::Zero, &[a, b]).unwrap();
+        assert_eq!(zero, perm(vec![Scalar::zero(), a, b, Scalar::zero()]));
+        let merkle = poseidon
+            .hash_with_tag(DomainTag::MerkleTree, &[a, b, a])
+            .unwrap();
+        assert_eq!(merkle, perm(vec![Scalar::from(7), a, b, a]));
+        let length = poseidon
+            .hash_with_tag(DomainTag::ConstantLength, &[a, b])
+            .unwrap();
+        let tag = Scalar::from(2) * Scalar::from(u64::MAX) + Scalar::from(2);
+        assert_eq!(length, perm(vec![tag, a, b, Scalar::zero()]));
+        let custom = poseidon
+            .hash_with_tag(DomainTag::Custom(Scalar::from(5)), &[a])
+            .unwrap();
+        assert_eq!(
+            custom,
+            perm(vec![Scalar::from(5), a, Scalar::zero(), Scalar::zero()])
+        );
+
+        assert!(poseidon.hash_with_tag(DomainTag::Zero, &[a; 4]).is_err());
+    }
+
+    #[test]
+    fn hash_to_n() {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let (a, b) = (Scalar::from(1), Scalar::from(2));
+        // the full permuted state of [0, 1, 2], i.e., circomlibjs' poseidon([1, 2], 0, 3)
+        let expected: Vec<Scalar> = [
+            "0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a",
+            "0x0fca49b798923ab0239de1c9e7a4a9a2210312b6a2f616d18b5a87f9b628ae29",
+            "0x0e7ae82e40091e63cbd4f16a6d16310b3729d4b6e138fcf54110e2867045a30c",
+        ]
+        .iter()
+        .map(|hex| field_from_hex_string(hex).unwrap())
+        .collect();
+        assert_eq!(poseidon.hash_to_n(&[a, b], 3).unwrap(), expected);
+        assert_eq!(
+            poseidon.hash_to_n(&[a, b], 1).unwrap(),
+            vec![poseidon.hash(&[a, b]).unwrap()]
+        );
+
+        // further outputs are squeezed from the next permutation
+        let output = poseidon.hash_to_n(&[a, b], 5).unwrap();
+        assert_eq!(output[..3], expected);
+        assert_eq!(output[3..], poseidon.permutation(expected).unwrap()[..2]);
+        assert!(poseidon.hash_to_n(&[a, b], 0).unwrap().is_empty());
+        assert!(poseidon.hash_to_n(&[a, b, a], 2).is_err());
+    }
+
+    #[test]
+    fn hash_bytes() {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let hash1 = poseidon.hash_bytes(&[1]).unwrap();
+        let hash2 = poseidon.hash_bytes(&[1, 0]).unwrap();
+        let hash3 = poseidon.hash_bytes(&[]).unwrap();
+        let hash4 = poseidon.hash_bytes(&[0xff; 62]).unwrap();
+        let hash5 = poseidon.hash_bytes(&[0xff; 63]).unwrap();
+        assert_ne!(hash1, hash2);
+        assert_ne!(hash1, hash3);
+        assert_ne!(hash4, hash5);
+        assert_eq!(hash1, poseidon.hash_bytes(&[1]).unwrap());
+        assert_eq!(
+            hash1,
+            poseidon
+                .hash_segments(&[&[Scalar::one()], &[Scalar::one()]])
+                .unwrap()
+        );
+    }
+
+    #[cfg(feature = "ark-serialize")]
+    #[test]
+    fn hash_canonical() {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let items = [Scalar::from(1), Scalar::from(2)];
+        let hash = poseidon.hash_canonical(&items).unwrap();
+
+        let mut bytes = Vec::new();
+        items[0].serialize_compressed(&mut bytes).unwrap();
+        items[1].serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(hash, poseidon.hash_bytes(&bytes).unwrap());
+        // hashing the serialized bytes differs from hashing the field elements directly
+        assert_ne!(hash, poseidon.hash_segments(&[&items]).unwrap());
+    }
+
     #[test]
     fn opt_equals_not_opt() {
         let mut rng = thread_rng();
@@ -0,0 +1,238 @@
+//! # Poseidon2
+//! Contains the Poseidon2 permutation, which replaces the single MDS matrix of Poseidon with
+//! cheap external (full rounds) and internal (partial rounds) linear layers. The shipped BN254
+//! instance ([`POSEIDON2_BN_3_PARAMS`](crate::bn254::poseidon2_t3::POSEIDON2_BN_3_PARAMS)) is
+//! the one of the HorizenLabs reference implementation, not the one of gnark-crypto.
+
+pub mod parameters;
+
+use crate::{error::Error, poseidon::pow_sbox};
+use alloc::{sync::Arc, vec::Vec};
+use ark_ff::PrimeField;
+use parameters::Poseidon2Params;
+
+#[derive(Clone, Debug)]
+pub struct Poseidon2<F: PrimeField> {
+    pub(crate) params: Arc<Poseidon2Params<F>>,
+}
+
+impl<F: PrimeField> Poseidon2<F> {
+    pub fn new(params: &Arc<Poseidon2Params<F>>) -> Self {
+        Poseidon2 {
+            params: params.clone(),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.params.t
+    }
+
+    pub fn permutation(&self, input: Vec<F>) -> Result<Vec<F>, Error> {
+        let mut state = input;
+        self.permutation_in_place(&mut state)?;
+        Ok(state)
+    }
+
+    /// Applies the permutation to the given state in place.
+    pub fn permutation_in_place(&self, state: &mut [F]) -> Result<(), Error> {
+        let t = self.params.t;
+        if state.len() != t {
+            return Err(Error::WrongInputLength {
+                expected: t,
+                got: state.len(),
+            });
+        }
+
+        // Linear layer at beginning
+        self.matmul_external(state);
+
+        for r in 0..self.params.rounds_f_beginning {
+            self.add_rc(state, &self.params.round_constants[r]);
+            self.sbox(state);
+            self.matmul_external(state);
+        }
+        let p_end = self.params.rounds_f_beginning + self.params.rounds_p;
+        for r in self.params.rounds_f_beginning..p_end {
+            state[0].add_assign(&self.params.round_constants[r][0]);
+            state[0] = pow_sbox(state[0], self.params.d);
+            self.matmul_internal(state);
+        }
+        for r in p_end..self.params.rounds {
+            self.add_rc(state, &self.params.round_constants[r]);
+            self.sbox(state);
+            self.matmul_external(state);
+        }
+        Ok(())
+    }
+
+    fn sbox(&self, input: &mut [F]) {
+        input
+            .iter_mut()
+            .for_each(|el| *el = pow_sbox(*el, self.params.d));
+    }
+
+    // Multiplication with the 4x4 matrix [[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]]
+    fn matmul_m4(input: &mut [F]) {
+        for chunk in input.chunks_exact_mut(4) {
+            let t_0 = chunk[0] + chunk[1];
+            let t_1 = chunk[2] + chunk[3];
+            let t_2 = chunk[1].double() + t_1;
+            let t_3 = chunk[3].double() + t_0;
+            let t_4 = t_1.double().double() + t_3;
+            let t_5 = t_0.double().double() + t_2;
+            let t_6 = t_3 + t_5;
+            let t_7 = t_2 + t_4;
+            chunk[0] = t_6;
+            chunk[1] = t_5;
+            chunk[2] = t_7;
+            chunk[3] = t_4;
+        }
+    }
+
+    fn matmul_external(&self, input: &mut [F]) {
+        let t = self.params.t;
+        match t {
+            2 | 3 => {
+                // Matrix circ(2, 1) or circ(2, 1, 1)
+                let sum: F = input.iter().sum();
+                input.iter_mut().for_each(|el| el.add_assign(&sum));
+            }
+            4 => Self::matmul_m4(input),
+            _ => {
+                // Matrix circ(2 * M4, M4, ..., M4)
+                Self::matmul_m4(input);
+                let mut stored = [F::zero(); 4];
+                for chunk in input.chunks_exact(4) {
+                    for (s, el) in stored.iter_mut().zip(chunk.iter()) {
+                        s.add_assign(el);
+                    }
+                }
+                for chunk in input.chunks_exact_mut(4) {
+                    for (el, s) in chunk.iter_mut().zip(stored.iter()) {
+                        el.add_assign(s);
+                    }
+                }
+            }
+        }
+    }
+
+    fn matmul_internal(&self, input: &mut [F]) {
+        let sum: F = input.iter().sum();
+        for (el, diag) in input
+            .iter_mut()
+            .zip(self.params.mat_internal_diag_m_1.iter())
+        {
+            el.mul_assign(diag);
+            el.add_assign(&sum);
+        }
+    }
+
+    fn add_rc(&self, input: &mut [F], rc: &[F]) {
+        debug_assert_eq!(input.len(), rc.len());
+        input.iter_mut().zip(rc.iter()).for_each(|(a, b)| {
+            a.add_assign(b);
+        });
+    }
+}
+
+#[cfg(test)]
+mod poseidon2_bn254_tests {
+    use super::*;
+    use crate::{bn254::poseidon2_t3::POSEIDON2_BN_3_PARAMS, field_from_hex_string};
+    use ark_ff::{One, UniformRand, Zero};
+    use rand::thread_rng;
+
+    static TESTRUNS: usize = 5;
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn consistent_perm() {
+        let mut rng = thread_rng();
+
+        let poseidon2 = Poseidon2::new(&POSEIDON2_BN_3_PARAMS);
+        let t = poseidon2.params.t;
+        for _ in 0..TESTRUNS {
+            let input1: Vec<Scalar> = (0..t).map(|_| Scalar::rand(&mut rng)).collect();
+
+            let mut input2: Vec<Scalar>;
+            loop {
+                input2 = (0..t).map(|_| Scalar::rand(&mut rng)).collect();
+                if input1 != input2 {
+                    break;
+                }
+            }
+
+            let perm1 = poseidon2.permutation(input1.to_owned()).unwrap();
+            let perm2 = poseidon2.permutation(input1).unwrap();
+            let perm3 = poseidon2.permutation(input2).unwrap();
+            assert_eq!(perm1, perm2);
+            assert_ne!(perm1, perm3);
+        }
+    }
+
+    // Test vector of the reference implementation (HorizenLabs/poseidon2)
+    #[test]
+    fn kats_t3() {
+        let poseidon2 = Poseidon2::new(&POSEIDON2_BN_3_PARAMS);
+        let input: Vec<Scalar> = vec![Scalar::zero(), Scalar::one(), Scalar::from(2)];
+        let perm = poseidon2.permutation(input).unwrap();
+        assert_eq!(
+            perm[0],
+            field_from_hex_string(
+                "0x0bb61d24daca55eebcb1929a82650f328134334da98ea4f847f760054f4a3033"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            perm[1],
+            field_from_hex_string(
+                "0x303b6f7c86d043bfcbcc80214f26a30277a15d3f74ca654992defe7ff8d03570"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            perm[2],
+            field_from_hex_string(
+                "0x1ed25194542b12eef8617361c3ba7c52e660b145994427cc86296242cf766ec8"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn external_matrix() {
+        // compare the external linear layers with explicit matrix multiplications
+        let mut rng = thread_rng();
+        let m4 = [[5u64, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]];
+        for t in [2, 3, 4, 8, 12] {
+            let params = Poseidon2Params::new(
+                t,
+                5,
+                8,
+                0,
+                vec![Scalar::one(); t],
+                vec![vec![Scalar::zero(); t]; 8],
+            )
+            .unwrap();
+            let poseidon2 = Poseidon2::new(&Arc::new(params));
+            let input: Vec<Scalar> = (0..t).map(|_| Scalar::rand(&mut rng)).collect();
+            let mut output = input.to_owned();
+            poseidon2.matmul_external(&mut output);
+
+            for (row, out) in output.iter().enumerate() {
+                let mut expected = Scalar::zero();
+                for (col, inp) in input.iter().enumerate() {
+                    let factor = match t {
+                        2 | 3 => 1 + u64::from(row == col),
+                        _ => {
+                            let block = m4[row % 4][col % 4];
+                            block * (1 + u64::from(row / 4 == col / 4 && t > 4))
+                        }
+                    };
+                    expected += Scalar::from(factor) * inp;
+                }
+                assert_eq!(*out, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{
+    error::Error,
+    parameters::{bytes::decode_elements, check_round_constants},
+};
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+
+#[derive(Clone, Debug)]
+pub struct Poseidon2Params<F: PrimeField> {
+    pub(crate) t: usize, // statesize
+    pub(crate) d: usize, // sbox degree
+    pub(crate) rounds_f_beginning: usize,
+    pub(crate) rounds_p: usize,
+    #[allow(dead_code)]
+    pub(crate) rounds_f_end: usize,
+    pub(crate) rounds: usize,
+    pub(crate) mat_internal_diag_m_1: Vec<F>, // diagonal of the internal matrix minus 1
+    pub(crate) round_constants: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> Poseidon2Params<F> {
+    /// Creates a new parameter set. The external matrix is fixed by the statesize, which has to
+    /// be 2, 3, or a multiple of 4 up to 24. The internal matrix is given by its diagonal minus
+    /// one, i.e., it is the all-ones matrix plus `diag(mat_internal_diag_m_1)`. The round
+    /// constants contain one row of length t per round, where only the first element is used in
+    /// partial rounds.
+    pub fn new(
+        t: usize,
+        d: usize,
+        rounds_f: usize,
+        rounds_p: usize,
+        mat_internal_diag_m_1: Vec<F>,
+        round_constants: Vec<Vec<F>>,
+    ) -> Result<Self, Error> {
+        if !(t == 2 || t == 3 || (t % 4 == 0 && t <= 24)) {
+            return Err(Error::InvalidParameters);
+        }
+        if mat_internal_diag_m_1.len() != t {
+            return Err(Error::WrongInputLength {
+                expected: t,
+                got: mat_internal_diag_m_1.len(),
+            });
+        }
+        let rounds = rounds_f + rounds_p;
+        check_round_constants(rounds, t, &round_constants)?;
+        if rounds_f % 2 != 0 {
+            return Err(Error::OddFullRounds(rounds_f));
+        }
+        let r = rounds_f / 2;
+
+        Ok(Poseidon2Params {
+            t,
+            d,
+            rounds_f_beginning: r,
+            rounds_p,
+            rounds_f_end: r,
+            rounds,
+            mat_internal_diag_m_1,
+            round_constants,
+        })
+    }
+
+    /// Loads parameters from the binary encoding (see [`PoseidonParams::to_bytes`]) of the
+    /// diagonal of the internal matrix minus one, followed by the round constants.
+    ///
+    /// [`PoseidonParams::to_bytes`]: crate::parameters::PoseidonParams::to_bytes
+    pub fn from_bytes(
+        t: usize,
+        d: usize,
+        rounds_f: usize,
+        rounds_p: usize,
+        bytes: &[u8],
+    ) -> Result<Self, Error> {
+        let rounds = rounds_f + rounds_p;
+        let elements = decode_elements::<F>(bytes, t + rounds * t)?;
+        if t == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        let (diag, round_constants) = elements.split_at(t);
+        let round_constants = round_constants.chunks(t).map(<[F]>::to_vec).collect();
+        Self::new(t, d, rounds_f, rounds_p, diag.to_vec(), round_constants)
+    }
+}
@@ -0,0 +1,131 @@
+//! # Precompile Encoding
+//! Contains the byte encoding of a Poseidon precompile in the style of EIP-5988, where the state
+//! is passed as a sequence of 32-byte big-endian words. Decoding is strict, i.e., words which are
+//! not smaller than the field modulus are rejected instead of being reduced, such that every
+//! state has exactly one encoding.
+
+use crate::{error::Error, poseidon::Poseidon};
+use alloc::vec::Vec;
+use ark_ff::{BigInteger, PrimeField};
+use num_bigint::BigUint;
+
+/// The size of an encoded field element in bytes.
+pub const WORD_SIZE: usize = 32;
+
+fn check_field_size<F: PrimeField>() -> Result<(), Error> {
+    if F::MODULUS_BIT_SIZE as usize > WORD_SIZE * 8 {
+        return Err(Error::InvalidParameters);
+    }
+    Ok(())
+}
+
+/// Encodes the state as concatenation of 32-byte big-endian words. Fails for fields whose
+/// elements do not fit into 32 bytes.
+pub fn encode_state_be<F: PrimeField>(state: &[F]) -> Result<Vec<u8>, Error> {
+    check_field_size::<F>()?;
+    let mut res = Vec::with_capacity(state.len() * WORD_SIZE);
+    for el in state {
+        let bytes = el.into_bigint().to_bytes_be();
+        // the big integer may have more limbs than needed, all excess bytes are zero
+        let start = bytes.len().saturating_sub(WORD_SIZE);
+        res.resize(res.len() + WORD_SIZE - (bytes.len() - start), 0);
+        res.extend_from_slice(&bytes[start..]);
+    }
+    Ok(res)
+}
+
+/// Decodes a state from a concatenation of 32-byte big-endian words. Fails if the length is not
+/// a multiple of 32 bytes or a word is not smaller than the field modulus.
+pub fn decode_state_be<F: PrimeField>(bytes: &[u8]) -> Result<Vec<F>, Error> {
+    check_field_size::<F>()?;
+    if bytes.len() % WORD_SIZE != 0 {
+        return Err(Error::InvalidInputLength(bytes.len()));
+    }
+    let modulus: BigUint = F::MODULUS.into();
+    bytes
+        .chunks_exact(WORD_SIZE)
+        .map(|word| {
+            let value = BigUint::from_bytes_be(word);
+            if value >= modulus {
+                return Err(Error::NonCanonical);
+            }
+            Ok(value.into())
+        })
+        .collect()
+}
+
+/// Applies the permutation to a state of t encoded words and returns the encoded result, i.e.,
+/// the input and output of a Poseidon precompile.
+pub fn permute_bytes<F: PrimeField>(
+    poseidon: &Poseidon<F>,
+    input: &[u8],
+) -> Result<Vec<u8>, Error> {
+    if input.len() != poseidon.get_t() * WORD_SIZE {
+        return Err(Error::InvalidInputLength(input.len()));
+    }
+    let mut state = decode_state_be(input)?;
+    poseidon.permutation_in_place(&mut state)?;
+    encode_state_be(&state)
+}
+
+#[cfg(test)]
+mod precompile_tests {
+    use super::*;
+    use crate::{bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, goldilocks::field::Goldilocks};
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+
+    #[test]
+    fn encoding() {
+        let mut rng = thread_rng();
+        let state: Vec<Scalar> = (0..3).map(|_| Scalar::rand(&mut rng)).collect();
+        let bytes = encode_state_be(&state).unwrap();
+        assert_eq!(bytes.len(), 3 * WORD_SIZE);
+        assert_eq!(decode_state_be::<Scalar>(&bytes).unwrap(), state);
+
+        // small fields are left-padded with zeros
+        let bytes = encode_state_be(&[Goldilocks::from(0x0102u64)]).unwrap();
+        assert_eq!(bytes[..30], [0; 30]);
+        assert_eq!(bytes[30..], [1, 2]);
+        assert_eq!(
+            decode_state_be::<Goldilocks>(&bytes).unwrap(),
+            vec![Goldilocks::from(0x0102u64)]
+        );
+    }
+
+    #[test]
+    fn strict_decoding() {
+        let mut modulus = vec![0u8; WORD_SIZE];
+        let be = Scalar::MODULUS.to_bytes_be();
+        modulus[WORD_SIZE - be.len()..].copy_from_slice(&be);
+        assert!(matches!(
+            decode_state_be::<Scalar>(&modulus),
+            Err(Error::NonCanonical)
+        ));
+        assert!(matches!(
+            decode_state_be::<Scalar>(&[0; 33]),
+            Err(Error::InvalidInputLength(33))
+        ));
+        assert!(matches!(
+            decode_state_be::<Goldilocks>(&[0xff; 32]),
+            Err(Error::NonCanonical)
+        ));
+    }
+
+    #[test]
+    fn permute() {
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let state = vec![Scalar::from(0), Scalar::from(1), Scalar::from(2)];
+        let input = encode_state_be(&state).unwrap();
+        let output = permute_bytes(&poseidon, &input).unwrap();
+        let expected = poseidon.permutation(state).unwrap();
+        assert_eq!(decode_state_be::<Scalar>(&output).unwrap(), expected);
+
+        assert!(matches!(
+            permute_bytes(&poseidon, &input[WORD_SIZE..]),
+            Err(Error::InvalidInputLength(64))
+        ));
+    }
+}
@@ -0,0 +1,80 @@
+//! # PRF
+//! Contains a pseudorandom function and a key derivation over the BN254 scalar field, as used
+//! for the secrets of ZK identity schemes.
+//!
+//! The PRF is the circom hash of the inputs followed by the key, which is the layout of the
+//! nullifier hash `Poseidon(externalNullifier, identityNullifier)` of Semaphore. The key
+//! derivation absorbs the domain and the seed into the sponge over the circom t = 3 parameters
+//! and squeezes the requested number of keys.
+
+use crate::{
+    bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom::poseidon_hash, error::Error,
+    sponge::PoseidonSponge,
+};
+use alloc::vec::Vec;
+use ark_bn254::Fr;
+
+/// The maximal number of inputs of [`poseidon_prf`], the key takes the remaining slot of the
+/// largest circom parameter set.
+pub const MAX_PRF_INPUTS: usize = 15;
+
+/// Evaluates the PRF with the given key on up to [`MAX_PRF_INPUTS`] inputs.
+pub fn poseidon_prf(key: Fr, inputs: &[Fr]) -> Result<Fr, Error> {
+    if inputs.len() > MAX_PRF_INPUTS {
+        return Err(Error::UnsupportedArity(inputs.len()));
+    }
+    let mut state = Vec::with_capacity(inputs.len() + 1);
+    state.extend_from_slice(inputs);
+    state.push(key);
+    poseidon_hash(&state)
+}
+
+/// Derives n keys from the seed. Different domains (e.g., `"nullifier"` and `"trapdoor"`) result
+/// in independent keys for the same seed.
+pub fn derive_keys(seed: Fr, n: usize, domain: &str) -> Result<Vec<Fr>, Error> {
+    let mut sponge = PoseidonSponge::new(&POSEIDON_CIRCOM_BN_3_PARAMS)?;
+    sponge.absorb_bytes(domain.as_bytes())?;
+    sponge.absorb(&[seed])?;
+    sponge.squeeze(n)
+}
+
+#[cfg(test)]
+mod prf_tests {
+    use super::*;
+    use ark_ff::UniformRand;
+    use rand::thread_rng;
+
+    #[test]
+    fn semaphore_nullifier() {
+        let mut rng = thread_rng();
+        let identity_nullifier = Fr::rand(&mut rng);
+        let external_nullifier = Fr::rand(&mut rng);
+        assert_eq!(
+            poseidon_prf(identity_nullifier, &[external_nullifier]).unwrap(),
+            poseidon_hash(&[external_nullifier, identity_nullifier]).unwrap()
+        );
+        assert_ne!(
+            poseidon_prf(identity_nullifier, &[external_nullifier]).unwrap(),
+            poseidon_prf(external_nullifier, &[identity_nullifier]).unwrap()
+        );
+
+        let inputs = [Fr::from(1); MAX_PRF_INPUTS + 1];
+        assert!(poseidon_prf(identity_nullifier, &inputs[..MAX_PRF_INPUTS]).is_ok());
+        assert!(matches!(
+            poseidon_prf(identity_nullifier, &inputs),
+            Err(Error::UnsupportedArity(16))
+        ));
+    }
+
+    #[test]
+    fn key_derivation() {
+        let seed = Fr::from(42);
+        let keys = derive_keys(seed, 5, "nullifier").unwrap();
+        assert_eq!(keys.len(), 5);
+        // a prefix of the keys does not depend on n
+        assert_eq!(derive_keys(seed, 2, "nullifier").unwrap(), keys[..2]);
+        assert_ne!(derive_keys(seed, 5, "trapdoor").unwrap(), keys);
+        assert_ne!(derive_keys(Fr::from(43), 5, "nullifier").unwrap(), keys);
+        assert!(derive_keys(seed, 0, "nullifier").unwrap().is_empty());
+    }
+}
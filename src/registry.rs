@@ -0,0 +1,349 @@
+//! # Registry
+//! Contains a thread-safe cache of Poseidon instances for the BN254 scalar field, and a
+//! thread-safe registry of parameter sets by name, such that services can select the instance
+//! from their configuration at startup.
+//!
+//! The names of the shipped parameter sets have the form `<field>/<family>/t<t>`:
+//! - `bn254/circom/t2` to `bn254/circom/t17`: circomlib's instances,
+//! - `pallas/halo2/t3` and `vesta/halo2/t3`: halo2's `P128Pow5T3`,
+//! - `goldilocks/plonky2/t12`: Plonky2's instance,
+//! - `goldilocks/grain/t8`, `goldilocks/grain/t12`, `grumpkin/grain/t3`, `grumpkin/grain/t4`,
+//!   `babybear/grain/t16`, `babybear/grain/t24`, `mersenne31/grain/t16`, and
+//!   `mersenne31/grain/t24`: the instances generated by the Grain LFSR in this crate.
+//!
+//! With the `bls12-381` feature, `bls12_381/neptune/t3`, `bls12_381/neptune/t5`, and
+//! `bls12_381/neptune/t9` are neptune's instances and `bls12_381/hades/t5` is dusk-network's
+//! Hades-252 in reversed state order, see [`bls12_381`](crate::bls12_381). Further sets can be
+//! registered at runtime.
+
+use crate::{
+    bn254::{
+        circom_t10::POSEIDON_CIRCOM_BN_10_PARAMS, circom_t11::POSEIDON_CIRCOM_BN_11_PARAMS,
+        circom_t12::POSEIDON_CIRCOM_BN_12_PARAMS, circom_t13::POSEIDON_CIRCOM_BN_13_PARAMS,
+        circom_t14::POSEIDON_CIRCOM_BN_14_PARAMS, circom_t15::POSEIDON_CIRCOM_BN_15_PARAMS,
+        circom_t16::POSEIDON_CIRCOM_BN_16_PARAMS, circom_t17::POSEIDON_CIRCOM_BN_17_PARAMS,
+        circom_t2::POSEIDON_CIRCOM_BN_2_PARAMS, circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+        circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS, circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS,
+        circom_t6::POSEIDON_CIRCOM_BN_6_PARAMS, circom_t7::POSEIDON_CIRCOM_BN_7_PARAMS,
+        circom_t8::POSEIDON_CIRCOM_BN_8_PARAMS, circom_t9::POSEIDON_CIRCOM_BN_9_PARAMS,
+    },
+    error::Error,
+    goldilocks::{
+        plonky2_t12::POSEIDON_PLONKY2_12_PARAMS, t12::POSEIDON_GOLDILOCKS_12_PARAMS,
+        t8::POSEIDON_GOLDILOCKS_8_PARAMS,
+    },
+    grumpkin::{t3::POSEIDON_GRUMPKIN_3_PARAMS, t4::POSEIDON_GRUMPKIN_4_PARAMS},
+    parameters::PoseidonParams,
+    pasta::{fp_t3::POSEIDON_PASTA_FP_3_PARAMS, fq_t3::POSEIDON_PASTA_FQ_3_PARAMS},
+    poseidon::Poseidon,
+    small_fields::{
+        babybear_t16::POSEIDON_BABYBEAR_16_PARAMS, babybear_t24::POSEIDON_BABYBEAR_24_PARAMS,
+        mersenne31_t16::POSEIDON_MERSENNE31_16_PARAMS,
+        mersenne31_t24::POSEIDON_MERSENNE31_24_PARAMS,
+    },
+};
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use lazy_static::lazy_static;
+use std::{
+    any::Any,
+    borrow::ToOwned,
+    boxed::Box,
+    collections::{BTreeMap, HashMap},
+    string::String,
+    sync::{Arc, RwLock},
+    vec::Vec,
+};
+
+/// A thread-safe registry which lazily constructs [`Poseidon`] instances and caches them by the
+/// fingerprint of their parameters (see [`PoseidonParams::fingerprint`]). Requesting an instance
+/// for parameters with the same fingerprint returns a handle to the same instance. Since the
+/// fingerprint is cached in the parameters, only the first lookup of a parameter set hashes its
+/// constants.
+#[derive(Debug, Default)]
+pub struct PoseidonRegistry {
+    instances: RwLock<HashMap<[u8; 32], Arc<Poseidon<Fr>>>>,
+}
+
+impl PoseidonRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached instance for the given parameters, constructing it on first use.
+    pub fn get(&self, params: &Arc<PoseidonParams<Fr>>) -> Arc<Poseidon<Fr>> {
+        let fingerprint = params.fingerprint();
+        if let Some(poseidon) = self
+            .instances
+            .read()
+            .expect("registry lock is poisoned")
+            .get(&fingerprint)
+        {
+            return poseidon.to_owned();
+        }
+
+        self.instances
+            .write()
+            .expect("registry lock is poisoned")
+            .entry(fingerprint)
+            .or_insert_with(|| Arc::new(Poseidon::new(params)))
+            .to_owned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.instances
+            .read()
+            .expect("registry lock is poisoned")
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// A type-erased `Arc<PoseidonParams<F>>`
+type AnyParams = Box<dyn Any + Send + Sync>;
+
+#[derive(Debug)]
+enum Entry {
+    // the shipped sets are only constructed when they are requested
+    Builtin(fn() -> AnyParams),
+    Registered(AnyParams),
+}
+
+macro_rules! builtin {
+    ($($name:literal => $params:ident,)*) => {
+        [$(($name, (|| Box::new($params.to_owned())) as fn() -> AnyParams),)*]
+    };
+}
+
+/// A thread-safe registry of parameter sets by name. The sets can be defined over any field, and
+/// looking a set up with another field fails.
+#[derive(Debug, Default)]
+pub struct ParamsRegistry {
+    sets: RwLock<BTreeMap<String, Entry>>,
+}
+
+impl ParamsRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry containing the shipped parameter sets, see the [module](self)
+    /// documentation for their names.
+    pub fn with_builtin() -> Self {
+        #[allow(unused_mut)]
+        let mut builtin = Vec::from(builtin! {
+            "bn254/circom/t2" => POSEIDON_CIRCOM_BN_2_PARAMS,
+            "bn254/circom/t3" => POSEIDON_CIRCOM_BN_3_PARAMS,
+            "bn254/circom/t4" => POSEIDON_CIRCOM_BN_4_PARAMS,
+            "bn254/circom/t5" => POSEIDON_CIRCOM_BN_5_PARAMS,
+            "bn254/circom/t6" => POSEIDON_CIRCOM_BN_6_PARAMS,
+            "bn254/circom/t7" => POSEIDON_CIRCOM_BN_7_PARAMS,
+            "bn254/circom/t8" => POSEIDON_CIRCOM_BN_8_PARAMS,
+            "bn254/circom/t9" => POSEIDON_CIRCOM_BN_9_PARAMS,
+            "bn254/circom/t10" => POSEIDON_CIRCOM_BN_10_PARAMS,
+            "bn254/circom/t11" => POSEIDON_CIRCOM_BN_11_PARAMS,
+            "bn254/circom/t12" => POSEIDON_CIRCOM_BN_12_PARAMS,
+            "bn254/circom/t13" => POSEIDON_CIRCOM_BN_13_PARAMS,
+            "bn254/circom/t14" => POSEIDON_CIRCOM_BN_14_PARAMS,
+            "bn254/circom/t15" => POSEIDON_CIRCOM_BN_15_PARAMS,
+            "bn254/circom/t16" => POSEIDON_CIRCOM_BN_16_PARAMS,
+            "bn254/circom/t17" => POSEIDON_CIRCOM_BN_17_PARAMS,
+            "pallas/halo2/t3" => POSEIDON_PASTA_FP_3_PARAMS,
+            "vesta/halo2/t3" => POSEIDON_PASTA_FQ_3_PARAMS,
+            "goldilocks/plonky2/t12" => POSEIDON_PLONKY2_12_PARAMS,
+            "goldilocks/grain/t8" => POSEIDON_GOLDILOCKS_8_PARAMS,
+            "goldilocks/grain/t12" => POSEIDON_GOLDILOCKS_12_PARAMS,
+            "grumpkin/grain/t3" => POSEIDON_GRUMPKIN_3_PARAMS,
+            "grumpkin/grain/t4" => POSEIDON_GRUMPKIN_4_PARAMS,
+            "babybear/grain/t16" => POSEIDON_BABYBEAR_16_PARAMS,
+            "babybear/grain/t24" => POSEIDON_BABYBEAR_24_PARAMS,
+            "mersenne31/grain/t16" => POSEIDON_MERSENNE31_16_PARAMS,
+            "mersenne31/grain/t24" => POSEIDON_MERSENNE31_24_PARAMS,
+        });
+        #[cfg(feature = "bls12-381")]
+        {
+            use crate::bls12_381::{
+                hades_t5::POSEIDON_HADES_5_PARAMS, neptune_t3::POSEIDON_NEPTUNE_3_PARAMS,
+                neptune_t5::POSEIDON_NEPTUNE_5_PARAMS, neptune_t9::POSEIDON_NEPTUNE_9_PARAMS,
+            };
+            builtin.extend(builtin! {
+                "bls12_381/hades/t5" => POSEIDON_HADES_5_PARAMS,
+                "bls12_381/neptune/t3" => POSEIDON_NEPTUNE_3_PARAMS,
+                "bls12_381/neptune/t5" => POSEIDON_NEPTUNE_5_PARAMS,
+                "bls12_381/neptune/t9" => POSEIDON_NEPTUNE_9_PARAMS,
+            });
+        }
+        let sets = builtin
+            .into_iter()
+            .map(|(name, load)| (name.to_owned(), Entry::Builtin(load)))
+            .collect();
+        ParamsRegistry {
+            sets: RwLock::new(sets),
+        }
+    }
+
+    /// Registers the parameters under the given name, fails if the name is already taken.
+    pub fn register<F: PrimeField>(
+        &self,
+        name: &str,
+        params: Arc<PoseidonParams<F>>,
+    ) -> Result<(), Error> {
+        let mut sets = self.sets.write().expect("registry lock is poisoned");
+        if sets.contains_key(name) {
+            return Err(Error::DuplicateParameters(name.to_owned()));
+        }
+        sets.insert(name.to_owned(), Entry::Registered(Box::new(params)));
+        Ok(())
+    }
+
+    /// Returns the parameter set with the given name, fails if no set with this name is known
+    /// or if it is defined over another field than `F`.
+    pub fn get<F: PrimeField>(&self, name: &str) -> Result<Arc<PoseidonParams<F>>, Error> {
+        let sets = self.sets.read().expect("registry lock is poisoned");
+        let entry = sets
+            .get(name)
+            .ok_or_else(|| Error::UnknownParameters(name.to_owned()))?;
+        let builtin;
+        let params = match entry {
+            Entry::Builtin(load) => {
+                builtin = load();
+                &builtin
+            }
+            Entry::Registered(params) => params,
+        };
+        params
+            .downcast_ref::<Arc<PoseidonParams<F>>>()
+            .cloned()
+            .ok_or_else(|| Error::ParametersFieldMismatch(name.to_owned()))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.sets
+            .read()
+            .expect("registry lock is poisoned")
+            .contains_key(name)
+    }
+
+    /// The names of all parameter sets in lexicographic order.
+    pub fn names(&self) -> Vec<String> {
+        self.sets
+            .read()
+            .expect("registry lock is poisoned")
+            .keys()
+            .cloned()
+            .collect()
+    }
+}
+
+lazy_static! {
+    static ref GLOBAL_REGISTRY: ParamsRegistry = ParamsRegistry::with_builtin();
+}
+
+/// The process-wide registry, which initially contains the shipped parameter sets.
+pub fn global() -> &'static ParamsRegistry {
+    &GLOBAL_REGISTRY
+}
+
+/// Returns the parameter set with the given name from the [`global`] registry, e.g.,
+/// `params::<ark_bn254::Fr>("bn254/circom/t3")`.
+pub fn params<F: PrimeField>(name: &str) -> Result<Arc<PoseidonParams<F>>, Error> {
+    global().get(name)
+}
+
+/// Registers the parameters under the given name in the [`global`] registry.
+pub fn register<F: PrimeField>(name: &str, params: Arc<PoseidonParams<F>>) -> Result<(), Error> {
+    global().register(name, params)
+}
+
+#[cfg(test)]
+mod registry_tests {
+    use super::*;
+
+    #[test]
+    fn shared_instances() {
+        let registry = PoseidonRegistry::new();
+        assert!(registry.is_empty());
+
+        let poseidon1 = registry.get(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let poseidon2 = registry.get(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        assert!(Arc::ptr_eq(&poseidon1.params, &poseidon2.params));
+        assert!(Arc::ptr_eq(&poseidon1, &poseidon2));
+
+        // equal parameters in a different allocation map to the cached instance
+        let copy = Arc::new(POSEIDON_CIRCOM_BN_3_PARAMS.as_ref().to_owned());
+        let poseidon3 = registry.get(&copy);
+        assert!(Arc::ptr_eq(&poseidon1.params, &poseidon3.params));
+
+        let poseidon4 = registry.get(&POSEIDON_CIRCOM_BN_4_PARAMS);
+        assert!(!Arc::ptr_eq(&poseidon1.params, &poseidon4.params));
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn named_params() {
+        use crate::{goldilocks::field::Goldilocks, pasta::fields::Fp};
+
+        let registry = ParamsRegistry::with_builtin();
+        let params = registry.get::<Fr>("bn254/circom/t3").unwrap();
+        assert!(Arc::ptr_eq(&params, &POSEIDON_CIRCOM_BN_3_PARAMS));
+        let params = registry.get::<Fp>("pallas/halo2/t3").unwrap();
+        assert!(Arc::ptr_eq(&params, &POSEIDON_PASTA_FP_3_PARAMS));
+        let bls12_381 = if cfg!(feature = "bls12-381") { 4 } else { 0 };
+        assert_eq!(registry.names().len(), 27 + bls12_381);
+        assert!(registry
+            .names()
+            .iter()
+            .all(|name| name.split('/').count() == 3));
+
+        assert!(matches!(
+            registry.get::<Fr>("bls12_381/neptune/a4"),
+            Err(Error::UnknownParameters(_))
+        ));
+        assert!(matches!(
+            registry.get::<Goldilocks>("bn254/circom/t3"),
+            Err(Error::ParametersFieldMismatch(_))
+        ));
+
+        // registering at runtime
+        let custom = Arc::new(POSEIDON_GOLDILOCKS_8_PARAMS.as_ref().to_owned());
+        registry
+            .register("goldilocks/custom/t8", custom.clone())
+            .unwrap();
+        assert!(registry.contains("goldilocks/custom/t8"));
+        let registered = registry.get::<Goldilocks>("goldilocks/custom/t8").unwrap();
+        assert!(Arc::ptr_eq(&registered, &custom));
+        assert!(matches!(
+            registry.register("bn254/circom/t3", custom),
+            Err(Error::DuplicateParameters(_))
+        ));
+
+        assert!(ParamsRegistry::new().names().is_empty());
+        assert!(Arc::ptr_eq(
+            &super::params::<Fr>("bn254/circom/t17").unwrap(),
+            &POSEIDON_CIRCOM_BN_17_PARAMS
+        ));
+    }
+
+    #[test]
+    fn concurrent_access() {
+        let registry = Arc::new(PoseidonRegistry::new());
+        let handles = (0..4)
+            .map(|_| {
+                let registry = registry.clone();
+                std::thread::spawn(move || registry.get(&POSEIDON_CIRCOM_BN_3_PARAMS))
+            })
+            .collect::<Vec<_>>();
+        let instances = handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .collect::<Vec<_>>();
+        for poseidon in instances.iter() {
+            assert!(Arc::ptr_eq(&instances[0].params, &poseidon.params));
+        }
+        assert_eq!(registry.len(), 1);
+    }
+}
@@ -0,0 +1,217 @@
+//! # Rescue-Prime
+//! Contains the Rescue-Prime permutation as an alternative to Poseidon with the same interface.
+//! Every round applies the sbox x^alpha to all elements, the MDS matrix, and the first round
+//! constants, followed by the inverse sbox x^(1/alpha), the MDS matrix, and the second round
+//! constants. The inverse sbox makes Rescue-Prime more expensive to evaluate than Poseidon, but
+//! it needs considerably fewer rounds.
+//!
+//! The parameters are given explicitly like for [`PoseidonParams`](crate::parameters::PoseidonParams).
+//! [`rp64_256`](rp64_256::RESCUE_RP64_256_PARAMS) contains the instance of winterfell's
+//! `Rp64_256` over Goldilocks (t = 12, alpha = 7, 7 rounds) with its MDS matrix and round
+//! constants, checked against the vector of its sage reference implementation.
+
+pub mod parameters;
+pub mod rp64_256;
+
+use crate::{error::Error, poseidon::pow_sbox};
+use alloc::{sync::Arc, vec, vec::Vec};
+use ark_ff::PrimeField;
+use parameters::RescueParams;
+
+#[derive(Clone, Debug)]
+pub struct RescuePrime<F: PrimeField> {
+    pub(crate) params: Arc<RescueParams<F>>,
+}
+
+impl<F: PrimeField> RescuePrime<F> {
+    pub fn new(params: &Arc<RescueParams<F>>) -> Self {
+        RescuePrime {
+            params: params.clone(),
+        }
+    }
+
+    pub fn get_t(&self) -> usize {
+        self.params.t
+    }
+
+    pub fn permutation(&self, input: Vec<F>) -> Result<Vec<F>, Error> {
+        let mut state = input;
+        self.permutation_in_place(&mut state)?;
+        Ok(state)
+    }
+
+    /// Applies the permutation to the given state in place.
+    pub fn permutation_in_place(&self, state: &mut [F]) -> Result<(), Error> {
+        let params = &self.params;
+        let t = params.t;
+        if state.len() != t {
+            return Err(Error::WrongInputLength {
+                expected: t,
+                got: state.len(),
+            });
+        }
+        let mut scratch = vec![F::zero(); t];
+        for rc in params.round_constants.chunks_exact(2) {
+            state
+                .iter_mut()
+                .for_each(|el| *el = pow_sbox(*el, params.alpha));
+            params.mds.mat_vec_mul_add_into(state, &rc[0], &mut scratch);
+            state.copy_from_slice(&scratch);
+
+            state
+                .iter_mut()
+                .for_each(|el| *el = el.pow(&params.alpha_inv));
+            params.mds.mat_vec_mul_add_into(state, &rc[1], &mut scratch);
+            state.copy_from_slice(&scratch);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod rescue_tests {
+    use super::*;
+    use crate::{field_hasher::FieldHasher, merkle::tree::MerkleTree};
+    use ark_ff::{Field, UniformRand};
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+
+    fn random_params(t: usize, rounds: usize) -> Arc<RescueParams<Scalar>> {
+        let mut rng = thread_rng();
+        // Cauchy matrix
+        let mds = (0..t)
+            .map(|i| {
+                (0..t)
+                    .map(|j| Scalar::from((i + t + j) as u64).inverse().unwrap())
+                    .collect()
+            })
+            .collect();
+        let rc = (0..2 * rounds)
+            .map(|_| (0..t).map(|_| Scalar::rand(&mut rng)).collect())
+            .collect();
+        Arc::new(RescueParams::new(t, 5, rounds, mds, rc).unwrap())
+    }
+
+    #[test]
+    fn inverse_sbox() {
+        let mut rng = thread_rng();
+        let params = random_params(3, 8);
+        for _ in 0..5 {
+            let x = Scalar::rand(&mut rng);
+            assert_eq!(pow_sbox(x, 5).pow(&params.alpha_inv), x);
+        }
+    }
+
+    #[test]
+    fn permutation() {
+        let mut rng = thread_rng();
+        let params = random_params(3, 8);
+        let rescue = RescuePrime::new(&params);
+        let input: Vec<Scalar> = (0..3).map(|_| Scalar::rand(&mut rng)).collect();
+
+        // a single round computed by hand
+        let one_round = RescuePrime::new(&Arc::new(
+            RescueParams::new(
+                3,
+                5,
+                1,
+                params.mds.to_vec(),
+                params.round_constants[..2].to_vec(),
+            )
+            .unwrap(),
+        ));
+        let mut expected = params
+            .mds
+            .mat_vec_mul(&input.iter().map(|x| pow_sbox(*x, 5)).collect::<Vec<_>>());
+        expected = add(&expected, &params.round_constants[0]);
+        expected = params.mds.mat_vec_mul(
+            &expected
+                .iter()
+                .map(|x| x.pow(&params.alpha_inv))
+                .collect::<Vec<_>>(),
+        );
+        expected = add(&expected, &params.round_constants[1]);
+        assert_eq!(one_round.permutation(input.to_owned()).unwrap(), expected);
+
+        let output = rescue.permutation(input.to_owned()).unwrap();
+        assert_ne!(output, input);
+        assert!(rescue.permutation(vec![Scalar::from(0); 2]).is_err());
+
+        // usable through the shared interface
+        let leaves: Vec<Scalar> = (0..4).map(Scalar::from).collect();
+        let tree = MerkleTree::with_hasher(
+            rescue.to_owned(),
+            crate::merkle::store::MemoryStore::new(),
+            leaves.to_owned(),
+        )
+        .unwrap();
+        let proof = tree.proof(2).unwrap();
+        assert!(
+            MerkleTree::verify_proof(&rescue, &tree.root(), tree.depth(), leaves[2], &proof)
+                .unwrap()
+        );
+        assert_eq!(
+            rescue.compress(leaves[0], leaves[1]).unwrap(),
+            rescue
+                .permutation(vec![Scalar::from(0), leaves[0], leaves[1]])
+                .unwrap()[0]
+        );
+    }
+
+    // The `apply_permutation` test of winter-crypto 0.13.1, whose expected values are obtained
+    // from the sage reference implementation
+    #[test]
+    fn rp64_256_kat() {
+        use crate::goldilocks::field::Goldilocks;
+        use rp64_256::RESCUE_RP64_256_PARAMS;
+
+        let rescue = RescuePrime::new(&RESCUE_RP64_256_PARAMS);
+        let input: Vec<Goldilocks> = (0..12).map(Goldilocks::from).collect();
+        let expected: Vec<Goldilocks> = [
+            11084501481526603421u64,
+            6291559951628160880,
+            13626645864671311919,
+            18397438323058963117,
+            7443014167353970324,
+            17930833023906771425,
+            4275355080008025761,
+            7676681476902901785,
+            3460534574143792217,
+            11912731278641497187,
+            8104899243369883110,
+            674509706691634438,
+        ]
+        .into_iter()
+        .map(Goldilocks::from)
+        .collect();
+        assert_eq!(rescue.permutation(input).unwrap(), expected);
+    }
+
+    fn add(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
+        a.iter().zip(b.iter()).map(|(a, b)| *a + b).collect()
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let mds = vec![vec![Scalar::from(1); 3]; 3];
+        let rc = vec![vec![Scalar::from(1); 3]; 4];
+        assert!(RescueParams::new(3, 5, 2, mds.to_owned(), rc.to_owned()).is_ok());
+        // x^3 is not a permutation over BN254
+        assert!(matches!(
+            RescueParams::new(3, 3, 2, mds.to_owned(), rc.to_owned()),
+            Err(Error::InvalidParameters)
+        ));
+        assert!(matches!(
+            RescueParams::new(3, 5, 3, mds.to_owned(), rc.to_owned()),
+            Err(Error::RoundConstantShape {
+                expected: (6, 3),
+                got: (4, 3)
+            })
+        ));
+        assert!(matches!(
+            RescueParams::new(3, 5, 2, mds[..2].to_vec(), rc),
+            Err(Error::NonSquareMds { t: 3, got: (2, 3) })
+        ));
+    }
+}
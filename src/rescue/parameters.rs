@@ -0,0 +1,85 @@
+use crate::{
+    error::Error,
+    parameters::{
+        bytes::decode_elements, check_mds, check_round_constants, inverse_exponent, matrix::Matrix,
+    },
+};
+use alloc::vec::Vec;
+use ark_ff::PrimeField;
+
+#[derive(Clone, Debug)]
+pub struct RescueParams<F: PrimeField> {
+    pub(crate) t: usize,            // statesize
+    pub(crate) alpha: usize,        // sbox degree
+    pub(crate) alpha_inv: Vec<u64>, // exponent of the inverse sbox, 1 / alpha mod p - 1
+    pub(crate) rounds: usize,
+    pub(crate) mds: Matrix<F>,
+    pub(crate) round_constants: Vec<Vec<F>>, // two rows per round
+}
+
+impl<F: PrimeField> RescueParams<F> {
+    /// Creates a new parameter set. The sbox x^alpha has to be a permutation, i.e., alpha has to
+    /// be coprime to p - 1. The round constants contain two rows of length t per round, the
+    /// first one is added after the forward sbox layer and the second one after the inverse sbox
+    /// layer.
+    pub fn new(
+        t: usize,
+        alpha: usize,
+        rounds: usize,
+        mds: Vec<Vec<F>>,
+        round_constants: Vec<Vec<F>>,
+    ) -> Result<Self, Error> {
+        if t < 2 || alpha < 3 {
+            return Err(Error::InvalidParameters);
+        }
+        check_mds(t, &mds)?;
+        check_round_constants(2 * rounds, t, &round_constants)?;
+        let alpha_inv = inverse_exponent::<F>(alpha)?;
+
+        Ok(RescueParams {
+            t,
+            alpha,
+            alpha_inv,
+            rounds,
+            mds: Matrix::new(mds)?,
+            round_constants,
+        })
+    }
+
+    /// Loads parameters from the binary encoding of the MDS matrix followed by the round
+    /// constants, as for [`PoseidonParams::from_bytes`](crate::parameters::PoseidonParams::from_bytes).
+    pub fn from_bytes(t: usize, alpha: usize, rounds: usize, bytes: &[u8]) -> Result<Self, Error> {
+        if t == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        let count = rounds
+            .checked_mul(2)
+            .and_then(|rows| rows.checked_add(t))
+            .and_then(|rows| rows.checked_mul(t))
+            .ok_or(Error::InvalidParameters)?;
+        let elements = decode_elements::<F>(bytes, count)?;
+        let (mds, round_constants) = elements.split_at(t * t);
+        let mds = mds.chunks(t).map(<[F]>::to_vec).collect();
+        let round_constants = round_constants.chunks(t).map(<[F]>::to_vec).collect();
+        Self::new(t, alpha, rounds, mds, round_constants)
+    }
+
+    /// The statesize.
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// The degree of the forward sbox.
+    pub fn alpha(&self) -> usize {
+        self.alpha
+    }
+
+    /// The number of rounds, each consisting of a forward and an inverse sbox layer.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    pub fn mds(&self) -> &Matrix<F> {
+        &self.mds
+    }
+}
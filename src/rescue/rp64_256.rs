@@ -0,0 +1,20 @@
+// Autogenerated file
+// KAT:
+// RescuePrime([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]) = [0x99d40f5e86af2e9d, 0x57501c711fdecf70, 0xbd1b923783c7782f, 0xff50d4ae571672ad, 0x674ae5f34f554e94, 0xf8d71da234ce71e1, 0x3b551ccb208a46a1, 0x6a890d21649e5819, 0x300649dc48cc6459, 0xa55285fb481c8463, 0x707a62eb9564e5e6, 0x095c570128e86506];
+use super::parameters::RescueParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::goldilocks::field::Goldilocks;
+
+lazy_static! {
+    pub static ref RESCUE_RP64_256_PARAMS: Arc<RescueParams<Scalar>> = Arc::new(
+        RescueParams::from_bytes(
+            12,
+            7,
+            7,
+            include_bytes!(concat!(env!("OUT_DIR"), "/params/rescue/rp64_256.bin")),
+        )
+        .unwrap()
+    );
+}
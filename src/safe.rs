@@ -0,0 +1,300 @@
+//! # SAFE
+//! Contains an implementation of SAFE (Sponge API for Field Elements) over the Poseidon
+//! permutation with capacity 1. Before use, the sponge is instantiated with an IO pattern, i.e.,
+//! the sequence of absorb and squeeze calls the protocol is going to make. The pattern is
+//! compressed into a tag, which is placed in the capacity element, such that sponges used with
+//! different patterns or domain separators produce independent outputs. Every call has to match
+//! the next operation of the declared pattern, otherwise the state is cleared and the sponge
+//! returns [`Error::IOPatternViolation`].
+//!
+//! The tag is computed as specified: consecutive operations of the same kind are aggregated, each
+//! operation is encoded as 32-bit big-endian word (the length, with the most significant bit set
+//! for absorbing), the domain separator is appended, and the first 128 bits of the SHA3-256 hash
+//! of the result are interpreted as little-endian integer.
+
+use crate::{error::Error, parameters::PoseidonParams, poseidon::Poseidon};
+use alloc::{sync::Arc, vec, vec::Vec};
+use ark_ff::PrimeField;
+use sha3::{Digest, Sha3_256};
+
+const ABSORB_FLAG: u32 = 1 << 31;
+
+/// A single operation of an [`IOPattern`] with its length in field elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpongeOp {
+    Absorb(u32),
+    Squeeze(u32),
+}
+
+/// The sequence of calls a [`SafeSponge`] accepts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IOPattern(pub Vec<SpongeOp>);
+
+impl IOPattern {
+    /// Aggregates consecutive operations of the same kind into their encoding as 32-bit words.
+    /// Fails if the pattern is empty or contains an (aggregated) length outside of 1..2^31.
+    fn encode(&self) -> Result<Vec<u32>, Error> {
+        let mut words: Vec<u32> = Vec::with_capacity(self.0.len());
+        let mut last_absorb = None;
+        for op in &self.0 {
+            let (absorb, len) = match op {
+                SpongeOp::Absorb(len) => (true, *len),
+                SpongeOp::Squeeze(len) => (false, *len),
+            };
+            if len == 0 || len >= ABSORB_FLAG {
+                return Err(Error::InvalidParameters);
+            }
+            match words.last_mut() {
+                Some(word) if last_absorb == Some(absorb) => {
+                    let aggregated = (*word & !ABSORB_FLAG) + len;
+                    if aggregated >= ABSORB_FLAG {
+                        return Err(Error::InvalidParameters);
+                    }
+                    *word += len;
+                }
+                _ => words.push(if absorb { ABSORB_FLAG | len } else { len }),
+            }
+            last_absorb = Some(absorb);
+        }
+        if words.is_empty() {
+            return Err(Error::InvalidParameters);
+        }
+        Ok(words)
+    }
+
+    /// Computes the tag of the pattern for the given domain separator.
+    pub fn tag<F: PrimeField>(&self, domain: &[u8]) -> Result<F, Error> {
+        let mut hasher = Sha3_256::new();
+        for word in self.encode()? {
+            hasher.update(word.to_be_bytes());
+        }
+        hasher.update(domain);
+        Ok(F::from_le_bytes_mod_order(&hasher.finalize()[..16]))
+    }
+}
+
+/// A duplex sponge over the Poseidon permutation following the SAFE API.
+#[derive(Clone, Debug)]
+pub struct SafeSponge<F: PrimeField> {
+    poseidon: Poseidon<F>,
+    state: Vec<F>,
+    pattern: IOPattern,
+    io_count: usize,
+    absorb_pos: usize,
+    squeeze_pos: usize,
+}
+
+impl<F: PrimeField> SafeSponge<F> {
+    /// Starts a sponge for the IO pattern and domain separator.
+    pub fn start(
+        params: &Arc<PoseidonParams<F>>,
+        pattern: IOPattern,
+        domain: &[u8],
+    ) -> Result<Self, Error> {
+        let tag = pattern.tag(domain)?;
+        let poseidon = Poseidon::new(params);
+        let t = poseidon.get_t();
+        if t < 2 {
+            return Err(Error::InvalidParameters);
+        }
+        let mut state = vec![F::zero(); t];
+        state[0] = tag;
+        Ok(SafeSponge {
+            poseidon,
+            state,
+            pattern,
+            io_count: 0,
+            absorb_pos: 0,
+            squeeze_pos: 0,
+        })
+    }
+
+    fn rate(&self) -> usize {
+        self.state.len() - 1
+    }
+
+    fn permute(&mut self) -> Result<(), Error> {
+        let state = core::mem::take(&mut self.state);
+        self.state = self.poseidon.permutation(state)?;
+        Ok(())
+    }
+
+    // Checks the call against the next operation of the pattern, clears the state on a mismatch
+    fn expect(&mut self, op: SpongeOp) -> Result<(), Error> {
+        if self.pattern.0.get(self.io_count) != Some(&op) {
+            self.clear();
+            return Err(Error::IOPatternViolation);
+        }
+        self.io_count += 1;
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.state.iter_mut().for_each(|el| *el = F::zero());
+        // no further call can match the pattern
+        self.io_count = usize::MAX;
+    }
+
+    pub fn absorb(&mut self, input: &[F]) -> Result<(), Error> {
+        let len = u32::try_from(input.len()).map_err(|_| Error::IOPatternViolation)?;
+        self.expect(SpongeOp::Absorb(len))?;
+        for el in input {
+            if self.absorb_pos == self.rate() {
+                self.permute()?;
+                self.absorb_pos = 0;
+            }
+            self.state[self.absorb_pos + 1] += el;
+            self.absorb_pos += 1;
+        }
+        // the next squeeze permutes first
+        self.squeeze_pos = self.rate();
+        Ok(())
+    }
+
+    pub fn squeeze(&mut self, n: usize) -> Result<Vec<F>, Error> {
+        let len = u32::try_from(n).map_err(|_| Error::IOPatternViolation)?;
+        self.expect(SpongeOp::Squeeze(len))?;
+        let mut output = Vec::with_capacity(n);
+        for _ in 0..n {
+            if self.squeeze_pos == self.rate() {
+                self.permute()?;
+                self.squeeze_pos = 0;
+                self.absorb_pos = 0;
+            }
+            output.push(self.state[self.squeeze_pos + 1]);
+            self.squeeze_pos += 1;
+        }
+        Ok(output)
+    }
+
+    /// Finishes the sponge, fails if not all operations of the pattern were called.
+    pub fn finish(mut self) -> Result<(), Error> {
+        let complete = self.io_count == self.pattern.0.len();
+        self.clear();
+        if complete {
+            Ok(())
+        } else {
+            Err(Error::IOPatternViolation)
+        }
+    }
+}
+
+#[cfg(test)]
+mod safe_tests {
+    use super::*;
+    use crate::bn254::{
+        circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS,
+    };
+
+    type Scalar = ark_bn254::Fr;
+
+    fn inputs(n: u64) -> Vec<Scalar> {
+        (1..=n).map(Scalar::from).collect()
+    }
+
+    #[test]
+    fn tag() {
+        let aggregated = IOPattern(vec![SpongeOp::Absorb(5), SpongeOp::Squeeze(1)]);
+        let split = IOPattern(vec![
+            SpongeOp::Absorb(2),
+            SpongeOp::Absorb(3),
+            SpongeOp::Squeeze(1),
+        ]);
+        assert_eq!(
+            aggregated.tag::<Scalar>(b"test").unwrap(),
+            split.tag::<Scalar>(b"test").unwrap()
+        );
+        assert_ne!(
+            aggregated.tag::<Scalar>(b"test").unwrap(),
+            aggregated.tag::<Scalar>(b"other").unwrap()
+        );
+        let longer = IOPattern(vec![SpongeOp::Absorb(5), SpongeOp::Squeeze(2)]);
+        assert_ne!(
+            aggregated.tag::<Scalar>(b"test").unwrap(),
+            longer.tag::<Scalar>(b"test").unwrap()
+        );
+
+        // the tag is the truncated SHA3-256 hash of the encoding
+        let mut encoding = vec![0x80, 0, 0, 5, 0, 0, 0, 1];
+        encoding.extend_from_slice(b"test");
+        let hash = Sha3_256::digest(&encoding);
+        assert_eq!(
+            aggregated.tag::<Scalar>(b"test").unwrap(),
+            Scalar::from_le_bytes_mod_order(&hash[..16])
+        );
+    }
+
+    #[test]
+    fn duplex() {
+        let pattern = IOPattern(vec![
+            SpongeOp::Absorb(3),
+            SpongeOp::Squeeze(1),
+            SpongeOp::Absorb(1),
+            SpongeOp::Squeeze(3),
+        ]);
+        let mut sponge =
+            SafeSponge::start(&POSEIDON_CIRCOM_BN_3_PARAMS, pattern.clone(), b"test").unwrap();
+        sponge.absorb(&inputs(3)).unwrap();
+        let out1 = sponge.squeeze(1).unwrap();
+        sponge.absorb(&inputs(1)).unwrap();
+        let out2 = sponge.squeeze(3).unwrap();
+        sponge.finish().unwrap();
+
+        // the same computation with the bare permutation (rate 2)
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        let tag = pattern.tag::<Scalar>(b"test").unwrap();
+        let state = poseidon
+            .permutation(vec![tag, Scalar::from(1), Scalar::from(2)])
+            .unwrap();
+        let state = poseidon
+            .permutation(vec![state[0], state[1] + Scalar::from(3), state[2]])
+            .unwrap();
+        assert_eq!(out1, vec![state[1]]);
+        let state = poseidon
+            .permutation(vec![state[0], state[1] + Scalar::from(1), state[2]])
+            .unwrap();
+        let next = poseidon.permutation(state.to_owned()).unwrap();
+        assert_eq!(out2, vec![state[1], state[2], next[1]]);
+    }
+
+    #[test]
+    fn pattern_violations() {
+        let pattern = IOPattern(vec![SpongeOp::Absorb(2), SpongeOp::Squeeze(1)]);
+        let start =
+            || SafeSponge::start(&POSEIDON_CIRCOM_BN_5_PARAMS, pattern.clone(), b"").unwrap();
+
+        let mut sponge = start();
+        assert!(matches!(
+            sponge.absorb(&inputs(3)),
+            Err(Error::IOPatternViolation)
+        ));
+        // the sponge stays unusable after a violation
+        assert!(matches!(
+            sponge.absorb(&inputs(2)),
+            Err(Error::IOPatternViolation)
+        ));
+
+        let mut sponge = start();
+        assert!(matches!(sponge.squeeze(1), Err(Error::IOPatternViolation)));
+
+        let mut sponge = start();
+        sponge.absorb(&inputs(2)).unwrap();
+        assert!(matches!(sponge.finish(), Err(Error::IOPatternViolation)));
+
+        let mut sponge = start();
+        sponge.absorb(&inputs(2)).unwrap();
+        sponge.squeeze(1).unwrap();
+        assert!(matches!(sponge.squeeze(1), Err(Error::IOPatternViolation)));
+
+        for invalid in [
+            vec![],
+            vec![SpongeOp::Absorb(0)],
+            vec![SpongeOp::Squeeze(1 << 31)],
+        ] {
+            assert!(matches!(
+                SafeSponge::start(&POSEIDON_CIRCOM_BN_5_PARAMS, IOPattern(invalid), b""),
+                Err(Error::InvalidParameters)
+            ));
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! # Semaphore
+//! Contains the hashes of the Semaphore protocol over the BN254 scalar field, such that
+//! identities, nullifiers, and groups can be computed outside of the TypeScript implementation:
+//!
+//! - the identity secret `Poseidon(identityNullifier, identityTrapdoor)` and the identity
+//!   commitment `Poseidon(secret)`,
+//! - the nullifier hash `Poseidon(externalNullifier, identityNullifier)`,
+//! - the hash of signals and external nullifiers, i.e., keccak256 shifted right by 8 bits,
+//! - the group, a [`LeanMerkleTree`] over the identity commitments as in Semaphore v4.
+//!
+//! All hashes use the circom parameters. The EdDSA identities of Semaphore v4, whose commitment
+//! is the hash of the public key, are not covered by this module.
+
+use crate::{
+    bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+    circom::poseidon_hash,
+    error::Error,
+    merkle::lean::{LeanMerkleProof, LeanMerkleTree},
+};
+use ark_bn254::Fr;
+use ark_ff::{PrimeField, Zero};
+use sha3::{Digest, Keccak256};
+
+/// Returns the secret of an identity.
+pub fn identity_secret(identity_nullifier: Fr, identity_trapdoor: Fr) -> Result<Fr, Error> {
+    poseidon_hash(&[identity_nullifier, identity_trapdoor])
+}
+
+/// Returns the public commitment of an identity, which is added to groups.
+pub fn identity_commitment(secret: Fr) -> Result<Fr, Error> {
+    poseidon_hash(&[secret])
+}
+
+/// Returns the nullifier hash, which prevents an identity from signaling twice for the same
+/// external nullifier.
+pub fn nullifier_hash(external_nullifier: Fr, identity_nullifier: Fr) -> Result<Fr, Error> {
+    poseidon_hash(&[external_nullifier, identity_nullifier])
+}
+
+/// Hashes a signal or an external nullifier, given as the bytes the contract hashes, into the
+/// field. The keccak256 hash is shifted right by 8 bits, such that it is smaller than the
+/// modulus.
+pub fn hash_to_field(message: &[u8]) -> Fr {
+    let hash = Keccak256::digest(message);
+    Fr::from_be_bytes_mod_order(&hash[..31])
+}
+
+/// A Semaphore group, i.e., the tree of the identity commitments of its members.
+#[derive(Clone, Debug)]
+pub struct Group {
+    tree: LeanMerkleTree<Fr>,
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Group {
+            tree: LeanMerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS)
+                .expect("the circom parameters have statesize 3"),
+        }
+    }
+}
+
+impl Group {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a member and returns its index, fails for the zero commitment, which marks removed
+    /// members.
+    pub fn add_member(&mut self, commitment: Fr) -> Result<usize, Error> {
+        if commitment.is_zero() {
+            return Err(Error::InvalidParameters);
+        }
+        self.tree.insert(commitment)
+    }
+
+    pub fn update_member(&mut self, index: usize, commitment: Fr) -> Result<(), Error> {
+        if commitment.is_zero() {
+            return Err(Error::InvalidParameters);
+        }
+        self.tree.update(index, commitment)
+    }
+
+    /// Removes a member by setting its leaf to zero, the indices of the other members are kept.
+    pub fn remove_member(&mut self, index: usize) -> Result<(), Error> {
+        self.tree.update(index, Fr::zero())
+    }
+
+    pub fn index_of(&self, commitment: &Fr) -> Option<usize> {
+        self.tree.index_of(commitment)
+    }
+
+    pub fn members(&self) -> &[Fr] {
+        self.tree.leaves()
+    }
+
+    pub fn size(&self) -> usize {
+        self.tree.size()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.tree.depth()
+    }
+
+    /// Returns the root, or `None` if the group has no members.
+    pub fn root(&self) -> Option<Fr> {
+        self.tree.root()
+    }
+
+    pub fn merkle_proof(&self, index: usize) -> Result<LeanMerkleProof<Fr>, Error> {
+        self.tree.proof(index)
+    }
+
+    /// Verifies the proof against the root it contains.
+    pub fn verify_merkle_proof(&self, proof: &LeanMerkleProof<Fr>) -> Result<bool, Error> {
+        LeanMerkleTree::verify_proof(self.tree.poseidon(), proof)
+    }
+}
+
+#[cfg(test)]
+mod semaphore_tests {
+    use super::*;
+    use crate::field_from_hex_string;
+
+    fn hex(str: &str) -> Fr {
+        field_from_hex_string(str).unwrap()
+    }
+
+    #[test]
+    fn identity_and_nullifier() {
+        // circomlibjs: poseidon([1, 2])
+        let expected = hex("0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a");
+        let secret = identity_secret(Fr::from(1), Fr::from(2)).unwrap();
+        assert_eq!(secret, expected);
+        assert_eq!(nullifier_hash(Fr::from(1), Fr::from(2)).unwrap(), expected);
+        assert_eq!(
+            identity_commitment(secret).unwrap(),
+            poseidon_hash(&[secret]).unwrap()
+        );
+        assert_ne!(
+            nullifier_hash(Fr::from(2), Fr::from(1)).unwrap(),
+            nullifier_hash(Fr::from(1), Fr::from(2)).unwrap()
+        );
+    }
+
+    // semaphore-rs 0.5.4: `Identity::from_secret(b"secret", None)`, whose trapdoor and nullifier
+    // are sha256(hex(sha256("secret")) || "identity_trapdoor" / "identity_nullifier") mod p, and
+    // `generate_nullifier_hash(&identity, hash_to_field(b"app_id"))`.
+    #[test]
+    fn semaphore_rs_identity() {
+        let trapdoor = hex("0x2d0d2d53435a47f2f699c2947030b0cc9f7ebbaa7a3918af8d9487f5834542c6");
+        let nullifier = hex("0x156b28cb55283374abebde46bb4a533dfaa5be822d6d4d82a95c4cac5d2c32a5");
+        let secret = identity_secret(nullifier, trapdoor).unwrap();
+        assert_eq!(
+            secret,
+            hex("0x086a647240229aa93a5a891f862d300d483f6782079aa503ab9e695957a22ee1")
+        );
+        assert_eq!(
+            identity_commitment(secret).unwrap(),
+            hex("0x17501e26df55e3917cde218007e7577fca0d704f55673e1228e3789426d1d0b1")
+        );
+        let external_nullifier = hash_to_field(b"app_id");
+        assert_eq!(
+            external_nullifier,
+            hex("0x00a9bd22903c33d4a27fe3899730091e82472e492d8772bec671efe3a41b6110")
+        );
+        assert_eq!(
+            nullifier_hash(external_nullifier, nullifier).unwrap(),
+            hex("0x1e69d25e5c678fd25df50ae1c1454269c9ce12c9f90282836daabfa7166f62b4")
+        );
+    }
+
+    // The roots of zk-kit-lean-imt 0.1.1's `LeanIMT` over the leaves [1, .., n], hashing the
+    // concatenated big-endian children with semaphore-rs-poseidon 0.5.4's `hash2`.
+    #[test]
+    fn lean_imt_roots() {
+        for (n, expected) in [
+            (
+                1,
+                "0x0000000000000000000000000000000000000000000000000000000000000001",
+            ),
+            (
+                2,
+                "0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a",
+            ),
+            (
+                3,
+                "0x1e8c05563aa22ff357008db7a754ea0404695de07b950ce845b872a8bcff2ca9",
+            ),
+            (
+                5,
+                "0x1973be9a0ac928df30c68c1698876c310c8246a3f215d33764045ec9da859b08",
+            ),
+            (
+                8,
+                "0x2057f9fa34cbdc2664d96ba53ade5d0511262b98f56953039be24ee92f9a7677",
+            ),
+        ] {
+            let mut group = Group::new();
+            for member in 1..=n {
+                group.add_member(Fr::from(member)).unwrap();
+            }
+            assert_eq!(group.root(), Some(hex(expected)));
+        }
+    }
+
+    #[test]
+    fn signal_hash() {
+        // keccak256("") = 0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470
+        assert_eq!(
+            hash_to_field(b""),
+            hex("0x00c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a4")
+        );
+    }
+
+    #[test]
+    fn group() {
+        let mut group = Group::new();
+        assert_eq!(group.root(), None);
+        let members: Vec<Fr> = (1..=3).map(Fr::from).collect();
+        for member in &members {
+            group.add_member(*member).unwrap();
+        }
+        assert_eq!(group.members(), &members);
+        // poseidon([poseidon([1, 2]), 3])
+        let left = hex("0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a");
+        assert_eq!(
+            group.root(),
+            Some(poseidon_hash(&[left, Fr::from(3)]).unwrap())
+        );
+
+        let proof = group.merkle_proof(2).unwrap();
+        assert_eq!(proof.siblings, vec![left]);
+        assert!(group.verify_merkle_proof(&proof).unwrap());
+
+        group.remove_member(0).unwrap();
+        assert_eq!(group.members()[0], Fr::zero());
+        assert_eq!(group.size(), 3);
+        // old proofs are valid for the old root only
+        assert_ne!(Some(proof.root), group.root());
+        assert!(group
+            .verify_merkle_proof(&group.merkle_proof(2).unwrap())
+            .unwrap());
+        assert!(group.add_member(Fr::zero()).is_err());
+        group.update_member(0, Fr::from(4)).unwrap();
+        assert_eq!(group.index_of(&Fr::from(4)), Some(0));
+    }
+}
@@ -0,0 +1,24 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]) = [0x49feffd3, 0x1a4ee7e9, 0x5fe186c1, 0x0ba24809, 0x30c8e988, 0x754fe288, 0x1828e706, 0x2ebc4f67, 0x4792980e, 0x1c59dce3, 0x6e32c576, 0x5c564057, 0x030e052f, 0x3122ac89, 0x66c063fb, 0x64bb0f31];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::small_fields::fields::BabyBear;
+
+lazy_static! {
+    pub static ref POSEIDON_BABYBEAR_16_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            16,
+            7,
+            8,
+            13,
+            include_bytes!(concat!(
+                env!("OUT_DIR"),
+                "/params/small_fields/babybear_t16.bin"
+            )),
+        )
+        .unwrap()
+    );
+}
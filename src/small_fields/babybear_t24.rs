@@ -0,0 +1,24 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23]) = [0x645ee161, 0x3efec82e, 0x259aee11, 0x11a44ea8, 0x322c35b8, 0x0292719f, 0x3b20c6d1, 0x3d308c21, 0x2d5e9579, 0x4e13e36a, 0x627abe1d, 0x1ea6edef, 0x41e3b453, 0x1e46bd43, 0x4d16b384, 0x5f3bea51, 0x4c5aa2c6, 0x0fa134b6, 0x3a27fd29, 0x46b03855, 0x6ae33b7d, 0x595ae084, 0x4bc18e76, 0x59f111a7];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::small_fields::fields::BabyBear;
+
+lazy_static! {
+    pub static ref POSEIDON_BABYBEAR_24_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            24,
+            7,
+            8,
+            21,
+            include_bytes!(concat!(
+                env!("OUT_DIR"),
+                "/params/small_fields/babybear_t24.bin"
+            )),
+        )
+        .unwrap()
+    );
+}
@@ -0,0 +1,238 @@
+//! # Small Fields
+//! Contains the 31-bit BabyBear (p = 2^31 - 2^27 + 1) and Mersenne31 (p = 2^31 - 1) prime fields.
+//! Instead of the Montgomery backend of ark-ff, elements are stored in canonical form, such that
+//! a product fits into a single `u64` and is reduced with one modular reduction.
+
+use ark_ff::{
+    fields::{Fp64, FpConfig},
+    BigInt, SqrtPrecomputation,
+};
+use core::marker::PhantomData;
+
+/// A prime field with a modulus below 2^32.
+pub trait SmallPrime: Send + Sync + 'static + Sized {
+    /// The modulus of the field.
+    const MODULUS: u64;
+    /// A multiplicative generator of the field.
+    const GENERATOR: u64;
+    /// The largest k such that 2^k divides p - 1.
+    const TWO_ADICITY: u32;
+    /// GENERATOR^((p - 1) / 2^TWO_ADICITY).
+    const TWO_ADIC_ROOT_OF_UNITY: u64;
+
+    /// Reduces a product of two canonical elements modulo p.
+    fn reduce(x: u64) -> u64 {
+        x % Self::MODULUS
+    }
+}
+
+/// [`FpConfig`] for a [`SmallPrime`], storing elements in canonical form.
+pub struct SmallFieldConfig<C: SmallPrime>(PhantomData<C>);
+
+/// A field with a modulus below 2^32.
+pub type SmallField<C> = Fp64<SmallFieldConfig<C>>;
+
+/// [`SmallPrime`] for BabyBear.
+pub struct BabyBearPrime;
+/// The BabyBear field with modulus 2^31 - 2^27 + 1.
+pub type BabyBear = SmallField<BabyBearPrime>;
+
+impl SmallPrime for BabyBearPrime {
+    const MODULUS: u64 = 0x7800_0001;
+    const GENERATOR: u64 = 31;
+    const TWO_ADICITY: u32 = 27;
+    const TWO_ADIC_ROOT_OF_UNITY: u64 = 0x1a42_7a41;
+}
+
+/// [`SmallPrime`] for Mersenne31.
+pub struct Mersenne31Prime;
+/// The Mersenne31 field with modulus 2^31 - 1.
+pub type Mersenne31 = SmallField<Mersenne31Prime>;
+
+impl SmallPrime for Mersenne31Prime {
+    const MODULUS: u64 = 0x7fff_ffff;
+    const GENERATOR: u64 = 7;
+    const TWO_ADICITY: u32 = 1;
+    const TWO_ADIC_ROOT_OF_UNITY: u64 = 0x7fff_fffe;
+
+    #[inline(always)]
+    fn reduce(x: u64) -> u64 {
+        // 2^31 = 1 mod p, the input is smaller than 2^62
+        let x = (x & Self::MODULUS) + (x >> 31);
+        let x = (x & Self::MODULUS) + (x >> 31);
+        if x >= Self::MODULUS {
+            x - Self::MODULUS
+        } else {
+            x
+        }
+    }
+}
+
+const fn element<C: SmallPrime>(value: u64) -> SmallField<C> {
+    ark_ff::Fp(BigInt([value]), PhantomData)
+}
+
+#[inline(always)]
+fn value<C: SmallPrime>(a: &SmallField<C>) -> u64 {
+    a.0 .0[0]
+}
+
+#[inline(always)]
+fn add<C: SmallPrime>(a: u64, b: u64) -> u64 {
+    let sum = a + b;
+    if sum >= C::MODULUS {
+        sum - C::MODULUS
+    } else {
+        sum
+    }
+}
+
+#[inline(always)]
+fn sub<C: SmallPrime>(a: u64, b: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + C::MODULUS - b
+    }
+}
+
+fn pow<C: SmallPrime>(mut base: u64, mut exp: u64) -> u64 {
+    let mut res = 1;
+    while exp != 0 {
+        if exp & 1 == 1 {
+            res = C::reduce(res * base);
+        }
+        base = C::reduce(base * base);
+        exp >>= 1;
+    }
+    res
+}
+
+impl<C: SmallPrime> FpConfig<1> for SmallFieldConfig<C> {
+    const MODULUS: BigInt<1> = BigInt([C::MODULUS]);
+    const GENERATOR: SmallField<C> = element(C::GENERATOR);
+    const ZERO: SmallField<C> = element(0);
+    const ONE: SmallField<C> = element(1);
+    const TWO_ADICITY: u32 = C::TWO_ADICITY;
+    const TWO_ADIC_ROOT_OF_UNITY: SmallField<C> = element(C::TWO_ADIC_ROOT_OF_UNITY);
+    const SQRT_PRECOMP: Option<SqrtPrecomputation<SmallField<C>>> =
+        Some(SqrtPrecomputation::TonelliShanks {
+            two_adicity: C::TWO_ADICITY,
+            quadratic_nonresidue_to_trace: element(C::TWO_ADIC_ROOT_OF_UNITY),
+            trace_of_modulus_minus_one_div_two: &[(C::MODULUS >> C::TWO_ADICITY) / 2],
+        });
+
+    #[inline(always)]
+    fn add_assign(a: &mut SmallField<C>, b: &SmallField<C>) {
+        a.0 .0[0] = add::<C>(value(a), value(b));
+    }
+
+    #[inline(always)]
+    fn sub_assign(a: &mut SmallField<C>, b: &SmallField<C>) {
+        a.0 .0[0] = sub::<C>(value(a), value(b));
+    }
+
+    #[inline(always)]
+    fn double_in_place(a: &mut SmallField<C>) {
+        a.0 .0[0] = add::<C>(value(a), value(a));
+    }
+
+    #[inline(always)]
+    fn neg_in_place(a: &mut SmallField<C>) {
+        a.0 .0[0] = sub::<C>(0, value(a));
+    }
+
+    #[inline(always)]
+    fn mul_assign(a: &mut SmallField<C>, b: &SmallField<C>) {
+        a.0 .0[0] = C::reduce(value(a) * value(b));
+    }
+
+    fn sum_of_products<const T: usize>(
+        a: &[SmallField<C>; T],
+        b: &[SmallField<C>; T],
+    ) -> SmallField<C> {
+        let sum = a.iter().zip(b.iter()).fold(0, |acc, (a, b)| {
+            add::<C>(acc, C::reduce(value(a) * value(b)))
+        });
+        element(sum)
+    }
+
+    #[inline(always)]
+    fn square_in_place(a: &mut SmallField<C>) {
+        a.0 .0[0] = C::reduce(value(a) * value(a));
+    }
+
+    fn inverse(a: &SmallField<C>) -> Option<SmallField<C>> {
+        match value(a) {
+            0 => None,
+            a => Some(element(pow::<C>(a, C::MODULUS - 2))),
+        }
+    }
+
+    fn from_bigint(other: BigInt<1>) -> Option<SmallField<C>> {
+        (other.0[0] < C::MODULUS).then(|| element(other.0[0]))
+    }
+
+    #[inline(always)]
+    fn into_bigint(other: SmallField<C>) -> BigInt<1> {
+        other.0
+    }
+}
+
+#[cfg(test)]
+mod small_field_tests {
+    #![allow(non_local_definitions)]
+
+    use super::{BabyBear, Mersenne31, SmallField, SmallPrime};
+    use ark_ff::{
+        fields::{Fp64, MontBackend, MontConfig},
+        FftField, Field, PrimeField, UniformRand,
+    };
+    use rand::thread_rng;
+
+    #[derive(MontConfig)]
+    #[modulus = "2013265921"]
+    #[generator = "31"]
+    struct BabyBearMontConfig;
+
+    #[derive(MontConfig)]
+    #[modulus = "2147483647"]
+    #[generator = "7"]
+    struct Mersenne31MontConfig;
+
+    fn matches_montgomery<C: SmallPrime, R: PrimeField + FftField>() {
+        let to_ref = |x: SmallField<C>| R::from(x.into_bigint().0[0]);
+        let mut rng = thread_rng();
+        let edge = [0, 1, 2, C::MODULUS - 1, C::MODULUS - 2, 1 << 30];
+        let mut values: Vec<SmallField<C>> = edge.into_iter().map(SmallField::from).collect();
+        values.extend((0..20).map(|_| SmallField::<C>::rand(&mut rng)));
+        for a in values.iter() {
+            assert_eq!(to_ref(-*a), -to_ref(*a));
+            assert_eq!(to_ref(a.double()), to_ref(*a).double());
+            assert_eq!(to_ref(a.square()), to_ref(*a).square());
+            assert_eq!(a.inverse().map(to_ref), to_ref(*a).inverse());
+            assert_eq!(a.sqrt().map(|s| s.square()), to_ref(*a).sqrt().map(|_| *a));
+            for b in values.iter() {
+                assert_eq!(to_ref(*a + b), to_ref(*a) + to_ref(*b));
+                assert_eq!(to_ref(*a - b), to_ref(*a) - to_ref(*b));
+                assert_eq!(to_ref(*a * b), to_ref(*a) * to_ref(*b));
+            }
+        }
+        assert_eq!(
+            to_ref(SmallField::<C>::TWO_ADIC_ROOT_OF_UNITY),
+            R::TWO_ADIC_ROOT_OF_UNITY
+        );
+        assert_eq!(
+            SmallField::<C>::GENERATOR.pow([C::MODULUS - 1]),
+            SmallField::<C>::ONE
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        matches_montgomery::<super::BabyBearPrime, Fp64<MontBackend<BabyBearMontConfig, 1>>>();
+        matches_montgomery::<super::Mersenne31Prime, Fp64<MontBackend<Mersenne31MontConfig, 1>>>();
+        assert_eq!(BabyBear::MODULUS_BIT_SIZE, 31);
+        assert_eq!(Mersenne31::MODULUS_BIT_SIZE, 31);
+    }
+}
@@ -0,0 +1,24 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]) = [0x548212b2, 0x6a61e6ff, 0x24c30c37, 0x70f22bc9, 0x43d53ac0, 0x61326428, 0x4878dbff, 0x3ca30ffc, 0x4036a4e8, 0x4b39c133, 0x7b0142bb, 0x1d074fbf, 0x14806de4, 0x331ea431, 0x32f92b65, 0x3bb448db];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::small_fields::fields::Mersenne31;
+
+lazy_static! {
+    pub static ref POSEIDON_MERSENNE31_16_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            16,
+            5,
+            8,
+            14,
+            include_bytes!(concat!(
+                env!("OUT_DIR"),
+                "/params/small_fields/mersenne31_t16.bin"
+            )),
+        )
+        .unwrap()
+    );
+}
@@ -0,0 +1,24 @@
+// Autogenerated file
+// KAT:
+// Poseidon([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23]) = [0x5fdb221f, 0x0010b8a8, 0x3446941d, 0x0ff458ec, 0x604863b2, 0x0bd0fcef, 0x3d3a7cb9, 0x61e35ec2, 0x3047660c, 0x086ed742, 0x3a5fd8ef, 0x1be2f590, 0x425f5b64, 0x72021ddc, 0x43ac41ef, 0x78f188d6, 0x4c57c3f9, 0x47e435ba, 0x6456c869, 0x5ec91224, 0x05f27767, 0x7d6dcd68, 0x619e17e5, 0x73646a5e];
+use crate::parameters::PoseidonParams;
+use alloc::sync::Arc;
+use lazy_static::lazy_static;
+
+type Scalar = crate::small_fields::fields::Mersenne31;
+
+lazy_static! {
+    pub static ref POSEIDON_MERSENNE31_24_PARAMS: Arc<PoseidonParams<Scalar>> = Arc::new(
+        PoseidonParams::from_bytes(
+            24,
+            5,
+            8,
+            22,
+            include_bytes!(concat!(
+                env!("OUT_DIR"),
+                "/params/small_fields/mersenne31_t24.bin"
+            )),
+        )
+        .unwrap()
+    );
+}
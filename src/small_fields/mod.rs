@@ -0,0 +1,164 @@
+//! # Small Fields
+//! Contains Poseidon parameters for the 31-bit BabyBear and Mersenne31 fields used by STARK-based
+//! proof systems, with t = 16 and t = 24. BabyBear uses the sbox x^7 and Mersenne31 the sbox
+//! x^5, with the round numbers of the Poseidon paper (R_F = 8, R_P = 13 and 21 for BabyBear,
+//! R_P = 14 and 22 for Mersenne31).
+//!
+//! As for [`goldilocks`](crate::goldilocks), the round constants are generated by the Grain LFSR
+//! and Cauchy matrices are sampled until one passes
+//! [`PoseidonParams::validate_security`](crate::parameters::PoseidonParams::validate_security).
+
+pub mod babybear_t16;
+pub mod babybear_t24;
+pub mod fields;
+pub mod mersenne31_t16;
+pub mod mersenne31_t24;
+
+#[cfg(test)]
+mod small_fields_tests {
+    use super::{
+        babybear_t16::POSEIDON_BABYBEAR_16_PARAMS,
+        babybear_t24::POSEIDON_BABYBEAR_24_PARAMS,
+        fields::{BabyBear, Mersenne31},
+        mersenne31_t16::POSEIDON_MERSENNE31_16_PARAMS,
+        mersenne31_t24::POSEIDON_MERSENNE31_24_PARAMS,
+    };
+    use crate::{field_from_hex_string, poseidon::Poseidon};
+    use ark_ff::PrimeField;
+
+    fn check_kat<F: PrimeField>(poseidon: &Poseidon<F>, expected: &[&str]) {
+        let input: Vec<F> = (0..expected.len() as u64).map(F::from).collect();
+        let perm = poseidon.permutation(input.to_owned()).unwrap();
+        let expected: Vec<F> = expected
+            .iter()
+            .map(|e| field_from_hex_string(e).unwrap())
+            .collect();
+        assert_eq!(perm, expected);
+        assert_eq!(poseidon.permutation_not_opt(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn kats() {
+        check_kat::<BabyBear>(
+            &Poseidon::new(&POSEIDON_BABYBEAR_16_PARAMS),
+            &[
+                "0x49feffd3",
+                "0x1a4ee7e9",
+                "0x5fe186c1",
+                "0x0ba24809",
+                "0x30c8e988",
+                "0x754fe288",
+                "0x1828e706",
+                "0x2ebc4f67",
+                "0x4792980e",
+                "0x1c59dce3",
+                "0x6e32c576",
+                "0x5c564057",
+                "0x030e052f",
+                "0x3122ac89",
+                "0x66c063fb",
+                "0x64bb0f31",
+            ],
+        );
+        check_kat::<BabyBear>(
+            &Poseidon::new(&POSEIDON_BABYBEAR_24_PARAMS),
+            &[
+                "0x645ee161",
+                "0x3efec82e",
+                "0x259aee11",
+                "0x11a44ea8",
+                "0x322c35b8",
+                "0x0292719f",
+                "0x3b20c6d1",
+                "0x3d308c21",
+                "0x2d5e9579",
+                "0x4e13e36a",
+                "0x627abe1d",
+                "0x1ea6edef",
+                "0x41e3b453",
+                "0x1e46bd43",
+                "0x4d16b384",
+                "0x5f3bea51",
+                "0x4c5aa2c6",
+                "0x0fa134b6",
+                "0x3a27fd29",
+                "0x46b03855",
+                "0x6ae33b7d",
+                "0x595ae084",
+                "0x4bc18e76",
+                "0x59f111a7",
+            ],
+        );
+        check_kat::<Mersenne31>(
+            &Poseidon::new(&POSEIDON_MERSENNE31_16_PARAMS),
+            &[
+                "0x548212b2",
+                "0x6a61e6ff",
+                "0x24c30c37",
+                "0x70f22bc9",
+                "0x43d53ac0",
+                "0x61326428",
+                "0x4878dbff",
+                "0x3ca30ffc",
+                "0x4036a4e8",
+                "0x4b39c133",
+                "0x7b0142bb",
+                "0x1d074fbf",
+                "0x14806de4",
+                "0x331ea431",
+                "0x32f92b65",
+                "0x3bb448db",
+            ],
+        );
+        check_kat::<Mersenne31>(
+            &Poseidon::new(&POSEIDON_MERSENNE31_24_PARAMS),
+            &[
+                "0x5fdb221f",
+                "0x0010b8a8",
+                "0x3446941d",
+                "0x0ff458ec",
+                "0x604863b2",
+                "0x0bd0fcef",
+                "0x3d3a7cb9",
+                "0x61e35ec2",
+                "0x3047660c",
+                "0x086ed742",
+                "0x3a5fd8ef",
+                "0x1be2f590",
+                "0x425f5b64",
+                "0x72021ddc",
+                "0x43ac41ef",
+                "0x78f188d6",
+                "0x4c57c3f9",
+                "0x47e435ba",
+                "0x6456c869",
+                "0x5ec91224",
+                "0x05f27767",
+                "0x7d6dcd68",
+                "0x619e17e5",
+                "0x73646a5e",
+            ],
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn matches_generation() {
+        use crate::parameters::{
+            generate::{generate_with_rounds, round_numbers},
+            PoseidonParams,
+        };
+
+        fn check<F: PrimeField>(params: &PoseidonParams<F>, t: usize, d: usize) {
+            let (rounds_f, rounds_p) = round_numbers::<F>(t, d, 128);
+            let generated = generate_with_rounds::<F>(t, d, rounds_f, rounds_p).unwrap();
+            assert_eq!(generated.round_constants, params.round_constants);
+            params.validate_security(128).unwrap();
+        }
+
+        check::<BabyBear>(&POSEIDON_BABYBEAR_16_PARAMS, 16, 7);
+        check::<BabyBear>(&POSEIDON_BABYBEAR_24_PARAMS, 24, 7);
+        check::<Mersenne31>(&POSEIDON_MERSENNE31_16_PARAMS, 16, 5);
+        check::<Mersenne31>(&POSEIDON_MERSENNE31_24_PARAMS, 24, 5);
+    }
+}
@@ -0,0 +1,728 @@
+//! # Sponge
+//! Contains a sponge construction over the Poseidon permutation, supporting arbitrary-length
+//! input and multi-element output.
+
+use crate::{
+    error::Error,
+    field_hasher::FieldHasher,
+    parameters::PoseidonParams,
+    poseidon::{bytes_chunk_size, DomainTag, Poseidon},
+};
+use alloc::{sync::Arc, vec, vec::Vec};
+#[cfg(feature = "ark-sponge")]
+use ark_ff::BigInteger;
+use ark_ff::PrimeField;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// The padding rule applied to the input before the first squeeze. The rules which do not encode
+/// the input length are only safe for inputs of a length fixed by the protocol.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Padding {
+    /// A single one followed by zeros (10*), which distinguishes inputs of different lengths.
+    #[default]
+    OneZeros,
+    /// No padding, the last partial block is implicitly filled with zeros, i.e., inputs which
+    /// only differ in trailing zeros collide.
+    None,
+    /// No padding, but the length has to be declared in advance and is placed in the capacity
+    /// element as len * 2^64 (the constant-length tag of neptune). Squeezing fails if a different
+    /// number of elements was absorbed.
+    FixedLength(usize),
+    /// A one followed by zeros and a final one in the last element of the rate (10*1), appending
+    /// a block if only one element of the rate is left.
+    MultiRateBit,
+    /// No padding, and the output is read from the whole state starting with the capacity
+    /// element, as circomlib's `PoseidonEx` does. For t - 1 inputs, the first output is the
+    /// circom hash of the inputs.
+    Circom,
+    /// No padding in overwrite mode with the given capacity placed at the end of the state, as
+    /// Plonky2's `hash_n_to_m_no_pad` and Plonky3's `PaddingFreeSponge` do. The input overwrites
+    /// the first t - capacity elements, the last partial block is permuted as is, and the output
+    /// is read from the rate without a further permutation. An empty input hashes to zeros. With
+    /// [`POSEIDON_PLONKY2_12_PARAMS`](crate::goldilocks::plonky2_t12::POSEIDON_PLONKY2_12_PARAMS),
+    /// `Overwrite(4)` is Plonky2's `PoseidonHash::hash_no_pad`.
+    Overwrite(usize),
+}
+
+impl Padding {
+    fn capacity<F: PrimeField>(&self, t: usize) -> F {
+        match self {
+            Padding::FixedLength(len) => DomainTag::ConstantLength.value(t, *len),
+            _ => F::zero(),
+        }
+    }
+}
+
+/// A sponge over the Poseidon permutation with capacity 1 (state element 0) and rate t - 1, except
+/// for [`Padding::Overwrite`]. Any other [`FieldHasher`] can be used as permutation with
+/// [`PoseidonSponge::with_hasher`].
+///
+/// Input is added to the rate part of the state, where the permutation is applied each time the
+/// rate is full. Before the first squeeze, the input is padded according to the [`Padding`] of the
+/// sponge, by default with a single one followed by zeros (10* padding), such that inputs of
+/// different lengths are distinguished. The output is read from the rate part of the state,
+/// permuting whenever it is exhausted.
+#[derive(Clone, Debug)]
+pub struct PoseidonSponge<F: PrimeField, H = Poseidon<F>> {
+    hasher: H,
+    state: Vec<F>,
+    pos: usize, // position in the rate part of the state, or in the output window when squeezing
+    absorbed: usize,
+    padding: Padding,
+    squeezing: bool,
+}
+
+impl<F: PrimeField> PoseidonSponge<F> {
+    pub fn new(params: &Arc<PoseidonParams<F>>) -> Result<Self, Error> {
+        Self::with_padding(params, Padding::default())
+    }
+
+    /// Creates a sponge with the given padding rule.
+    pub fn with_padding(params: &Arc<PoseidonParams<F>>, padding: Padding) -> Result<Self, Error> {
+        Self::with_hasher(Poseidon::new(params), padding)
+    }
+
+    /// Creates a sponge whose capacity element is initialized to the given value instead of zero.
+    pub(crate) fn with_capacity(
+        params: &Arc<PoseidonParams<F>>,
+        capacity: F,
+    ) -> Result<Self, Error> {
+        Self::with_hasher_and_capacity(Poseidon::new(params), capacity)
+    }
+}
+
+impl<F: PrimeField, H: FieldHasher<F>> PoseidonSponge<F, H> {
+    /// Creates a sponge over the permutation of the given hasher with the given padding rule.
+    pub fn with_hasher(hasher: H, padding: Padding) -> Result<Self, Error> {
+        let capacity = padding.capacity(hasher.state_size());
+        Self::init(hasher, capacity, padding)
+    }
+
+    /// Creates a sponge over the permutation of the given hasher whose capacity element is
+    /// initialized to the given value instead of zero.
+    pub(crate) fn with_hasher_and_capacity(hasher: H, capacity: F) -> Result<Self, Error> {
+        Self::init(hasher, capacity, Padding::default())
+    }
+
+    fn init(hasher: H, capacity: F, padding: Padding) -> Result<Self, Error> {
+        let t = hasher.state_size();
+        if t < 2 || matches!(padding, Padding::Overwrite(c) if c == 0 || c >= t) {
+            return Err(Error::InvalidParameters);
+        }
+        let mut state = vec![F::zero(); t];
+        state[0] = capacity;
+        Ok(PoseidonSponge {
+            hasher,
+            state,
+            pos: 0,
+            absorbed: 0,
+            padding,
+            squeezing: false,
+        })
+    }
+
+    fn rate(&self) -> usize {
+        match self.padding {
+            Padding::Overwrite(capacity) => self.state.len() - capacity,
+            _ => self.state.len() - 1,
+        }
+    }
+
+    // index of the first rate element, the capacity comes last in overwrite mode
+    fn rate_offset(&self) -> usize {
+        match self.padding {
+            Padding::Overwrite(_) => 0,
+            _ => 1,
+        }
+    }
+
+    fn permute(&mut self) -> Result<(), Error> {
+        self.hasher.permute(&mut self.state)
+    }
+
+    /// Absorbs the input into the sponge. Absorbing is not possible anymore after squeezing.
+    pub fn absorb(&mut self, input: &[F]) -> Result<(), Error> {
+        if self.squeezing {
+            return Err(Error::AbsorbAfterSqueeze);
+        }
+        let offset = self.rate_offset();
+        for el in input {
+            match self.padding {
+                Padding::Overwrite(_) => self.state[self.pos] = *el,
+                _ => self.state[self.pos + offset].add_assign(el),
+            }
+            self.pos += 1;
+            if self.pos == self.rate() {
+                self.permute()?;
+                self.pos = 0;
+            }
+        }
+        self.absorbed += input.len();
+        Ok(())
+    }
+
+    /// Absorbs an arbitrary byte string. Like in [`Poseidon::hash_bytes`], the bytes are packed
+    /// into little-endian chunks of `(MODULUS_BIT_SIZE - 1) / 8` bytes, which are followed by the
+    /// byte length, such that consecutive byte strings are uniquely separated.
+    pub fn absorb_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        let chunks = bytes
+            .chunks(bytes_chunk_size::<F>()?)
+            .map(F::from_le_bytes_mod_order)
+            .collect::<Vec<_>>();
+        self.absorb(&chunks)?;
+        self.absorb(&[F::from(bytes.len() as u64)])
+    }
+
+    // Pads the input and permutes, there is always space left in the rate since full blocks are
+    // permuted immediately
+    fn pad(&mut self) -> Result<(), Error> {
+        let rate = self.rate();
+        match self.padding {
+            Padding::OneZeros => {
+                self.state[self.pos + 1].add_assign(F::one());
+                self.permute()?;
+            }
+            Padding::MultiRateBit => {
+                self.state[self.pos + 1].add_assign(F::one());
+                if self.pos + 1 == rate {
+                    self.permute()?;
+                }
+                self.state[rate].add_assign(F::one());
+                self.permute()?;
+            }
+            Padding::FixedLength(len) if len != self.absorbed => {
+                return Err(Error::InvalidInputLength(self.absorbed));
+            }
+            Padding::None | Padding::FixedLength(_) | Padding::Circom => {
+                // permute the last partial block, or the initial state for empty inputs
+                if self.pos != 0 || self.absorbed == 0 {
+                    self.permute()?;
+                }
+            }
+            Padding::Overwrite(_) => {
+                if self.pos != 0 {
+                    self.permute()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Squeezes `n` field elements out of the sponge.
+    pub fn squeeze(&mut self, n: usize) -> Result<Vec<F>, Error> {
+        if !self.squeezing {
+            self.pad()?;
+            self.pos = 0;
+            self.squeezing = true;
+        }
+        // circom reads the output from the whole state
+        let (offset, width) = match self.padding {
+            Padding::Circom => (0, self.state.len()),
+            _ => (self.rate_offset(), self.rate()),
+        };
+        let mut output = Vec::with_capacity(n);
+        for _ in 0..n {
+            if self.pos == width {
+                self.permute()?;
+                self.pos = 0;
+            }
+            output.push(self.state[self.pos + offset]);
+            self.pos += 1;
+        }
+        Ok(output)
+    }
+
+    /// Consumes the sponge and returns a single field element.
+    pub fn finalize(mut self) -> Result<F, Error> {
+        Ok(self.squeeze(1)?[0])
+    }
+}
+
+// arkworks' sponge is a duplex, i.e., absorbing after squeezing continues with the current state,
+// and the next squeeze pads and permutes the newly absorbed elements
+#[cfg(feature = "ark-sponge")]
+impl<F: PrimeField, H: FieldHasher<F>> PoseidonSponge<F, H> {
+    fn duplex_absorb(&mut self, input: &[F]) -> Result<(), Error> {
+        if self.squeezing && !input.is_empty() {
+            self.pos = 0;
+            self.absorbed = 0;
+            self.squeezing = false;
+        }
+        self.absorb(input)
+    }
+}
+
+/// The sponge as `CryptographicSponge` of arkworks, such that it can be used as random oracle in
+/// arkworks-based proof systems. Absorbing after squeezing is possible (see above). The methods
+/// panic if the sponge fails, which only happens for [`Padding::FixedLength`] with a different
+/// number of absorbed elements. [`CryptographicSponge::new`] panics for t < 2.
+#[cfg(feature = "ark-sponge")]
+impl<F: PrimeField> ark_crypto_primitives::sponge::CryptographicSponge for PoseidonSponge<F> {
+    type Config = Arc<PoseidonParams<F>>;
+
+    fn new(params: &Self::Config) -> Self {
+        PoseidonSponge::new(params).expect("the statesize is at least 2")
+    }
+
+    fn absorb(&mut self, input: &impl ark_crypto_primitives::sponge::Absorb) {
+        self.duplex_absorb(&input.to_sponge_field_elements_as_vec())
+            .expect("absorbing never fails");
+    }
+
+    // like the Poseidon sponge of arkworks, the bytes and bits are taken from the first
+    // MODULUS_BIT_SIZE - 1 bits of each element
+    fn squeeze_bytes(&mut self, num_bytes: usize) -> Vec<u8> {
+        use ark_crypto_primitives::sponge::FieldBasedCryptographicSponge;
+        let usable = (F::MODULUS_BIT_SIZE as usize - 1) / 8;
+        let mut bytes = Vec::with_capacity(num_bytes.next_multiple_of(usable));
+        for el in self.squeeze_native_field_elements(num_bytes.div_ceil(usable)) {
+            bytes.extend_from_slice(&el.into_bigint().to_bytes_le()[..usable]);
+        }
+        bytes.truncate(num_bytes);
+        bytes
+    }
+
+    fn squeeze_bits(&mut self, num_bits: usize) -> Vec<bool> {
+        use ark_crypto_primitives::sponge::FieldBasedCryptographicSponge;
+        let usable = F::MODULUS_BIT_SIZE as usize - 1;
+        let mut bits = Vec::with_capacity(num_bits.next_multiple_of(usable));
+        for el in self.squeeze_native_field_elements(num_bits.div_ceil(usable)) {
+            bits.extend_from_slice(&el.into_bigint().to_bits_le()[..usable]);
+        }
+        bits.truncate(num_bits);
+        bits
+    }
+
+    // Native elements are squeezed directly, other fields are sampled from the squeezed bits like
+    // in the default implementation
+    fn squeeze_field_elements_with_sizes<F2: PrimeField>(
+        &mut self,
+        sizes: &[ark_crypto_primitives::sponge::FieldElementSize],
+    ) -> Vec<F2> {
+        use ark_crypto_primitives::sponge::{FieldBasedCryptographicSponge, FieldElementSize};
+        if F::characteristic() == F2::characteristic() {
+            return self
+                .squeeze_native_field_elements_with_sizes(sizes)
+                .iter()
+                .map(|el| F2::from_le_bytes_mod_order(&el.into_bigint().to_bytes_le()))
+                .collect();
+        }
+        let num_bits = |size: &FieldElementSize| match size {
+            FieldElementSize::Full => F2::MODULUS_BIT_SIZE as usize - 1,
+            FieldElementSize::Truncated(bits) => *bits,
+        };
+        let bits = self.squeeze_bits(sizes.iter().map(num_bits).sum());
+        let mut bits = bits.as_slice();
+        sizes
+            .iter()
+            .map(|size| {
+                let (el, rest) = bits.split_at(num_bits(size));
+                bits = rest;
+                let bytes: Vec<u8> = el
+                    .chunks(8)
+                    .map(|byte| (0..byte.len()).map(|i| (byte[i] as u8) << i).sum())
+                    .collect();
+                F2::from_le_bytes_mod_order(&bytes)
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "ark-sponge")]
+impl<F: PrimeField> ark_crypto_primitives::sponge::FieldBasedCryptographicSponge<F>
+    for PoseidonSponge<F>
+{
+    fn squeeze_native_field_elements(&mut self, num_elements: usize) -> Vec<F> {
+        self.squeeze(num_elements)
+            .expect("squeezing only fails for an unexpected fixed length")
+    }
+}
+
+/// Clears the state, the sponge is empty afterwards.
+#[cfg(feature = "zeroize")]
+impl<F: PrimeField, H: FieldHasher<F>> Zeroize for PoseidonSponge<F, H> {
+    fn zeroize(&mut self) {
+        self.state.zeroize();
+        self.state.resize(self.hasher.state_size(), F::zero());
+        self.state[0] = self.padding.capacity(self.state.len());
+        self.pos = 0;
+        self.absorbed = 0;
+        self.squeezing = false;
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<F: PrimeField, H> Drop for PoseidonSponge<F, H> {
+    fn drop(&mut self) {
+        self.state.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<F: PrimeField, H> ZeroizeOnDrop for PoseidonSponge<F, H> {}
+
+#[cfg(test)]
+mod sponge_tests {
+    use super::*;
+    use crate::{
+        bn254::{
+            circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS,
+            poseidon2_t3::POSEIDON2_BN_3_PARAMS,
+        },
+        poseidon2::Poseidon2,
+    };
+    use ark_ff::{UniformRand, Zero};
+    use rand::thread_rng;
+
+    type Scalar = ark_bn254::Fr;
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize() {
+        let mut sponge = PoseidonSponge::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+        let fresh = sponge.clone().finalize().unwrap();
+        sponge.absorb(&[Scalar::from(42)]).unwrap();
+        sponge.squeeze(1).unwrap();
+        sponge.zeroize();
+        assert!(sponge.state.iter().all(Zero::is_zero));
+        assert_eq!(sponge.finalize().unwrap(), fresh);
+    }
+
+    #[test]
+    fn absorb_in_pieces() {
+        let mut rng = thread_rng();
+        let input: Vec<Scalar> = (0..10).map(|_| Scalar::rand(&mut rng)).collect();
+
+        let mut sponge1 = PoseidonSponge::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+        sponge1.absorb(&input).unwrap();
+        let mut sponge2 = PoseidonSponge::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+        for chunk in input.chunks(3) {
+            sponge2.absorb(chunk).unwrap();
+        }
+        assert_eq!(sponge1.finalize().unwrap(), sponge2.finalize().unwrap());
+    }
+
+    #[test]
+    fn squeeze_in_pieces() {
+        let mut rng = thread_rng();
+        let input: Vec<Scalar> = (0..5).map(|_| Scalar::rand(&mut rng)).collect();
+
+        let mut sponge1 = PoseidonSponge::new(&POSEIDON_CIRCOM_BN_4_PARAMS).unwrap();
+        sponge1.absorb(&input).unwrap();
+        let output1 = sponge1.squeeze(7).unwrap();
+        let mut sponge2 = PoseidonSponge::new(&POSEIDON_CIRCOM_BN_4_PARAMS).unwrap();
+        sponge2.absorb(&input).unwrap();
+        let mut output2 = sponge2.squeeze(2).unwrap();
+        output2.extend(sponge2.squeeze(5).unwrap());
+        assert_eq!(output1, output2);
+        assert_eq!(output1.len(), 7);
+        assert_ne!(output1[0], output1[3]);
+    }
+
+    #[test]
+    fn distinct_lengths() {
+        let mut outputs = Vec::new();
+        for len in 0..6 {
+            let mut sponge = PoseidonSponge::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+            sponge.absorb(&vec![Scalar::zero(); len]).unwrap();
+            outputs.push(sponge.finalize().unwrap());
+        }
+        for (i, out1) in outputs.iter().enumerate() {
+            for out2 in outputs.iter().skip(i + 1) {
+                assert_ne!(out1, out2);
+            }
+        }
+    }
+
+    #[test]
+    fn absorb_bytes() {
+        let hash = |parts: &[&[u8]]| {
+            let mut sponge = PoseidonSponge::<Scalar>::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+            for part in parts {
+                sponge.absorb_bytes(part).unwrap();
+            }
+            sponge.finalize().unwrap()
+        };
+        let long = [0xffu8; 100];
+        let outputs = [
+            hash(&[]),
+            hash(&[b""]),
+            hash(&[b"\0"]),
+            hash(&[b"abc"]),
+            hash(&[b"ab", b"c"]),
+            hash(&[b"a", b"bc"]),
+            hash(&[&long]),
+            hash(&[&long[..99]]),
+        ];
+        for (i, out1) in outputs.iter().enumerate() {
+            for out2 in outputs.iter().skip(i + 1) {
+                assert_ne!(out1, out2);
+            }
+        }
+    }
+
+    #[test]
+    fn absorb_after_squeeze() {
+        let mut sponge = PoseidonSponge::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap();
+        sponge.absorb(&[Scalar::zero()]).unwrap();
+        sponge.squeeze(1).unwrap();
+        assert!(matches!(
+            sponge.absorb(&[Scalar::zero()]),
+            Err(Error::AbsorbAfterSqueeze)
+        ));
+    }
+
+    #[test]
+    fn paddings() {
+        let hash = |padding: Padding, input: &[Scalar]| {
+            let mut sponge =
+                PoseidonSponge::with_padding(&POSEIDON_CIRCOM_BN_4_PARAMS, padding).unwrap();
+            sponge.absorb(input).unwrap();
+            sponge.squeeze(2).unwrap()
+        };
+        let input: Vec<Scalar> = (1..=3).map(Scalar::from).collect();
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS);
+
+        // circom: a single permutation of [0, inputs], read from the capacity element on
+        let permuted = poseidon
+            .permutation([&[Scalar::zero()], input.as_slice()].concat())
+            .unwrap();
+        assert_eq!(hash(Padding::Circom, &input), permuted[..2]);
+        assert_eq!(
+            hash(Padding::Circom, &input)[0],
+            crate::circom::poseidon_hash(&input).unwrap()
+        );
+        // no padding: the same permutation, read from the rate
+        assert_eq!(hash(Padding::None, &input), permuted[1..3]);
+        assert_eq!(
+            hash(Padding::None, &input[..2]),
+            hash(Padding::None, &[input[0], input[1], Scalar::zero()])
+        );
+
+        // multi-rate: 10*1 fits into the rate for 1 input, needs another block for 2 inputs
+        let one = Scalar::from(1);
+        let state = poseidon
+            .permutation(vec![Scalar::zero(), input[0], one, one])
+            .unwrap();
+        assert_eq!(hash(Padding::MultiRateBit, &input[..1]), state[1..3]);
+        let state = poseidon
+            .permutation(vec![Scalar::zero(), input[0], input[1], one])
+            .unwrap();
+        let state = poseidon
+            .permutation(vec![state[0], state[1], state[2], state[3] + one])
+            .unwrap();
+        assert_eq!(hash(Padding::MultiRateBit, &input[..2]), state[1..3]);
+        assert_ne!(
+            hash(Padding::MultiRateBit, &input[..2]),
+            hash(Padding::MultiRateBit, &[input[0], input[1], Scalar::zero()])
+        );
+
+        // the default is 10*
+        assert_eq!(
+            hash(Padding::OneZeros, &input),
+            PoseidonSponge::new(&POSEIDON_CIRCOM_BN_4_PARAMS)
+                .and_then(|mut sponge| {
+                    sponge.absorb(&input)?;
+                    sponge.squeeze(2)
+                })
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn fixed_length() {
+        let input: Vec<Scalar> = (1..=3).map(Scalar::from).collect();
+        let mut sponge =
+            PoseidonSponge::with_padding(&POSEIDON_CIRCOM_BN_4_PARAMS, Padding::FixedLength(3))
+                .unwrap();
+        sponge.absorb(&input).unwrap();
+        // a single permutation with the length in the capacity element
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_4_PARAMS);
+        let tag = DomainTag::ConstantLength.value(4, 3);
+        let permuted = poseidon
+            .permutation([&[tag], input.as_slice()].concat())
+            .unwrap();
+        assert_eq!(sponge.squeeze(1).unwrap(), permuted[1..2]);
+
+        let mut sponge =
+            PoseidonSponge::with_padding(&POSEIDON_CIRCOM_BN_4_PARAMS, Padding::FixedLength(3))
+                .unwrap();
+        sponge.absorb(&input[..2]).unwrap();
+        assert!(matches!(
+            sponge.squeeze(1),
+            Err(Error::InvalidInputLength(2))
+        ));
+    }
+
+    #[test]
+    fn with_hasher() {
+        let input = [Scalar::from(1), Scalar::from(2), Scalar::from(3)];
+        fn hash<H: FieldHasher<Scalar>>(mut sponge: PoseidonSponge<Scalar, H>) -> Vec<Scalar> {
+            sponge
+                .absorb(&[Scalar::from(1), Scalar::from(2), Scalar::from(3)])
+                .unwrap();
+            sponge.squeeze(3).unwrap()
+        }
+        let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+        assert_eq!(
+            hash(PoseidonSponge::with_hasher(poseidon, Padding::default()).unwrap()),
+            hash(PoseidonSponge::new(&POSEIDON_CIRCOM_BN_3_PARAMS).unwrap())
+        );
+
+        // the sponge over Poseidon2 permutes with Poseidon2
+        let poseidon2 = Poseidon2::new(&POSEIDON2_BN_3_PARAMS);
+        let sponge = PoseidonSponge::with_hasher(poseidon2.to_owned(), Padding::Circom).unwrap();
+        let mut state = vec![Scalar::zero(), input[0], input[1]];
+        state = poseidon2.permutation(state).unwrap();
+        state[1] += input[2];
+        state = poseidon2.permutation(state).unwrap();
+        assert_eq!(hash(sponge), state);
+    }
+
+    // The outputs of Plonky2 1.1.0's `PoseidonHash::hash_no_pad` for the inputs [1, 2, .., len],
+    // and of `hash_n_to_m_no_pad` with 10 outputs for len = 9.
+    #[test]
+    fn plonky2() {
+        use crate::goldilocks::{field::Goldilocks, plonky2_t12::POSEIDON_PLONKY2_12_PARAMS};
+
+        let hash = |len: u64, n: usize| {
+            let input: Vec<Goldilocks> = (1..=len).map(Goldilocks::from).collect();
+            let mut sponge =
+                PoseidonSponge::with_padding(&POSEIDON_PLONKY2_12_PARAMS, Padding::Overwrite(4))
+                    .unwrap();
+            sponge.absorb(&input).unwrap();
+            sponge.squeeze(n).unwrap()
+        };
+        let from_hex = |hex: &[&str]| -> Vec<Goldilocks> {
+            hex.iter()
+                .map(|e| crate::field_from_hex_string(e).unwrap())
+                .collect()
+        };
+        assert_eq!(hash(0, 4), vec![Goldilocks::zero(); 4]);
+        let expected = [
+            (
+                1,
+                [
+                    "0xd074b8cee5dcf415",
+                    "0x2346a1b4c0f390e8",
+                    "0x47969c1f5a6a25b1",
+                    "0xda62fdf84a21108e",
+                ],
+            ),
+            (
+                3,
+                [
+                    "0xe1eec9650118aeda",
+                    "0xaac1ef5aed3348ba",
+                    "0x926bcc7746915c95",
+                    "0x1c659ce9f438d490",
+                ],
+            ),
+            (
+                8,
+                [
+                    "0xd110aa6a46373941",
+                    "0x8f238fcceb658894",
+                    "0x9cd4f8353866fb4f",
+                    "0x274913f0007aa232",
+                ],
+            ),
+            (
+                9,
+                [
+                    "0x5a90f7c562413c2b",
+                    "0xa1874b91e26076d4",
+                    "0x37b5cd4fe1fb94da",
+                    "0x3db54acf2fa3b131",
+                ],
+            ),
+            (
+                20,
+                [
+                    "0x126d1cf7493dd3a3",
+                    "0x412269f04af16ec7",
+                    "0xc89c25b2130b33d7",
+                    "0xaced01ae551169be",
+                ],
+            ),
+        ];
+        for (len, expected) in expected {
+            assert_eq!(hash(len, 4), from_hex(&expected));
+        }
+        let expected = from_hex(&[
+            "0x5a90f7c562413c2b",
+            "0xa1874b91e26076d4",
+            "0x37b5cd4fe1fb94da",
+            "0x3db54acf2fa3b131",
+            "0x23d8e7223c4d6a9f",
+            "0x445881543a86b6d7",
+            "0xa03487cf97947c9e",
+            "0xd2e7788f4ddebfe6",
+            "0xd02c6dffc6a0321f",
+            "0xea94478e3a596b3a",
+        ]);
+        assert_eq!(hash(9, 10), expected);
+
+        for capacity in [0, 12] {
+            assert!(matches!(
+                PoseidonSponge::with_padding(
+                    &POSEIDON_PLONKY2_12_PARAMS,
+                    Padding::Overwrite(capacity)
+                ),
+                Err(Error::InvalidParameters)
+            ));
+        }
+    }
+
+    #[cfg(feature = "ark-sponge")]
+    #[test]
+    fn ark_sponge() {
+        use crate::goldilocks::field::Goldilocks;
+        use ark_crypto_primitives::sponge::{CryptographicSponge, FieldBasedCryptographicSponge};
+        use ark_ff::{BigInteger, PrimeField};
+
+        // the random oracle of a generic arkworks protocol
+        fn challenges<S: CryptographicSponge>(sponge: &mut S, messages: &[Scalar]) -> Vec<Scalar> {
+            messages
+                .iter()
+                .flat_map(|message| {
+                    sponge.absorb(message);
+                    sponge.squeeze_field_elements::<Scalar>(2)
+                })
+                .collect()
+        }
+
+        let params = POSEIDON_CIRCOM_BN_3_PARAMS.to_owned();
+        let messages = [Scalar::from(1), Scalar::from(2)];
+        let mut sponge = <PoseidonSponge<Scalar> as CryptographicSponge>::new(&params);
+        let output = challenges(&mut sponge, &messages);
+
+        // absorbing after squeezing continues with the state after the squeeze
+        let mut expected = PoseidonSponge::new(&params).unwrap();
+        expected.absorb(&messages[..1]).unwrap();
+        assert_eq!(output[..2], expected.squeeze(2).unwrap());
+        expected.duplex_absorb(&messages[1..]).unwrap();
+        assert_eq!(output[2..], expected.squeeze(2).unwrap());
+        assert_ne!(output[..2], output[2..]);
+
+        let mut sponge = <PoseidonSponge<Scalar> as CryptographicSponge>::new(&params);
+        CryptographicSponge::absorb(&mut sponge, &b"message".as_slice());
+        let mut forked = sponge.fork(b"domain");
+        let bytes = sponge.clone().squeeze_bytes(40);
+        let native = sponge.squeeze_native_field_elements(2);
+        assert_eq!(bytes.len(), 40);
+        assert_eq!(bytes[..31], native[0].into_bigint().to_bytes_le()[..31]);
+        assert_eq!(bytes[31..], native[1].into_bigint().to_bytes_le()[..9]);
+        assert_ne!(forked.squeeze_native_field_elements(2), native);
+
+        // elements of other fields are sampled from the squeezed bits
+        let mut sponge = <PoseidonSponge<Scalar> as CryptographicSponge>::new(&params);
+        let mut bits = sponge.clone();
+        let small = sponge.squeeze_field_elements::<Goldilocks>(2);
+        let bits = bits.squeeze_bits(2 * 63);
+        let value = |bits: &[bool]| (0..63).map(|i| (bits[i] as u64) << i).sum::<u64>();
+        assert_eq!(small[0], Goldilocks::from(value(&bits[..63])));
+        assert_eq!(small[1], Goldilocks::from(value(&bits[63..])));
+    }
+}
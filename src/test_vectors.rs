@@ -0,0 +1,262 @@
+//! # Test Vectors
+//! Contains the known-answer tests (KATs) of all bundled parameter sets in a machine-readable
+//! format, such that downstream crates and implementations in other languages can check against
+//! the same vectors. The vectors are stored as JSON in `test-vectors/<module>/<name>.json`, i.e.,
+//! next to the parameter set in `params/<module>/<name>.json`, and look as follows:
+//!
+//! ```json
+//! {
+//!   "parameters": "bn254/circom_t3",
+//!   "permutation": "poseidon",
+//!   "t": 3,
+//!   "vectors": [{ "input": ["0x00..", ..], "output": ["0x..", ..] }, ..]
+//! }
+//! ```
+//!
+//! The permutation is `poseidon`, `poseidon2`, `rescue`, or `monolith`. All field elements are hex strings
+//! zero-padded to the byte size of the modulus. Every file contains the inputs [0, 1, .., t - 1]
+//! (the KAT in the header of the corresponding module), [0, .., 0], and [-1, -2, .., -t].
+
+use crate::{error::Error, field_from_str_radix};
+use alloc::{borrow::ToOwned, format, string::ToString, vec::Vec};
+use ark_ff::PrimeField;
+use serde_json::Value;
+
+macro_rules! test_vectors {
+    ($($name:literal),* $(,)?) => {
+        /// The names of all parameter sets for which test vectors are available.
+        pub const NAMES: &[&str] = &[$($name),*];
+
+        /// Returns the JSON test vectors of the parameter set with the given name, e.g.,
+        /// `bn254/circom_t3`.
+        pub fn json(name: &str) -> Option<&'static str> {
+            match name {
+                $($name => Some(include_str!(concat!("../test-vectors/", $name, ".json"))),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+test_vectors!(
+    "bls12_381/hades_t5",
+    "bls12_381/neptune_t3",
+    "bls12_381/neptune_t5",
+    "bls12_381/neptune_t9",
+    "bn254/circom_t2",
+    "bn254/circom_t3",
+    "bn254/circom_t4",
+    "bn254/circom_t5",
+    "bn254/circom_t6",
+    "bn254/circom_t7",
+    "bn254/circom_t8",
+    "bn254/circom_t9",
+    "bn254/circom_t10",
+    "bn254/circom_t11",
+    "bn254/circom_t12",
+    "bn254/circom_t13",
+    "bn254/circom_t14",
+    "bn254/circom_t15",
+    "bn254/circom_t16",
+    "bn254/circom_t17",
+    "bn254/poseidon2_t3",
+    "goldilocks/t8",
+    "goldilocks/t12",
+    "goldilocks/plonky2_t12",
+    "grumpkin/t3",
+    "grumpkin/t4",
+    "monolith/plonky2_t12",
+    "pasta/fp_t3",
+    "pasta/fq_t3",
+    "rescue/rp64_256",
+    "small_fields/babybear_t16",
+    "small_fields/babybear_t24",
+    "small_fields/mersenne31_t16",
+    "small_fields/mersenne31_t24",
+);
+
+/// A single known-answer test of a permutation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PermutationVector<F: PrimeField> {
+    pub input: Vec<F>,
+    pub output: Vec<F>,
+}
+
+/// Loads the test vectors of the parameter set with the given name. The caller has to choose the
+/// field of the parameter set, values which do not fit into the field are rejected.
+pub fn permutation_vectors<F: PrimeField>(name: &str) -> Result<Vec<PermutationVector<F>>, Error> {
+    let json = json(name).ok_or(Error::UnknownParameters(name.to_owned()))?;
+    parse_vectors(json)
+}
+
+/// Parses test vectors in the format described in the [module documentation](self).
+pub fn parse_vectors<F: PrimeField>(json: &str) -> Result<Vec<PermutationVector<F>>, Error> {
+    let value: Value = serde_json::from_str(json).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    let t = value["t"]
+        .as_u64()
+        .ok_or(Error::InvalidJson("missing statesize t".to_owned()))? as usize;
+    value["vectors"]
+        .as_array()
+        .ok_or(Error::InvalidJson("missing vectors".to_owned()))?
+        .iter()
+        .map(|vector| {
+            let input = parse_state(&vector["input"], t)?;
+            let output = parse_state(&vector["output"], t)?;
+            Ok(PermutationVector { input, output })
+        })
+        .collect()
+}
+
+fn parse_state<F: PrimeField>(value: &Value, t: usize) -> Result<Vec<F>, Error> {
+    let state = value
+        .as_array()
+        .ok_or(Error::InvalidJson("expected an array".to_owned()))?
+        .iter()
+        .map(|el| {
+            let hex = el
+                .as_str()
+                .ok_or(Error::InvalidJson("expected a hex string".to_owned()))?;
+            // rejects values which are not smaller than the modulus instead of reducing them
+            field_from_str_radix(hex.strip_prefix("0x").unwrap_or(hex), 16)
+        })
+        .collect::<Result<Vec<F>, Error>>()?;
+    if state.len() != t {
+        return Err(Error::InvalidJson(format!(
+            "expected a state of size {t}, got {}",
+            state.len()
+        )));
+    }
+    Ok(state)
+}
+
+#[cfg(test)]
+mod test_vectors_tests {
+    use super::*;
+    use crate::{
+        bn254::{
+            circom_t10::POSEIDON_CIRCOM_BN_10_PARAMS, circom_t11::POSEIDON_CIRCOM_BN_11_PARAMS,
+            circom_t12::POSEIDON_CIRCOM_BN_12_PARAMS, circom_t13::POSEIDON_CIRCOM_BN_13_PARAMS,
+            circom_t14::POSEIDON_CIRCOM_BN_14_PARAMS, circom_t15::POSEIDON_CIRCOM_BN_15_PARAMS,
+            circom_t16::POSEIDON_CIRCOM_BN_16_PARAMS, circom_t17::POSEIDON_CIRCOM_BN_17_PARAMS,
+            circom_t2::POSEIDON_CIRCOM_BN_2_PARAMS, circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+            circom_t4::POSEIDON_CIRCOM_BN_4_PARAMS, circom_t5::POSEIDON_CIRCOM_BN_5_PARAMS,
+            circom_t6::POSEIDON_CIRCOM_BN_6_PARAMS, circom_t7::POSEIDON_CIRCOM_BN_7_PARAMS,
+            circom_t8::POSEIDON_CIRCOM_BN_8_PARAMS, circom_t9::POSEIDON_CIRCOM_BN_9_PARAMS,
+            poseidon2_t3::POSEIDON2_BN_3_PARAMS,
+        },
+        goldilocks::{
+            plonky2_t12::POSEIDON_PLONKY2_12_PARAMS, t12::POSEIDON_GOLDILOCKS_12_PARAMS,
+            t8::POSEIDON_GOLDILOCKS_8_PARAMS,
+        },
+        grumpkin::{t3::POSEIDON_GRUMPKIN_3_PARAMS, t4::POSEIDON_GRUMPKIN_4_PARAMS},
+        pasta::{fp_t3::POSEIDON_PASTA_FP_3_PARAMS, fq_t3::POSEIDON_PASTA_FQ_3_PARAMS},
+        poseidon::Poseidon,
+        poseidon2::Poseidon2,
+        rescue::{rp64_256::RESCUE_RP64_256_PARAMS, RescuePrime},
+        small_fields::{
+            babybear_t16::POSEIDON_BABYBEAR_16_PARAMS, babybear_t24::POSEIDON_BABYBEAR_24_PARAMS,
+            mersenne31_t16::POSEIDON_MERSENNE31_16_PARAMS,
+            mersenne31_t24::POSEIDON_MERSENNE31_24_PARAMS,
+        },
+    };
+
+    fn check<F: PrimeField>(name: &str, permutation: impl Fn(Vec<F>) -> Vec<F>) {
+        let vectors = permutation_vectors::<F>(name).unwrap();
+        assert_eq!(vectors.len(), 3);
+        let t = vectors[0].input.len();
+        let counting: Vec<F> = (0..t as u64).map(F::from).collect();
+        assert_eq!(vectors[0].input, counting);
+        for vector in vectors {
+            assert_eq!(permutation(vector.input), vector.output, "{name}");
+        }
+    }
+
+    macro_rules! check_poseidon {
+        ($($name:literal => $params:expr),* $(,)?) => {
+            $({
+                let poseidon = Poseidon::new(&$params);
+                check($name, |input| poseidon.permutation(input).unwrap());
+            })*
+        };
+    }
+
+    #[test]
+    fn all_parameter_sets() {
+        check_poseidon!(
+            "bn254/circom_t2" => POSEIDON_CIRCOM_BN_2_PARAMS,
+            "bn254/circom_t3" => POSEIDON_CIRCOM_BN_3_PARAMS,
+            "bn254/circom_t4" => POSEIDON_CIRCOM_BN_4_PARAMS,
+            "bn254/circom_t5" => POSEIDON_CIRCOM_BN_5_PARAMS,
+            "bn254/circom_t6" => POSEIDON_CIRCOM_BN_6_PARAMS,
+            "bn254/circom_t7" => POSEIDON_CIRCOM_BN_7_PARAMS,
+            "bn254/circom_t8" => POSEIDON_CIRCOM_BN_8_PARAMS,
+            "bn254/circom_t9" => POSEIDON_CIRCOM_BN_9_PARAMS,
+            "bn254/circom_t10" => POSEIDON_CIRCOM_BN_10_PARAMS,
+            "bn254/circom_t11" => POSEIDON_CIRCOM_BN_11_PARAMS,
+            "bn254/circom_t12" => POSEIDON_CIRCOM_BN_12_PARAMS,
+            "bn254/circom_t13" => POSEIDON_CIRCOM_BN_13_PARAMS,
+            "bn254/circom_t14" => POSEIDON_CIRCOM_BN_14_PARAMS,
+            "bn254/circom_t15" => POSEIDON_CIRCOM_BN_15_PARAMS,
+            "bn254/circom_t16" => POSEIDON_CIRCOM_BN_16_PARAMS,
+            "bn254/circom_t17" => POSEIDON_CIRCOM_BN_17_PARAMS,
+            "goldilocks/t8" => POSEIDON_GOLDILOCKS_8_PARAMS,
+            "goldilocks/t12" => POSEIDON_GOLDILOCKS_12_PARAMS,
+            "goldilocks/plonky2_t12" => POSEIDON_PLONKY2_12_PARAMS,
+            "grumpkin/t3" => POSEIDON_GRUMPKIN_3_PARAMS,
+            "grumpkin/t4" => POSEIDON_GRUMPKIN_4_PARAMS,
+            "pasta/fp_t3" => POSEIDON_PASTA_FP_3_PARAMS,
+            "pasta/fq_t3" => POSEIDON_PASTA_FQ_3_PARAMS,
+            "small_fields/babybear_t16" => POSEIDON_BABYBEAR_16_PARAMS,
+            "small_fields/babybear_t24" => POSEIDON_BABYBEAR_24_PARAMS,
+            "small_fields/mersenne31_t16" => POSEIDON_MERSENNE31_16_PARAMS,
+            "small_fields/mersenne31_t24" => POSEIDON_MERSENNE31_24_PARAMS,
+        );
+        #[cfg(feature = "bls12-381")]
+        {
+            use crate::bls12_381::{
+                hades_t5::POSEIDON_HADES_5_PARAMS, neptune_t3::POSEIDON_NEPTUNE_3_PARAMS,
+                neptune_t5::POSEIDON_NEPTUNE_5_PARAMS, neptune_t9::POSEIDON_NEPTUNE_9_PARAMS,
+            };
+            check_poseidon!(
+                "bls12_381/hades_t5" => POSEIDON_HADES_5_PARAMS,
+                "bls12_381/neptune_t3" => POSEIDON_NEPTUNE_3_PARAMS,
+                "bls12_381/neptune_t5" => POSEIDON_NEPTUNE_5_PARAMS,
+                "bls12_381/neptune_t9" => POSEIDON_NEPTUNE_9_PARAMS,
+            );
+        }
+        let poseidon2 = Poseidon2::new(&POSEIDON2_BN_3_PARAMS);
+        check("bn254/poseidon2_t3", |input| {
+            poseidon2.permutation(input).unwrap()
+        });
+        let rescue = RescuePrime::new(&RESCUE_RP64_256_PARAMS);
+        check("rescue/rp64_256", |input| {
+            rescue.permutation(input).unwrap()
+        });
+        #[cfg(feature = "monolith")]
+        {
+            use crate::monolith::{plonky2_t12::MONOLITH_PLONKY2_12_PARAMS, Monolith};
+            let monolith = Monolith::new(&MONOLITH_PLONKY2_12_PARAMS);
+            check("monolith/plonky2_t12", |input| {
+                monolith.permutation(input).unwrap()
+            });
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(
+            permutation_vectors::<ark_bn254::Fr>("bn254/circom_t1"),
+            Err(Error::UnknownParameters(_))
+        ));
+        // the BN254 values do not fit into Goldilocks
+        assert!(matches!(
+            permutation_vectors::<crate::goldilocks::field::Goldilocks>("bn254/circom_t3"),
+            Err(Error::NonCanonical)
+        ));
+        let wrong_size = r#"{"t": 2, "vectors": [{"input": ["0x01"], "output": ["0x02"]}]}"#;
+        assert!(matches!(
+            parse_vectors::<crate::goldilocks::field::Goldilocks>(wrong_size),
+            Err(Error::InvalidJson(_))
+        ));
+    }
+}
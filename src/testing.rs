@@ -0,0 +1,246 @@
+//! # Testing
+//! Helpers for property-based and differential testing of the permutation, such that downstream
+//! integrators and fuzzers can reuse the oracles of this crate:
+//! - [`random_params`] and [`random_params_with_rounds`] sample valid parameter sets with a
+//!   Cauchy MDS matrix (all square submatrices invertible, as required by the optimized
+//!   permutation) and random round constants,
+//! - [`reference_permutation`] is a naive implementation of the permutation, which is
+//!   independent of [`Poseidon`] (no shared sbox, matrix, or round constant code), and
+//!   [`differential`] compares it with [`Poseidon::permutation`] and
+//!   [`Poseidon::permutation_not_opt`],
+//! - [`strategies`] contains `proptest` strategies for field elements and parameter sets.
+
+use crate::{
+    error::Error,
+    parameters::{mds, PoseidonParams},
+    poseidon::Poseidon,
+};
+use alloc::{sync::Arc, vec::Vec};
+use ark_ff::PrimeField;
+use ark_std::rand::Rng;
+use num_bigint::BigUint;
+
+// x^d is a permutation of the field iff gcd(d, p - 1) = 1
+fn is_permutation<F: PrimeField>(d: u64) -> bool {
+    let modulus: BigUint = F::MODULUS.into();
+    let rem = (modulus - 1u32) % d;
+    let (mut a, mut b) = (d, rem.iter_u64_digits().next().unwrap_or(0));
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a == 1
+}
+
+/// The smallest sbox degree d, such that x^d is a permutation of the field, i.e., gcd(d, p-1) = 1.
+pub fn smallest_sbox_degree<F: PrimeField>() -> usize {
+    (3..)
+        .step_by(2)
+        .find(|d| is_permutation::<F>(*d))
+        .expect("an odd degree coprime to p - 1 exists") as usize
+}
+
+/// Samples parameters with the given statesize, sbox degree, and round numbers, a random Cauchy
+/// MDS matrix, and random round constants. Fails if the parameters are invalid, e.g., if x^d is
+/// not a permutation or the number of full rounds is odd.
+pub fn random_params_with_rounds<F: PrimeField, R: Rng + ?Sized>(
+    rng: &mut R,
+    t: usize,
+    d: usize,
+    rounds_f: usize,
+    rounds_p: usize,
+) -> Result<PoseidonParams<F>, Error> {
+    if t == 0 || d < 3 || !is_permutation::<F>(d as u64) {
+        return Err(Error::InvalidParameters);
+    }
+    let mds = loop {
+        let x: Vec<F> = (0..t).map(|_| F::rand(rng)).collect();
+        let y: Vec<F> = (0..t).map(|_| F::rand(rng)).collect();
+        if let Ok(mds) = mds::cauchy(&x, &y) {
+            break mds;
+        }
+    };
+    let round_constants = (0..rounds_f + rounds_p)
+        .map(|_| (0..t).map(|_| F::rand(rng)).collect())
+        .collect();
+    PoseidonParams::new(t, d, rounds_f, rounds_p, mds, round_constants)
+}
+
+/// Samples a random valid parameter set with 2 <= t <= 8, the smallest sbox degree of the field,
+/// 2 to 8 full rounds, and 0 to 16 partial rounds. The round numbers are far from secure, the
+/// parameters are only meant to exercise the implementation.
+pub fn random_params<F: PrimeField, R: Rng + ?Sized>(rng: &mut R) -> PoseidonParams<F> {
+    let t = rng.gen_range(2..=8);
+    let rounds_f = 2 * rng.gen_range(1..=4);
+    let rounds_p = rng.gen_range(0..=16);
+    random_params_with_rounds(rng, t, smallest_sbox_degree::<F>(), rounds_f, rounds_p)
+        .expect("the sampled parameters are valid")
+}
+
+/// A naive implementation of the permutation straight from the Poseidon paper: every round adds
+/// the round constants, applies x^d to the full state (full rounds) or to the first element
+/// (partial rounds), and multiplies with the MDS matrix.
+pub fn reference_permutation<F: PrimeField>(
+    params: &PoseidonParams<F>,
+    input: &[F],
+) -> Result<Vec<F>, Error> {
+    let t = params.t();
+    if input.len() != t {
+        return Err(Error::WrongInputLength {
+            expected: t,
+            got: input.len(),
+        });
+    }
+    let half_f = params.rounds_full() / 2;
+    let mds = params.mds().to_vec();
+    let round_constants = params.round_constants().to_vec();
+    let exponent = [params.d() as u64];
+
+    let mut state = input.to_vec();
+    for (r, rc) in round_constants.iter().enumerate() {
+        for (s, c) in state.iter_mut().zip(rc) {
+            *s += c;
+        }
+        let full = r < half_f || r >= half_f + params.rounds_partial();
+        let sbox_len = if full { t } else { 1 };
+        for s in state.iter_mut().take(sbox_len) {
+            *s = s.pow(exponent);
+        }
+        state = mds
+            .iter()
+            .map(|row| row.iter().zip(&state).map(|(m, s)| *m * s).sum())
+            .collect();
+    }
+    Ok(state)
+}
+
+/// The outputs of the implementations of the permutation for the same input, see
+/// [`differential`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DifferentialOutputs<F: PrimeField> {
+    /// [`Poseidon::permutation`]
+    pub optimized: Vec<F>,
+    /// [`Poseidon::permutation_not_opt`]
+    pub not_opt: Vec<F>,
+    /// [`reference_permutation`]
+    pub reference: Vec<F>,
+}
+
+impl<F: PrimeField> DifferentialOutputs<F> {
+    /// Whether all implementations agree.
+    pub fn agree(&self) -> bool {
+        self.optimized == self.not_opt && self.not_opt == self.reference
+    }
+}
+
+/// Evaluates the optimized, the unoptimized, and the reference permutation on the input.
+pub fn differential<F: PrimeField>(
+    params: &Arc<PoseidonParams<F>>,
+    input: &[F],
+) -> Result<DifferentialOutputs<F>, Error> {
+    let poseidon = Poseidon::new(params);
+    Ok(DifferentialOutputs {
+        optimized: poseidon.permutation(input.to_vec())?,
+        not_opt: poseidon.permutation_not_opt(input.to_vec())?,
+        reference: reference_permutation(params, input)?,
+    })
+}
+
+/// Panics with all outputs if the implementations of the permutation disagree on the input.
+pub fn assert_differential<F: PrimeField>(params: &Arc<PoseidonParams<F>>, input: &[F]) {
+    let outputs = differential(params, input).expect("the input has the length t");
+    assert!(
+        outputs.agree(),
+        "the permutations disagree for {params} on {input:?}: {outputs:?}"
+    );
+}
+
+/// `proptest` strategies for field elements and parameter sets.
+pub mod strategies {
+    use super::*;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use proptest::prelude::*;
+
+    /// Uniformly random field elements, where 0, 1, and -1 are sampled more often as they are the
+    /// usual edge cases.
+    pub fn field_element<F: PrimeField>() -> impl Strategy<Value = F> {
+        // 16 additional bytes make the bias of the reduction negligible
+        let len = (F::MODULUS_BIT_SIZE as usize).div_ceil(8) + 16;
+        prop_oneof![
+            1 => Just(F::zero()),
+            1 => Just(F::one()),
+            1 => Just(-F::one()),
+            7 => proptest::collection::vec(any::<u8>(), len)
+                .prop_map(|bytes| F::from_le_bytes_mod_order(&bytes)),
+        ]
+    }
+
+    /// Vectors of `len` field elements, see [`field_element`].
+    pub fn field_elements<F: PrimeField>(len: usize) -> impl Strategy<Value = Vec<F>> {
+        proptest::collection::vec(field_element(), len)
+    }
+
+    /// Parameter sets sampled by [`random_params`], where the randomness is derived from a seed
+    /// drawn by proptest, such that failing cases are reproducible.
+    pub fn params<F: PrimeField>() -> impl Strategy<Value = Arc<PoseidonParams<F>>> {
+        any::<[u8; 32]>().prop_map(|seed| Arc::new(random_params(&mut StdRng::from_seed(seed))))
+    }
+
+    /// Parameter sets together with an input of length t, see [`params`] and
+    /// [`field_elements`].
+    pub fn params_and_input<F: PrimeField>(
+    ) -> impl Strategy<Value = (Arc<PoseidonParams<F>>, Vec<F>)> {
+        params().prop_flat_map(|params| {
+            let t = params.t();
+            (Just(params), field_elements(t))
+        })
+    }
+}
+
+#[cfg(test)]
+mod testing_tests {
+    use super::{strategies::*, *};
+    use crate::{
+        bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS, goldilocks::t8::POSEIDON_GOLDILOCKS_8_PARAMS,
+    };
+    use ark_ff::UniformRand;
+    use proptest::prelude::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn sbox_degree() {
+        assert_eq!(smallest_sbox_degree::<ark_bn254::Fr>(), 5);
+        assert_eq!(
+            smallest_sbox_degree::<crate::goldilocks::field::Goldilocks>(),
+            7
+        );
+    }
+
+    #[test]
+    fn shipped_params() {
+        let mut rng = thread_rng();
+        let input: Vec<_> = (0..3).map(|_| ark_bn254::Fr::rand(&mut rng)).collect();
+        assert_differential(&POSEIDON_CIRCOM_BN_3_PARAMS, &input);
+        let input: Vec<_> = (0..8).map(|i| i.into()).collect();
+        assert_differential(&POSEIDON_GOLDILOCKS_8_PARAMS, &input);
+    }
+
+    #[test]
+    fn invalid_params() {
+        let mut rng = thread_rng();
+        assert!(matches!(
+            random_params_with_rounds::<ark_bn254::Fr, _>(&mut rng, 3, 3, 8, 57),
+            Err(Error::InvalidParameters)
+        ));
+        assert!(random_params_with_rounds::<ark_bn254::Fr, _>(&mut rng, 3, 5, 7, 57).is_err());
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn permutations_agree((params, input) in params_and_input::<ark_bn254::Fr>()) {
+            let outputs = differential(&params, &input).unwrap();
+            prop_assert!(outputs.agree(), "{:?}", outputs);
+        }
+    }
+}
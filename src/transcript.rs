@@ -0,0 +1,110 @@
+//! # Transcript
+//! Contains a Fiat-Shamir transcript built on the Poseidon sponge, which derives challenges from
+//! all previously absorbed messages.
+
+use crate::{error::Error, parameters::PoseidonParams, sponge::PoseidonSponge};
+use alloc::sync::Arc;
+use ark_ff::PrimeField;
+
+/// A Fiat-Shamir transcript. Every message is preceded by a label, which separates the roles of
+/// the messages in a protocol, and the transcript itself is initialized with a protocol-specific
+/// domain separator.
+///
+/// Labels and byte strings are absorbed with [`PoseidonSponge::absorb_bytes`]. A challenge is
+/// squeezed from a sponge over all messages since the previous challenge, and a fresh sponge
+/// continues with the challenge as its first input. Thus, each challenge depends on the whole
+/// transcript.
+#[derive(Clone, Debug)]
+pub struct PoseidonTranscript<F: PrimeField> {
+    params: Arc<PoseidonParams<F>>,
+    sponge: PoseidonSponge<F>,
+}
+
+impl<F: PrimeField> PoseidonTranscript<F> {
+    /// Creates a transcript with the given domain separator.
+    pub fn new(params: &Arc<PoseidonParams<F>>, domain: &[u8]) -> Result<Self, Error> {
+        let mut transcript = PoseidonTranscript {
+            params: params.clone(),
+            sponge: PoseidonSponge::new(params)?,
+        };
+        transcript.sponge.absorb_bytes(domain)?;
+        Ok(transcript)
+    }
+
+    pub fn absorb_field(&mut self, label: &[u8], el: &F) -> Result<(), Error> {
+        self.absorb_fields(label, core::slice::from_ref(el))
+    }
+
+    /// Absorbs a list of field elements, including its length.
+    pub fn absorb_fields(&mut self, label: &[u8], els: &[F]) -> Result<(), Error> {
+        self.sponge.absorb_bytes(label)?;
+        self.sponge.absorb(els)?;
+        self.sponge.absorb(&[F::from(els.len() as u64)])
+    }
+
+    pub fn absorb_bytes(&mut self, label: &[u8], bytes: &[u8]) -> Result<(), Error> {
+        self.sponge.absorb_bytes(label)?;
+        self.sponge.absorb_bytes(bytes)
+    }
+
+    /// Derives a challenge from the transcript and appends it to the transcript.
+    pub fn challenge_scalar(&mut self, label: &[u8]) -> Result<F, Error> {
+        self.sponge.absorb_bytes(label)?;
+        let sponge = core::mem::replace(&mut self.sponge, PoseidonSponge::new(&self.params)?);
+        let challenge = sponge.finalize()?;
+        self.sponge.absorb(&[challenge])?;
+        Ok(challenge)
+    }
+}
+
+#[cfg(test)]
+mod transcript_tests {
+    use super::*;
+    use crate::bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS;
+
+    type Scalar = ark_bn254::Fr;
+
+    fn transcript(domain: &[u8]) -> PoseidonTranscript<Scalar> {
+        PoseidonTranscript::new(&POSEIDON_CIRCOM_BN_3_PARAMS, domain).unwrap()
+    }
+
+    #[test]
+    fn deterministic() {
+        let mut challenges = Vec::new();
+        for _ in 0..2 {
+            let mut t = transcript(b"protocol");
+            t.absorb_field(b"commitment", &Scalar::from(42)).unwrap();
+            t.absorb_bytes(b"message", b"hello").unwrap();
+            let c1 = t.challenge_scalar(b"alpha").unwrap();
+            let c2 = t.challenge_scalar(b"beta").unwrap();
+            assert_ne!(c1, c2);
+            challenges.push((c1, c2));
+        }
+        assert_eq!(challenges[0], challenges[1]);
+    }
+
+    #[test]
+    fn domain_separation() {
+        let challenge = |domain: &[u8], label: &[u8], bytes: &[u8]| {
+            let mut t = transcript(domain);
+            t.absorb_bytes(label, bytes).unwrap();
+            t.challenge_scalar(b"c").unwrap()
+        };
+        let c = challenge(b"protocol", b"m", b"ab");
+        assert_ne!(c, challenge(b"other", b"m", b"ab"));
+        assert_ne!(c, challenge(b"protocol", b"n", b"ab"));
+        assert_ne!(c, challenge(b"protocol", b"ma", b"b"));
+        assert_ne!(c, challenge(b"protocol", b"m", b"ab\0"));
+
+        let mut t1 = transcript(b"protocol");
+        t1.absorb_fields(b"x", &[Scalar::from(1), Scalar::from(2)])
+            .unwrap();
+        let mut t2 = transcript(b"protocol");
+        t2.absorb_field(b"x", &Scalar::from(1)).unwrap();
+        t2.absorb_field(b"x", &Scalar::from(2)).unwrap();
+        assert_ne!(
+            t1.challenge_scalar(b"c").unwrap(),
+            t2.challenge_scalar(b"c").unwrap()
+        );
+    }
+}
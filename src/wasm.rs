@@ -0,0 +1,111 @@
+//! # Wasm
+//! Contains `wasm-bindgen` bindings for web frontends. Field elements are passed as decimal or
+//! `0x`-prefixed hex strings (see [`field_from_str`]) and returned as `0x`-prefixed hex strings.
+//! The randomness of the guessing game is a hex string with or without `0x` prefix (see
+//! [`parse_hex`](crate::guessing_game::parse_hex)), as in the library and the binaries.
+
+use crate::{
+    bn254::circom_t3::POSEIDON_CIRCOM_BN_3_PARAMS,
+    circom,
+    error::Error,
+    field_from_str, field_to_hex_string,
+    merkle::tree::{MerkleProof, MerkleTree},
+    poseidon::Poseidon,
+};
+use alloc::{string::String, vec::Vec};
+use ark_bn254::Fr;
+use wasm_bindgen::prelude::*;
+
+fn parse_all(inputs: &[String]) -> Result<Vec<Fr>, Error> {
+    inputs.iter().map(|s| field_from_str(s)).collect()
+}
+
+fn to_js_error(error: Error) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+fn hash(inputs: &[String]) -> Result<String, Error> {
+    let inputs = parse_all(inputs)?;
+    circom::poseidon_hash(&inputs).map(|h| field_to_hex_string(&h))
+}
+
+fn commit(guess: u16, address: &str, r: &str) -> Result<String, Error> {
+    crate::guessing_game_commit(guess, address, r).map(|c| field_to_hex_string(&c))
+}
+
+fn verify(
+    root: &str,
+    depth: usize,
+    leaf: &str,
+    index: usize,
+    siblings: &[String],
+) -> Result<bool, Error> {
+    let proof = MerkleProof {
+        index,
+        siblings: parse_all(siblings)?,
+    };
+    let poseidon = Poseidon::new(&POSEIDON_CIRCOM_BN_3_PARAMS);
+    let root = field_from_str(root)?;
+    let leaf = field_from_str(leaf)?;
+    MerkleTree::verify_proof(&poseidon, &root, depth, leaf, &proof)
+}
+
+/// Hashes one to sixteen field elements like circomlibjs' `poseidon(inputs)`.
+#[wasm_bindgen(js_name = poseidonHash)]
+pub fn poseidon_hash(inputs: Vec<String>) -> Result<String, JsError> {
+    hash(&inputs).map_err(to_js_error)
+}
+
+/// Computes the commitment of the guessing game, see [`crate::guessing_game_commit`].
+#[wasm_bindgen(js_name = guessingGameCommit)]
+pub fn guessing_game_commit(guess: u16, address: &str, r: &str) -> Result<String, JsError> {
+    commit(guess, address, r).map_err(to_js_error)
+}
+
+/// Verifies an inclusion proof of a [`MerkleTree`] over the circom t=3 instance. The siblings
+/// are ordered from the leaf level to the root, and their number has to match the depth of the
+/// tree.
+#[wasm_bindgen(js_name = verifyMerkleProof)]
+pub fn verify_merkle_proof(
+    root: &str,
+    depth: usize,
+    leaf: &str,
+    index: usize,
+    siblings: Vec<String>,
+) -> Result<bool, JsError> {
+    verify(root, depth, leaf, index, &siblings).map_err(to_js_error)
+}
+
+#[cfg(test)]
+mod wasm_tests {
+    use super::*;
+
+    #[test]
+    fn hex_interface() {
+        let inputs = ["0x1".to_owned(), "2".to_owned()];
+        assert_eq!(
+            hash(&inputs).unwrap(),
+            "0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"
+        );
+        assert!(hash(&["0xzz".to_owned()]).is_err());
+
+        let leaves: Vec<Fr> = (0..5).map(Fr::from).collect();
+        let tree = MerkleTree::new(&POSEIDON_CIRCOM_BN_3_PARAMS, leaves).unwrap();
+        let proof = tree.proof(3).unwrap();
+        let root = field_to_hex_string(&tree.root());
+        let siblings: Vec<String> = proof.siblings.iter().map(field_to_hex_string).collect();
+        assert!(verify(&root, 3, "0x3", 3, &siblings).unwrap());
+        assert!(!verify(&root, 3, "0x4", 3, &siblings).unwrap());
+        assert!(!verify(&root, 4, "0x3", 3, &siblings).unwrap());
+
+        let address = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8";
+        assert_eq!(
+            commit(5, address, "a").unwrap(),
+            "0x2346b3b208c9e65959af9824ccab4da69ae27d222204fcf0ace7f725e02e512d"
+        );
+        assert_eq!(
+            commit(5, address, "10").unwrap(),
+            commit(5, address, "0x10").unwrap()
+        );
+    }
+}